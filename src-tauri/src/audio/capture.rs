@@ -1,17 +1,116 @@
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use cpal::{Device, Host, Stream, StreamConfig};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
-use crate::types::AudioDevice;
+use crate::audio::loopback::{self, LoopbackCapture, DEFAULT_MONITOR, SYSTEM_DEVICE_PREFIX};
+use crate::audio::spool::{RecordedAudio, SpoolBuffer};
+use crate::types::{AppSettings, AudioDevice, AudioDeviceKind};
 
-/// Limite du buffer audio : 10 minutes à 48kHz mono
-const MAX_BUFFER_SAMPLES: usize = 48000 * 60 * 10;
+/// Audio récent gardé en mémoire ; le reste part dans le spool disque
+const MEMORY_WINDOW_SECS: u32 = 120;
+
+/// Durée maximale d'un enregistrement avec spool disque
+const MAX_RECORDING_SECS: u32 = 4 * 3600;
+
+/// Limite si le spool disque est indisponible : 10 minutes à 48kHz mono
+const MEMORY_ONLY_MAX_SAMPLES: usize = 48000 * 60 * 10;
+
+/// Fraction de la limite à partir de laquelle l'utilisateur est prévenu
+const LIMIT_WARNING_RATIO: f32 = 0.9;
+
+/// Intervalle minimal entre deux vérifications de présence du périphérique
+const DEVICE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Fréquence de capture de l'audio système seul
+const SYSTEM_AUDIO_SAMPLE_RATE: u32 = 48000;
+
+/// Avance maximale de l'audio système sur le micro en mode mixé (secondes)
+const MIX_MAX_LAG_SECS: u32 = 1;
+
+/// Entrée audio à utiliser : micros par ordre de préférence et canal à capturer
+#[derive(Debug, Clone, Default)]
+pub struct InputSelection {
+    /// Noms des périphériques, du plus prioritaire au moins prioritaire.
+    /// Liste vide : périphérique par défaut du système.
+    pub preferred_devices: Vec<String>,
+    /// Canal à capturer (0 = premier). `None` : moyenne de tous les canaux.
+    pub channel: Option<u16>,
+    /// Mixe l'audio système avec le micro (les deux côtés d'un appel)
+    pub mix_system_audio: bool,
+    /// Source système à mixer (`system:...`), `None` : sortie par défaut
+    pub system_device: Option<String>,
+}
+
+impl InputSelection {
+    pub fn from_settings(settings: &AppSettings) -> Self {
+        let mut preferred_devices = settings.preferred_microphones.clone();
+        // Compatibilité : ancien réglage à micro unique
+        if preferred_devices.is_empty() {
+            if let Some(ref id) = settings.microphone_id {
+                preferred_devices.push(id.clone());
+            }
+        }
+        Self {
+            preferred_devices,
+            channel: settings.microphone_channel,
+            mix_system_audio: settings.mix_system_audio,
+            system_device: settings.system_audio_device.clone(),
+        }
+    }
+
+    pub fn device(device_id: Option<&str>) -> Self {
+        Self {
+            preferred_devices: device_id.map(|id| vec![id.to_string()]).unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+
+    fn mix_source(&self) -> String {
+        self.system_device
+            .as_deref()
+            .and_then(loopback::source_from_device_id)
+            .unwrap_or(DEFAULT_MONITOR)
+            .to_string()
+    }
+}
+
+/// Payload de l'événement `audio-device-changed` émis après la perte d'un micro
+#[derive(Debug, Clone, Serialize)]
+pub struct DeviceFallbackEvent {
+    pub lost_device: String,
+    /// Micro utilisé désormais, `None` si aucun n'est disponible
+    pub new_device: Option<String>,
+}
+
+/// Payload de l'événement `recording-limit-warning`
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingLimitEvent {
+    pub recorded_secs: f32,
+    pub limit_secs: f32,
+    /// L'audio est déversé sur disque (sinon limité par la mémoire)
+    pub spooled: bool,
+    /// La limite est atteinte : la suite de l'audio n'est plus enregistrée
+    pub limit_reached: bool,
+}
 
 pub struct AudioCapture {
     stream: Option<Stream>,
-    buffer: Arc<Mutex<Vec<f32>>>,
+    buffer: Arc<Mutex<SpoolBuffer>>,
     sample_rate: u32,
     channels: u16,
+    device_name: String,
+    selection: InputSelection,
+    device_lost: Arc<AtomicBool>,
+    last_device_check: Instant,
+    limit_warned: bool,
+    limit_reached_reported: bool,
+    loopback: Option<LoopbackCapture>,
+    /// Audio système en attente d'être mixé au micro
+    mix_queue: Option<Arc<Mutex<VecDeque<f32>>>>,
 }
 
 impl AudioCapture {
@@ -25,81 +124,250 @@ impl AudioCapture {
             .map_err(|e| e.to_string())?
             .filter_map(|device| {
                 let name = device.name().ok()?;
+                let channels = device
+                    .default_input_config()
+                    .map(|c| c.channels())
+                    .unwrap_or(1);
                 Some(AudioDevice {
                     id: name.clone(),
                     name: name.clone(),
                     is_default: Some(&name) == default_name.as_ref(),
+                    channels,
+                    kind: AudioDeviceKind::Microphone,
                 })
             })
             .collect();
 
+        let mut devices = devices;
+        let monitors = loopback::list_monitor_sources();
+        if !monitors.is_empty() {
+            devices.push(AudioDevice {
+                id: format!("{}{}", SYSTEM_DEVICE_PREFIX, DEFAULT_MONITOR),
+                name: "Audio système (sortie par défaut)".to_string(),
+                is_default: false,
+                channels: 1,
+                kind: AudioDeviceKind::SystemAudio,
+            });
+        }
+        devices.extend(monitors.into_iter().map(|source| AudioDevice {
+            id: format!("{}{}", SYSTEM_DEVICE_PREFIX, source.name),
+            name: format!("Audio système : {}", source.description),
+            is_default: false,
+            channels: 1,
+            kind: AudioDeviceKind::SystemAudio,
+        }));
+
         Ok(devices)
     }
 
-    pub fn new(device_id: Option<&str>) -> Result<Self, String> {
+    pub fn new(selection: &InputSelection) -> Result<Self, String> {
         let host = cpal::default_host();
-        let device = Self::get_device(&host, device_id)?;
-        let config = device.default_input_config().map_err(|e| e.to_string())?;
+        let (sample_rate, channels, device_name) = match Self::preferred_system_source(&host, selection) {
+            Some(source) => (SYSTEM_AUDIO_SAMPLE_RATE, 1, format!("{}{}", SYSTEM_DEVICE_PREFIX, source)),
+            None => {
+                let device = Self::resolve_device(&host, selection, None)?;
+                let config = device.default_input_config().map_err(|e| e.to_string())?;
+                (config.sample_rate().0, config.channels(), device.name().unwrap_or_default())
+            }
+        };
 
         Ok(Self {
             stream: None,
-            buffer: Arc::new(Mutex::new(Vec::new())),
-            sample_rate: config.sample_rate().0,
-            channels: config.channels(),
+            buffer: Arc::new(Mutex::new(SpoolBuffer::new())),
+            sample_rate,
+            channels,
+            device_name,
+            selection: selection.clone(),
+            device_lost: Arc::new(AtomicBool::new(false)),
+            last_device_check: Instant::now(),
+            limit_warned: false,
+            limit_reached_reported: false,
+            loopback: None,
+            mix_queue: None,
         })
     }
 
-    fn get_device(host: &Host, device_id: Option<&str>) -> Result<Device, String> {
-        match device_id {
-            Some(id) => host
+    /// Source système si la première entrée disponible de la liste de préférence
+    /// est un périphérique "audio système" (capture de l'audio système seul)
+    fn preferred_system_source(host: &Host, selection: &InputSelection) -> Option<String> {
+        for id in &selection.preferred_devices {
+            if let Some(source) = loopback::source_from_device_id(id) {
+                if loopback::is_source_available(source) {
+                    return Some(source.to_string());
+                }
+                continue;
+            }
+            let present = host
+                .input_devices()
+                .map(|mut devices| devices.any(|d| d.name().ok().as_deref() == Some(id.as_str())))
+                .unwrap_or(false);
+            if present {
+                return None;
+            }
+        }
+        None
+    }
+
+    /// Premier micro préféré encore branché, sinon le micro par défaut.
+    /// `exclude` écarte un périphérique qui vient de disparaître.
+    fn resolve_device(host: &Host, selection: &InputSelection, exclude: Option<&str>) -> Result<Device, String> {
+        for id in &selection.preferred_devices {
+            if Some(id.as_str()) == exclude || id.starts_with(SYSTEM_DEVICE_PREFIX) {
+                continue;
+            }
+            let found = host
                 .input_devices()
                 .map_err(|e| e.to_string())?
-                .find(|d| d.name().ok().as_deref() == Some(id))
-                .ok_or_else(|| format!("Device '{}' not found", id)),
-            None => host
-                .default_input_device()
-                .ok_or_else(|| "No default input device".to_string()),
+                .find(|d| d.name().ok().as_deref() == Some(id.as_str()));
+            if let Some(device) = found {
+                return Ok(device);
+            }
+            log::info!("Preferred input device '{}' not available, trying next", id);
         }
+
+        let device = host
+            .default_input_device()
+            .ok_or_else(|| "No default input device".to_string())?;
+        if exclude.is_some() && device.name().ok().as_deref() == exclude {
+            return Err("No other input device available".to_string());
+        }
+        Ok(device)
     }
 
-    pub fn start(&mut self, device_id: Option<&str>) -> Result<(), String> {
+    pub fn start(&mut self, selection: &InputSelection) -> Result<(), String> {
         let host = cpal::default_host();
-        let device = Self::get_device(&host, device_id)?;
-        let config = device.default_input_config().map_err(|e| e.to_string())?;
 
-        self.sample_rate = config.sample_rate().0;
-        self.channels = config.channels();
+        self.stream = None;
+        self.loopback = None;
+        self.mix_queue = None;
+        self.selection = selection.clone();
+        self.limit_warned = false;
+        self.limit_reached_reported = false;
         if let Ok(mut buf) = self.buffer.lock() {
             buf.clear();
         }
 
+        if let Some(source) = Self::preferred_system_source(&host, selection) {
+            return self.start_system_only(&source);
+        }
+
+        let device = Self::resolve_device(&host, selection, None)?;
+        self.open_stream(&device)
+    }
+
+    /// Audio système seul : pas de flux cpal, parec écrit directement dans le buffer
+    fn start_system_only(&mut self, source: &str) -> Result<(), String> {
+        self.sample_rate = SYSTEM_AUDIO_SAMPLE_RATE;
+        self.channels = 1;
+        self.device_name = format!("{}{}", SYSTEM_DEVICE_PREFIX, source);
+
+        let buffer = self.buffer.clone();
+        let max_samples = self.max_samples();
+        self.loopback = Some(LoopbackCapture::start(source, self.sample_rate, move |samples| {
+            if let Ok(mut buf) = buffer.lock() {
+                let limit = if buf.spool_failed() { MEMORY_ONLY_MAX_SAMPLES } else { max_samples };
+                if buf.len() < limit {
+                    buf.push(samples);
+                }
+            }
+        })?);
+        Ok(())
+    }
+
+    /// Mode mixé : l'audio système est mis en file d'attente, puis ajouté au
+    /// micro dans le callback cpal (le micro donne l'horloge)
+    fn start_mix_loopback(&mut self) {
+        self.loopback = None;
+        let queue = Arc::new(Mutex::new(VecDeque::new()));
+        let queue_clone = queue.clone();
+        let max_lag = (MIX_MAX_LAG_SECS * self.sample_rate) as usize;
+        let source = self.selection.mix_source();
+
+        match LoopbackCapture::start(&source, self.sample_rate, move |samples| {
+            if let Ok(mut q) = queue_clone.lock() {
+                q.extend(samples.iter().copied());
+                // Dérive d'horloge : on ne garde pas plus de `max_lag` d'avance
+                if q.len() > max_lag {
+                    let excess = q.len() - max_lag;
+                    q.drain(..excess);
+                }
+            }
+        }) {
+            Ok(capture) => {
+                self.loopback = Some(capture);
+                self.mix_queue = Some(queue);
+            }
+            Err(e) => {
+                log::error!("[LOOPBACK] System audio mix unavailable, recording microphone only: {}", e);
+                self.mix_queue = None;
+            }
+        }
+    }
+
+    fn open_stream(&mut self, device: &Device) -> Result<(), String> {
+        let config = device.default_input_config().map_err(|e| e.to_string())?;
+
+        self.sample_rate = config.sample_rate().0;
+        self.channels = config.channels();
+        self.device_name = device.name().unwrap_or_default();
+        self.device_lost.store(false, Ordering::SeqCst);
+        self.last_device_check = Instant::now();
+
         let buffer = self.buffer.clone();
         let channels = self.channels as usize;
+        let channel = match self.selection.channel {
+            Some(ch) if (ch as usize) < channels => Some(ch as usize),
+            Some(ch) => {
+                log::warn!(
+                    "Input channel {} not available on '{}' ({} channel(s)), mixing all channels",
+                    ch + 1, self.device_name, channels
+                );
+                None
+            }
+            None => None,
+        };
+        let device_lost = self.device_lost.clone();
+        let max_samples = self.max_samples();
         let config: StreamConfig = config.into();
 
-        log::info!("Starting audio capture: {}Hz, {} channel(s)", self.sample_rate, channels);
+        // (Re)démarre la capture système au débit du micro
+        if self.selection.mix_system_audio {
+            self.start_mix_loopback();
+        }
+        let mix_queue = self.mix_queue.clone();
+
+        log::info!(
+            "Starting audio capture on '{}': {}Hz, {} channel(s), capturing {}",
+            self.device_name,
+            self.sample_rate,
+            channels,
+            channel.map(|c| format!("channel {}", c + 1)).unwrap_or_else(|| "mix".to_string())
+        );
 
         let stream = device
             .build_input_stream(
                 &config,
                 move |data: &[f32], _: &cpal::InputCallbackInfo| {
                     if let Ok(mut buf) = buffer.lock() {
-                        if buf.len() >= MAX_BUFFER_SAMPLES {
+                        let limit = if buf.spool_failed() { MEMORY_ONLY_MAX_SAMPLES } else { max_samples };
+                        if buf.len() >= limit {
                             return;
                         }
-                        // Convertir stéréo → mono si nécessaire
-                        if channels > 1 {
-                            for chunk in data.chunks(channels) {
-                                let mono: f32 = chunk.iter().sum::<f32>() / channels as f32;
-                                buf.push(mono);
+                        let before = buf.len();
+                        push_mono(&mut buf, data, channels, channel);
+                        let pushed = buf.len() - before;
+                        if let Some(ref queue) = mix_queue {
+                            if let Ok(mut q) = queue.lock() {
+                                buf.mix_into_tail(pushed, &mut q);
                             }
-                        } else {
-                            buf.extend_from_slice(data);
                         }
                     }
                 },
-                |err| {
+                move |err| {
                     log::error!("Audio stream error: {}", err);
+                    if matches!(err, cpal::StreamError::DeviceNotAvailable) {
+                        device_lost.store(true, Ordering::SeqCst);
+                    }
                 },
                 None,
             )
@@ -111,31 +379,186 @@ impl AudioCapture {
         Ok(())
     }
 
-    pub fn stop(&mut self) -> Result<(Vec<f32>, u32), String> {
+    /// À appeler régulièrement pendant l'enregistrement.
+    /// Si le micro a disparu, bascule sur le suivant disponible (préférés puis défaut)
+    /// en conservant l'audio déjà capturé, et retourne l'événement à notifier.
+    pub fn poll_device(&mut self) -> Option<DeviceFallbackEvent> {
+        self.stream.as_ref()?;
+
+        let mut lost = self.device_lost.load(Ordering::SeqCst);
+        if !lost && self.last_device_check.elapsed() >= DEVICE_CHECK_INTERVAL {
+            self.last_device_check = Instant::now();
+            // Certains backends (ALSA) ne signalent pas le débranchement : on vérifie la liste
+            let host = cpal::default_host();
+            lost = match host.input_devices() {
+                Ok(mut devices) => !devices.any(|d| d.name().ok().as_deref() == Some(self.device_name.as_str())),
+                Err(_) => false,
+            };
+        }
+        if !lost {
+            return None;
+        }
+
+        let lost_device = self.device_name.clone();
+        log::warn!("Input device '{}' disconnected", lost_device);
         self.stream = None;
-        let buffer = self.buffer.lock()
-            .map_err(|e| format!("Failed to lock audio buffer: {}", e))?
-            .clone();
-        let sample_rate = self.sample_rate;
-        if let Ok(mut buf) = self.buffer.lock() {
-            buf.clear();
+
+        let host = cpal::default_host();
+        let old_rate = self.sample_rate;
+        let new_device = match Self::resolve_device(&host, &self.selection, Some(&lost_device))
+            .and_then(|device| self.open_stream(&device))
+        {
+            Ok(()) => {
+                if self.sample_rate != old_rate {
+                    let keep = (MEMORY_WINDOW_SECS * self.sample_rate) as usize;
+                    if let Ok(mut buf) = self.buffer.lock() {
+                        buf.resample(old_rate, self.sample_rate, keep);
+                    }
+                }
+                log::info!("Switched audio capture to '{}'", self.device_name);
+                Some(self.device_name.clone())
+            }
+            Err(e) => {
+                log::error!("No fallback input device: {}", e);
+                None
+            }
+        };
+
+        Some(DeviceFallbackEvent { lost_device, new_device })
+    }
+
+    /// À appeler régulièrement pendant l'enregistrement (thread propriétaire, pas
+    /// le callback audio) : déverse l'audio ancien sur disque et signale
+    /// l'approche de la durée maximale.
+    pub fn maintain(&mut self) -> Option<RecordingLimitEvent> {
+        if !self.is_recording() {
+            return None;
         }
-        Ok((buffer, sample_rate))
+
+        let keep = (MEMORY_WINDOW_SECS * self.sample_rate) as usize;
+        let job = self.buffer.lock().ok()?.begin_spill(keep);
+        // Écriture disque hors verrou : le callback audio le prend à chaque bloc
+        let written = job.map(|mut job| {
+            let result = job.write();
+            (job, result)
+        });
+        let (len, spooled, spool_failed) = {
+            let mut buf = self.buffer.lock().ok()?;
+            if let Some((job, result)) = written {
+                if let Err(e) = buf.finish_spill(job, result) {
+                    log::error!("{}. Recording limited to memory ({} min)", e, MEMORY_ONLY_MAX_SAMPLES / 48000 / 60);
+                }
+            }
+            (buf.len(), buf.is_spooled(), buf.spool_failed())
+        };
+
+        let limit = if spool_failed { MEMORY_ONLY_MAX_SAMPLES } else { self.max_samples() };
+        let event = |limit_reached| RecordingLimitEvent {
+            recorded_secs: len as f32 / self.sample_rate as f32,
+            limit_secs: limit as f32 / self.sample_rate as f32,
+            spooled,
+            limit_reached,
+        };
+
+        if len >= limit && !self.limit_reached_reported {
+            self.limit_reached_reported = true;
+            log::warn!("Recording limit reached, further audio is dropped");
+            return Some(event(true));
+        }
+        if len as f32 >= limit as f32 * LIMIT_WARNING_RATIO && !self.limit_warned {
+            self.limit_warned = true;
+            log::warn!("Recording approaching its maximum duration");
+            return Some(event(false));
+        }
+        None
+    }
+
+    fn max_samples(&self) -> usize {
+        MAX_RECORDING_SECS as usize * self.sample_rate as usize
+    }
+
+    pub fn stop(&mut self) -> Result<RecordedAudio, String> {
+        self.stream = None;
+        self.loopback = None;
+        self.mix_queue = None;
+        let audio = std::mem::take(
+            &mut *self.buffer.lock()
+                .map_err(|e| format!("Failed to lock audio buffer: {}", e))?,
+        );
+        Ok(RecordedAudio {
+            audio,
+            sample_rate: self.sample_rate,
+        })
     }
 
     pub fn is_recording(&self) -> bool {
-        self.stream.is_some()
+        self.stream.is_some() || self.loopback.is_some()
     }
 
     pub fn sample_rate(&self) -> u32 {
         self.sample_rate
     }
 
+    /// Nombre d'échantillons capturés depuis le début (disque + mémoire)
+    pub fn captured_samples(&self) -> usize {
+        self.buffer.lock().map(|buf| buf.len()).unwrap_or(0)
+    }
+
+    /// Aperçu micro : ne conserve que les `keep` derniers échantillons
+    pub fn keep_recent(&self, keep: usize) {
+        if let Ok(mut buf) = self.buffer.lock() {
+            buf.keep_tail(keep);
+        }
+    }
+
     /// Retourne un snapshot de l'audio accumulé sans arrêter l'enregistrement
     pub fn get_audio_snapshot(&self) -> (Vec<f32>, u32) {
+        self.get_audio_since(0)
+    }
+
+    /// Snapshot à partir de l'échantillon `offset` : évite de relire tout le
+    /// spool disque quand seul l'audio récent est utile (streaming)
+    pub fn get_audio_since(&self, offset: usize) -> (Vec<f32>, u32) {
         let buffer = self.buffer.lock()
-            .map(|buf| buf.clone())
+            .map(|buf| buf.read_range(offset, buf.len()))
             .unwrap_or_default();
         (buffer, self.sample_rate)
     }
 }
+
+/// Ajoute des frames entrelacées au buffer mono : un canal précis, ou la moyenne
+fn push_mono(buf: &mut SpoolBuffer, data: &[f32], channels: usize, channel: Option<usize>) {
+    if channels <= 1 {
+        buf.push(data);
+        return;
+    }
+    for frame in data.chunks(channels) {
+        match channel {
+            Some(ch) => buf.push_sample(frame.get(ch).copied().unwrap_or(0.0)),
+            None => buf.push_sample(frame.iter().sum::<f32>() / channels as f32),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_mono_selects_channel() {
+        let interleaved = [0.1, 0.9, 0.2, 0.8, 0.3, 0.7];
+        let mut buf = SpoolBuffer::new();
+        push_mono(&mut buf, &interleaved, 2, Some(1));
+        assert_eq!(buf.to_vec(), vec![0.9, 0.8, 0.7]);
+    }
+
+    #[test]
+    fn test_push_mono_mixes_all_channels() {
+        let interleaved = [0.2, 0.4, 1.0, 0.0];
+        let mut buf = SpoolBuffer::new();
+        push_mono(&mut buf, &interleaved, 2, None);
+        let buf = buf.to_vec();
+        assert!((buf[0] - 0.3).abs() < 1e-6);
+        assert!((buf[1] - 0.5).abs() < 1e-6);
+    }
+}
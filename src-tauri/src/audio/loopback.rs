@@ -0,0 +1,205 @@
+//! Capture de l'audio système (loopback) : ce que l'ordinateur joue (appel
+//! visio, vidéo...), via les sources "monitor" de PulseAudio / PipeWire
+//! (pipewire-pulse). Linux uniquement : `pactl` pour lister, `parec` pour capturer.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Préfixe des identifiants de périphériques "audio système" dans `list_audio_devices`
+pub const SYSTEM_DEVICE_PREFIX: &str = "system:";
+
+/// Source monitor de la sortie par défaut (résolue par PulseAudio)
+pub const DEFAULT_MONITOR: &str = "@DEFAULT_MONITOR@";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorSource {
+    pub name: String,
+    pub description: String,
+}
+
+/// Retourne le nom de source PulseAudio d'un identifiant `system:...`
+pub fn source_from_device_id(id: &str) -> Option<&str> {
+    id.strip_prefix(SYSTEM_DEVICE_PREFIX)
+}
+
+/// Liste les sources monitor disponibles (vide hors Linux ou sans `pactl`)
+pub fn list_monitor_sources() -> Vec<MonitorSource> {
+    #[cfg(target_os = "linux")]
+    {
+        use std::process::Command;
+        // Sortie non localisée pour pouvoir parser "Name:" / "Description:"
+        match Command::new("pactl").env("LC_ALL", "C").args(["list", "sources"]).output() {
+            Ok(output) if output.status.success() => {
+                parse_pactl_sources(&String::from_utf8_lossy(&output.stdout))
+            }
+            Ok(output) => {
+                log::warn!("[LOOPBACK] pactl failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+                Vec::new()
+            }
+            Err(e) => {
+                log::debug!("[LOOPBACK] pactl not available: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        Vec::new()
+    }
+}
+
+/// Vrai si la source existe (la source par défaut est toujours considérée présente)
+pub fn is_source_available(source: &str) -> bool {
+    source == DEFAULT_MONITOR || list_monitor_sources().iter().any(|s| s.name == source)
+}
+
+/// Extrait les sources `*.monitor` de la sortie de `pactl list sources`
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_pactl_sources(output: &str) -> Vec<MonitorSource> {
+    let mut sources = Vec::new();
+    let mut current_name: Option<String> = None;
+
+    for line in output.lines() {
+        let line = line.trim();
+        if let Some(name) = line.strip_prefix("Name: ") {
+            current_name = Some(name.trim().to_string());
+        } else if let Some(description) = line.strip_prefix("Description: ") {
+            if let Some(name) = current_name.take() {
+                if name.ends_with(".monitor") {
+                    sources.push(MonitorSource {
+                        name,
+                        description: description.trim().to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    sources
+}
+
+/// Capture d'une source monitor en cours. Les échantillons (mono, f32) sont
+/// passés à `sink` depuis un thread de lecture dédié.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub struct LoopbackCapture {
+    #[cfg(target_os = "linux")]
+    child: std::process::Child,
+    running: Arc<AtomicBool>,
+    reader: Option<std::thread::JoinHandle<()>>,
+}
+
+impl LoopbackCapture {
+    #[cfg(target_os = "linux")]
+    pub fn start<F>(source: &str, sample_rate: u32, mut sink: F) -> Result<Self, String>
+    where
+        F: FnMut(&[f32]) + Send + 'static,
+    {
+        use std::io::Read;
+        use std::process::{Command, Stdio};
+
+        let mut child = Command::new("parec")
+            .arg(format!("--device={}", source))
+            .arg(format!("--rate={}", sample_rate))
+            .args(["--format=float32le", "--channels=1", "--latency-msec=50", "--raw"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("Failed to start parec: {}. Install with 'sudo apt install pulseaudio-utils'", e))?;
+
+        let mut stdout = child.stdout.take().ok_or("Failed to get parec stdout")?;
+        let running = Arc::new(AtomicBool::new(true));
+        let running_clone = running.clone();
+        let source_name = source.to_string();
+
+        let reader = std::thread::spawn(move || {
+            let mut bytes = [0u8; 4096];
+            let mut pending: Vec<u8> = Vec::with_capacity(4);
+            let mut samples: Vec<f32> = Vec::with_capacity(bytes.len() / 4);
+
+            while running_clone.load(Ordering::SeqCst) {
+                let n = match stdout.read(&mut bytes) {
+                    Ok(0) => break,
+                    Ok(n) => n,
+                    Err(e) => {
+                        log::error!("[LOOPBACK] Read error: {}", e);
+                        break;
+                    }
+                };
+
+                pending.extend_from_slice(&bytes[..n]);
+                samples.clear();
+                let whole = pending.len() / 4 * 4;
+                samples.extend(
+                    pending[..whole]
+                        .chunks_exact(4)
+                        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+                );
+                pending.drain(..whole);
+                sink(&samples);
+            }
+
+            if running_clone.load(Ordering::SeqCst) {
+                log::warn!("[LOOPBACK] Capture of '{}' ended unexpectedly", source_name);
+            }
+        });
+
+        log::info!("[LOOPBACK] Capturing system audio from '{}' at {}Hz", source, sample_rate);
+
+        Ok(Self {
+            child,
+            running,
+            reader: Some(reader),
+        })
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn start<F>(_source: &str, _sample_rate: u32, _sink: F) -> Result<Self, String>
+    where
+        F: FnMut(&[f32]) + Send + 'static,
+    {
+        Err("System audio capture is only supported on Linux".to_string())
+    }
+}
+
+impl Drop for LoopbackCapture {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        #[cfg(target_os = "linux")]
+        {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+        // parec tué : stdout fermé, le thread de lecture sort de `read`
+        if let Some(reader) = self.reader.take() {
+            let _ = reader.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pactl_sources_keeps_monitors_only() {
+        let output = "Source #0\n\
+            \tState: SUSPENDED\n\
+            \tName: alsa_output.pci-0000_00_1f.3.analog-stereo.monitor\n\
+            \tDescription: Monitor of Built-in Audio Analog Stereo\n\
+            \tDriver: PipeWire\n\
+            \n\
+            Source #1\n\
+            \tName: alsa_input.usb-Blue_Yeti-00.analog-stereo\n\
+            \tDescription: Yeti Stereo Microphone\n";
+
+        let sources = parse_pactl_sources(output);
+        assert_eq!(
+            sources,
+            vec![MonitorSource {
+                name: "alsa_output.pci-0000_00_1f.3.analog-stereo.monitor".to_string(),
+                description: "Monitor of Built-in Audio Analog Stereo".to_string(),
+            }]
+        );
+    }
+}
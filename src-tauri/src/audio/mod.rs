@@ -1,10 +1,13 @@
 pub mod capture;
 pub mod decoder;
+pub mod loopback;
 pub mod processing;
 pub mod resampling;
+pub mod spool;
 pub mod streaming;
 
 pub use capture::*;
 pub use decoder::AudioDecoder;
-pub use processing::AudioProcessor;
+pub use processing::{AudioProcessor, SilenceDetector};
+pub use spool::RecordedAudio;
 pub use streaming::*;
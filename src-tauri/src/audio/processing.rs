@@ -202,27 +202,7 @@ impl AudioProcessor {
             }
 
             total_frames += 1;
-
-            // RMS energy
-            let rms = (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt();
-
-            // Zero-crossing rate
-            let zcr = frame
-                .windows(2)
-                .filter(|w| (w[0] >= 0.0) != (w[1] >= 0.0))
-                .count() as f32
-                / (frame.len() - 1).max(1) as f32;
-
-            // Update adaptive noise floor (slow adaptation)
-            if rms < self.noise_floor * 2.0 {
-                self.noise_floor = self.noise_floor * 0.99 + rms * 0.01;
-            }
-
-            // Speech detection: energy well above noise floor + reasonable ZCR
-            let energy_above_noise = rms > self.noise_floor * 3.0 && rms > VAD_ENERGY_THRESHOLD;
-            let zcr_in_speech_range = zcr > 0.02 && zcr < 0.5;
-
-            if energy_above_noise && zcr_in_speech_range {
+            if is_speech_frame(frame, &mut self.noise_floor, 1.0) {
                 speech_frames += 1;
             }
         }
@@ -243,6 +223,77 @@ impl AudioProcessor {
     }
 }
 
+/// Energy and zero-crossing test of one VAD frame, updating the adaptive noise floor.
+/// `zcr_scale` brings the zero-crossing rate back to 16kHz (capture rate / 16000).
+fn is_speech_frame(frame: &[f32], noise_floor: &mut f32, zcr_scale: f32) -> bool {
+    // RMS energy
+    let rms = (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt();
+
+    // Zero-crossing rate
+    let zcr = frame
+        .windows(2)
+        .filter(|w| (w[0] >= 0.0) != (w[1] >= 0.0))
+        .count() as f32
+        / (frame.len() - 1).max(1) as f32
+        * zcr_scale;
+
+    // Update adaptive noise floor (slow adaptation)
+    if rms < *noise_floor * 2.0 {
+        *noise_floor = *noise_floor * 0.99 + rms * 0.01;
+    }
+
+    // Speech detection: energy well above noise floor + reasonable ZCR
+    let energy_above_noise = rms > *noise_floor * 3.0 && rms > VAD_ENERGY_THRESHOLD;
+    let zcr_in_speech_range = zcr > 0.02 && zcr < 0.5;
+
+    energy_above_noise && zcr_in_speech_range
+}
+
+/// Tracks silence on the live capture, for hands-free auto-stop.
+/// Works on raw audio at the capture sample rate, 20ms frames.
+pub struct SilenceDetector {
+    sample_rate: u32,
+    frame_size: usize,
+    noise_floor: f32,
+    heard_speech: bool,
+    silent_frames: usize,
+    /// Samples carried over to the next call (incomplete frame)
+    pending: Vec<f32>,
+}
+
+impl SilenceDetector {
+    pub fn new(sample_rate: u32) -> Self {
+        Self {
+            sample_rate,
+            frame_size: (sample_rate as usize / 50).max(1),
+            noise_floor: 0.001,
+            heard_speech: false,
+            silent_frames: 0,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Feeds newly captured samples and returns the current silence length in
+    /// seconds. Silence only counts once speech has been heard.
+    pub fn push(&mut self, audio: &[f32]) -> f32 {
+        self.pending.extend_from_slice(audio);
+        let zcr_scale = self.sample_rate as f32 / 16000.0;
+
+        let complete = self.pending.len() / self.frame_size * self.frame_size;
+        for frame in self.pending[..complete].chunks(self.frame_size) {
+            if is_speech_frame(frame, &mut self.noise_floor, zcr_scale) {
+                self.heard_speech = true;
+                self.silent_frames = 0;
+            } else if self.heard_speech {
+                self.silent_frames += 1;
+            }
+        }
+        self.pending.drain(..complete);
+
+        self.silent_frames as f32 * self.frame_size as f32 / self.sample_rate as f32
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -356,4 +407,24 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_silence_detector_counts_silence_after_speech() {
+        let sample_rate = 48000;
+        let tone = |secs: f32| -> Vec<f32> {
+            (0..(secs * sample_rate as f32) as usize)
+                .map(|i| (i as f32 / sample_rate as f32 * 300.0 * std::f32::consts::TAU).sin() * 0.3)
+                .collect()
+        };
+        let silence = |secs: f32| vec![0.0f32; (secs * sample_rate as f32) as usize];
+
+        let mut detector = SilenceDetector::new(sample_rate);
+        assert_eq!(detector.push(&silence(2.0)), 0.0, "No silence before any speech");
+        detector.push(&tone(1.0));
+        assert!(detector.push(&silence(0.5)) < 0.6);
+        let silent = detector.push(&silence(1.0));
+        assert!((silent - 1.5).abs() < 0.05, "Silence after speech: {}", silent);
+        detector.push(&tone(0.2));
+        assert_eq!(detector.push(&[]), 0.0, "Speech resets the silence");
+    }
 }
@@ -0,0 +1,489 @@
+//! Buffer d'enregistrement sans limite de durée : les échantillons récents
+//! restent en mémoire, les plus anciens sont déversés dans un fichier
+//! temporaire (f32 little-endian brut).
+
+use crate::audio::resampling::resample_audio;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Compteur pour des noms de fichiers spool uniques dans le processus
+static SPOOL_COUNTER: AtomicUsize = AtomicUsize::new(0);
+/// Identifie un contenu de buffer : change à chaque `clear`, pour écarter un
+/// déversement préparé sur un enregistrement qui a été vidé ou remplacé depuis
+static GENERATION_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+const BYTES_PER_SAMPLE: usize = 4;
+
+/// Taille des blocs ré-échantillonnés lors d'un changement de micro
+const RESAMPLE_BLOCK_SECS: usize = 30;
+
+pub struct SpoolBuffer {
+    /// Échantillons les plus récents (après `spooled_samples`)
+    memory: Vec<f32>,
+    file: Option<File>,
+    path: Option<PathBuf>,
+    spooled_samples: usize,
+    spool_failed: bool,
+    /// Un `SpillJob` détient le fichier et écrit hors verrou
+    spilling: bool,
+    generation: usize,
+}
+
+impl Default for SpoolBuffer {
+    fn default() -> Self {
+        Self {
+            memory: Vec::new(),
+            file: None,
+            path: None,
+            spooled_samples: 0,
+            spool_failed: false,
+            spilling: false,
+            generation: GENERATION_COUNTER.fetch_add(1, Ordering::SeqCst),
+        }
+    }
+}
+
+/// Déversement préparé sous le verrou du buffer (`begin_spill`), écrit sans
+/// le tenir (`write`) puis validé (`finish_spill`) : le callback audio, qui
+/// prend le même verrou, n'attend jamais le disque
+pub struct SpillJob {
+    samples: Vec<f32>,
+    file: Option<File>,
+    path: Option<PathBuf>,
+    generation: usize,
+}
+
+impl SpillJob {
+    /// Ajoute les échantillons au fichier spool, créé au premier déversement
+    pub fn write(&mut self) -> Result<(), String> {
+        if self.file.is_none() {
+            let path = std::env::temp_dir().join(format!(
+                "phonon_spool_{}_{}.f32",
+                std::process::id(),
+                SPOOL_COUNTER.fetch_add(1, Ordering::SeqCst)
+            ));
+            let file = OpenOptions::new()
+                .create(true)
+                .truncate(true)
+                .read(true)
+                .write(true)
+                .open(&path)
+                .map_err(|e| format!("Failed to create audio spool {:?}: {}", path, e))?;
+            log::info!("Audio spool started: {:?}", path);
+            self.file = Some(file);
+            self.path = Some(path);
+        }
+
+        let bytes: Vec<u8> = self.samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        let file = self.file.as_mut().ok_or("Audio spool not open")?;
+        file.seek(SeekFrom::End(0)).map_err(|e| e.to_string())?;
+        file.write_all(&bytes)
+            .map_err(|e| format!("Failed to write audio spool: {}", e))
+    }
+
+    /// Le buffer a été vidé pendant l'écriture : le fichier n'appartient plus à personne
+    fn discard(self) {
+        if let Some(mut file) = self.file {
+            if crate::privacy::is_enabled() {
+                let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+                wipe(&mut file, len);
+            }
+        }
+        if let Some(path) = self.path {
+            let _ = fs::remove_file(&path);
+        }
+    }
+}
+
+impl SpoolBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Nombre total d'échantillons (disque + mémoire)
+    pub fn len(&self) -> usize {
+        self.spooled_samples + self.memory.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_spooled(&self) -> bool {
+        self.spooled_samples > 0
+    }
+
+    /// Le fichier spool n'a pas pu être écrit : l'enregistrement reste en mémoire
+    pub fn spool_failed(&self) -> bool {
+        self.spool_failed
+    }
+
+    /// Ajout en mémoire uniquement : appelé depuis le callback audio, sans I/O
+    pub fn push(&mut self, samples: &[f32]) {
+        self.memory.extend_from_slice(samples);
+    }
+
+    pub fn push_sample(&mut self, sample: f32) {
+        self.memory.push(sample);
+    }
+
+    /// Ajoute `source` aux `count` derniers échantillons (mixage micro + système).
+    /// Les échantillons manquants dans `source` comptent comme du silence.
+    pub fn mix_into_tail(&mut self, count: usize, source: &mut std::collections::VecDeque<f32>) {
+        let start = self.memory.len().saturating_sub(count);
+        for sample in &mut self.memory[start..] {
+            let other = source.pop_front().unwrap_or(0.0);
+            *sample = (*sample + other).clamp(-1.0, 1.0);
+        }
+    }
+
+    /// Déverse sur disque tout ce qui dépasse `keep_in_memory` échantillons.
+    /// En cas d'erreur disque, le spool est désactivé et l'audio reste en mémoire.
+    pub fn spill(&mut self, keep_in_memory: usize) -> Result<(), String> {
+        match self.begin_spill(keep_in_memory) {
+            Some(mut job) => {
+                let result = job.write();
+                self.finish_spill(job, result)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Copie ce qui dépasse `keep_in_memory` et confie le fichier au `SpillJob`,
+    /// sans I/O. Les échantillons restent lisibles en mémoire jusqu'à `finish_spill`.
+    pub fn begin_spill(&mut self, keep_in_memory: usize) -> Option<SpillJob> {
+        if self.spool_failed || self.spilling || self.memory.len() <= keep_in_memory {
+            return None;
+        }
+        self.spilling = true;
+        let count = self.memory.len() - keep_in_memory;
+        Some(SpillJob {
+            samples: self.memory[..count].to_vec(),
+            file: self.file.take(),
+            path: self.path.clone(),
+            generation: self.generation,
+        })
+    }
+
+    /// Libère de la mémoire les échantillons écrits, ou désactive le spool si l'écriture a échoué
+    pub fn finish_spill(&mut self, job: SpillJob, result: Result<(), String>) -> Result<(), String> {
+        if job.generation != self.generation {
+            job.discard();
+            return Ok(());
+        }
+        self.spilling = false;
+        self.file = job.file;
+        self.path = job.path;
+        match result {
+            Ok(()) => {
+                self.memory.drain(..job.samples.len());
+                self.spooled_samples += job.samples.len();
+                Ok(())
+            }
+            Err(e) => {
+                self.spool_failed = true;
+                Err(e)
+            }
+        }
+    }
+
+    /// Lit les échantillons `[start, end)`, depuis le disque et/ou la mémoire
+    pub fn read_range(&self, start: usize, end: usize) -> Vec<f32> {
+        let end = end.min(self.len());
+        if start >= end {
+            return Vec::new();
+        }
+
+        let mut out = Vec::with_capacity(end - start);
+
+        if start < self.spooled_samples {
+            let disk_end = end.min(self.spooled_samples);
+            match self.read_spooled(start, disk_end) {
+                Ok(samples) => out.extend(samples),
+                Err(e) => {
+                    log::error!("Failed to read audio spool: {}", e);
+                    out.resize(disk_end - start, 0.0);
+                }
+            }
+        }
+
+        if end > self.spooled_samples {
+            let mem_start = start.saturating_sub(self.spooled_samples);
+            let mem_end = end - self.spooled_samples;
+            out.extend_from_slice(&self.memory[mem_start..mem_end]);
+        }
+
+        out
+    }
+
+    fn read_spooled(&self, start: usize, end: usize) -> Result<Vec<f32>, String> {
+        let path = self.path.as_ref().ok_or("Audio spool not open")?;
+        // Handle séparé : la lecture ne doit pas déplacer le curseur d'écriture
+        let mut file = File::open(path).map_err(|e| e.to_string())?;
+        file.seek(SeekFrom::Start((start * BYTES_PER_SAMPLE) as u64))
+            .map_err(|e| e.to_string())?;
+        let mut bytes = vec![0u8; (end - start) * BYTES_PER_SAMPLE];
+        file.read_exact(&mut bytes).map_err(|e| e.to_string())?;
+        Ok(bytes
+            .chunks_exact(BYTES_PER_SAMPLE)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect())
+    }
+
+    /// Tout l'enregistrement en mémoire (à réserver aux enregistrements courts)
+    pub fn to_vec(&self) -> Vec<f32> {
+        self.read_range(0, self.len())
+    }
+
+    /// Remplace tout le contenu
+    pub fn replace_all(&mut self, samples: Vec<f32>) {
+        self.clear();
+        self.memory = samples;
+    }
+
+    /// Ne garde que les `keep` derniers échantillons (aperçu micro)
+    pub fn keep_tail(&mut self, keep: usize) {
+        let len = self.len();
+        if len <= keep {
+            return;
+        }
+        if self.is_spooled() {
+            let tail = self.read_range(len - keep, len);
+            self.replace_all(tail);
+        } else {
+            self.memory.drain(..len - keep);
+        }
+    }
+
+    /// Ré-échantillonne tout l'enregistrement (changement de micro) par blocs,
+    /// sans jamais charger le spool disque entier en mémoire
+    pub fn resample(&mut self, from_rate: u32, to_rate: u32, keep_in_memory: usize) {
+        if from_rate == to_rate || self.is_empty() {
+            return;
+        }
+        let block = RESAMPLE_BLOCK_SECS * from_rate as usize;
+        let mut out = SpoolBuffer::new();
+        let mut start = 0;
+        while start < self.len() {
+            let end = (start + block).min(self.len());
+            out.push(&resample_audio(&self.read_range(start, end), from_rate, to_rate));
+            if let Err(e) = out.spill(keep_in_memory) {
+                log::error!("{}", e);
+            }
+            start = end;
+        }
+        // L'ancien fichier spool est supprimé au drop ; un déversement en cours sera écarté
+        *self = out;
+    }
+
+    pub fn clear(&mut self) {
+        self.remove_file();
+        self.memory.clear();
+        self.spooled_samples = 0;
+        self.spool_failed = false;
+        self.spilling = false;
+        self.generation = GENERATION_COUNTER.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn remove_file(&mut self) {
+        if let Some(mut file) = self.file.take() {
+            // Mode confidentialité : pas d'audio récupérable après suppression
+            if crate::privacy::is_enabled() {
+                wipe(&mut file, (self.spooled_samples * BYTES_PER_SAMPLE) as u64);
+            }
+        }
+        if let Some(path) = self.path.take() {
+            let _ = fs::remove_file(&path);
+        }
+    }
+}
+
+/// Écrase les `len` premiers octets du fichier avant sa suppression
+fn wipe(file: &mut File, len: u64) {
+    let zeros = vec![0u8; 64 * 1024];
+    let mut remaining = len as usize;
+    if file.seek(SeekFrom::Start(0)).is_ok() {
+        while remaining > 0 {
+            let n = remaining.min(zeros.len());
+            if file.write_all(&zeros[..n]).is_err() {
+                break;
+            }
+            remaining -= n;
+        }
+        let _ = file.sync_all();
+    }
+}
+
+impl Drop for SpoolBuffer {
+    fn drop(&mut self) {
+        self.remove_file();
+    }
+}
+
+/// Enregistrement terminé, prêt à être transcrit
+pub struct RecordedAudio {
+    pub audio: SpoolBuffer,
+    pub sample_rate: u32,
+}
+
+// Les échantillons ne sont jamais écrits dans les logs
+impl std::fmt::Debug for RecordedAudio {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RecordedAudio")
+            .field("samples", &self.audio.len())
+            .field("sample_rate", &self.sample_rate)
+            .finish()
+    }
+}
+
+impl RecordedAudio {
+    pub fn duration_secs(&self) -> f32 {
+        self.audio.len() as f32 / self.sample_rate.max(1) as f32
+    }
+
+    /// Découpe l'enregistrement en segments d'environ `target_secs`, en coupant
+    /// sur le passage le plus calme autour de chaque frontière pour ne pas
+    /// trancher un mot.
+    pub fn segments(&self, target_secs: f32) -> Vec<(usize, usize)> {
+        let rate = self.sample_rate as usize;
+        let total = self.audio.len();
+        let target = (target_secs * self.sample_rate as f32) as usize;
+        let search = rate * 2;
+        let frame = rate / 50; // 20ms
+
+        let mut bounds = Vec::new();
+        let mut start = 0;
+        while total - start > target + search {
+            let window_start = start + target.saturating_sub(search);
+            let window = self.audio.read_range(window_start, window_start + 2 * search);
+            let split = window_start + find_quiet_split(&window, frame);
+            bounds.push((start, split));
+            start = split;
+        }
+        bounds.push((start, total));
+        bounds
+    }
+}
+
+/// Position (en échantillons) du début de la trame la plus calme de `window`
+pub(crate) fn find_quiet_split(window: &[f32], frame: usize) -> usize {
+    if frame == 0 || window.len() < frame {
+        return window.len() / 2;
+    }
+
+    let mut best_pos = window.len() / 2;
+    let mut best_energy = f32::MAX;
+    for (i, chunk) in window.chunks_exact(frame).enumerate() {
+        let energy = chunk.iter().map(|s| s * s).sum::<f32>();
+        if energy < best_energy {
+            best_energy = energy;
+            best_pos = i * frame;
+        }
+    }
+    best_pos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spill_and_read_back() {
+        let mut buf = SpoolBuffer::new();
+        let samples: Vec<f32> = (0..1000).map(|i| i as f32).collect();
+        buf.push(&samples);
+        buf.spill(100).unwrap();
+
+        assert!(buf.is_spooled());
+        assert_eq!(buf.len(), 1000);
+        assert_eq!(buf.read_range(850, 950), samples[850..950].to_vec());
+        assert_eq!(buf.to_vec(), samples);
+
+        let path = buf.path.clone().unwrap();
+        drop(buf);
+        assert!(!path.exists(), "spool file must be removed on drop");
+    }
+
+    #[test]
+    fn test_keep_tail() {
+        let samples: Vec<f32> = (0..1000).map(|i| i as f32).collect();
+
+        let mut buf = SpoolBuffer::new();
+        buf.push(&samples);
+        buf.keep_tail(100);
+        assert_eq!(buf.to_vec(), samples[900..].to_vec());
+
+        let mut spooled = SpoolBuffer::new();
+        spooled.push(&samples);
+        spooled.spill(50).unwrap();
+        spooled.keep_tail(100);
+        assert!(!spooled.is_spooled());
+        assert_eq!(spooled.to_vec(), samples[900..].to_vec());
+    }
+
+    #[test]
+    fn test_spill_outside_lock() {
+        let samples: Vec<f32> = (0..1000).map(|i| i as f32).collect();
+        let mut buf = SpoolBuffer::new();
+        buf.push(&samples[..800]);
+
+        let mut job = buf.begin_spill(100).unwrap();
+        assert!(buf.begin_spill(100).is_none(), "one spill at a time");
+        // Le callback continue d'écrire et les lectures voient tout pendant l'écriture
+        buf.push(&samples[800..]);
+        assert_eq!(buf.to_vec(), samples);
+        let result = job.write();
+        buf.finish_spill(job, result).unwrap();
+
+        assert!(buf.is_spooled());
+        assert_eq!(buf.memory.len(), 300);
+        assert_eq!(buf.to_vec(), samples);
+
+        // Buffer vidé pendant l'écriture : le déversement est écarté
+        let mut job = buf.begin_spill(0).unwrap();
+        buf.clear();
+        let result = job.write();
+        let path = job.path.clone().unwrap();
+        buf.finish_spill(job, result).unwrap();
+        assert!(buf.is_empty());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_resample_spooled_buffer() {
+        let mut buf = SpoolBuffer::new();
+        buf.push(&vec![0.1f32; 48000 * 65]);
+        buf.spill(48000).unwrap();
+
+        buf.resample(48000, 16000, 16000);
+        let expected = 16000 * 65;
+        assert!((buf.len() as i64 - expected as i64).abs() < 16000, "len {}", buf.len());
+        assert!(buf.is_spooled());
+    }
+
+    #[test]
+    fn test_find_quiet_split() {
+        let mut window = vec![0.5f32; 1000];
+        for s in &mut window[600..700] {
+            *s = 0.0;
+        }
+        assert_eq!(find_quiet_split(&window, 100), 600);
+    }
+
+    #[test]
+    fn test_segments_cover_whole_recording() {
+        let mut audio = SpoolBuffer::new();
+        audio.push(&vec![0.1f32; 16000 * 95]);
+        let recorded = RecordedAudio { audio, sample_rate: 16000 };
+
+        let segments = recorded.segments(30.0);
+        assert!(segments.len() >= 3);
+        assert_eq!(segments.first().unwrap().0, 0);
+        assert_eq!(segments.last().unwrap().1, 16000 * 95);
+        for pair in segments.windows(2) {
+            assert_eq!(pair[0].1, pair[1].0);
+        }
+    }
+}
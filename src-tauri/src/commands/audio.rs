@@ -1,4 +1,4 @@
-use crate::audio::AudioCapture;
+use crate::audio::{AudioCapture, InputSelection};
 use crate::types::AudioDevice;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
@@ -8,6 +8,9 @@ use serde::Serialize;
 static MIC_PREVIEW_ACTIVE: AtomicBool = AtomicBool::new(false);
 static MIC_PREVIEW_DEVICE: Mutex<Option<String>> = Mutex::new(None);
 
+/// Audio conservé par l'aperçu micro (~0,2 s à 48 kHz)
+const PREVIEW_WINDOW_SAMPLES: usize = 9600;
+
 #[derive(Clone, Serialize)]
 struct MicLevelEvent {
     levels: Vec<f32>,
@@ -30,7 +33,8 @@ pub fn start_mic_preview(app: AppHandle, device_id: Option<String>) -> Result<()
     }
 
     std::thread::spawn(move || {
-        let mut capture = match AudioCapture::new(device_id.as_deref()) {
+        let selection = InputSelection::device(device_id.as_deref());
+        let mut capture = match AudioCapture::new(&selection) {
             Ok(c) => c,
             Err(e) => {
                 log::error!("Mic preview: failed to create capture: {}", e);
@@ -38,7 +42,7 @@ pub fn start_mic_preview(app: AppHandle, device_id: Option<String>) -> Result<()
             }
         };
 
-        if let Err(e) = capture.start(device_id.as_deref()) {
+        if let Err(e) = capture.start(&selection) {
             log::error!("Mic preview: failed to start capture: {}", e);
             return;
         }
@@ -49,12 +53,12 @@ pub fn start_mic_preview(app: AppHandle, device_id: Option<String>) -> Result<()
         while MIC_PREVIEW_ACTIVE.load(Ordering::SeqCst) {
             std::thread::sleep(std::time::Duration::from_millis(50));
 
-            let (buffer, _sample_rate) = capture.get_audio_snapshot();
-            let len = buffer.len();
+            // L'aperçu n'enregistre rien : seul l'audio récent est conservé
+            capture.keep_recent(PREVIEW_WINDOW_SAMPLES);
 
             // Take only the last ~2400 samples (~50ms at 48kHz)
-            let recent_start = len.saturating_sub(2400);
-            let recent = &buffer[recent_start..];
+            let recent_start = capture.captured_samples().saturating_sub(2400);
+            let (recent, _sample_rate) = capture.get_audio_since(recent_start);
 
             let mut levels = Vec::with_capacity(num_bars);
             if recent.is_empty() {
@@ -80,6 +84,10 @@ pub fn start_mic_preview(app: AppHandle, device_id: Option<String>) -> Result<()
             }
 
             let _ = app.emit("mic-level", MicLevelEvent { levels });
+
+            if let Some(event) = capture.poll_device() {
+                let _ = app.emit("audio-device-changed", event);
+            }
         }
 
         // Cleanup
@@ -0,0 +1,11 @@
+use crate::storage::encryption;
+
+#[tauri::command]
+pub fn get_storage_encryption_status() -> encryption::EncryptionStatus {
+    encryption::status()
+}
+
+#[tauri::command]
+pub fn rotate_storage_key() -> Result<String, String> {
+    encryption::rotate_key()
+}
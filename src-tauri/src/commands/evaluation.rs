@@ -0,0 +1,190 @@
+use crate::audio::AudioDecoder;
+use crate::engines::evaluation::{self, EngineEvaluation, EvaluationReport, FileEvaluation, PeakMemorySampler};
+use crate::state::AppState;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tauri::{AppHandle, Emitter, State};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EvaluationProgress {
+    pub model: String,
+    pub engine_index: usize,
+    pub engine_count: usize,
+    pub current: usize,
+    pub total: usize,
+    pub file_name: String,
+}
+
+/// Fichier audio du corpus et sa transcription de référence (`<nom>.txt`)
+struct CorpusEntry {
+    path: PathBuf,
+    file_name: String,
+    reference: String,
+}
+
+/// Audio supporté du dossier ayant une référence ; les autres sont listés à part
+fn read_corpus(folder: &Path) -> Result<(Vec<CorpusEntry>, Vec<String>), String> {
+    let entries = std::fs::read_dir(folder)
+        .map_err(|e| format!("Failed to read corpus folder {:?}: {}", folder, e))?;
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file() && AudioDecoder::is_supported(path))
+        .collect();
+    paths.sort();
+
+    let mut corpus = Vec::new();
+    let mut skipped = Vec::new();
+    for path in paths {
+        let file_name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown")
+            .to_string();
+        match std::fs::read_to_string(path.with_extension("txt")) {
+            Ok(reference) => corpus.push(CorpusEntry {
+                path,
+                file_name,
+                reference: reference.trim().to_string(),
+            }),
+            Err(_) => skipped.push(file_name),
+        }
+    }
+    Ok((corpus, skipped))
+}
+
+/// Évalue chaque moteur et modèle installé sur un dossier de fichiers audio
+/// accompagnés de leur transcription de référence, puis écrit le rapport
+/// en JSON et CSV (dans `output_dir`, par défaut le dossier du corpus).
+#[tauri::command]
+pub async fn evaluate_engines(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    folder: String,
+    output_dir: Option<String>,
+) -> Result<EvaluationReport, String> {
+    let folder_path = PathBuf::from(&folder);
+    let (corpus, skipped_files) = read_corpus(&folder_path)?;
+    if corpus.is_empty() {
+        return Err("No audio file with a reference transcript (.txt) in this folder".to_string());
+    }
+
+    let settings = {
+        let settings = state.settings.read().map_err(|e| e.to_string())?;
+        settings.clone()
+    };
+    let variants = state.installed_engine_variants(&settings);
+    if variants.is_empty() {
+        return Err("No transcription engine installed".to_string());
+    }
+
+    let engine_count = variants.len();
+    let mut engines = Vec::new();
+
+    for (engine_index, (engine_type, model, variant)) in variants.into_iter().enumerate() {
+        log::info!("[EVAL] Evaluating {} on {} file(s)", model, corpus.len());
+
+        // Mesure mémoire à partir du chargement du modèle
+        let sampler = PeakMemorySampler::start();
+        let load_start = Instant::now();
+        let engine = match state.load_standalone_engine(&variant, engine_type) {
+            Ok(engine) => engine,
+            Err(e) => {
+                sampler.stop();
+                log::warn!("[EVAL] Failed to load {}: {}", model, e);
+                engines.push(EngineEvaluation::failed(engine_type, model, e));
+                continue;
+            }
+        };
+        let load_time_ms = load_start.elapsed().as_millis() as u64;
+
+        let mut files = Vec::new();
+        for (index, entry) in corpus.iter().enumerate() {
+            let _ = app.emit(
+                "evaluation-progress",
+                EvaluationProgress {
+                    model: model.clone(),
+                    engine_index: engine_index + 1,
+                    engine_count,
+                    current: index + 1,
+                    total: corpus.len(),
+                    file_name: entry.file_name.clone(),
+                },
+            );
+
+            let mut file = FileEvaluation {
+                file_name: entry.file_name.clone(),
+                reference: entry.reference.clone(),
+                hypothesis: None,
+                audio_seconds: 0.0,
+                processing_ms: 0,
+                word_errors: Default::default(),
+                char_errors: Default::default(),
+                error: None,
+            };
+
+            let (audio, sample_rate) = match AudioDecoder::decode_file(&entry.path) {
+                Ok(data) => data,
+                Err(e) => {
+                    file.error = Some(format!("Failed to decode: {}", e));
+                    files.push(file);
+                    continue;
+                }
+            };
+            file.audio_seconds = audio.len() as f64 / sample_rate.max(1) as f64;
+
+            let start = Instant::now();
+            let result = engine.transcribe_with_options(&audio, sample_rate, &settings.whisper_decode);
+            file.processing_ms = start.elapsed().as_millis() as u64;
+
+            match result {
+                Ok(result) => {
+                    file.word_errors = evaluation::word_errors(&entry.reference, &result.text);
+                    file.char_errors = evaluation::char_errors(&entry.reference, &result.text);
+                    file.hypothesis = Some(result.text);
+                }
+                Err(e) => file.error = Some(e),
+            }
+            files.push(file);
+        }
+
+        drop(engine);
+        let memory = sampler.stop();
+        let evaluation = EngineEvaluation::from_files(engine_type, model, load_time_ms, memory, files);
+        log::info!(
+            "[EVAL] {}: WER {:.3}, CER {:.3}, RTF {:.3}",
+            evaluation.model,
+            evaluation.word_error_rate,
+            evaluation.char_error_rate,
+            evaluation.real_time_factor
+        );
+        engines.push(evaluation);
+    }
+
+    let now = chrono::Local::now();
+    let mut report = EvaluationReport {
+        corpus_path: folder,
+        created_at: now.to_rfc3339(),
+        file_count: corpus.len(),
+        skipped_files,
+        engines,
+        json_path: None,
+        csv_path: None,
+    };
+
+    let out_dir = output_dir.map(PathBuf::from).unwrap_or(folder_path);
+    let stem = format!("phonon-evaluation-{}", now.format("%Y%m%d-%H%M%S"));
+    let json_path = out_dir.join(format!("{}.json", stem));
+    let csv_path = out_dir.join(format!("{}.csv", stem));
+    report.json_path = Some(json_path.to_string_lossy().to_string());
+    report.csv_path = Some(csv_path.to_string_lossy().to_string());
+
+    let json = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+    std::fs::write(&json_path, json)
+        .map_err(|e| format!("Failed to write report {:?}: {}", json_path, e))?;
+    std::fs::write(&csv_path, report.to_csv())
+        .map_err(|e| format!("Failed to write report {:?}: {}", csv_path, e))?;
+
+    Ok(report)
+}
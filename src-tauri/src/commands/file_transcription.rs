@@ -1,7 +1,7 @@
 use crate::audio::AudioDecoder;
 use crate::state::AppState;
 use crate::storage::history;
-use crate::types::TranscriptionResult;
+use crate::types::{TranscriptionResult, WhisperDecodeOptions};
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Emitter, State};
 
@@ -21,12 +21,14 @@ pub struct FileTranscriptionProgress {
     pub status: String,
 }
 
-/// Transcribe multiple audio files using the currently configured engine
+/// Transcribe multiple audio files using the currently configured engine.
+/// `decode_options` remplace les options Whisper des réglages pour ce lot.
 #[tauri::command]
 pub async fn transcribe_files(
     app: AppHandle,
     state: State<'_, AppState>,
     paths: Vec<String>,
+    decode_options: Option<WhisperDecodeOptions>,
 ) -> Result<Vec<FileTranscriptionResult>, String> {
     let mut results = Vec::new();
     let total = paths.len();
@@ -86,15 +88,8 @@ pub async fn transcribe_files(
             },
         );
 
-        // Transcribe using current engine
-        let transcription = {
-            let engine_guard = state.engine.read().map_err(|e| e.to_string())?;
-            if let Some(ref engine) = *engine_guard {
-                engine.transcribe(&audio, sample_rate)
-            } else {
-                Err("No engine initialized".to_string())
-            }
-        };
+        // Moteur actif, puis chaîne de secours
+        let transcription = state.transcribe_with_options(&audio, sample_rate, decode_options.as_ref());
 
         match transcription {
             Ok(result) => {
@@ -1,52 +1,42 @@
 use std::sync::Arc;
-use keyring::Entry;
 use tauri::{Emitter, State};
 use tokio::sync::RwLock;
 
 use crate::engines::ModelManager;
 use crate::llm::{groq_client, LocalLlmEngine};
 use crate::storage::config;
+use crate::storage::credentials::{self, CredentialStoreStatus, GROQ_API_KEY};
+use crate::storage::managed;
 use crate::types::{LlmProvider, LocalLlmModel};
 
-const SERVICE_NAME: &str = "wakascribe";
-const ACCOUNT_NAME: &str = "groq_api_key";
-
-/// Stocke la clé API Groq dans les settings de l'application (et keyring en backup)
+/// Stocke la clé API Groq dans le trousseau (ou le fichier chiffré de repli)
 #[tauri::command]
 pub fn set_groq_api_key(key: String) -> Result<(), String> {
-    // Stocker dans les settings (méthode principale)
-    let mut settings = config::load_settings();
-    settings.groq_api_key = Some(key.clone());
-    config::save_settings(&settings)?;
-
-    // Essayer aussi le keyring en backup (mais ne pas échouer si ça marche pas)
-    if let Ok(entry) = Entry::new(SERVICE_NAME, ACCOUNT_NAME) {
-        let _ = entry.set_password(&key);
+    if managed::is_groq_disabled() {
+        return Err("Groq est désactivé par votre organisation".to_string());
     }
-
+    let key = key.trim();
+    if key.is_empty() {
+        return Err("Clé API Groq vide".to_string());
+    }
+    let backend = credentials::set(GROQ_API_KEY, key)?;
+    log::info!("Groq API key stored ({:?})", backend);
     Ok(())
 }
 
-/// Récupère la clé API Groq (settings en priorité, puis keyring)
+/// Récupère la clé API Groq
 #[tauri::command]
 pub fn get_groq_api_key() -> Option<String> {
     get_groq_api_key_internal()
 }
 
 /// Récupère la clé API Groq (usage interne sans attribut tauri::command)
-/// Vérifie d'abord dans les settings, puis dans le keyring
+/// `None` si Groq est désactivé par la configuration d'équipe
 pub fn get_groq_api_key_internal() -> Option<String> {
-    // 1. Vérifier dans les settings
-    let settings = config::load_settings();
-    if let Some(ref key) = settings.groq_api_key {
-        if !key.is_empty() {
-            return Some(key.clone());
-        }
+    if managed::is_groq_disabled() {
+        return None;
     }
-
-    // 2. Fallback sur le keyring
-    let entry = Entry::new(SERVICE_NAME, ACCOUNT_NAME).ok()?;
-    entry.get_password().ok()
+    credentials::get(GROQ_API_KEY)
 }
 
 /// Vérifie si une clé API Groq existe
@@ -82,20 +72,16 @@ pub async fn validate_groq_api_key(key: String) -> bool {
     }
 }
 
-/// Supprime la clé API Groq (des settings et du keyring)
+/// Supprime la clé API Groq du stockage des secrets
 #[tauri::command]
 pub fn delete_groq_api_key() -> Result<(), String> {
-    // Supprimer des settings
-    let mut settings = config::load_settings();
-    settings.groq_api_key = None;
-    config::save_settings(&settings)?;
-
-    // Essayer de supprimer du keyring aussi
-    if let Ok(entry) = Entry::new(SERVICE_NAME, ACCOUNT_NAME) {
-        let _ = entry.delete_credential();
-    }
+    credentials::delete(GROQ_API_KEY)
+}
 
-    Ok(())
+/// Indique où sont stockés les secrets (trousseau ou fichier chiffré)
+#[tauri::command]
+pub fn get_credential_store_status() -> CredentialStoreStatus {
+    credentials::status()
 }
 
 /// Récupère les informations de quota Groq
@@ -1,5 +1,7 @@
 pub mod app_log;
 pub mod audio;
+pub mod encryption;
+pub mod evaluation;
 pub mod file_transcription;
 pub mod integrations;
 pub mod llm;
@@ -10,9 +12,11 @@ pub mod stats;
 pub mod system_integration;
 pub mod transcription;
 pub mod translation_history;
+pub mod voice_commands;
 
 pub use app_log::*;
 pub use audio::*;
+pub use encryption::*;
 pub use integrations::*;
 pub use llm::*;
 pub use models::*;
@@ -22,3 +26,4 @@ pub use stats::*;
 pub use system_integration::*;
 pub use transcription::*;
 pub use translation_history::*;
+pub use voice_commands::*;
@@ -1,4 +1,5 @@
 use tauri::{AppHandle, Emitter, State};
+use crate::engines::EngineHealth;
 use crate::state::AppState;
 use crate::types::{EngineType, ModelSize, ParakeetModelSize, VoskLanguage};
 use serde::{Deserialize, Serialize};
@@ -11,6 +12,30 @@ pub struct ModelInfo {
     pub display_name: String,
     pub available: bool,
     pub size_bytes: u64,
+    /// Mémoire estimée pour l'inférence
+    pub ram_bytes: u64,
+    /// Vitesse relative à large-v3
+    pub relative_speed: f32,
+    pub english_only: bool,
+    pub quantization: Option<String>,
+    /// La mémoire estimée tient dans la RAM totale de la machine
+    pub fits_in_memory: bool,
+}
+
+/// Mémoire de la machine, pour choisir un modèle
+#[derive(Debug, Clone, Serialize)]
+pub struct SystemMemory {
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+}
+
+fn system_memory() -> SystemMemory {
+    let mut system = sysinfo::System::new();
+    system.refresh_memory();
+    SystemMemory {
+        total_bytes: system.total_memory(),
+        available_bytes: system.available_memory(),
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -22,17 +47,29 @@ pub struct DownloadProgress {
 
 #[tauri::command]
 pub fn get_available_models(state: State<'_, AppState>) -> Vec<ModelInfo> {
-    [ModelSize::Tiny, ModelSize::Small, ModelSize::Medium]
+    let memory = system_memory();
+    ModelSize::ALL
         .into_iter()
         .map(|size| ModelInfo {
             size,
-            display_name: size.display_name().to_string(),
+            display_name: size.display_name(),
             available: state.model_manager.is_model_available(size),
             size_bytes: size.size_bytes(),
+            ram_bytes: size.ram_bytes(),
+            relative_speed: size.relative_speed(),
+            english_only: size.is_english_only(),
+            quantization: size.quantization().map(String::from),
+            // Sans information mémoire (plateforme non supportée), ne rien exclure
+            fits_in_memory: memory.total_bytes == 0 || size.ram_bytes() <= memory.total_bytes,
         })
         .collect()
 }
 
+#[tauri::command]
+pub fn get_system_memory() -> SystemMemory {
+    system_memory()
+}
+
 #[tauri::command]
 pub fn get_current_model(state: State<'_, AppState>) -> Result<ModelSize, String> {
     let settings = state.settings.read().map_err(|e| e.to_string())?;
@@ -111,6 +148,12 @@ pub fn is_engine_ready(state: State<'_, AppState>) -> bool {
     state.engine.read().map(|e| e.is_some()).unwrap_or(false)
 }
 
+/// Vérifie immédiatement le moteur actif et la chaîne de secours
+#[tauri::command]
+pub fn get_engine_health(state: State<'_, AppState>) -> Vec<EngineHealth> {
+    state.check_engine_health()
+}
+
 // ===== Vosk Model Commands =====
 
 #[derive(Debug, Clone, Serialize)]
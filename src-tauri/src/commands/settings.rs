@@ -1,8 +1,10 @@
 use tauri::{AppHandle, Emitter, State};
-use tauri_plugin_global_shortcut::GlobalShortcutExt;
-use crate::hotkeys::parse_hotkey;
+use crate::hotkeys::{self, HotkeyStatus};
 use crate::state::AppState;
-use crate::storage::{config, dictionary};
+use crate::storage::config::{self, SettingsIssue};
+use crate::storage::managed::{self, ManagedStatus};
+use crate::storage::settings_export::{self, SettingsImportSummary};
+use crate::storage::{dictionary, encryption};
 use crate::types::AppSettings;
 
 #[tauri::command]
@@ -17,13 +19,62 @@ pub fn update_settings(
     state: State<'_, AppState>,
     new_settings: AppSettings
 ) -> Result<(), String> {
+    let mut issues = config::validate(&new_settings);
+    issues.extend(managed::check(&new_settings));
+    if !issues.is_empty() {
+        return Err(format!("Invalid settings: {}", join_issues(&issues)));
+    }
+    let hotkey_errors = apply_settings(&app, &state, new_settings)?;
+    if !hotkey_errors.is_empty() {
+        return Err(format!("Shortcut not changed: {}", hotkey_errors.join("; ")));
+    }
+    Ok(())
+}
+
+/// Persiste et applique des réglages déjà validés (raccourcis, moteur, chiffrement...).
+/// Renvoie les raccourcis refusés par l'OS, sauvegardés avec leur ancienne valeur.
+fn apply_settings(app: &AppHandle, state: &AppState, mut new_settings: AppSettings) -> Result<Vec<String>, String> {
+    for issue in managed::enforce(&mut new_settings) {
+        log::warn!("[SETTINGS] {} (team configuration)", issue);
+    }
     let old_settings = state.settings.read().map_err(|e| e.to_string())?.clone();
-    let ptt_hotkey_changed = old_settings.hotkey_push_to_talk != new_settings.hotkey_push_to_talk;
-    let translate_hotkey_changed = old_settings.hotkey_translate != new_settings.hotkey_translate;
-    let translation_enabled_changed = old_settings.translation_enabled != new_settings.translation_enabled;
+    let hotkeys_changed = old_settings.hotkey_push_to_talk != new_settings.hotkey_push_to_talk
+        || old_settings.hotkey_toggle_record != new_settings.hotkey_toggle_record
+        || old_settings.hotkey_translate != new_settings.hotkey_translate
+        || old_settings.hotkey_voice_action != new_settings.hotkey_voice_action
+        || old_settings.translation_enabled != new_settings.translation_enabled;
     let engine_type_changed = old_settings.engine_type != new_settings.engine_type;
 
-    config::save_settings(&new_settings)?;
+    // Migre les fichiers avant de persister le réglage : en cas d'échec
+    // (trousseau indisponible), l'ancien état reste en vigueur
+    if old_settings.storage_encryption_enabled != new_settings.storage_encryption_enabled {
+        if let Err(e) = encryption::set_enabled(new_settings.storage_encryption_enabled) {
+            let _ = encryption::set_enabled(old_settings.storage_encryption_enabled);
+            return Err(format!("Failed to update storage encryption: {}", e));
+        }
+    }
+
+    // Raccourcis enregistrés avant la sauvegarde : config.json ne garde que ceux
+    // que l'OS a acceptés, les autres reprennent leur ancienne valeur
+    let hotkey_errors = if hotkeys_changed {
+        let statuses = hotkeys::apply(app, &new_settings);
+        let errors = hotkeys::keep_registered(&statuses, &old_settings, &mut new_settings);
+        if !errors.is_empty() {
+            hotkeys::apply(app, &new_settings);
+        }
+        errors
+    } else {
+        Vec::new()
+    };
+
+    if let Err(e) = config::save_settings(&new_settings) {
+        if hotkeys_changed {
+            hotkeys::apply(app, &old_settings);
+        }
+        return Err(e);
+    }
+    crate::privacy::set_enabled(new_settings.privacy_mode);
+    crate::engines::parakeet::set_beam_size(new_settings.parakeet_beam_size);
 
     {
         let mut settings = state.settings.write().map_err(|e| e.to_string())?;
@@ -36,56 +87,58 @@ pub fn update_settings(
         }
     }
 
-    if ptt_hotkey_changed {
-        if let Err(e) = update_shortcut(&app, &old_settings.hotkey_push_to_talk, &new_settings.hotkey_push_to_talk) {
-            log::warn!("Failed to update PTT shortcut: {}. Restart may be required.", e);
-        }
-    }
-
-    if translation_enabled_changed || translate_hotkey_changed {
-        if old_settings.translation_enabled {
-            if let Some(old_shortcut) = parse_hotkey(&old_settings.hotkey_translate) {
-                let _ = app.global_shortcut().unregister(old_shortcut);
-            }
-        }
-        if new_settings.translation_enabled {
-            if let Some(new_shortcut) = parse_hotkey(&new_settings.hotkey_translate) {
-                if let Err(e) = app.global_shortcut().register(new_shortcut) {
-                    log::warn!("Failed to register translate shortcut: {}", e);
-                } else {
-                    log::info!("Translate shortcut registered: {}", new_settings.hotkey_translate);
-                }
-            }
-        }
-    }
-
     // Notify all windows of settings change (for subtitles, floating window, etc.)
     let _ = app.emit("settings-changed", &new_settings);
 
-    Ok(())
+    Ok(hotkey_errors)
 }
 
-/// Met à jour un raccourci dynamiquement
-fn update_shortcut(app: &AppHandle, old_hotkey: &str, new_hotkey: &str) -> Result<(), String> {
-    if let Some(old_shortcut) = parse_hotkey(old_hotkey) {
-        let _ = app.global_shortcut().unregister(old_shortcut);
-    }
+fn join_issues(issues: &[SettingsIssue]) -> String {
+    issues.iter().map(|issue| issue.to_string()).collect::<Vec<_>>().join("; ")
+}
 
-    if let Some(new_shortcut) = parse_hotkey(new_hotkey) {
-        app.global_shortcut()
-            .register(new_shortcut)
-            .map_err(|e| format!("Failed to register new shortcut: {}", e))?;
-        log::info!("PTT shortcut updated to: {}", new_hotkey);
-    } else {
-        return Err(format!("Invalid hotkey format: {}", new_hotkey));
-    }
+/// Exporte réglages, dictionnaire et snippets (sans secrets) dans un fichier partageable
+#[tauri::command]
+pub fn export_settings(path: String) -> Result<(), String> {
+    settings_export::export(std::path::Path::new(&path))
+}
 
-    Ok(())
+/// Importe un export : applique les réglages, puis fusionne dictionnaire et
+/// snippets une fois les réglages acceptés
+#[tauri::command]
+pub fn import_settings(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<SettingsImportSummary, String> {
+    let current = state.settings.read().map_err(|e| e.to_string())?.clone();
+    let pending = settings_export::import(std::path::Path::new(&path), &current)?;
+    let hotkey_errors = apply_settings(&app, &state, pending.settings.clone())?;
+
+    let mut summary = pending.commit()?;
+    summary.issues.extend(hotkey_errors.into_iter().map(|message| SettingsIssue {
+        field: "hotkeys".to_string(),
+        message,
+    }));
+    let _ = app.emit("snippets-changed", ());
+    Ok(summary)
+}
+
+/// Réglages imposés ou pré-remplis par la configuration d'équipe
+#[tauri::command]
+pub fn get_managed_settings() -> ManagedStatus {
+    managed::status()
+}
+
+/// État d'enregistrement de chaque raccourci global
+#[tauri::command]
+pub fn get_hotkey_status() -> Vec<HotkeyStatus> {
+    hotkeys::status()
 }
 
 #[tauri::command]
 pub fn get_dictionary() -> Result<Vec<String>, String> {
-    Ok(dictionary::load_dictionary().words)
+    Ok(dictionary::effective_words())
 }
 
 #[tauri::command]
@@ -95,5 +148,8 @@ pub fn add_dictionary_word(word: String) -> Result<(), String> {
 
 #[tauri::command]
 pub fn remove_dictionary_word(word: String) -> Result<(), String> {
+    if managed::dictionary().contains(&word) {
+        return Err("This word belongs to the team dictionary".to_string());
+    }
     dictionary::remove_word(&word)
 }
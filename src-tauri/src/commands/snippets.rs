@@ -1,9 +1,9 @@
-use crate::storage::snippets;
+use crate::storage::{managed, snippets};
 use crate::types::Snippet;
 
 #[tauri::command]
 pub fn get_snippets() -> Result<Vec<Snippet>, String> {
-    Ok(snippets::load_snippets().snippets)
+    Ok(snippets::effective_snippets())
 }
 
 #[tauri::command]
@@ -13,10 +13,20 @@ pub fn add_snippet(snippet: Snippet) -> Result<(), String> {
 
 #[tauri::command]
 pub fn update_snippet(id: String, snippet: Snippet) -> Result<(), String> {
+    ensure_user_snippet(&id)?;
     snippets::update_snippet(&id, snippet)
 }
 
 #[tauri::command]
 pub fn remove_snippet(id: String) -> Result<(), String> {
+    ensure_user_snippet(&id)?;
     snippets::remove_snippet(&id)
 }
+
+/// Les snippets de la bibliothèque d'équipe sont en lecture seule
+fn ensure_user_snippet(id: &str) -> Result<(), String> {
+    if managed::is_team_snippet(id) {
+        return Err("This snippet is managed by your organization".to_string());
+    }
+    Ok(())
+}
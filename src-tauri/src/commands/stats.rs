@@ -3,7 +3,7 @@ use crate::types::UsageStats;
 
 #[tauri::command]
 pub fn get_usage_stats() -> Result<UsageStats, String> {
-    Ok(stats::load_stats())
+    stats::try_load_stats()
 }
 
 #[tauri::command]
@@ -1,4 +1,4 @@
-use std::process::Command;
+use crate::platform::{self, TextInsertionReport};
 use tauri::{AppHandle, Manager, WebviewWindow};
 use tauri_plugin_clipboard_manager::ClipboardExt;
 
@@ -12,7 +12,7 @@ fn copy_to_clipboard(app: &AppHandle, text: &str) -> Result<(), String> {
 /// Simulates Cmd+V paste keystroke via AppleScript (macOS)
 #[cfg(target_os = "macos")]
 fn simulate_paste() -> Result<(), String> {
-    let output = Command::new("osascript")
+    let output = std::process::Command::new("osascript")
         .args([
             "-e",
             "tell application \"System Events\" to keystroke \"v\" using command down",
@@ -105,58 +105,12 @@ fn simulate_paste() -> Result<(), String> {
     Ok(())
 }
 
-/// Simulates Ctrl+V paste keystroke on Linux (X11 via xdotool, Wayland via wtype/ydotool)
+/// Simulates Ctrl+V paste keystroke on Linux (XTest or uinput, then wtype/ydotool/xdotool)
 #[cfg(target_os = "linux")]
 fn simulate_paste() -> Result<(), String> {
-    // Detect display server
-    let wayland = std::env::var("WAYLAND_DISPLAY").is_ok();
-
-    if wayland {
-        // Try wtype first (more common on Wayland)
-        let wtype_result = Command::new("wtype")
-            .args(["-M", "ctrl", "v", "-m", "ctrl"])
-            .output();
-
-        match wtype_result {
-            Ok(output) if output.status.success() => return Ok(()),
-            _ => {
-                // Fallback to ydotool (requires ydotoold daemon)
-                let ydotool_result = Command::new("ydotool")
-                    .args(["key", "29:1", "47:1", "47:0", "29:0"]) // Ctrl down, V down, V up, Ctrl up
-                    .output();
-
-                match ydotool_result {
-                    Ok(output) if output.status.success() => return Ok(()),
-                    Ok(output) => {
-                        let stderr = String::from_utf8_lossy(&output.stderr);
-                        return Err(format!(
-                            "Wayland paste failed. Install 'wtype' or 'ydotool'. Error: {}",
-                            stderr
-                        ));
-                    }
-                    Err(e) => {
-                        return Err(format!(
-                            "Wayland paste failed. Install 'wtype' or 'ydotool'. Error: {}",
-                            e
-                        ));
-                    }
-                }
-            }
-        }
-    } else {
-        // X11 - use xdotool
-        let output = Command::new("xdotool")
-            .args(["key", "--clearmodifiers", "ctrl+v"])
-            .output()
-            .map_err(|e| format!("Failed to execute xdotool. Install it with 'sudo apt install xdotool'. Error: {}", e))?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(format!("xdotool failed: {}", stderr));
-        }
-
-        Ok(())
-    }
+    let path = crate::linux_input::send_paste_keys()?;
+    log::debug!("[PASTE] Paste keystroke sent via {}", path);
+    Ok(())
 }
 
 /// Fallback for other platforms (BSD, etc.)
@@ -188,6 +142,12 @@ pub async fn auto_paste(app: AppHandle, text: String) -> Result<(), String> {
     Ok(())
 }
 
+/// Reports which text insertion paths work on the current session
+#[tauri::command]
+pub fn diagnose_text_insertion() -> TextInsertionReport {
+    platform::diagnose_text_insertion()
+}
+
 // ============================================================================
 // Floating Window Management
 // ============================================================================
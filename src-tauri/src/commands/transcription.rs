@@ -6,10 +6,12 @@ use crate::engines::SpeechEngine;
 use crate::state::AppState;
 use crate::storage::history;
 use crate::types::TranscriptionResult;
-use crate::audio::AudioCapture;
+use crate::audio::{AudioCapture, InputSelection, RecordedAudio};
 use crate::audio::AudioProcessor;
+use crate::normalization;
 use crate::voice_commands;
 use crate::llm;
+use crate::profiles;
 
 /// Taux d'échantillonnage requis par Whisper
 const TARGET_SAMPLE_RATE: u32 = 16000;
@@ -17,6 +19,12 @@ const TARGET_SAMPLE_RATE: u32 = 16000;
 /// Durée d'un chunk en secondes pour le streaming
 const STREAMING_CHUNK_DURATION_SECS: f32 = 2.5;
 
+/// Au-delà de cette durée, l'enregistrement est transcrit par segments
+pub(crate) const SEGMENTED_TRANSCRIPTION_THRESHOLD_SECS: f32 = 90.0;
+
+/// Durée cible d'un segment pour la transcription des longs enregistrements
+const SEGMENT_DURATION_SECS: f32 = 30.0;
+
 /// État global pour le streaming
 static STREAMING_ACTIVE: AtomicBool = AtomicBool::new(false);
 
@@ -27,23 +35,20 @@ static STREAMING_SAMPLE_RATE: Mutex<u32> = Mutex::new(16000);
 /// Channels pour communiquer avec le thread audio
 static AUDIO_CMD_SENDER: Mutex<Option<mpsc::Sender<AudioCommand>>> = Mutex::new(None);
 static AUDIO_RESULT_RECEIVER: Mutex<Option<mpsc::Receiver<AudioResult>>> = Mutex::new(None);
-static AUDIO_SNAPSHOT_SENDER: Mutex<Option<mpsc::Sender<()>>> = Mutex::new(None);
+/// Demande de snapshot : index du premier échantillon voulu
+static AUDIO_SNAPSHOT_SENDER: Mutex<Option<mpsc::Sender<usize>>> = Mutex::new(None);
 static AUDIO_SNAPSHOT_RECEIVER: Mutex<Option<mpsc::Receiver<(Vec<f32>, u32)>>> = Mutex::new(None);
 
 /// Commandes pour le thread audio
 #[derive(Debug)]
 enum AudioCommand {
-    Start { device_id: Option<String> },
+    Start { selection: InputSelection },
     Stop,
     GetSnapshot,
 }
 
-/// Résultats du thread audio
-#[derive(Debug)]
-struct AudioResult {
-    audio: Vec<f32>,
-    sample_rate: u32,
-}
+/// Résultat du thread audio : `None` si aucune capture n'était active
+type AudioResult = Option<RecordedAudio>;
 
 /// Payload pour les événements de streaming
 #[derive(Clone, Serialize)]
@@ -64,10 +69,10 @@ fn emit_streaming_chunk(app: &AppHandle, chunk: StreamingChunkEvent) {
 }
 
 /// Initialise le thread audio dédié pour les commandes de transcription GUI
-pub fn init_gui_audio_thread() {
+pub fn init_gui_audio_thread(app: AppHandle) {
     let (cmd_tx, cmd_rx) = mpsc::channel::<AudioCommand>();
     let (result_tx, result_rx) = mpsc::channel::<AudioResult>();
-    let (snapshot_req_tx, snapshot_req_rx) = mpsc::channel::<()>();
+    let (snapshot_req_tx, snapshot_req_rx) = mpsc::channel::<usize>();
     let (snapshot_res_tx, snapshot_res_rx) = mpsc::channel::<(Vec<f32>, u32)>();
 
     // Stocker les channels
@@ -91,9 +96,9 @@ pub fn init_gui_audio_thread() {
 
         loop {
             // Vérifier les demandes de snapshot (non-bloquant)
-            if let Ok(()) = snapshot_req_rx.try_recv() {
+            if let Ok(offset) = snapshot_req_rx.try_recv() {
                 if let Some(ref cap) = capture {
-                    let (audio, sample_rate) = cap.get_audio_snapshot();
+                    let (audio, sample_rate) = cap.get_audio_since(offset);
                     let _ = snapshot_res_tx.send((audio, sample_rate));
                 } else {
                     let _ = snapshot_res_tx.send((vec![], 16000));
@@ -102,11 +107,11 @@ pub fn init_gui_audio_thread() {
 
             // Vérifier les commandes (avec timeout pour permettre les snapshots)
             match cmd_rx.recv_timeout(std::time::Duration::from_millis(50)) {
-                Ok(AudioCommand::Start { device_id }) => {
-                    log::info!("GUI Audio: Starting capture (device: {:?})", device_id);
-                    match AudioCapture::new(device_id.as_deref()) {
+                Ok(AudioCommand::Start { selection }) => {
+                    log::info!("GUI Audio: Starting capture (devices: {:?})", selection.preferred_devices);
+                    match AudioCapture::new(&selection) {
                         Ok(mut cap) => {
-                            if let Err(e) = cap.start(device_id.as_deref()) {
+                            if let Err(e) = cap.start(&selection) {
                                 log::error!("Failed to start audio capture: {}", e);
                                 continue;
                             }
@@ -122,20 +127,20 @@ pub fn init_gui_audio_thread() {
                     log::info!("GUI Audio: Stopping capture");
                     if let Some(mut cap) = capture.take() {
                         match cap.stop() {
-                            Ok((audio, sample_rate)) => {
-                                log::info!("GUI Audio: Captured {} samples at {}Hz", audio.len(), sample_rate);
-                                let _ = result_tx.send(AudioResult { audio, sample_rate });
+                            Ok(recorded) => {
+                                log::info!("GUI Audio: Captured {} samples at {}Hz", recorded.audio.len(), recorded.sample_rate);
+                                let _ = result_tx.send(Some(recorded));
                             }
                             Err(e) => {
                                 log::error!("Failed to stop audio capture: {}", e);
                                 // Send empty result to unblock caller
-                                let _ = result_tx.send(AudioResult { audio: vec![], sample_rate: 16000 });
+                                let _ = result_tx.send(None);
                             }
                         }
                     } else {
                         log::warn!("GUI Audio: No active capture to stop");
                         // Send empty result to unblock caller
-                        let _ = result_tx.send(AudioResult { audio: vec![], sample_rate: 16000 });
+                        let _ = result_tx.send(None);
                     }
                 }
                 Ok(AudioCommand::GetSnapshot) => {
@@ -147,7 +152,15 @@ pub fn init_gui_audio_thread() {
                     }
                 }
                 Err(mpsc::RecvTimeoutError::Timeout) => {
-                    // Continue la boucle pour vérifier les snapshots
+                    // Continue la boucle pour vérifier les snapshots et le micro
+                    if let Some(ref mut cap) = capture {
+                        if let Some(event) = cap.maintain() {
+                            let _ = app.emit("recording-limit-warning", event);
+                        }
+                        if let Some(event) = cap.poll_device() {
+                            let _ = app.emit("audio-device-changed", event);
+                        }
+                    }
                 }
                 Err(mpsc::RecvTimeoutError::Disconnected) => {
                     log::info!("GUI audio thread: channel closed, exiting");
@@ -165,16 +178,28 @@ pub fn start_recording(app: AppHandle, state: State<'_, AppState>) -> Result<(),
         return Err("Already recording".to_string());
     }
 
-    let settings = state.settings.read().map_err(|e| e.to_string())?;
-    let device_id = settings.microphone_id.clone();
+    // Profil de l'application au premier plan
+    profiles::activate(&app);
+    let engine_app = app.clone();
+    std::thread::spawn(move || profiles::prepare_engine(&engine_app));
+
+    let settings = {
+        let settings = state.settings.read().map_err(|e| e.to_string())?;
+        profiles::effective_settings(&settings)
+    };
+    let selection = InputSelection::from_settings(&settings);
     let streaming_enabled = settings.streaming_enabled;
+    // "commande stop" détectée pendant le streaming termine l'enregistrement
+    let stop_language = settings
+        .voice_commands_enabled
+        .then(|| settings.transcription_language.clone());
     drop(settings);
 
     // Envoyer la commande de démarrage au thread audio
     {
         let guard = AUDIO_CMD_SENDER.lock().map_err(|e| e.to_string())?;
         if let Some(ref sender) = *guard {
-            sender.send(AudioCommand::Start { device_id }).map_err(|e| e.to_string())?;
+            sender.send(AudioCommand::Start { selection }).map_err(|e| e.to_string())?;
         } else {
             return Err("Audio thread not initialized".to_string());
         }
@@ -197,7 +222,7 @@ pub fn start_recording(app: AppHandle, state: State<'_, AppState>) -> Result<(),
         let engine_clone = state.engine.clone();
         let processor_clone = state.audio_processor.clone();
         std::thread::spawn(move || {
-            run_streaming_task(app_clone, engine_clone, processor_clone);
+            run_streaming_task(app_clone, engine_clone, processor_clone, stop_language);
         });
     }
 
@@ -210,6 +235,7 @@ fn run_streaming_task(
     app: AppHandle,
     state: Arc<RwLock<Option<Box<dyn SpeechEngine>>>>,
     processor: Arc<RwLock<AudioProcessor>>,
+    stop_language: Option<String>,
 ) {
     log::info!("Streaming task started with real-time transcription");
 
@@ -231,7 +257,7 @@ fn run_streaming_task(
             let receiver_guard = AUDIO_SNAPSHOT_RECEIVER.lock().ok();
 
             if let (Some(ref sender), Some(ref receiver)) = (guard.as_ref().and_then(|g| g.as_ref()), receiver_guard.as_ref().and_then(|g| g.as_ref())) {
-                if sender.send(()).is_ok() {
+                if sender.send(last_processed_samples).is_ok() {
                     receiver.recv_timeout(std::time::Duration::from_millis(500)).ok()
                 } else {
                     None
@@ -244,14 +270,14 @@ fn run_streaming_task(
         if let Some((audio, sample_rate)) = snapshot {
             let elapsed = start_time.elapsed().as_secs_f32();
 
-            // Calculer combien de nouveaux échantillons nous avons
-            let current_samples = audio.len();
-            let new_samples = current_samples.saturating_sub(last_processed_samples);
+            // Le snapshot ne contient que les échantillons depuis `last_processed_samples`
+            let new_samples = audio.len();
+            let current_samples = last_processed_samples + new_samples;
 
             // Si nous avons assez de nouveaux échantillons pour un chunk
-            if new_samples >= chunk_samples && current_samples >= chunk_samples {
+            if new_samples >= chunk_samples {
                 // Transcribe the new segment (from last_processed to current)
-                let chunk_audio = &audio[last_processed_samples..current_samples];
+                let chunk_audio = &audio[..];
 
                 // Audio processing: pre-resample (noise suppression)
                 let chunk_processed = if let Ok(mut proc) = processor.write() {
@@ -289,12 +315,21 @@ fn run_streaming_task(
                                 if !trimmed.is_empty() {
                                     accumulated_text.push(trimmed);
                                     let full_text = accumulated_text.join(" ");
-                                    log::info!("Streaming chunk #{}: '{}'", accumulated_text.len(), accumulated_text.last().unwrap());
+                                    log::info!("Streaming chunk #{}: '{}'", accumulated_text.len(), crate::privacy::redact(accumulated_text.last().unwrap()));
                                     emit_streaming_chunk(&app, StreamingChunkEvent {
                                         text: full_text,
                                         is_final: false,
                                         duration_seconds: elapsed,
                                     });
+
+                                    let stop_heard = stop_language.as_deref().is_some_and(|language| {
+                                        voice_commands::contains_stop_command(accumulated_text.last().unwrap(), language)
+                                    });
+                                    if stop_heard {
+                                        log::info!("Stop command heard, ending recording");
+                                        STREAMING_ACTIVE.store(false, Ordering::SeqCst);
+                                        let _ = app.emit("voice-stop-requested", ());
+                                    }
                                 }
                             }
                             Err(e) => {
@@ -365,21 +400,15 @@ pub async fn stop_recording(app: AppHandle, state: State<'_, AppState>) -> Resul
         }
     };
 
-    let audio_buffer = audio_result.audio;
-    let sample_rate = audio_result.sample_rate;
-
-    // Audio processing: pre-resample (noise suppression)
-    let audio_buffer = {
-        let mut processor = state.audio_processor.write().map_err(|e| e.to_string())?;
-        processor.process_pre_resample(&audio_buffer, sample_rate)
+    let recorded = match audio_result {
+        Some(recorded) if !recorded.audio.is_empty() => recorded,
+        _ => {
+            emit_recording_status(&app, "idle");
+            return Err("No audio captured".to_string());
+        }
     };
-
-    if audio_buffer.is_empty() {
-        emit_recording_status(&app, "idle");
-        return Err("No audio captured".to_string());
-    }
-
-    let duration_seconds = audio_buffer.len() as f32 / sample_rate as f32;
+    let sample_rate = recorded.sample_rate;
+    let duration_seconds = recorded.duration_secs();
 
     if duration_seconds < 0.5 {
         emit_recording_status(&app, "idle");
@@ -388,65 +417,96 @@ pub async fn stop_recording(app: AppHandle, state: State<'_, AppState>) -> Resul
 
     log::info!("Audio received: {:.1}s at {}Hz", duration_seconds, sample_rate);
 
-    // Resampling si nécessaire
-    let resampled_audio = if sample_rate != TARGET_SAMPLE_RATE {
-        log::info!("Resampling audio from {}Hz to {}Hz", sample_rate, TARGET_SAMPLE_RATE);
-        resample_audio(&audio_buffer, sample_rate, TARGET_SAMPLE_RATE)
+    let result = if duration_seconds > SEGMENTED_TRANSCRIPTION_THRESHOLD_SECS {
+        match transcribe_segmented(&state, &recorded) {
+            Ok(result) => result,
+            Err(e) => {
+                emit_recording_status(&app, "idle");
+                return Err(e);
+            }
+        }
     } else {
-        audio_buffer
-    };
+        // Audio processing: pre-resample (noise suppression)
+        let audio_buffer = {
+            let mut processor = state.audio_processor.write().map_err(|e| e.to_string())?;
+            processor.process_pre_resample(&recorded.audio.to_vec(), sample_rate)
+        };
 
-    // Audio processing: post-resample (limiter + AGC + VAD)
-    let (resampled_audio, has_speech) = {
-        let mut processor = state.audio_processor.write().map_err(|e| e.to_string())?;
-        processor.process_post_resample(&resampled_audio)
-    };
+        // Resampling si nécessaire
+        let resampled_audio = if sample_rate != TARGET_SAMPLE_RATE {
+            log::info!("Resampling audio from {}Hz to {}Hz", sample_rate, TARGET_SAMPLE_RATE);
+            resample_audio(&audio_buffer, sample_rate, TARGET_SAMPLE_RATE)
+        } else {
+            audio_buffer
+        };
 
-    if !has_speech {
-        log::info!("VAD: no speech detected, skipping transcription");
-        emit_recording_status(&app, "idle");
-        return Err("No speech detected in recording".to_string());
-    }
+        // Audio processing: post-resample (limiter + AGC + VAD)
+        let (resampled_audio, has_speech) = {
+            let mut processor = state.audio_processor.write().map_err(|e| e.to_string())?;
+            processor.process_post_resample(&resampled_audio)
+        };
+
+        if !has_speech {
+            log::info!("VAD: no speech detected, skipping transcription");
+            emit_recording_status(&app, "idle");
+            return Err("No speech detected in recording".to_string());
+        }
 
-    // Transcription
-    let result = {
-        let engine_guard = state.engine.read().map_err(|e| e.to_string())?;
-        let engine = engine_guard
-            .as_ref()
-            .ok_or("Whisper engine not initialized. Please download a model first.")?;
-        engine.transcribe(&resampled_audio, TARGET_SAMPLE_RATE)?
+        // Transcription (moteur actif, puis chaîne de secours)
+        state.transcribe(&resampled_audio, TARGET_SAMPLE_RATE)?
     };
 
     // Lire les settings pour le post-processing
-    let (voice_commands_enabled, dictation_mode, llm_enabled, llm_mode, system_commands_enabled) = {
+    let settings = {
         let settings = state.settings.read().map_err(|e| e.to_string())?;
-        (
-            settings.voice_commands_enabled,
-            settings.dictation_mode,
-            settings.llm_enabled,
-            settings.llm_mode,
-            settings.system_commands_enabled,
-        )
+        profiles::effective_settings(&settings)
     };
+    let dictation_mode = settings.dictation_mode;
 
     // Post-traitement
     let mut final_text = result.text.clone();
 
+    // Langue du texte : langue détectée, sinon langue configurée
+    let command_language = result
+        .detected_language
+        .clone()
+        .unwrap_or_else(|| settings.transcription_language.clone());
+
+    // Nombres, dates, montants et adresses écrits avant les commandes vocales
+    if settings.text_normalization.enabled_for(dictation_mode) {
+        final_text = normalization::normalize(&final_text, &command_language, dictation_mode);
+    }
+
     // Voice commands
-    if voice_commands_enabled {
-        let snippets = crate::storage::snippets::load_snippets().snippets;
-        let parse_result = voice_commands::parse(&final_text, dictation_mode, &snippets, system_commands_enabled);
-        final_text = parse_result.text;
+    if settings.voice_commands_enabled {
+        let snippets = crate::storage::snippets::effective_snippets();
+        let parse_result = voice_commands::parse(
+            &final_text,
+            &voice_commands::ParseContext {
+                mode: dictation_mode,
+                snippets: &snippets,
+                system_commands_enabled: settings.system_commands_enabled,
+                language: &command_language,
+                code_language: settings.code_language,
+            },
+        );
         if !parse_result.actions.is_empty() {
-            log::info!("Voice commands detected: {:?}", parse_result.actions);
-            voice_commands::execute_actions(&parse_result.actions, &snippets);
+            log::info!(
+                "Voice commands detected: {}",
+                crate::privacy::redact(&format!("{:?}", parse_result.actions))
+            );
         }
+        // Actions dans l'ordre où elles ont été dictées, comme en push-to-talk.
+        // Rien n'est collé : le texte assemblé revient à l'interface.
+        voice_commands::execute_tokens(&app, &parse_result.tokens, &snippets, &settings, |_| {});
+        final_text = parse_result.text;
     }
 
     // LLM post-processing
-    if llm_enabled {
+    if settings.llm_enabled {
         if let Some(api_key) = super::llm::get_groq_api_key_internal() {
-            match llm::process(&final_text, llm_mode, dictation_mode, &api_key).await {
+            let prompt = profiles::llm_prompt();
+            match llm::process(&final_text, settings.llm_mode, dictation_mode, prompt.as_deref(), &api_key).await {
                 Ok(processed) => {
                     log::info!("LLM processing successful");
                     final_text = processed;
@@ -460,7 +520,8 @@ pub async fn stop_recording(app: AppHandle, state: State<'_, AppState>) -> Resul
         }
     }
 
-    // Créer le résultat final
+    // Créer le résultat final ; les horodatages ne valent que pour le texte brut du moteur
+    let tokens = if final_text == result.text { result.tokens } else { Vec::new() };
     let final_result = TranscriptionResult {
         text: final_text.clone(),
         confidence: result.confidence,
@@ -469,6 +530,7 @@ pub async fn stop_recording(app: AppHandle, state: State<'_, AppState>) -> Resul
         detected_language: result.detected_language,
         timestamp: result.timestamp,
         model_used: result.model_used,
+        tokens,
     };
 
     // Émettre le chunk final
@@ -481,7 +543,11 @@ pub async fn stop_recording(app: AppHandle, state: State<'_, AppState>) -> Resul
     // Émettre le statut "idle"
     emit_recording_status(&app, "idle");
 
-    history::add_transcription(final_result.clone())?;
+    // Historique illisible (trousseau verrouillé) : la dictée aboutit quand même,
+    // l'erreur est remontée par `get_history`
+    if let Err(e) = history::add_transcription(final_result.clone()) {
+        log::warn!("Failed to save history: {}", e);
+    }
 
     // Send notification if enabled
     if state.settings.read().map(|s| s.notification_on_complete).unwrap_or(false) {
@@ -516,9 +582,106 @@ pub async fn stop_recording(app: AppHandle, state: State<'_, AppState>) -> Resul
     Ok(final_result)
 }
 
+/// Transcrit un long enregistrement segment par segment, en lisant l'audio
+/// depuis le spool disque : la mémoire reste bornée quelle que soit la durée.
+/// Chaque segment profite de la chaîne de secours de `AppState::transcribe`.
+pub(crate) fn transcribe_segmented(
+    state: &AppState,
+    recorded: &RecordedAudio,
+) -> Result<TranscriptionResult, String> {
+    let processor = &state.audio_processor;
+    let segments = recorded.segments(SEGMENT_DURATION_SECS);
+    log::info!(
+        "Segmented transcription: {:.1}s in {} segment(s)",
+        recorded.duration_secs(),
+        segments.len()
+    );
+
+    // Options de la dictée ; avec report de contexte, chaque segment reçoit la fin du précédent
+    let options = {
+        let settings = state.settings.read().map_err(|e| e.to_string())?;
+        profiles::effective_settings(&settings).whisper_decode
+    };
+
+    let mut texts: Vec<String> = Vec::new();
+    let mut confidence_sum = 0.0f32;
+    let mut processing_time_ms = 0u64;
+    let mut detected_language = None;
+    let mut models_used: Vec<String> = Vec::new();
+    let mut tokens = Vec::new();
+
+    for (index, (start, end)) in segments.iter().enumerate() {
+        let segment = recorded.audio.read_range(*start, *end);
+
+        let segment = match processor.write() {
+            Ok(mut proc) => proc.process_pre_resample(&segment, recorded.sample_rate),
+            Err(_) => segment,
+        };
+        let segment = if recorded.sample_rate != TARGET_SAMPLE_RATE {
+            resample_audio(&segment, recorded.sample_rate, TARGET_SAMPLE_RATE)
+        } else {
+            segment
+        };
+        let (segment, has_speech) = match processor.write() {
+            Ok(mut proc) => proc.process_post_resample(&segment),
+            Err(_) => (segment, true),
+        };
+
+        if !has_speech {
+            log::debug!("Segment {}: no speech detected, skipping", index + 1);
+            continue;
+        }
+
+        let segment_options = options.with_previous_text(texts.last().map_or("", |t| t.as_str()));
+        match state.transcribe_with_options(&segment, TARGET_SAMPLE_RATE, Some(&segment_options)) {
+            Ok(result) => {
+                processing_time_ms += result.processing_time_ms;
+                let trimmed = result.text.trim();
+                if !trimmed.is_empty() {
+                    texts.push(trimmed.to_string());
+                    confidence_sum += result.confidence;
+                }
+                // Horodatages du segment ramenés au début de l'enregistrement
+                let offset = *start as f32 / recorded.sample_rate as f32;
+                tokens.extend(result.tokens.into_iter().map(|mut token| {
+                    token.start += offset;
+                    token.end += offset;
+                    token
+                }));
+                detected_language = detected_language.or(result.detected_language);
+                // Un repli en cours d'enregistrement mêle plusieurs moteurs
+                if let Some(model) = result.model_used {
+                    if !models_used.contains(&model) {
+                        models_used.push(model);
+                    }
+                }
+            }
+            Err(e) => {
+                // Un segment en échec ne doit pas faire perdre tout l'enregistrement
+                log::warn!("Segment {} transcription failed: {}", index + 1, e);
+            }
+        }
+    }
+
+    if texts.is_empty() {
+        return Err("No speech detected in recording".to_string());
+    }
+
+    Ok(TranscriptionResult {
+        confidence: confidence_sum / texts.len() as f32,
+        text: texts.join(" "),
+        duration_seconds: recorded.duration_secs(),
+        processing_time_ms,
+        detected_language,
+        timestamp: chrono::Utc::now().timestamp(),
+        model_used: (!models_used.is_empty()).then(|| models_used.join(" + ")),
+        tokens,
+    })
+}
+
 #[tauri::command]
 pub fn get_history() -> Result<Vec<TranscriptionResult>, String> {
-    Ok(history::load_history().transcriptions)
+    Ok(history::try_load_history()?.transcriptions)
 }
 
 #[tauri::command]
@@ -2,8 +2,8 @@ use crate::storage::translation_history;
 use crate::types::TranslationEntry;
 
 #[tauri::command]
-pub fn get_translation_history() -> Vec<TranslationEntry> {
-    translation_history::load_translation_history().translations
+pub fn get_translation_history() -> Result<Vec<TranslationEntry>, String> {
+    Ok(translation_history::try_load_translation_history()?.translations)
 }
 
 #[tauri::command]
@@ -0,0 +1,17 @@
+use crate::voice_commands::user_commands::{self, UserCommandsStatus};
+
+#[tauri::command]
+pub fn get_user_voice_commands_status() -> UserCommandsStatus {
+    user_commands::status()
+}
+
+#[tauri::command]
+pub fn reload_user_voice_commands() -> UserCommandsStatus {
+    user_commands::reload()
+}
+
+/// Crée le fichier s'il n'existe pas et retourne son chemin (ouvert par le frontend)
+#[tauri::command]
+pub fn create_user_voice_commands_file() -> Result<String, String> {
+    user_commands::ensure_file()
+}
@@ -0,0 +1,361 @@
+//! Évaluation comparative des moteurs : taux d'erreur mots/caractères (WER/CER),
+//! facteur temps réel et mémoire crête, sur un corpus de référence.
+//! Le calcul des scores est pur et réutilisable dans les tests.
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::types::EngineType;
+
+/// Intervalle d'échantillonnage de la mémoire du processus
+const MEMORY_SAMPLE_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Nombre d'erreurs (substitutions + insertions + suppressions) rapporté
+/// à la longueur de la référence. Additionnable pour un score sur tout le corpus.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ErrorCounts {
+    pub errors: usize,
+    pub reference_len: usize,
+}
+
+impl ErrorCounts {
+    pub fn rate(&self) -> f64 {
+        if self.reference_len == 0 {
+            // Référence vide : toute sortie est une insertion
+            return if self.errors == 0 { 0.0 } else { 1.0 };
+        }
+        self.errors as f64 / self.reference_len as f64
+    }
+
+    pub fn add(&mut self, other: ErrorCounts) {
+        self.errors += other.errors;
+        self.reference_len += other.reference_len;
+    }
+}
+
+/// Minuscules, ponctuation retirée, espaces normalisés.
+/// L'apostrophe est conservée ("aujourd'hui", "don't").
+pub fn normalize_for_scoring(text: &str) -> String {
+    let cleaned: String = text
+        .chars()
+        .map(|c| {
+            let c = if c == '\u{2019}' { '\'' } else { c };
+            if c.is_alphanumeric() || c == '\'' { c } else { ' ' }
+        })
+        .collect::<String>()
+        .to_lowercase();
+    cleaned.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Distance de Levenshtein entre deux séquences
+pub fn edit_distance<T: PartialEq>(reference: &[T], hypothesis: &[T]) -> usize {
+    let mut previous: Vec<usize> = (0..=hypothesis.len()).collect();
+    let mut current = vec![0; hypothesis.len() + 1];
+
+    for (i, r) in reference.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, h) in hypothesis.iter().enumerate() {
+            let substitution = previous[j] + usize::from(r != h);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[hypothesis.len()]
+}
+
+/// Erreurs au niveau des mots, après normalisation
+pub fn word_errors(reference: &str, hypothesis: &str) -> ErrorCounts {
+    let reference = normalize_for_scoring(reference);
+    let hypothesis = normalize_for_scoring(hypothesis);
+    let reference: Vec<&str> = reference.split(' ').filter(|w| !w.is_empty()).collect();
+    let hypothesis: Vec<&str> = hypothesis.split(' ').filter(|w| !w.is_empty()).collect();
+    ErrorCounts {
+        errors: edit_distance(&reference, &hypothesis),
+        reference_len: reference.len(),
+    }
+}
+
+/// Erreurs au niveau des caractères (espaces compris), après normalisation
+pub fn char_errors(reference: &str, hypothesis: &str) -> ErrorCounts {
+    let reference: Vec<char> = normalize_for_scoring(reference).chars().collect();
+    let hypothesis: Vec<char> = normalize_for_scoring(hypothesis).chars().collect();
+    ErrorCounts {
+        errors: edit_distance(&reference, &hypothesis),
+        reference_len: reference.len(),
+    }
+}
+
+/// Résultat d'un moteur sur un fichier du corpus
+#[derive(Debug, Clone, Serialize)]
+pub struct FileEvaluation {
+    pub file_name: String,
+    pub reference: String,
+    pub hypothesis: Option<String>,
+    pub audio_seconds: f64,
+    pub processing_ms: u64,
+    pub word_errors: ErrorCounts,
+    pub char_errors: ErrorCounts,
+    pub error: Option<String>,
+}
+
+/// Résultat agrégé d'un moteur et d'un modèle sur tout le corpus
+#[derive(Debug, Clone, Serialize)]
+pub struct EngineEvaluation {
+    pub engine_type: EngineType,
+    pub model: String,
+    pub word_error_rate: f64,
+    pub char_error_rate: f64,
+    /// Temps de transcription / durée audio (< 1 : plus rapide que le temps réel)
+    pub real_time_factor: f64,
+    pub load_time_ms: u64,
+    /// Mémoire résidente crête du processus pendant le chargement et la transcription
+    pub peak_memory_mb: f64,
+    /// Crête au-dessus de la mémoire mesurée avant le chargement du modèle
+    pub memory_delta_mb: f64,
+    pub files_evaluated: usize,
+    pub files_failed: usize,
+    pub error: Option<String>,
+    pub files: Vec<FileEvaluation>,
+}
+
+impl EngineEvaluation {
+    /// Agrège les résultats par fichier ; les fichiers en échec sont exclus des scores
+    pub fn from_files(
+        engine_type: EngineType,
+        model: String,
+        load_time_ms: u64,
+        memory: MemoryUsage,
+        files: Vec<FileEvaluation>,
+    ) -> Self {
+        let mut words = ErrorCounts::default();
+        let mut chars = ErrorCounts::default();
+        let mut audio_seconds = 0.0;
+        let mut processing_ms = 0;
+        let mut failed = 0;
+
+        for file in &files {
+            if file.error.is_some() {
+                failed += 1;
+                continue;
+            }
+            words.add(file.word_errors);
+            chars.add(file.char_errors);
+            audio_seconds += file.audio_seconds;
+            processing_ms += file.processing_ms;
+        }
+
+        let real_time_factor = if audio_seconds > 0.0 {
+            processing_ms as f64 / 1000.0 / audio_seconds
+        } else {
+            0.0
+        };
+
+        Self {
+            engine_type,
+            model,
+            word_error_rate: words.rate(),
+            char_error_rate: chars.rate(),
+            real_time_factor,
+            load_time_ms,
+            peak_memory_mb: bytes_to_mb(memory.peak),
+            memory_delta_mb: bytes_to_mb(memory.peak.saturating_sub(memory.baseline)),
+            files_evaluated: files.len() - failed,
+            files_failed: failed,
+            error: None,
+            files,
+        }
+    }
+
+    /// Moteur qui n'a pas pu être chargé
+    pub fn failed(engine_type: EngineType, model: String, error: String) -> Self {
+        Self {
+            engine_type,
+            model,
+            word_error_rate: 0.0,
+            char_error_rate: 0.0,
+            real_time_factor: 0.0,
+            load_time_ms: 0,
+            peak_memory_mb: 0.0,
+            memory_delta_mb: 0.0,
+            files_evaluated: 0,
+            files_failed: 0,
+            error: Some(error),
+            files: Vec::new(),
+        }
+    }
+}
+
+/// Rapport complet d'une évaluation
+#[derive(Debug, Clone, Serialize)]
+pub struct EvaluationReport {
+    pub corpus_path: String,
+    pub created_at: String,
+    /// Fichiers audio du corpus ayant une transcription de référence
+    pub file_count: usize,
+    /// Fichiers audio sans transcription de référence, ignorés
+    pub skipped_files: Vec<String>,
+    pub engines: Vec<EngineEvaluation>,
+    /// Chemins des rapports JSON et CSV écrits
+    pub json_path: Option<String>,
+    pub csv_path: Option<String>,
+}
+
+impl EvaluationReport {
+    /// Résumé par moteur et modèle, une ligne chacun
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "engine,model,wer,cer,real_time_factor,load_time_ms,peak_memory_mb,memory_delta_mb,files_evaluated,files_failed,error\n",
+        );
+        for engine in &self.engines {
+            let row = [
+                format!("{:?}", engine.engine_type).to_lowercase(),
+                engine.model.clone(),
+                format!("{:.4}", engine.word_error_rate),
+                format!("{:.4}", engine.char_error_rate),
+                format!("{:.4}", engine.real_time_factor),
+                engine.load_time_ms.to_string(),
+                format!("{:.1}", engine.peak_memory_mb),
+                format!("{:.1}", engine.memory_delta_mb),
+                engine.files_evaluated.to_string(),
+                engine.files_failed.to_string(),
+                engine.error.clone().unwrap_or_default(),
+            ];
+            let row: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn bytes_to_mb(bytes: u64) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0)
+}
+
+/// Mémoire résidente du processus avant et pendant une mesure, en octets
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MemoryUsage {
+    pub baseline: u64,
+    pub peak: u64,
+}
+
+/// Échantillonne la mémoire résidente du processus dans un thread dédié
+/// jusqu'à `stop`, pour capter la crête pendant le chargement et l'inférence.
+pub struct PeakMemorySampler {
+    baseline: u64,
+    running: Arc<AtomicBool>,
+    handle: Option<JoinHandle<u64>>,
+}
+
+impl PeakMemorySampler {
+    pub fn start() -> Self {
+        let baseline = current_rss().unwrap_or(0);
+        let running = Arc::new(AtomicBool::new(true));
+        let flag = running.clone();
+        let handle = std::thread::spawn(move || {
+            let mut peak = baseline;
+            while flag.load(Ordering::SeqCst) {
+                if let Some(rss) = current_rss() {
+                    peak = peak.max(rss);
+                }
+                std::thread::sleep(MEMORY_SAMPLE_INTERVAL);
+            }
+            peak.max(current_rss().unwrap_or(0))
+        });
+        Self {
+            baseline,
+            running,
+            handle: Some(handle),
+        }
+    }
+
+    pub fn stop(mut self) -> MemoryUsage {
+        self.running.store(false, Ordering::SeqCst);
+        let peak = self
+            .handle
+            .take()
+            .and_then(|handle| handle.join().ok())
+            .unwrap_or(self.baseline);
+        MemoryUsage {
+            baseline: self.baseline,
+            peak,
+        }
+    }
+}
+
+impl Drop for PeakMemorySampler {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+    }
+}
+
+fn current_rss() -> Option<u64> {
+    use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+
+    let pid = sysinfo::get_current_pid().ok()?;
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[pid]),
+        false,
+        ProcessRefreshKind::new().with_memory(),
+    );
+    system.process(pid).map(|process| process.memory())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_error_rate() {
+        assert_eq!(word_errors("Bonjour, le monde !", "bonjour le monde").errors, 0);
+        // 1 substitution + 1 suppression sur 4 mots
+        let counts = word_errors("the cat sat down", "the bat sat");
+        assert_eq!(counts, ErrorCounts { errors: 2, reference_len: 4 });
+        assert_eq!(counts.rate(), 0.5);
+        assert_eq!(word_errors("", "").rate(), 0.0);
+        assert_eq!(word_errors("", "bruit").rate(), 1.0);
+    }
+
+    #[test]
+    fn test_char_error_rate() {
+        assert_eq!(char_errors("chat", "chats"), ErrorCounts { errors: 1, reference_len: 4 });
+        assert_eq!(char_errors("Aujourd\u{2019}hui", "aujourd'hui").errors, 0);
+    }
+
+    #[test]
+    fn test_engine_evaluation_aggregates_over_corpus() {
+        let file = |words: ErrorCounts, seconds: f64, ms: u64, error: Option<&str>| FileEvaluation {
+            file_name: String::new(),
+            reference: String::new(),
+            hypothesis: None,
+            audio_seconds: seconds,
+            processing_ms: ms,
+            word_errors: words,
+            char_errors: ErrorCounts::default(),
+            error: error.map(String::from),
+        };
+        let files = vec![
+            file(ErrorCounts { errors: 1, reference_len: 10 }, 10.0, 1000, None),
+            file(ErrorCounts { errors: 3, reference_len: 10 }, 10.0, 3000, None),
+            file(ErrorCounts::default(), 5.0, 0, Some("decode failed")),
+        ];
+        let result = EngineEvaluation::from_files(EngineType::Whisper, "Whisper Tiny".into(), 0, MemoryUsage::default(), files);
+
+        assert_eq!(result.word_error_rate, 0.2);
+        assert_eq!(result.real_time_factor, 0.2);
+        assert_eq!(result.files_evaluated, 2);
+        assert_eq!(result.files_failed, 1);
+    }
+}
@@ -0,0 +1,66 @@
+//! Santé des moteurs de transcription et ordre de la chaîne de secours
+
+use serde::Serialize;
+
+use crate::types::EngineType;
+
+/// Intervalle entre deux vérifications périodiques
+pub const HEALTH_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// État d'un moteur de la chaîne, exposé à l'interface
+#[derive(Debug, Clone, Serialize)]
+pub struct EngineHealth {
+    pub engine_type: EngineType,
+    /// Moteur actif (sinon moteur de secours)
+    pub primary: bool,
+    /// Modèle chargé en mémoire
+    pub loaded: bool,
+    /// Chargé et opérationnel, ou chargeable à la demande
+    pub healthy: bool,
+    pub error: Option<String>,
+    pub checked_at: i64,
+}
+
+impl EngineHealth {
+    pub fn new(engine_type: EngineType, primary: bool, loaded: bool, error: Option<String>) -> Self {
+        Self {
+            engine_type,
+            primary,
+            loaded,
+            healthy: error.is_none(),
+            error,
+            checked_at: chrono::Utc::now().timestamp(),
+        }
+    }
+}
+
+/// Moteurs de secours à essayer après `primary`, dans l'ordre configuré,
+/// sans doublon ni moteur interdit
+pub fn fallback_order(
+    primary: EngineType,
+    chain: &[EngineType],
+    allowed: impl Fn(EngineType) -> bool,
+) -> Vec<EngineType> {
+    let mut order = Vec::new();
+    for &engine_type in chain {
+        if engine_type != primary && !order.contains(&engine_type) && allowed(engine_type) {
+            order.push(engine_type);
+        }
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fallback_order_skips_primary_duplicates_and_disallowed() {
+        use EngineType::*;
+
+        let chain = [Parakeet, Whisper, Parakeet, Vosk];
+        assert_eq!(fallback_order(Parakeet, &chain, |_| true), [Whisper, Vosk]);
+        assert_eq!(fallback_order(Whisper, &chain, |e| e != Vosk), [Parakeet]);
+        assert!(fallback_order(Whisper, &[], |_| true).is_empty());
+    }
+}
@@ -1,12 +1,16 @@
 pub mod error;
+pub mod evaluation;
+pub mod health;
 pub mod model_manager;
 pub mod parakeet;
 pub mod parakeet_coreml;
+pub mod tdt;
 pub mod traits;
 pub mod vosk;
 pub mod whisper;
 
 pub use error::EngineError;
+pub use health::EngineHealth;
 pub use model_manager::ModelManager;
 pub use parakeet::{ParakeetEngine, ParakeetModelSize};
 pub use parakeet_coreml::ParakeetCoreMLEngine;
@@ -46,7 +46,7 @@ impl ModelManager {
 
     /// Liste les modèles disponibles
     pub fn available_models(&self) -> Vec<ModelSize> {
-        [ModelSize::Tiny, ModelSize::Small, ModelSize::Medium]
+        ModelSize::ALL
             .into_iter()
             .filter(|&size| self.is_model_available(size))
             .collect()
@@ -67,6 +67,9 @@ impl ModelManager {
             .map_err(|e| format!("Failed to create models directory: {}", e))?;
 
         let dest_path = self.models_dir.join(size.file_name());
+        // Fichier partiel jusqu'à la fin : un téléchargement interrompu
+        // (modèles large de plusieurs Go) ne passe pas pour un modèle installé
+        let part_path = dest_path.with_extension("bin.part");
         let url = size.download_url();
 
         log::info!("Downloading model {} from {}", size.file_name(), url);
@@ -85,7 +88,7 @@ impl ModelManager {
         let total_size = response.content_length().unwrap_or(size.size_bytes());
         let mut downloaded: u64 = 0;
 
-        let mut file = fs::File::create(&dest_path)
+        let mut file = fs::File::create(&part_path)
             .await
             .map_err(|e| format!("Failed to create file: {}", e))?;
 
@@ -103,6 +106,11 @@ impl ModelManager {
         file.flush()
             .await
             .map_err(|e| format!("Flush error: {}", e))?;
+        drop(file);
+
+        fs::rename(&part_path, &dest_path)
+            .await
+            .map_err(|e| format!("Failed to finalize model file: {}", e))?;
 
         log::info!("Model {} downloaded successfully", size.file_name());
         Ok(dest_path)
@@ -1,14 +1,23 @@
+use crate::engines::tdt::{self, DecodedToken, TdtConfig, TdtJoint};
 use crate::engines::traits::SpeechEngine;
-use crate::types::TranscriptionResult;
+use crate::types::{EngineType, TokenTimestamp, TranscriptionResult};
 use chrono::Utc;
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Mutex;
 use tract_onnx::prelude::*;
 
 type TractModel = SimplePlan<TypedFact, Box<dyn TypedOp>, Graph<TypedFact, Box<dyn TypedOp>>>;
 
+/// Miroir de `AppSettings::parakeet_beam_size`, lu à chaque transcription
+static BEAM_SIZE: AtomicUsize = AtomicUsize::new(1);
+
+pub fn set_beam_size(size: u32) {
+    BEAM_SIZE.store(size.max(1) as usize, Ordering::Relaxed);
+}
+
 /// Parakeet model size options
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ParakeetModelSize {
@@ -100,7 +109,12 @@ impl ParakeetEngine {
             vocab.insert(idx as i64, token);
         }
 
-        let blank_id = 0i64;
+        // Les exports TDT placent le blank en fin de vocabulaire
+        let blank_id = vocab
+            .iter()
+            .find(|(_, token)| token.starts_with("<blk>"))
+            .map(|(&id, _)| id)
+            .unwrap_or(0);
 
         log::info!(
             "Parakeet model loaded successfully ({} tokens)",
@@ -269,87 +283,112 @@ impl ParakeetEngine {
         filterbank
     }
 
-    fn decode_tokens(&self, token_ids: &[i64]) -> String {
+    fn token_text(&self, id: usize) -> Option<String> {
+        // Handle SentencePiece tokens (underscore = space)
+        self.vocab.get(&(id as i64)).map(|token| token.replace("▁", " "))
+    }
+
+    fn decode_tokens(&self, tokens: &[DecodedToken]) -> String {
         let mut text = String::new();
 
-        for &id in token_ids {
-            if id == self.blank_id {
-                continue;
-            }
-            if let Some(token) = self.vocab.get(&id) {
-                // Handle SentencePiece tokens (underscore = space)
-                let token = token.replace("▁", " ");
-                text.push_str(&token);
+        for token in tokens {
+            if let Some(piece) = self.token_text(token.id) {
+                text.push_str(&piece);
             }
         }
 
         text.trim().to_string()
     }
 
-    fn greedy_decode(
-        &self,
-        encoder_out: &tract_ndarray::ArrayD<f32>,
-    ) -> Result<Vec<i64>, String> {
-        let shape = encoder_out.shape();
-        let time_steps = shape[1];
-        let encoder_dim = shape[2];
+    /// Horodatage de chaque token ; un token de durée nulle occupe tout de même sa trame
+    fn token_timestamps(&self, tokens: &[DecodedToken], frame_seconds: f32) -> Vec<TokenTimestamp> {
+        tokens
+            .iter()
+            .filter_map(|token| {
+                let text = self.token_text(token.id)?;
+                Some(TokenTimestamp {
+                    token: text,
+                    start: token.frame as f32 * frame_seconds,
+                    end: (token.frame + token.duration.max(1)) as f32 * frame_seconds,
+                    confidence: token.logprob.exp(),
+                })
+            })
+            .collect()
+    }
+
+    /// Décodage TDT : glouton ou en faisceau selon `BEAM_SIZE`
+    fn tdt_decode(&self, encoder_out: &tract_ndarray::ArrayD<f32>) -> Result<Vec<DecodedToken>, String> {
+        let time_steps = encoder_out.shape()[1];
+        if time_steps == 0 {
+            return Ok(Vec::new());
+        }
 
-        let mut decoded_tokens: Vec<i64> = Vec::new();
         let decoder_joint = self.decoder_joint.lock().map_err(|e| e.to_string())?;
+        let joint = TractJoint {
+            plan: &decoder_joint,
+            encoder_out,
+        };
 
-        // Initial decoder state
-        let mut last_token = self.blank_id;
-
-        for t in 0..time_steps {
-            // Get encoder frame at timestep t
-            let encoder_frame: Vec<f32> = (0..encoder_dim)
-                .map(|d| encoder_out[[0, t, d]])
-                .collect();
-
-            // Create input tensors for decoder+joiner
-            // Shape: [batch=1, time=1, features]
-            let encoder_tensor: Tensor = tract_ndarray::Array3::from_shape_vec(
-                (1, 1, encoder_dim),
-                encoder_frame,
-            )
-            .map_err(|e| format!("Encoder tensor error: {}", e))?
-            .into();
-
-            // Decoder input: previous token
-            let decoder_input: Tensor = tract_ndarray::Array2::from_shape_vec(
-                (1, 1),
-                vec![last_token],
-            )
-            .map_err(|e| format!("Decoder input error: {}", e))?
-            .into();
-
-            // Run decoder+joiner
-            let inputs = tvec![encoder_tensor.into(), decoder_input.into()];
-            let outputs = decoder_joint
-                .run(inputs)
-                .map_err(|e| format!("Decoder+joiner error: {}", e))?;
-
-            let logits = outputs[0]
-                .to_array_view::<f32>()
-                .map_err(|e| format!("Output error: {}", e))?;
-
-            // Find argmax
-            let mut max_idx = 0i64;
-            let mut max_val = f32::NEG_INFINITY;
-            for (i, &val) in logits.iter().enumerate() {
-                if val > max_val {
-                    max_val = val;
-                    max_idx = i as i64;
-                }
-            }
+        // Les logits au-delà du vocabulaire sont ceux des durées (0, 1, 2...)
+        let vocab_size = self.vocab.len();
+        let output_len = joint.logits(0, self.blank_id as usize)?.len();
+        let durations: Vec<usize> = (0..output_len.saturating_sub(vocab_size)).collect();
+
+        let config = TdtConfig {
+            blank_id: self.blank_id as usize,
+            vocab_size: vocab_size.min(output_len),
+            durations,
+            max_symbols_per_frame: tdt::DEFAULT_MAX_SYMBOLS_PER_FRAME,
+            beam_size: BEAM_SIZE.load(Ordering::Relaxed),
+        };
+        tdt::decode(&joint, time_steps, &config)
+    }
+}
 
-            if max_idx != self.blank_id {
-                decoded_tokens.push(max_idx);
-                last_token = max_idx;
-            }
-        }
+/// Décodeur + joint ONNX appliqué aux sorties de l'encodeur
+struct TractJoint<'a> {
+    plan: &'a TractModel,
+    encoder_out: &'a tract_ndarray::ArrayD<f32>,
+}
+
+impl TdtJoint for TractJoint<'_> {
+    fn logits(&self, frame: usize, last_token: usize) -> Result<Vec<f32>, String> {
+        let encoder_dim = self.encoder_out.shape()[2];
+
+        // Get encoder frame at timestep t
+        let encoder_frame: Vec<f32> = (0..encoder_dim)
+            .map(|d| self.encoder_out[[0, frame, d]])
+            .collect();
+
+        // Create input tensors for decoder+joiner
+        // Shape: [batch=1, time=1, features]
+        let encoder_tensor: Tensor = tract_ndarray::Array3::from_shape_vec(
+            (1, 1, encoder_dim),
+            encoder_frame,
+        )
+        .map_err(|e| format!("Encoder tensor error: {}", e))?
+        .into();
+
+        // Decoder input: previous token
+        let decoder_input: Tensor = tract_ndarray::Array2::from_shape_vec(
+            (1, 1),
+            vec![last_token as i64],
+        )
+        .map_err(|e| format!("Decoder input error: {}", e))?
+        .into();
+
+        // Run decoder+joiner
+        let inputs = tvec![encoder_tensor.into(), decoder_input.into()];
+        let outputs = self
+            .plan
+            .run(inputs)
+            .map_err(|e| format!("Decoder+joiner error: {}", e))?;
+
+        let logits = outputs[0]
+            .to_array_view::<f32>()
+            .map_err(|e| format!("Output error: {}", e))?;
 
-        Ok(decoded_tokens)
+        Ok(logits.iter().copied().collect())
     }
 }
 
@@ -405,9 +444,16 @@ impl SpeechEngine for ParakeetEngine {
 
         drop(encoder);
 
-        // Greedy decode
-        let token_ids = self.greedy_decode(&encoder_out_owned)?;
-        let text = self.decode_tokens(&token_ids);
+        // Décodage TDT et horodatage (une trame encodeur ≈ 80 ms)
+        let tokens = self.tdt_decode(&encoder_out_owned)?;
+        let text = self.decode_tokens(&tokens);
+        let frame_seconds = duration_seconds / encoder_out_owned.shape()[1].max(1) as f32;
+        let timestamps = self.token_timestamps(&tokens, frame_seconds);
+        let confidence = if tokens.is_empty() {
+            0.0
+        } else {
+            tokens.iter().map(|t| t.logprob.exp()).sum::<f32>() / tokens.len() as f32
+        };
 
         let processing_time_ms = start_time.elapsed().as_millis() as u64;
 
@@ -419,12 +465,13 @@ impl SpeechEngine for ParakeetEngine {
 
         Ok(TranscriptionResult {
             text,
-            confidence: 0.9,
+            confidence,
             duration_seconds,
             processing_time_ms,
             detected_language: Some("auto".to_string()),
             timestamp: Utc::now().timestamp(),
             model_used: Some(self.model_display_name()),
+            tokens: timestamps,
         })
     }
 
@@ -432,6 +479,10 @@ impl SpeechEngine for ParakeetEngine {
         "Parakeet"
     }
 
+    fn engine_type(&self) -> EngineType {
+        EngineType::Parakeet
+    }
+
     fn model_display_name(&self) -> String {
         format!("Parakeet {}", self.model_size.display_name())
     }
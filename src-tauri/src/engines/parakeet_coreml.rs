@@ -1,5 +1,5 @@
 use crate::engines::traits::SpeechEngine;
-use crate::types::TranscriptionResult;
+use crate::types::{EngineType, TranscriptionResult};
 use chrono::Utc;
 use serde::Deserialize;
 use std::io::{BufRead, BufReader, Write};
@@ -22,6 +22,21 @@ struct DaemonProcess {
     reader: BufReader<std::process::ChildStdout>,
 }
 
+/// WAV temporaire supprimé dès qu'il sort du scope, y compris sur erreur.
+/// En mode confidentialité, le contenu est écrasé avant suppression.
+struct TempWav(PathBuf);
+
+impl Drop for TempWav {
+    fn drop(&mut self) {
+        if crate::privacy::is_enabled() {
+            if let Ok(meta) = std::fs::metadata(&self.0) {
+                let _ = std::fs::write(&self.0, vec![0u8; meta.len() as usize]);
+            }
+        }
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
 pub struct ParakeetCoreMLEngine {
     sidecar_path: PathBuf,
     daemon: Mutex<Option<DaemonProcess>>,
@@ -89,7 +104,7 @@ impl ParakeetCoreMLEngine {
         Ok(())
     }
 
-    fn write_temp_wav(&self, audio: &[f32], sample_rate: u32) -> Result<PathBuf, String> {
+    fn write_temp_wav(&self, audio: &[f32], sample_rate: u32) -> Result<TempWav, String> {
         let temp_dir = std::env::temp_dir();
         let temp_path = temp_dir.join(format!("parakeet_input_{}.wav", std::process::id()));
 
@@ -102,6 +117,7 @@ impl ParakeetCoreMLEngine {
 
         let mut writer = hound::WavWriter::create(&temp_path, spec)
             .map_err(|e| format!("Failed to create temp WAV: {}", e))?;
+        let temp_wav = TempWav(temp_path);
 
         for &sample in audio {
             writer
@@ -113,7 +129,7 @@ impl ParakeetCoreMLEngine {
             .finalize()
             .map_err(|e| format!("Failed to finalize WAV: {}", e))?;
 
-        Ok(temp_path)
+        Ok(temp_wav)
     }
 }
 
@@ -145,7 +161,7 @@ impl SpeechEngine for ParakeetCoreMLEngine {
             // Send the request as a JSON line
             let request = format!(
                 "{{\"audio_path\":\"{}\"}}\n",
-                temp_wav.to_str().unwrap().replace('\\', "\\\\").replace('"', "\\\"")
+                temp_wav.0.to_str().unwrap().replace('\\', "\\\\").replace('"', "\\\"")
             );
             daemon.stdin.write_all(request.as_bytes())
                 .map_err(|e| format!("Failed to write to daemon stdin: {}", e))?;
@@ -158,13 +174,13 @@ impl SpeechEngine for ParakeetCoreMLEngine {
                 .map_err(|e| format!("Failed to read daemon response: {}", e))?;
 
             let output: SidecarOutput = serde_json::from_str(response_line.trim())
-                .map_err(|e| format!("Failed to parse daemon output: {} (output: {})", e, response_line.trim()))?;
+                .map_err(|e| format!("Failed to parse daemon output: {} (output: {})", e, crate::privacy::redact(response_line.trim())))?;
 
             output
         };
 
         // Clean up temp file
-        let _ = std::fs::remove_file(&temp_wav);
+        drop(temp_wav);
 
         if let Some(error) = result.error {
             return Err(error);
@@ -186,6 +202,7 @@ impl SpeechEngine for ParakeetCoreMLEngine {
             detected_language: Some("auto".to_string()),
             timestamp: Utc::now().timestamp(),
             model_used: Some(self.model_display_name()),
+            tokens: Vec::new(),
         })
     }
 
@@ -193,9 +210,23 @@ impl SpeechEngine for ParakeetCoreMLEngine {
         "Parakeet CoreML"
     }
 
+    fn engine_type(&self) -> EngineType {
+        EngineType::Parakeet
+    }
+
     fn model_display_name(&self) -> String {
         "Parakeet TDT 0.6B v3 (CoreML)".to_string()
     }
+
+    fn health_check(&self) -> Result<(), String> {
+        let mut daemon_guard = self.daemon.lock().map_err(|e| e.to_string())?;
+        let daemon = daemon_guard.as_mut().ok_or("Daemon not running")?;
+        match daemon.child.try_wait() {
+            Ok(None) => Ok(()),
+            Ok(Some(status)) => Err(format!("Sidecar daemon exited ({})", status)),
+            Err(e) => Err(format!("Failed to query sidecar daemon: {}", e)),
+        }
+    }
 }
 
 impl Drop for ParakeetCoreMLEngine {
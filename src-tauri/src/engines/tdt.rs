@@ -0,0 +1,413 @@
+//! Décodage TDT (Token-and-Duration Transducer) pour Parakeet.
+//!
+//! Le joint TDT produit pour chaque trame encodeur les logits des tokens
+//! (blank compris) suivis des logits de durée : la durée prédite indique de
+//! combien de trames avancer après l'émission. Une durée nulle permet
+//! d'émettre plusieurs tokens sur la même trame. Le décodage ne dépend que
+//! du trait `TdtJoint`, ce qui permet de le tester sur des logits enregistrés.
+
+use std::collections::HashMap;
+
+/// Nombre maximal de tokens émis sur une même trame avant d'avancer de force
+pub const DEFAULT_MAX_SYMBOLS_PER_FRAME: usize = 10;
+
+/// Réseau décodeur + joint : logits pour une trame encodeur, sachant le dernier token émis
+pub trait TdtJoint {
+    fn logits(&self, frame: usize, last_token: usize) -> Result<Vec<f32>, String>;
+}
+
+#[derive(Debug, Clone)]
+pub struct TdtConfig {
+    pub blank_id: usize,
+    /// Nombre de logits de tokens (blank compris) en tête de sortie du joint
+    pub vocab_size: usize,
+    /// Durées associées aux logits suivants (ex : [0, 1, 2, 3, 4]) ;
+    /// vide pour un transducteur classique (blank avance d'une trame)
+    pub durations: Vec<usize>,
+    pub max_symbols_per_frame: usize,
+    /// 1 : décodage glouton
+    pub beam_size: usize,
+}
+
+/// Token émis, avec sa trame de départ et le nombre de trames qu'il couvre
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedToken {
+    pub id: usize,
+    pub frame: usize,
+    pub duration: usize,
+    pub logprob: f32,
+}
+
+fn log_softmax(logits: &[f32]) -> Vec<f32> {
+    let max = logits.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let sum: f32 = logits.iter().map(|&v| (v - max).exp()).sum();
+    let log_sum = max + sum.ln();
+    logits.iter().map(|&v| v - log_sum).collect()
+}
+
+fn argmax(values: &[f32]) -> usize {
+    let mut best = 0;
+    for (i, &v) in values.iter().enumerate() {
+        if v > values[best] {
+            best = i;
+        }
+    }
+    best
+}
+
+fn log_add(a: f32, b: f32) -> f32 {
+    let max = a.max(b);
+    if max == f32::NEG_INFINITY {
+        return max;
+    }
+    max + ((a - max).exp() + (b - max).exp()).ln()
+}
+
+impl TdtConfig {
+    /// Sépare la sortie du joint en log-probabilités des tokens et des durées
+    fn split(&self, logits: &[f32]) -> Result<(Vec<f32>, Vec<f32>), String> {
+        let expected = self.vocab_size + self.durations.len();
+        if logits.len() < expected {
+            return Err(format!(
+                "Joint output has {} logits, expected {} tokens + {} durations",
+                logits.len(),
+                self.vocab_size,
+                self.durations.len()
+            ));
+        }
+        let tokens = log_softmax(&logits[..self.vocab_size]);
+        let durations = if self.durations.is_empty() {
+            Vec::new()
+        } else {
+            log_softmax(&logits[self.vocab_size..expected])
+        };
+        Ok((tokens, durations))
+    }
+
+    /// Durée d'un token sans prédiction de durée : le blank avance, les autres restent
+    fn default_duration(&self, token: usize) -> usize {
+        if token == self.blank_id {
+            1
+        } else {
+            0
+        }
+    }
+}
+
+/// Décode `frames` trames encodeur ; glouton si `beam_size` ≤ 1
+pub fn decode(joint: &impl TdtJoint, frames: usize, config: &TdtConfig) -> Result<Vec<DecodedToken>, String> {
+    if config.beam_size <= 1 {
+        greedy_decode(joint, frames, config)
+    } else {
+        beam_decode(joint, frames, config)
+    }
+}
+
+pub fn greedy_decode(joint: &impl TdtJoint, frames: usize, config: &TdtConfig) -> Result<Vec<DecodedToken>, String> {
+    let mut tokens = Vec::new();
+    let mut last_token = config.blank_id;
+    let mut frame = 0;
+    let mut symbols = 0;
+
+    while frame < frames {
+        let (token_logprobs, duration_logprobs) = config.split(&joint.logits(frame, last_token)?)?;
+        let token = argmax(&token_logprobs);
+        let mut duration = if config.durations.is_empty() {
+            config.default_duration(token)
+        } else {
+            config.durations[argmax(&duration_logprobs)]
+        };
+
+        if token != config.blank_id {
+            tokens.push(DecodedToken {
+                id: token,
+                frame,
+                duration,
+                logprob: token_logprobs[token],
+            });
+            last_token = token;
+            symbols += 1;
+        }
+
+        // Un blank de durée nulle bouclerait sur la même trame
+        if duration == 0 && (token == config.blank_id || symbols >= config.max_symbols_per_frame) {
+            duration = 1;
+        }
+        if duration > 0 {
+            frame += duration;
+            symbols = 0;
+        }
+    }
+
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone)]
+struct Hypothesis {
+    tokens: Vec<DecodedToken>,
+    last_token: usize,
+    frame: usize,
+    symbols: usize,
+    score: f32,
+}
+
+impl Hypothesis {
+    fn key(&self) -> (Vec<usize>, usize) {
+        (self.tokens.iter().map(|t| t.id).collect(), self.frame)
+    }
+}
+
+/// Ajoute une hypothèse au faisceau en fusionnant celles de même texte à la même trame
+fn merge_into(beam: &mut Vec<Hypothesis>, index: &mut HashMap<(Vec<usize>, usize), usize>, hyp: Hypothesis) {
+    match index.get(&hyp.key()) {
+        Some(&i) => {
+            let existing = &mut beam[i];
+            let score = log_add(existing.score, hyp.score);
+            if hyp.score > existing.score {
+                *existing = hyp;
+            }
+            existing.score = score;
+        }
+        None => {
+            index.insert(hyp.key(), beam.len());
+            beam.push(hyp);
+        }
+    }
+}
+
+fn prune(beam: &mut Vec<Hypothesis>, size: usize) {
+    beam.sort_by(|a, b| b.score.total_cmp(&a.score));
+    beam.truncate(size);
+}
+
+/// Recherche en faisceau : chaque hypothèse avance à son propre rythme selon
+/// les durées choisies ; les scores cumulent log-probabilités de token et de durée
+pub fn beam_decode(joint: &impl TdtJoint, frames: usize, config: &TdtConfig) -> Result<Vec<DecodedToken>, String> {
+    let beam_size = config.beam_size.max(1);
+    let mut active = vec![Hypothesis {
+        tokens: Vec::new(),
+        last_token: config.blank_id,
+        frame: 0,
+        symbols: 0,
+        score: 0.0,
+    }];
+    let mut finished: Vec<Hypothesis> = Vec::new();
+
+    while !active.is_empty() {
+        let mut next = Vec::new();
+        let mut next_index = HashMap::new();
+        let mut done_index: HashMap<(Vec<usize>, usize), usize> = finished
+            .iter()
+            .enumerate()
+            .map(|(i, hyp)| (hyp.key(), i))
+            .collect();
+
+        for hyp in &active {
+            let (token_logprobs, duration_logprobs) = config.split(&joint.logits(hyp.frame, hyp.last_token)?)?;
+
+            let mut candidates: Vec<usize> = (0..token_logprobs.len()).collect();
+            candidates.sort_by(|&a, &b| token_logprobs[b].total_cmp(&token_logprobs[a]));
+            candidates.truncate(beam_size);
+            // Le blank garantit que chaque hypothèse peut toujours avancer
+            if !candidates.contains(&config.blank_id) {
+                candidates.push(config.blank_id);
+            }
+
+            for token in candidates {
+                let durations: Vec<(usize, f32)> = if config.durations.is_empty() {
+                    vec![(config.default_duration(token), 0.0)]
+                } else {
+                    config.durations.iter().copied().zip(duration_logprobs.iter().copied()).collect()
+                };
+
+                for (duration, duration_logprob) in durations {
+                    let is_blank = token == config.blank_id;
+                    // Même règle qu'en glouton : pas de boucle sur place
+                    if duration == 0 && (is_blank || hyp.symbols + 1 >= config.max_symbols_per_frame) {
+                        continue;
+                    }
+
+                    let mut extended = hyp.clone();
+                    extended.score += token_logprobs[token] + duration_logprob;
+                    if !is_blank {
+                        extended.tokens.push(DecodedToken {
+                            id: token,
+                            frame: hyp.frame,
+                            duration,
+                            logprob: token_logprobs[token],
+                        });
+                        extended.last_token = token;
+                    }
+                    if duration > 0 {
+                        extended.frame += duration;
+                        extended.symbols = 0;
+                    } else {
+                        extended.symbols += 1;
+                    }
+
+                    if extended.frame >= frames {
+                        extended.frame = frames;
+                        merge_into(&mut finished, &mut done_index, extended);
+                    } else {
+                        merge_into(&mut next, &mut next_index, extended);
+                    }
+                }
+            }
+        }
+
+        prune(&mut finished, beam_size);
+        prune(&mut next, beam_size);
+
+        // Les scores ne font que baisser : une hypothèse déjà moins bonne que
+        // la pire terminée retenue ne peut plus entrer dans le faisceau
+        if finished.len() >= beam_size {
+            let worst = finished[finished.len() - 1].score;
+            next.retain(|hyp| hyp.score > worst);
+        }
+        active = next;
+    }
+
+    Ok(finished.into_iter().next().map(|hyp| hyp.tokens).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLANK: usize = 0;
+    const HIGH: f32 = 10.0;
+
+    /// Logits enregistrés par (trame, dernier token) ; à défaut, blank d'une trame
+    struct RecordedJoint {
+        vocab_size: usize,
+        durations: usize,
+        steps: HashMap<(usize, usize), (Vec<f32>, Vec<f32>)>,
+    }
+
+    impl RecordedJoint {
+        fn new(vocab_size: usize, durations: usize) -> Self {
+            Self { vocab_size, durations, steps: HashMap::new() }
+        }
+
+        /// Pic sur `token` et sur la durée d'indice `duration`
+        fn emit(mut self, frame: usize, last_token: usize, token: usize, duration: usize) -> Self {
+            let mut tokens = vec![0.0; self.vocab_size];
+            tokens[token] = HIGH;
+            let mut durations = vec![0.0; self.durations];
+            if self.durations > 0 {
+                durations[duration] = HIGH;
+            }
+            self.steps.insert((frame, last_token), (tokens, durations));
+            self
+        }
+
+        fn record(mut self, frame: usize, last_token: usize, tokens: Vec<f32>, durations: Vec<f32>) -> Self {
+            self.steps.insert((frame, last_token), (tokens, durations));
+            self
+        }
+    }
+
+    impl TdtJoint for RecordedJoint {
+        fn logits(&self, frame: usize, last_token: usize) -> Result<Vec<f32>, String> {
+            let (mut tokens, durations) = self.steps.get(&(frame, last_token)).cloned().unwrap_or_else(|| {
+                let mut tokens = vec![0.0; self.vocab_size];
+                tokens[BLANK] = HIGH;
+                let mut durations = vec![0.0; self.durations];
+                if self.durations > 1 {
+                    durations[1] = HIGH;
+                }
+                (tokens, durations)
+            });
+            tokens.extend(durations);
+            Ok(tokens)
+        }
+    }
+
+    fn config(vocab_size: usize, durations: Vec<usize>, beam_size: usize) -> TdtConfig {
+        TdtConfig {
+            blank_id: BLANK,
+            vocab_size,
+            durations,
+            max_symbols_per_frame: DEFAULT_MAX_SYMBOLS_PER_FRAME,
+            beam_size,
+        }
+    }
+
+    fn ids(tokens: &[DecodedToken]) -> Vec<usize> {
+        tokens.iter().map(|t| t.id).collect()
+    }
+
+    #[test]
+    fn test_greedy_emits_several_symbols_per_frame_and_skips_frames() {
+        // Trame 0 : deux tokens (durée 0 puis 2), trame 1 sautée, trame 2 : blank, trame 3 : token
+        let joint = RecordedJoint::new(4, 5)
+            .emit(0, BLANK, 1, 0)
+            .emit(0, 1, 2, 2)
+            .emit(1, 2, 3, 1)
+            .emit(3, 2, 3, 1);
+        let tokens = greedy_decode(&joint, 4, &config(4, vec![0, 1, 2, 3, 4], 1)).unwrap();
+
+        assert_eq!(ids(&tokens), vec![1, 2, 3]);
+        let spans: Vec<(usize, usize)> = tokens.iter().map(|t| (t.frame, t.duration)).collect();
+        assert_eq!(spans, vec![(0, 0), (0, 2), (3, 1)]);
+        assert!(tokens.iter().all(|t| t.logprob < 0.0 && t.logprob > -0.01));
+    }
+
+    #[test]
+    fn test_greedy_never_loops_on_a_frame() {
+        // Blank de durée nulle puis token répété sans fin sur la trame 1
+        let mut joint = RecordedJoint::new(3, 3).emit(0, BLANK, BLANK, 0);
+        for last in 0..3 {
+            joint = joint.emit(1, last, 2, 0);
+        }
+        let mut cfg = config(3, vec![0, 1, 2], 1);
+        cfg.max_symbols_per_frame = 3;
+        let tokens = greedy_decode(&joint, 2, &cfg).unwrap();
+
+        assert_eq!(ids(&tokens), vec![2, 2, 2]);
+        assert!(tokens.iter().all(|t| t.frame == 1));
+    }
+
+    #[test]
+    fn test_greedy_without_durations_behaves_like_rnnt() {
+        let joint = RecordedJoint::new(3, 0).emit(0, BLANK, 1, 0).emit(0, 1, 2, 0).emit(0, 2, BLANK, 0);
+        let tokens = greedy_decode(&joint, 2, &config(3, Vec::new(), 1)).unwrap();
+        assert_eq!(ids(&tokens), vec![1, 2]);
+        assert!(tokens.iter().all(|t| t.frame == 0));
+    }
+
+    #[test]
+    fn test_beam_recovers_from_greedy_mistake() {
+        // Token 1 l'emporte de peu en trame 0 mais mène à une suite improbable ;
+        // token 2 ouvre une suite très probable
+        let joint = RecordedJoint::new(4, 2)
+            .record(0, BLANK, vec![0.0, 2.0, 1.8, 0.0], vec![0.0, HIGH])
+            .record(1, 1, vec![1.0, 1.0, 1.0, 1.0], vec![0.0, HIGH])
+            .record(1, 2, vec![0.0, 0.0, 0.0, HIGH], vec![0.0, HIGH]);
+        let cfg = config(4, vec![0, 1], 1);
+
+        let greedy = decode(&joint, 2, &cfg).unwrap();
+        assert_eq!(ids(&greedy)[0], 1);
+
+        let beam = decode(&joint, 2, &TdtConfig { beam_size: 4, ..cfg }).unwrap();
+        assert_eq!(ids(&beam), vec![2, 3]);
+    }
+
+    #[test]
+    fn test_beam_matches_greedy_on_confident_logits() {
+        let joint = RecordedJoint::new(4, 5)
+            .emit(0, BLANK, 1, 0)
+            .emit(0, 1, 2, 2)
+            .emit(3, 2, 3, 1);
+        let greedy = greedy_decode(&joint, 4, &config(4, vec![0, 1, 2, 3, 4], 1)).unwrap();
+        let beam = beam_decode(&joint, 4, &config(4, vec![0, 1, 2, 3, 4], 3)).unwrap();
+        assert_eq!(ids(&beam), ids(&greedy));
+        assert_eq!(beam[1].duration, 2);
+    }
+
+    #[test]
+    fn test_short_joint_output_is_an_error() {
+        let joint = RecordedJoint::new(3, 0);
+        assert!(greedy_decode(&joint, 1, &config(3, vec![0, 1], 1)).is_err());
+    }
+}
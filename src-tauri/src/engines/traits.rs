@@ -1,7 +1,26 @@
-use crate::types::TranscriptionResult;
+use crate::types::{EngineType, TranscriptionResult, WhisperDecodeOptions};
 
 pub trait SpeechEngine: Send + Sync {
     fn transcribe(&self, audio: &[f32], sample_rate: u32) -> Result<TranscriptionResult, String>;
+    /// Transcription avec des options de décodage Whisper (profil, fichier...).
+    /// Les autres moteurs les ignorent.
+    fn transcribe_with_options(
+        &self,
+        audio: &[f32],
+        sample_rate: u32,
+        _options: &WhisperDecodeOptions,
+    ) -> Result<TranscriptionResult, String> {
+        self.transcribe(audio, sample_rate)
+    }
     fn name(&self) -> &str;
     fn model_display_name(&self) -> String;
+    fn engine_type(&self) -> EngineType;
+    /// Langue de transcription, `None` : détection automatique.
+    /// Sans effet pour les moteurs dont le modèle fixe la langue.
+    fn set_language(&mut self, _language: Option<String>) {}
+    /// Vérifie qu'un moteur chargé peut toujours transcrire (processus
+    /// externe vivant...). Les moteurs en mémoire le sont tant qu'ils existent.
+    fn health_check(&self) -> Result<(), String> {
+        Ok(())
+    }
 }
@@ -1,5 +1,5 @@
 use crate::engines::traits::SpeechEngine;
-use crate::types::{TranscriptionResult, VoskLanguage};
+use crate::types::{EngineType, TranscriptionResult, VoskLanguage};
 use chrono::Utc;
 use std::path::Path;
 use std::sync::Mutex;
@@ -83,6 +83,7 @@ impl SpeechEngine for VoskEngine {
             detected_language: Some(format!("{:?}", self.language).to_lowercase()),
             timestamp: Utc::now().timestamp(),
             model_used: Some(self.model_display_name()),
+            tokens: Vec::new(),
         })
     }
 
@@ -90,6 +91,10 @@ impl SpeechEngine for VoskEngine {
         "Vosk"
     }
 
+    fn engine_type(&self) -> EngineType {
+        EngineType::Vosk
+    }
+
     fn model_display_name(&self) -> String {
         format!("Vosk {}", self.language.display_name())
     }
@@ -1,5 +1,5 @@
 use crate::engines::traits::SpeechEngine;
-use crate::types::{ModelSize, TranscriptionResult};
+use crate::types::{EngineType, ModelSize, TranscriptionResult, WhisperDecodeOptions};
 use chrono::Utc;
 use std::path::Path;
 use std::sync::Mutex;
@@ -41,14 +41,19 @@ impl WhisperEngine {
     pub fn model_size(&self) -> ModelSize {
         self.model_size
     }
-
-    pub fn set_language(&mut self, language: Option<String>) {
-        self.language = language;
-    }
 }
 
 impl SpeechEngine for WhisperEngine {
     fn transcribe(&self, audio: &[f32], sample_rate: u32) -> Result<TranscriptionResult, String> {
+        self.transcribe_with_options(audio, sample_rate, &WhisperDecodeOptions::default())
+    }
+
+    fn transcribe_with_options(
+        &self,
+        audio: &[f32],
+        sample_rate: u32,
+        options: &WhisperDecodeOptions,
+    ) -> Result<TranscriptionResult, String> {
         let start_time = std::time::Instant::now();
 
         if sample_rate != 16000 {
@@ -65,16 +70,33 @@ impl SpeechEngine for WhisperEngine {
 
         let ctx = self.ctx.lock().map_err(|e| format!("Lock error: {}", e))?;
 
-        let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+        let strategy = if options.beam_size > 1 {
+            SamplingStrategy::BeamSearch {
+                beam_size: options.beam_size.min(8) as i32,
+                patience: -1.0,
+            }
+        } else {
+            SamplingStrategy::Greedy {
+                best_of: options.best_of.clamp(1, 8) as i32,
+            }
+        };
+        let mut params = FullParams::new(strategy);
+        params.set_n_threads(options.thread_count());
+
+        // Repli en température quand un segment décodé est peu probable
+        params.set_temperature(options.temperature);
+        params.set_temperature_inc(options.temperature_increment);
+        params.set_logprob_thold(options.logprob_threshold);
 
-        // Utiliser les coeurs physiques pour l'inférence (min 1, max 8)
-        let n_threads = std::thread::available_parallelism()
-            .map(|n| (n.get() / 2).clamp(1, 8) as i32)
-            .unwrap_or(4);
-        params.set_n_threads(n_threads);
+        if !options.initial_prompt.trim().is_empty() {
+            params.set_initial_prompt(options.initial_prompt.trim());
+        }
+        params.set_translate(options.translate);
 
-        // Configurer la langue
-        if let Some(ref lang) = self.language {
+        // Configurer la langue (les modèles .en ne connaissent que l'anglais)
+        if self.model_size.is_english_only() {
+            params.set_language(Some("en"));
+        } else if let Some(ref lang) = self.language {
             if lang != "auto" {
                 params.set_language(Some(lang));
             }
@@ -86,10 +108,11 @@ impl SpeechEngine for WhisperEngine {
         params.set_print_realtime(false);
         params.set_print_timestamps(false);
         params.set_single_segment(true);
-        params.set_no_context(true);
+        params.set_no_context(!options.carry_context);
 
         // Éviter les hallucinations (musique, sous-titres, etc.)
-        params.set_suppress_nst(true);
+        params.set_suppress_blank(options.suppress_blank);
+        params.set_suppress_nst(options.suppress_non_speech);
 
         // Créer un état pour cette transcription
         let mut state = ctx
@@ -132,6 +155,7 @@ impl SpeechEngine for WhisperEngine {
             detected_language,
             timestamp: Utc::now().timestamp(),
             model_used: Some(self.model_display_name()),
+            tokens: Vec::new(),
         })
     }
 
@@ -139,6 +163,14 @@ impl SpeechEngine for WhisperEngine {
         "Whisper"
     }
 
+    fn engine_type(&self) -> EngineType {
+        EngineType::Whisper
+    }
+
+    fn set_language(&mut self, language: Option<String>) {
+        self.language = language;
+    }
+
     fn model_display_name(&self) -> String {
         format!("Whisper {}", self.model_size.display_name())
     }
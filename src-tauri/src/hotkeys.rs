@@ -1,21 +1,91 @@
-use tauri_plugin_global_shortcut::{Code, Modifiers, Shortcut};
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut};
 
-/// Parse un raccourci clavier depuis un format string (ex: "Ctrl+Shift+R")
-pub fn parse_hotkey(hotkey: &str) -> Option<Shortcut> {
-    let parts: Vec<&str> = hotkey.split('+').collect();
-    if parts.is_empty() {
-        return None;
+use crate::types::AppSettings;
+
+/// Actions déclenchables par un raccourci global
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HotkeyAction {
+    PushToTalk,
+    ToggleRecord,
+    Translate,
+    VoiceAction,
+}
+
+impl HotkeyAction {
+    /// Ordre de priorité : en cas de doublon, l'action la plus haute garde le raccourci
+    pub const ALL: [HotkeyAction; 4] = [
+        HotkeyAction::PushToTalk,
+        HotkeyAction::ToggleRecord,
+        HotkeyAction::VoiceAction,
+        HotkeyAction::Translate,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            HotkeyAction::PushToTalk => "push-to-talk",
+            HotkeyAction::ToggleRecord => "hands-free dictation",
+            HotkeyAction::Translate => "quick translation",
+            HotkeyAction::VoiceAction => "voice action",
+        }
+    }
+
+    fn setting_mut(self, settings: &mut AppSettings) -> &mut String {
+        match self {
+            HotkeyAction::PushToTalk => &mut settings.hotkey_push_to_talk,
+            HotkeyAction::ToggleRecord => &mut settings.hotkey_toggle_record,
+            HotkeyAction::Translate => &mut settings.hotkey_translate,
+            HotkeyAction::VoiceAction => &mut settings.hotkey_voice_action,
+        }
     }
 
+    /// Raccourci configuré pour l'action, `None` si elle est désactivée
+    fn hotkey(self, settings: &AppSettings) -> Option<&str> {
+        let hotkey = match self {
+            HotkeyAction::PushToTalk => &settings.hotkey_push_to_talk,
+            HotkeyAction::ToggleRecord => &settings.hotkey_toggle_record,
+            HotkeyAction::Translate if !settings.translation_enabled => return None,
+            HotkeyAction::Translate => &settings.hotkey_translate,
+            HotkeyAction::VoiceAction => &settings.hotkey_voice_action,
+        };
+        let hotkey = hotkey.trim();
+        (!hotkey.is_empty()).then_some(hotkey)
+    }
+}
+
+/// État d'un raccourci après application des réglages
+#[derive(Debug, Clone, Serialize)]
+pub struct HotkeyStatus {
+    pub action: HotkeyAction,
+    pub hotkey: String,
+    pub registered: bool,
+    pub error: Option<String>,
+    /// Raccourci accepté mais déconseillé (touche de saisie sans modificateur)
+    pub warning: Option<String>,
+}
+
+/// Raccourcis actuellement enregistrés auprès de l'OS
+static REGISTERED: Mutex<Vec<(HotkeyAction, Shortcut)>> = Mutex::new(Vec::new());
+/// Dernier rapport transmis à l'interface
+static STATUS: Mutex<Vec<HotkeyStatus>> = Mutex::new(Vec::new());
+
+/// Parse un raccourci clavier depuis un format string (ex: "Ctrl+Shift+R")
+pub fn parse_hotkey(hotkey: &str) -> Result<Shortcut, String> {
     let mut modifiers = Modifiers::empty();
     let mut key_code: Option<Code> = None;
 
-    for part in parts {
-        let part_lower = part.trim().to_lowercase();
-        match part_lower.as_str() {
+    for part in hotkey.split('+') {
+        let part = part.trim();
+        if part.is_empty() {
+            return Err(format!("Invalid hotkey '{}': empty key", hotkey));
+        }
+        match part.to_lowercase().as_str() {
             "ctrl" | "control" => modifiers |= Modifiers::CONTROL,
-            "cmd" | "command" | "meta" => modifiers |= Modifiers::META,
-            "commandorcontrol" => {
+            "cmd" | "command" | "meta" | "super" => modifiers |= Modifiers::META,
+            "commandorcontrol" | "cmdorctrl" => {
                 #[cfg(target_os = "macos")]
                 { modifiers |= Modifiers::META; }
                 #[cfg(not(target_os = "macos"))]
@@ -24,72 +94,384 @@ pub fn parse_hotkey(hotkey: &str) -> Option<Shortcut> {
             "alt" | "option" => modifiers |= Modifiers::ALT,
             "shift" => modifiers |= Modifiers::SHIFT,
             _ => {
-                key_code = match part.to_uppercase().as_str() {
-                    "A" => Some(Code::KeyA),
-                    "B" => Some(Code::KeyB),
-                    "C" => Some(Code::KeyC),
-                    "D" => Some(Code::KeyD),
-                    "E" => Some(Code::KeyE),
-                    "F" => Some(Code::KeyF),
-                    "G" => Some(Code::KeyG),
-                    "H" => Some(Code::KeyH),
-                    "I" => Some(Code::KeyI),
-                    "J" => Some(Code::KeyJ),
-                    "K" => Some(Code::KeyK),
-                    "L" => Some(Code::KeyL),
-                    "M" => Some(Code::KeyM),
-                    "N" => Some(Code::KeyN),
-                    "O" => Some(Code::KeyO),
-                    "P" => Some(Code::KeyP),
-                    "Q" => Some(Code::KeyQ),
-                    "R" => Some(Code::KeyR),
-                    "S" => Some(Code::KeyS),
-                    "T" => Some(Code::KeyT),
-                    "U" => Some(Code::KeyU),
-                    "V" => Some(Code::KeyV),
-                    "W" => Some(Code::KeyW),
-                    "X" => Some(Code::KeyX),
-                    "Y" => Some(Code::KeyY),
-                    "Z" => Some(Code::KeyZ),
-                    "0" => Some(Code::Digit0),
-                    "1" => Some(Code::Digit1),
-                    "2" => Some(Code::Digit2),
-                    "3" => Some(Code::Digit3),
-                    "4" => Some(Code::Digit4),
-                    "5" => Some(Code::Digit5),
-                    "6" => Some(Code::Digit6),
-                    "7" => Some(Code::Digit7),
-                    "8" => Some(Code::Digit8),
-                    "9" => Some(Code::Digit9),
-                    "SPACE" => Some(Code::Space),
-                    "ENTER" | "RETURN" => Some(Code::Enter),
-                    "TAB" => Some(Code::Tab),
-                    "ESCAPE" | "ESC" => Some(Code::Escape),
-                    "BACKSPACE" => Some(Code::Backspace),
-                    "DELETE" => Some(Code::Delete),
-                    "F1" => Some(Code::F1),
-                    "F2" => Some(Code::F2),
-                    "F3" => Some(Code::F3),
-                    "F4" => Some(Code::F4),
-                    "F5" => Some(Code::F5),
-                    "F6" => Some(Code::F6),
-                    "F7" => Some(Code::F7),
-                    "F8" => Some(Code::F8),
-                    "F9" => Some(Code::F9),
-                    "F10" => Some(Code::F10),
-                    "F11" => Some(Code::F11),
-                    "F12" => Some(Code::F12),
-                    _ => None,
-                };
+                let code = parse_key(part).ok_or_else(|| format!("Unknown key '{}' in '{}'", part, hotkey))?;
+                if key_code.replace(code).is_some() {
+                    return Err(format!("Invalid hotkey '{}': only one non-modifier key is allowed", hotkey));
+                }
+            }
+        }
+    }
+
+    let code = key_code.ok_or_else(|| format!("Invalid hotkey '{}': a key is required besides the modifiers", hotkey))?;
+    if modifiers.is_empty() {
+        Ok(Shortcut::new(None, code))
+    } else {
+        Ok(Shortcut::new(Some(modifiers), code))
+    }
+}
+
+/// Avertissement pour une touche de saisie utilisée seule : acceptée pour ne pas
+/// casser les anciens réglages, mais elle ne peut plus être tapée tant qu'elle est enregistrée
+fn hotkey_warning(hotkey: &str, shortcut: &Shortcut) -> Option<String> {
+    (shortcut.mods.is_empty() && !is_standalone_key(shortcut.key)).then(|| format!(
+        "'{}' has no modifier and can no longer be typed while Phonon runs (only function, numpad and media keys work well alone)",
+        hotkey
+    ))
+}
+
+fn parse_key(key: &str) -> Option<Code> {
+    let code = match key.to_uppercase().as_str() {
+        "A" => Code::KeyA,
+        "B" => Code::KeyB,
+        "C" => Code::KeyC,
+        "D" => Code::KeyD,
+        "E" => Code::KeyE,
+        "F" => Code::KeyF,
+        "G" => Code::KeyG,
+        "H" => Code::KeyH,
+        "I" => Code::KeyI,
+        "J" => Code::KeyJ,
+        "K" => Code::KeyK,
+        "L" => Code::KeyL,
+        "M" => Code::KeyM,
+        "N" => Code::KeyN,
+        "O" => Code::KeyO,
+        "P" => Code::KeyP,
+        "Q" => Code::KeyQ,
+        "R" => Code::KeyR,
+        "S" => Code::KeyS,
+        "T" => Code::KeyT,
+        "U" => Code::KeyU,
+        "V" => Code::KeyV,
+        "W" => Code::KeyW,
+        "X" => Code::KeyX,
+        "Y" => Code::KeyY,
+        "Z" => Code::KeyZ,
+        "0" => Code::Digit0,
+        "1" => Code::Digit1,
+        "2" => Code::Digit2,
+        "3" => Code::Digit3,
+        "4" => Code::Digit4,
+        "5" => Code::Digit5,
+        "6" => Code::Digit6,
+        "7" => Code::Digit7,
+        "8" => Code::Digit8,
+        "9" => Code::Digit9,
+        "SPACE" => Code::Space,
+        "ENTER" | "RETURN" => Code::Enter,
+        "TAB" => Code::Tab,
+        "ESCAPE" | "ESC" => Code::Escape,
+        "BACKSPACE" => Code::Backspace,
+        "DELETE" | "DEL" => Code::Delete,
+        "INSERT" | "INS" => Code::Insert,
+        "HOME" => Code::Home,
+        "END" => Code::End,
+        "PAGEUP" => Code::PageUp,
+        "PAGEDOWN" => Code::PageDown,
+        "ARROWUP" | "UP" => Code::ArrowUp,
+        "ARROWDOWN" | "DOWN" => Code::ArrowDown,
+        "ARROWLEFT" | "LEFT" => Code::ArrowLeft,
+        "ARROWRIGHT" | "RIGHT" => Code::ArrowRight,
+        "F1" => Code::F1,
+        "F2" => Code::F2,
+        "F3" => Code::F3,
+        "F4" => Code::F4,
+        "F5" => Code::F5,
+        "F6" => Code::F6,
+        "F7" => Code::F7,
+        "F8" => Code::F8,
+        "F9" => Code::F9,
+        "F10" => Code::F10,
+        "F11" => Code::F11,
+        "F12" => Code::F12,
+        "F13" => Code::F13,
+        "F14" => Code::F14,
+        "F15" => Code::F15,
+        "F16" => Code::F16,
+        "F17" => Code::F17,
+        "F18" => Code::F18,
+        "F19" => Code::F19,
+        "F20" => Code::F20,
+        "F21" => Code::F21,
+        "F22" => Code::F22,
+        "F23" => Code::F23,
+        "F24" => Code::F24,
+        "NUMPAD0" | "NUM0" => Code::Numpad0,
+        "NUMPAD1" | "NUM1" => Code::Numpad1,
+        "NUMPAD2" | "NUM2" => Code::Numpad2,
+        "NUMPAD3" | "NUM3" => Code::Numpad3,
+        "NUMPAD4" | "NUM4" => Code::Numpad4,
+        "NUMPAD5" | "NUM5" => Code::Numpad5,
+        "NUMPAD6" | "NUM6" => Code::Numpad6,
+        "NUMPAD7" | "NUM7" => Code::Numpad7,
+        "NUMPAD8" | "NUM8" => Code::Numpad8,
+        "NUMPAD9" | "NUM9" => Code::Numpad9,
+        "NUMPADADD" | "NUMADD" => Code::NumpadAdd,
+        "NUMPADSUBTRACT" | "NUMSUBTRACT" => Code::NumpadSubtract,
+        "NUMPADMULTIPLY" | "NUMMULTIPLY" => Code::NumpadMultiply,
+        "NUMPADDIVIDE" | "NUMDIVIDE" => Code::NumpadDivide,
+        "NUMPADDECIMAL" | "NUMDECIMAL" => Code::NumpadDecimal,
+        "NUMPADENTER" | "NUMENTER" => Code::NumpadEnter,
+        "MEDIAPLAYPAUSE" => Code::MediaPlayPause,
+        "MEDIASTOP" => Code::MediaStop,
+        "MEDIATRACKNEXT" => Code::MediaTrackNext,
+        "MEDIATRACKPREVIOUS" | "MEDIATRACKPREV" => Code::MediaTrackPrevious,
+        "AUDIOVOLUMEUP" | "VOLUMEUP" => Code::AudioVolumeUp,
+        "AUDIOVOLUMEDOWN" | "VOLUMEDOWN" => Code::AudioVolumeDown,
+        "AUDIOVOLUMEMUTE" | "VOLUMEMUTE" => Code::AudioVolumeMute,
+        _ => return None,
+    };
+    Some(code)
+}
+
+/// Touches utilisables sans modificateur : elles ne servent pas à la saisie
+fn is_standalone_key(code: Code) -> bool {
+    matches!(
+        code,
+        Code::F1 | Code::F2 | Code::F3 | Code::F4 | Code::F5 | Code::F6
+            | Code::F7 | Code::F8 | Code::F9 | Code::F10 | Code::F11 | Code::F12
+            | Code::F13 | Code::F14 | Code::F15 | Code::F16 | Code::F17 | Code::F18
+            | Code::F19 | Code::F20 | Code::F21 | Code::F22 | Code::F23 | Code::F24
+            | Code::Numpad0 | Code::Numpad1 | Code::Numpad2 | Code::Numpad3 | Code::Numpad4
+            | Code::Numpad5 | Code::Numpad6 | Code::Numpad7 | Code::Numpad8 | Code::Numpad9
+            | Code::NumpadAdd | Code::NumpadSubtract | Code::NumpadMultiply
+            | Code::NumpadDivide | Code::NumpadDecimal | Code::NumpadEnter
+            | Code::MediaPlayPause | Code::MediaStop | Code::MediaTrackNext
+            | Code::MediaTrackPrevious | Code::AudioVolumeUp | Code::AudioVolumeDown
+            | Code::AudioVolumeMute
+    )
+}
+
+/// Parse les raccourcis des actions actives et signale les doublons.
+/// Les actions désactivées sont omises.
+fn resolve(settings: &AppSettings) -> Vec<(HotkeyAction, String, Result<Shortcut, String>)> {
+    let mut resolved: Vec<(HotkeyAction, String, Result<Shortcut, String>)> = Vec::new();
+
+    for action in HotkeyAction::ALL {
+        let Some(hotkey) = action.hotkey(settings) else { continue };
+        let parsed = parse_hotkey(hotkey).and_then(|shortcut| {
+            match resolved.iter().find(|(_, _, other)| other.as_ref().ok() == Some(&shortcut)) {
+                Some((other, _, _)) => Err(format!("'{}' is already used by {}", hotkey, other.label())),
+                None => Ok(shortcut),
+            }
+        });
+        resolved.push((action, hotkey.to_string(), parsed));
+    }
+
+    resolved
+}
+
+/// Action associée au raccourci déclenché
+pub fn action_for(shortcut: &Shortcut) -> Option<HotkeyAction> {
+    REGISTERED.lock().ok()?
+        .iter()
+        .find(|(_, registered)| registered == shortcut)
+        .map(|(action, _)| *action)
+}
+
+/// Dernier état connu des raccourcis
+pub fn status() -> Vec<HotkeyStatus> {
+    STATUS.lock().map(|status| status.clone()).unwrap_or_default()
+}
+
+/// Applique les raccourcis des réglages : désenregistre ceux qui ont disparu,
+/// enregistre les nouveaux et notifie l'interface ("hotkeys-updated")
+pub fn apply(app: &AppHandle, settings: &AppSettings) -> Vec<HotkeyStatus> {
+    let resolved = resolve(settings);
+    let mut registered = match REGISTERED.lock() {
+        Ok(guard) => guard,
+        Err(e) => {
+            log::error!("[HOTKEYS] Registry poisoned: {}", e);
+            return status();
+        }
+    };
+
+    let wanted: Vec<Shortcut> = resolved.iter()
+        .filter_map(|(_, _, parsed)| parsed.as_ref().ok().copied())
+        .collect();
+    for (_, shortcut) in registered.iter().filter(|(_, s)| !wanted.contains(s)) {
+        if let Err(e) = app.global_shortcut().unregister(*shortcut) {
+            log::warn!("[HOTKEYS] Failed to unregister {:?}: {}", shortcut, e);
+        }
+    }
+    let previous: Vec<(HotkeyAction, Shortcut)> = registered.drain(..).collect();
+    let was_registered = |shortcut: &Shortcut| previous.iter().any(|(_, s)| s == shortcut);
+
+    let mut statuses = Vec::new();
+    for (action, hotkey, parsed) in resolved {
+        let result = parsed.and_then(|shortcut| {
+            if !was_registered(&shortcut) {
+                app.global_shortcut().register(shortcut).map_err(|e| {
+                    format!("'{}' could not be registered (already taken by another application?): {}", hotkey, e)
+                })?;
+            }
+            Ok(shortcut)
+        });
+        let warning = result.as_ref().ok().and_then(|shortcut| hotkey_warning(&hotkey, shortcut));
+        let error = match &result {
+            Ok(shortcut) => {
+                log::info!("[HOTKEYS] {} -> '{}'", action.label(), hotkey);
+                registered.push((action, *shortcut));
+                None
             }
+            Err(e) => {
+                log::error!("[HOTKEYS] {}: {}", action.label(), e);
+                // Nouveau raccourci invalide, en double ou refusé par l'OS :
+                // l'ancien reste actif plutôt que rien
+                match restore_previous(app, action, &previous, &wanted, &registered) {
+                    Some(shortcut) => {
+                        registered.push((action, shortcut));
+                        Some(format!("{}; the previous shortcut is still active", e))
+                    }
+                    None => Some(e.clone()),
+                }
+            }
+        };
+        if let Some(warning) = &warning {
+            log::warn!("[HOTKEYS] {}: {}", action.label(), warning);
         }
+        statuses.push(HotkeyStatus {
+            action,
+            hotkey,
+            registered: result.is_ok(),
+            error,
+            warning,
+        });
     }
+    drop(registered);
 
-    key_code.map(|code| {
-        if modifiers.is_empty() {
-            Shortcut::new(None, code)
-        } else {
-            Shortcut::new(Some(modifiers), code)
+    if let Ok(mut guard) = STATUS.lock() {
+        *guard = statuses.clone();
+    }
+    let _ = app.emit("hotkeys-updated", &statuses);
+    statuses
+}
+
+/// Remet dans `settings` l'ancien raccourci des actions dont le nouveau n'a pas
+/// pu être enregistré, pour ne sauvegarder que ce qui est réellement actif.
+/// Renvoie les erreurs des raccourcis écartés.
+pub fn keep_registered(statuses: &[HotkeyStatus], old: &AppSettings, settings: &mut AppSettings) -> Vec<String> {
+    let mut old = old.clone();
+    let mut reverted = Vec::new();
+    for status in statuses.iter().filter(|status| !status.registered) {
+        let previous = status.action.setting_mut(&mut old).clone();
+        let setting = status.action.setting_mut(settings);
+        // Un raccourci inchangé qui échouait déjà ne bloque pas les autres réglages
+        if *setting != previous {
+            *setting = previous;
+            reverted.push(format!("{}: {}", status.action.label(), status.error.as_deref().unwrap_or("not registered")));
         }
-    })
+    }
+    reverted
+}
+
+/// Réenregistre l'ancien raccourci de l'action, s'il a été désenregistré et
+/// n'est pas repris par une autre action
+fn restore_previous(
+    app: &AppHandle,
+    action: HotkeyAction,
+    previous: &[(HotkeyAction, Shortcut)],
+    wanted: &[Shortcut],
+    registered: &[(HotkeyAction, Shortcut)],
+) -> Option<Shortcut> {
+    let (_, shortcut) = previous.iter().find(|(a, _)| *a == action)?;
+    if wanted.contains(shortcut) || registered.iter().any(|(_, s)| s == shortcut) {
+        return None;
+    }
+    match app.global_shortcut().register(*shortcut) {
+        Ok(()) => {
+            log::warn!("[HOTKEYS] {} kept its previous shortcut {:?}", action.label(), shortcut);
+            Some(*shortcut)
+        }
+        Err(e) => {
+            log::error!("[HOTKEYS] Failed to restore {:?} for {}: {}", shortcut, action.label(), e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hotkey_keys() {
+        let shortcut = parse_hotkey("Ctrl+Shift+R").unwrap();
+        assert_eq!(shortcut, Shortcut::new(Some(Modifiers::CONTROL | Modifiers::SHIFT), Code::KeyR));
+        assert_eq!(parse_hotkey("Control+Space").unwrap(), Shortcut::new(Some(Modifiers::CONTROL), Code::Space));
+        assert_eq!(parse_hotkey("F13").unwrap(), Shortcut::new(None, Code::F13));
+        assert_eq!(parse_hotkey("Numpad5").unwrap(), Shortcut::new(None, Code::Numpad5));
+        assert_eq!(parse_hotkey("Alt+NumpadAdd").unwrap(), Shortcut::new(Some(Modifiers::ALT), Code::NumpadAdd));
+        assert_eq!(parse_hotkey("MediaPlayPause").unwrap(), Shortcut::new(None, Code::MediaPlayPause));
+    }
+
+    #[test]
+    fn test_legacy_single_key_is_accepted_with_warning() {
+        // Les anciens réglages acceptaient une touche seule : elle reste valide
+        let shortcut = parse_hotkey("R").unwrap();
+        assert_eq!(shortcut, Shortcut::new(None, Code::KeyR));
+        assert!(hotkey_warning("R", &shortcut).unwrap().contains("no modifier"));
+
+        assert!(hotkey_warning("F13", &parse_hotkey("F13").unwrap()).is_none());
+        assert!(hotkey_warning("Ctrl+R", &parse_hotkey("Ctrl+R").unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_parse_hotkey_errors() {
+        assert!(parse_hotkey("Ctrl+Foo").unwrap_err().contains("Unknown key 'Foo'"));
+        assert!(parse_hotkey("Ctrl+Shift").unwrap_err().contains("key is required"));
+        assert!(parse_hotkey("Ctrl+A+B").unwrap_err().contains("only one"));
+        assert!(parse_hotkey("Ctrl+").is_err());
+    }
+
+    #[test]
+    fn test_resolve_detects_duplicates() {
+        let settings = AppSettings {
+            hotkey_push_to_talk: "Ctrl+Space".to_string(),
+            hotkey_toggle_record: "Control+Space".to_string(),
+            hotkey_voice_action: "Ctrl+Alt+A".to_string(),
+            hotkey_translate: "Ctrl+Alt+A".to_string(),
+            translation_enabled: false,
+            ..AppSettings::default()
+        };
+        let resolved = resolve(&settings);
+
+        assert_eq!(resolved.len(), 3);
+        assert!(resolved[0].2.is_ok());
+        assert_eq!(resolved[1].0, HotkeyAction::ToggleRecord);
+        assert!(resolved[1].2.as_ref().unwrap_err().contains("push-to-talk"));
+        // Traduction désactivée : pas de conflit avec Voice Action
+        assert!(resolved[2].2.is_ok());
+    }
+
+    #[test]
+    fn test_keep_registered_reverts_failed_changes() {
+        let old = AppSettings {
+            hotkey_push_to_talk: "Ctrl+Space".to_string(),
+            hotkey_voice_action: "F13".to_string(),
+            ..AppSettings::default()
+        };
+        let mut settings = AppSettings {
+            hotkey_push_to_talk: "Ctrl+Alt+P".to_string(),
+            hotkey_voice_action: "F13".to_string(),
+            ..old.clone()
+        };
+        let failed = |action, hotkey: &str| HotkeyStatus {
+            action,
+            hotkey: hotkey.to_string(),
+            registered: false,
+            error: Some("taken".to_string()),
+            warning: None,
+        };
+        let statuses = [
+            failed(HotkeyAction::PushToTalk, "Ctrl+Alt+P"),
+            // Inchangé : l'échec existait déjà, rien à annuler
+            failed(HotkeyAction::VoiceAction, "F13"),
+        ];
+
+        let errors = keep_registered(&statuses, &old, &mut settings);
+        assert_eq!(errors, ["push-to-talk: taken"]);
+        assert_eq!(settings.hotkey_push_to_talk, "Ctrl+Space");
+        assert_eq!(settings.hotkey_voice_action, "F13");
+    }
 }
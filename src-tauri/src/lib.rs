@@ -2,8 +2,13 @@ mod audio;
 mod commands;
 mod engines;
 mod hotkeys;
+#[cfg(target_os = "linux")]
+mod linux_input;
 mod llm;
+mod normalization;
 mod platform;
+mod privacy;
+mod profiles;
 mod ptt;
 mod state;
 mod storage;
@@ -24,7 +29,11 @@ use tokio::sync::RwLock;
 pub fn run() {
     env_logger::init();
 
+    storage::credentials::migrate_plaintext_settings();
     let settings = storage::config::load_settings();
+    storage::encryption::init(settings.storage_encryption_enabled);
+    privacy::set_enabled(settings.privacy_mode);
+    engines::parakeet::set_beam_size(settings.parakeet_beam_size);
     log::info!("[PTT] Using hotkey: {}", settings.hotkey_push_to_talk);
 
     tauri::Builder::default()
@@ -45,6 +54,10 @@ pub fn run() {
             commands::stop_mic_preview,
             commands::get_settings,
             commands::update_settings,
+            commands::get_hotkey_status,
+            commands::export_settings,
+            commands::import_settings,
+            commands::get_managed_settings,
             commands::get_dictionary,
             commands::add_dictionary_word,
             commands::remove_dictionary_word,
@@ -64,6 +77,8 @@ pub fn run() {
             commands::download_vosk_model,
             commands::select_vosk_language,
             commands::switch_engine_type,
+            commands::get_engine_health,
+            commands::get_system_memory,
             commands::is_parakeet_available,
             commands::get_parakeet_models,
             commands::download_parakeet_model,
@@ -74,6 +89,7 @@ pub fn run() {
             commands::has_groq_api_key,
             commands::validate_groq_api_key,
             commands::delete_groq_api_key,
+            commands::get_credential_store_status,
             commands::get_groq_quota,
             commands::translate_text,
             commands::translate_text_local,
@@ -86,6 +102,7 @@ pub fn run() {
             commands::summarize_text_local,
             commands::summarize_text_smart,
             commands::auto_paste,
+            commands::diagnose_text_insertion,
             commands::show_floating_window,
             commands::hide_floating_window,
             commands::toggle_floating_window,
@@ -97,6 +114,7 @@ pub fn run() {
             commands::toggle_subtitles,
             commands::file_transcription::transcribe_files,
             commands::file_transcription::get_supported_audio_formats,
+            commands::evaluation::evaluate_engines,
             commands::get_snippets,
             commands::add_snippet,
             commands::update_snippet,
@@ -110,6 +128,11 @@ pub fn run() {
             commands::get_app_logs,
             commands::clear_app_logs,
             commands::get_log_file_path,
+            commands::get_storage_encryption_status,
+            commands::rotate_storage_key,
+            commands::get_user_voice_commands_status,
+            commands::reload_user_voice_commands,
+            commands::create_user_voice_commands_file,
         ])
         .setup(|app| {
             // Initialiser l'état
@@ -128,9 +151,15 @@ pub fn run() {
             let llm_engine: Arc<RwLock<Option<LocalLlmEngine>>> = Arc::new(RwLock::new(None));
             app.manage(llm_engine);
 
+            // Vérification périodique des moteurs de transcription
+            AppState::start_health_checks(app.handle().clone());
+
             // Initialiser les threads audio
-            ptt::init_ptt_audio_thread();
-            commands::transcription::init_gui_audio_thread();
+            ptt::init_ptt_audio_thread(app.handle().clone());
+            commands::transcription::init_gui_audio_thread(app.handle().clone());
+
+            // Commandes vocales utilisateur (rechargement à chaud)
+            voice_commands::user_commands::init(app.handle().clone());
 
             // Enregistrer les raccourcis globaux
             ptt::setup_shortcuts(app)?;
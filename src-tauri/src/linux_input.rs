@@ -0,0 +1,746 @@
+//! Insertion de texte native sous Linux
+//!
+//! Sans dépendre de wl-copy, wtype, ydotool, xclip ou xdotool :
+//! - X11 : l'extension XTest tape directement n'importe quel caractère Unicode,
+//!   en réassignant un instant un keycode libre aux caractères absents de la disposition ;
+//! - Wayland : un clavier virtuel uinput (accès en écriture à /dev/uinput) envoie
+//!   Ctrl+V une fois le texte placé dans le presse-papiers.
+//!
+//! Le presse-papiers de l'utilisateur est restauré après chaque collage. Les
+//! outils en ligne de commande restent le dernier recours.
+
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::AsRawFd;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use arboard::{Clipboard, ImageData};
+use x11rb::connection::{Connection, RequestConnection};
+use x11rb::protocol::xproto::{self, ConnectionExt as _, Keycode, Keysym};
+use x11rb::protocol::xtest::{self, ConnectionExt as _};
+use x11rb::rust_connection::RustConnection;
+
+use crate::platform::{InsertionCheck, TextInsertionReport};
+
+/// Temps laissé à l'application cible pour lire le presse-papiers avant sa restauration
+const CLIPBOARD_RESTORE_DELAY: Duration = Duration::from_millis(400);
+
+const XK_RETURN: Keysym = 0xff0d;
+const XK_TAB: Keysym = 0xff09;
+const XK_SHIFT_L: Keysym = 0xffe1;
+const XK_CONTROL_L: Keysym = 0xffe3;
+const XK_V: Keysym = 0x0076;
+
+pub fn is_wayland() -> bool {
+    std::env::var("WAYLAND_DISPLAY").is_ok()
+}
+
+/// Keysym X11 d'un caractère : Latin-1 tel quel, le reste en keysym Unicode
+fn char_keysym(c: char) -> Option<Keysym> {
+    match c {
+        '\n' => Some(XK_RETURN),
+        '\t' => Some(XK_TAB),
+        c if c.is_control() => None,
+        ' '..='~' | '\u{a0}'..='\u{ff}' => Some(c as Keysym),
+        c => Some(0x0100_0000 | c as Keysym),
+    }
+}
+
+// ============================================================================
+// XTest (X11)
+// ============================================================================
+
+struct Xtest {
+    conn: RustConnection,
+    root: xproto::Window,
+    min_keycode: Keycode,
+    keysyms_per_keycode: u8,
+    keysyms: Vec<Keysym>,
+}
+
+impl Xtest {
+    fn connect() -> Result<Self, String> {
+        if is_wayland() {
+            return Err("Wayland session: XTest only reaches X11 windows".to_string());
+        }
+        let (conn, screen) = x11rb::connect(None).map_err(|e| format!("Cannot connect to the X server: {}", e))?;
+        conn.extension_information(xtest::X11_EXTENSION_NAME)
+            .map_err(|e| e.to_string())?
+            .ok_or("XTest extension not available")?;
+
+        let (root, min_keycode, max_keycode) = {
+            let setup = conn.setup();
+            (setup.roots[screen].root, setup.min_keycode, setup.max_keycode)
+        };
+        let mapping = conn
+            .get_keyboard_mapping(min_keycode, max_keycode - min_keycode + 1)
+            .map_err(|e| e.to_string())?
+            .reply()
+            .map_err(|e| format!("Cannot read the keyboard mapping: {}", e))?;
+
+        Ok(Self {
+            conn,
+            root,
+            min_keycode,
+            keysyms_per_keycode: mapping.keysyms_per_keycode,
+            keysyms: mapping.keysyms,
+        })
+    }
+
+    fn keycodes(&self) -> impl Iterator<Item = (Keycode, &[Keysym])> {
+        self.keysyms
+            .chunks(self.keysyms_per_keycode.max(1) as usize)
+            .enumerate()
+            .map(|(i, syms)| (self.min_keycode.saturating_add(i as u8), syms))
+    }
+
+    /// Keycode produisant `keysym` dans la disposition courante, et s'il faut Shift
+    fn find(&self, keysym: Keysym) -> Option<(Keycode, bool)> {
+        self.keycodes().find_map(|(keycode, syms)| {
+            syms.iter().take(2).position(|&s| s == keysym).map(|level| (keycode, level == 1))
+        })
+    }
+
+    /// Keycodes sans symbole, réassignables le temps d'une frappe
+    fn spare_keycodes(&self) -> Vec<Keycode> {
+        self.keycodes()
+            .filter(|(_, syms)| syms.iter().all(|&s| s == 0))
+            .map(|(keycode, _)| keycode)
+            .collect()
+    }
+
+    fn key(&self, keycode: Keycode, press: bool) -> Result<(), String> {
+        let event = if press { xproto::KEY_PRESS_EVENT } else { xproto::KEY_RELEASE_EVENT };
+        self.conn
+            .xtest_fake_input(event, keycode, x11rb::CURRENT_TIME, self.root, 0, 0, 0)
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    fn tap(&self, keycode: Keycode, shift: Option<Keycode>) -> Result<(), String> {
+        let mut pressed = PressedKeys::new(self);
+        if let Some(shift) = shift {
+            pressed.press(shift)?;
+        }
+        pressed.press(keycode)?;
+        pressed.release()
+    }
+
+    /// Aller-retour avec le serveur : les requêtes précédentes sont traitées
+    fn sync(&self) -> Result<(), String> {
+        self.conn
+            .get_input_focus()
+            .map_err(|e| e.to_string())?
+            .reply()
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    /// Relâche les modificateurs tenus (raccourci push-to-talk), comme `xdotool --clearmodifiers`
+    fn release_held_modifiers(&self) -> Result<HeldModifiers<'_>, String> {
+        let pressed = self
+            .conn
+            .query_keymap()
+            .map_err(|e| e.to_string())?
+            .reply()
+            .map_err(|e| e.to_string())?
+            .keys;
+        let mut held: Vec<Keycode> = self
+            .conn
+            .get_modifier_mapping()
+            .map_err(|e| e.to_string())?
+            .reply()
+            .map_err(|e| e.to_string())?
+            .keycodes
+            .into_iter()
+            .filter(|&k| k != 0 && pressed[k as usize / 8] & (1 << (k % 8)) != 0)
+            .collect();
+        held.sort_unstable();
+        held.dedup();
+
+        let mut released = HeldModifiers { xtest: self, keys: Vec::new() };
+        for keycode in held {
+            self.key(keycode, false)?;
+            released.keys.push(keycode);
+        }
+        Ok(released)
+    }
+
+    fn set_mapping(&self, keycode: Keycode, keysym: Keysym) -> Result<(), String> {
+        let syms = vec![keysym; self.keysyms_per_keycode as usize];
+        self.conn
+            .change_keyboard_mapping(1, keycode, self.keysyms_per_keycode, &syms)
+            .map_err(|e| e.to_string())?;
+        self.sync()
+    }
+
+    fn type_text(&self, text: &str) -> Result<(), String> {
+        let spare = self.spare_keycodes();
+        let shift = self.find(XK_SHIFT_L).map(|(keycode, _)| keycode);
+        let held = self.release_held_modifiers()?;
+
+        // Keycodes libres réassignés pendant cette frappe
+        let mut remapped: Vec<(Keycode, Keysym)> = Vec::new();
+        let mut next_spare = 0;
+
+        let mut typed = || -> Result<(), String> {
+            for c in text.chars() {
+                let Some(keysym) = char_keysym(c) else { continue };
+                match self.find(keysym) {
+                    Some((keycode, false)) => self.tap(keycode, None)?,
+                    Some((keycode, true)) if shift.is_some() => self.tap(keycode, shift)?,
+                    _ => {
+                        let keycode = match remapped.iter().find(|(_, s)| *s == keysym) {
+                            Some(&(keycode, _)) => keycode,
+                            None => {
+                                if spare.is_empty() {
+                                    return Err(format!("No free keycode to type '{}'", c));
+                                }
+                                let keycode = spare[next_spare % spare.len()];
+                                if next_spare >= spare.len() {
+                                    // Keycode déjà utilisé : l'application doit avoir lu la frappe précédente
+                                    self.sync()?;
+                                    std::thread::sleep(Duration::from_millis(30));
+                                }
+                                next_spare += 1;
+                                self.set_mapping(keycode, keysym)?;
+                                remapped.retain(|&(k, _)| k != keycode);
+                                remapped.push((keycode, keysym));
+                                keycode
+                            }
+                        };
+                        self.tap(keycode, None)?;
+                    }
+                }
+            }
+            Ok(())
+        };
+        let result = typed();
+
+        if !remapped.is_empty() {
+            self.sync()?;
+            std::thread::sleep(Duration::from_millis(50));
+            for &(keycode, _) in &remapped {
+                self.set_mapping(keycode, 0)?;
+            }
+        }
+        held.restore()?;
+        result
+    }
+
+    fn paste_keys(&self) -> Result<(), String> {
+        let (ctrl, _) = self.find(XK_CONTROL_L).ok_or("No Control key in the keyboard mapping")?;
+        let v = match self.find(XK_V) {
+            Some((keycode, false)) => keycode,
+            _ => return Err("No 'v' key in the keyboard mapping".to_string()),
+        };
+
+        let held = self.release_held_modifiers()?;
+        let mut pressed = PressedKeys::new(self);
+        pressed.press(ctrl)?;
+        self.tap(v, None)?;
+        pressed.release()?;
+        held.restore()
+    }
+}
+
+/// Modificateurs de l'utilisateur relâchés le temps de l'insertion, appuyés
+/// de nouveau par `restore` ou, si l'insertion échoue en route, au drop
+struct HeldModifiers<'a> {
+    xtest: &'a Xtest,
+    keys: Vec<Keycode>,
+}
+
+impl HeldModifiers<'_> {
+    fn restore(mut self) -> Result<(), String> {
+        for &keycode in &self.keys {
+            self.xtest.key(keycode, true)?;
+        }
+        self.keys.clear();
+        self.xtest.sync()
+    }
+}
+
+impl Drop for HeldModifiers<'_> {
+    fn drop(&mut self) {
+        if self.keys.is_empty() {
+            return;
+        }
+        for &keycode in &self.keys {
+            let _ = self.xtest.key(keycode, true);
+        }
+        let _ = self.xtest.conn.flush();
+    }
+}
+
+/// Touches appuyées par XTest. Relâchées dans l'ordre inverse au drop si
+/// l'insertion échoue en route : un Ctrl ou un Shift ne reste jamais enfoncé.
+struct PressedKeys<'a> {
+    xtest: &'a Xtest,
+    keys: Vec<Keycode>,
+}
+
+impl<'a> PressedKeys<'a> {
+    fn new(xtest: &'a Xtest) -> Self {
+        Self { xtest, keys: Vec::new() }
+    }
+
+    fn press(&mut self, keycode: Keycode) -> Result<(), String> {
+        self.xtest.key(keycode, true)?;
+        self.keys.push(keycode);
+        Ok(())
+    }
+
+    fn release(mut self) -> Result<(), String> {
+        while let Some(keycode) = self.keys.pop() {
+            self.xtest.key(keycode, false)?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for PressedKeys<'_> {
+    fn drop(&mut self) {
+        if self.keys.is_empty() {
+            return;
+        }
+        for &keycode in self.keys.iter().rev() {
+            let _ = self.xtest.key(keycode, false);
+        }
+        let _ = self.xtest.conn.flush();
+    }
+}
+
+// ============================================================================
+// Clavier virtuel uinput (X11 et Wayland)
+// ============================================================================
+
+const EV_SYN: u16 = 0x00;
+const EV_KEY: u16 = 0x01;
+const SYN_REPORT: u16 = 0;
+const BUS_VIRTUAL: u16 = 0x06;
+const KEY_LEFTCTRL: u16 = 29;
+const KEY_V: u16 = 47;
+/// Toutes les touches d'un clavier, sans quoi libinput ne le traite pas comme tel
+const KEY_LAST_KEYBOARD: u16 = 248;
+
+#[repr(C)]
+struct UinputSetup {
+    id: libc::input_id,
+    name: [u8; 80],
+    ff_effects_max: u32,
+}
+
+/// `_IO('U', nr)` et `_IOW('U', nr, size)` du noyau
+const fn uinput_io(nr: libc::c_ulong) -> libc::c_ulong {
+    ((b'U' as libc::c_ulong) << 8) | nr
+}
+
+const fn uinput_iow(nr: libc::c_ulong, size: usize) -> libc::c_ulong {
+    (1 << 30) | ((size as libc::c_ulong) << 16) | uinput_io(nr)
+}
+
+const UI_DEV_CREATE: libc::c_ulong = uinput_io(1);
+const UI_DEV_DESTROY: libc::c_ulong = uinput_io(2);
+const UI_DEV_SETUP: libc::c_ulong = uinput_iow(3, std::mem::size_of::<UinputSetup>());
+const UI_SET_EVBIT: libc::c_ulong = uinput_iow(100, std::mem::size_of::<libc::c_int>());
+const UI_SET_KEYBIT: libc::c_ulong = uinput_iow(101, std::mem::size_of::<libc::c_int>());
+
+/// Créé à la première utilisation puis gardé : le compositeur met un moment à le découvrir
+static KEYBOARD: Mutex<Option<VirtualKeyboard>> = Mutex::new(None);
+
+struct VirtualKeyboard {
+    file: File,
+}
+
+impl VirtualKeyboard {
+    fn create() -> Result<Self, String> {
+        let file = OpenOptions::new()
+            .write(true)
+            .custom_flags(libc::O_NONBLOCK)
+            .open("/dev/uinput")
+            .map_err(|e| format!("Cannot open /dev/uinput: {} (a udev rule granting access to the user is needed)", e))?;
+        let fd = file.as_raw_fd();
+
+        let mut setup = UinputSetup {
+            id: libc::input_id { bustype: BUS_VIRTUAL, vendor: 0x1209, product: 0x7068, version: 1 },
+            name: [0; 80],
+            ff_effects_max: 0,
+        };
+        let name = b"Phonon virtual keyboard";
+        setup.name[..name.len()].copy_from_slice(name);
+
+        let created = unsafe {
+            libc::ioctl(fd, UI_SET_EVBIT as _, EV_KEY as libc::c_int) >= 0
+                && (1..=KEY_LAST_KEYBOARD).all(|key| libc::ioctl(fd, UI_SET_KEYBIT as _, key as libc::c_int) >= 0)
+                && libc::ioctl(fd, UI_DEV_SETUP as _, &setup as *const UinputSetup) >= 0
+                && libc::ioctl(fd, UI_DEV_CREATE as _) >= 0
+        };
+        if !created {
+            return Err(format!("uinput device creation failed: {}", std::io::Error::last_os_error()));
+        }
+
+        std::thread::sleep(Duration::from_millis(300));
+        log::info!("[INPUT] uinput virtual keyboard created");
+        Ok(Self { file })
+    }
+
+    fn emit(&mut self, kind: u16, code: u16, value: i32) -> Result<(), String> {
+        let event = libc::input_event {
+            time: libc::timeval { tv_sec: 0, tv_usec: 0 },
+            type_: kind,
+            code,
+            value,
+        };
+        let bytes = unsafe {
+            std::slice::from_raw_parts(
+                &event as *const libc::input_event as *const u8,
+                std::mem::size_of::<libc::input_event>(),
+            )
+        };
+        self.file.write_all(bytes).map_err(|e| format!("uinput write failed: {}", e))
+    }
+
+    /// Appuie les touches dans l'ordre et les relâche dans l'ordre inverse
+    fn chord(&mut self, keys: &[u16]) -> Result<(), String> {
+        let mut pressed = PressedChord { keyboard: self, keys: Vec::new() };
+        for &key in keys {
+            pressed.keyboard.emit(EV_KEY, key, 1)?;
+            pressed.keys.push(key);
+        }
+        pressed.keyboard.emit(EV_SYN, SYN_REPORT, 0)?;
+        std::thread::sleep(Duration::from_millis(10));
+        pressed.release()
+    }
+}
+
+/// Touches appuyées sur le clavier virtuel, relâchées au drop si l'envoi échoue en route
+struct PressedChord<'a> {
+    keyboard: &'a mut VirtualKeyboard,
+    keys: Vec<u16>,
+}
+
+impl PressedChord<'_> {
+    fn release(mut self) -> Result<(), String> {
+        while let Some(key) = self.keys.pop() {
+            self.keyboard.emit(EV_KEY, key, 0)?;
+        }
+        self.keyboard.emit(EV_SYN, SYN_REPORT, 0)
+    }
+}
+
+impl Drop for PressedChord<'_> {
+    fn drop(&mut self) {
+        if self.keys.is_empty() {
+            return;
+        }
+        for &key in self.keys.iter().rev() {
+            let _ = self.keyboard.emit(EV_KEY, key, 0);
+        }
+        let _ = self.keyboard.emit(EV_SYN, SYN_REPORT, 0);
+    }
+}
+
+impl Drop for VirtualKeyboard {
+    fn drop(&mut self) {
+        unsafe {
+            libc::ioctl(self.file.as_raw_fd(), UI_DEV_DESTROY as _);
+        }
+    }
+}
+
+fn with_keyboard<T>(f: impl FnOnce(&mut VirtualKeyboard) -> Result<T, String>) -> Result<T, String> {
+    let mut keyboard = KEYBOARD.lock().map_err(|e| e.to_string())?;
+    if keyboard.is_none() {
+        *keyboard = Some(VirtualKeyboard::create()?);
+    }
+    match keyboard.as_mut() {
+        Some(keyboard) => f(keyboard),
+        None => Err("uinput virtual keyboard unavailable".to_string()),
+    }
+}
+
+// ============================================================================
+// Presse-papiers sauvegardé et restauré
+// ============================================================================
+
+enum SavedClipboard {
+    Text(String),
+    Image(ImageData<'static>),
+    Empty,
+}
+
+struct PendingRestore {
+    saved: SavedClipboard,
+    inserted: String,
+    generation: u64,
+}
+
+struct ClipboardState {
+    /// Gardé ouvert : sous X11, le texte copié n'est servi que tant qu'il vit
+    clipboard: Option<Clipboard>,
+    pending: Option<PendingRestore>,
+}
+
+static CLIPBOARD: Mutex<ClipboardState> = Mutex::new(ClipboardState { clipboard: None, pending: None });
+static PASTE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+fn with_clipboard<T>(f: impl FnOnce(&mut Clipboard, &mut Option<PendingRestore>) -> Result<T, String>) -> Result<T, String> {
+    let mut state = CLIPBOARD.lock().map_err(|e| e.to_string())?;
+    let state = &mut *state;
+    if state.clipboard.is_none() {
+        state.clipboard = Some(Clipboard::new().map_err(|e| format!("Clipboard unavailable: {}", e))?);
+    }
+    match state.clipboard.as_mut() {
+        Some(clipboard) => f(clipboard, &mut state.pending),
+        None => Err("Clipboard unavailable".to_string()),
+    }
+}
+
+fn save_clipboard(clipboard: &mut Clipboard) -> SavedClipboard {
+    if let Ok(text) = clipboard.get_text() {
+        SavedClipboard::Text(text)
+    } else if let Ok(image) = clipboard.get_image() {
+        SavedClipboard::Image(image.to_owned_img())
+    } else {
+        SavedClipboard::Empty
+    }
+}
+
+/// Place `text` dans le presse-papiers en gardant le contenu à restaurer
+fn replace_clipboard(text: &str) -> Result<u64, String> {
+    with_clipboard(|clipboard, pending| {
+        // Collages rapprochés (streaming) : le contenu d'origine reste celui à restaurer
+        let saved = match pending.take() {
+            Some(previous) => previous.saved,
+            None => save_clipboard(clipboard),
+        };
+        clipboard.set_text(text).map_err(|e| format!("Failed to set clipboard: {}", e))?;
+
+        let generation = PASTE_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+        *pending = Some(PendingRestore { saved, inserted: text.to_string(), generation });
+        Ok(generation)
+    })
+}
+
+fn schedule_restore(generation: u64) {
+    std::thread::spawn(move || {
+        std::thread::sleep(CLIPBOARD_RESTORE_DELAY);
+        let result = with_clipboard(|clipboard, pending| {
+            if pending.as_ref().map(|p| p.generation) != Some(generation) {
+                return Ok(());
+            }
+            let Some(restore) = pending.take() else { return Ok(()) };
+            // L'utilisateur a copié autre chose entre-temps : on n'y touche pas
+            if clipboard.get_text().ok().as_deref() != Some(restore.inserted.as_str()) {
+                return Ok(());
+            }
+            match restore.saved {
+                SavedClipboard::Text(text) => clipboard.set_text(text),
+                SavedClipboard::Image(image) => clipboard.set_image(image),
+                SavedClipboard::Empty => clipboard.clear(),
+            }
+            .map_err(|e| e.to_string())
+        });
+        if let Err(e) = result {
+            log::warn!("[INPUT] Failed to restore clipboard: {}", e);
+        }
+    });
+}
+
+/// Le texte reste dans le presse-papiers pour un collage manuel
+fn cancel_restore() {
+    if let Ok(mut state) = CLIPBOARD.lock() {
+        state.pending = None;
+    }
+}
+
+fn set_clipboard_cli(text: &str) -> Result<(), String> {
+    let (program, args): (&str, &[&str]) = if is_wayland() {
+        ("wl-copy", &[])
+    } else {
+        ("xclip", &["-selection", "clipboard"])
+    };
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("{}: {}", program, e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes()).map_err(|e| format!("{}: {}", program, e))?;
+    }
+    match child.wait() {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => Err(format!("{} exited with {}", program, status)),
+        Err(e) => Err(format!("{}: {}", program, e)),
+    }
+}
+
+// ============================================================================
+// Insertion
+// ============================================================================
+
+/// Tape `text` sans passer par le presse-papiers (X11 uniquement)
+pub fn type_text(text: &str) -> Result<(), String> {
+    Xtest::connect()?.type_text(text)
+}
+
+/// Envoie Ctrl+V par le premier chemin disponible et renvoie son nom
+pub fn send_paste_keys() -> Result<&'static str, String> {
+    let mut errors = Vec::new();
+
+    if !is_wayland() {
+        match Xtest::connect().and_then(|xtest| xtest.paste_keys()) {
+            Ok(()) => return Ok("xtest"),
+            Err(e) => errors.push(format!("xtest: {}", e)),
+        }
+    }
+
+    match with_keyboard(|keyboard| keyboard.chord(&[KEY_LEFTCTRL, KEY_V])) {
+        Ok(()) => return Ok("uinput"),
+        Err(e) => errors.push(format!("uinput: {}", e)),
+    }
+
+    let tools: &[(&'static str, &[&str])] = if is_wayland() {
+        &[("wtype", &["-M", "ctrl", "v", "-m", "ctrl"]), ("ydotool", &["key", "29:1", "47:1", "47:0", "29:0"])]
+    } else {
+        &[("xdotool", &["key", "--clearmodifiers", "ctrl+v"])]
+    };
+    for &(tool, args) in tools {
+        match Command::new(tool).args(args).output() {
+            Ok(output) if output.status.success() => return Ok(tool),
+            Ok(output) => errors.push(format!("{}: {}", tool, String::from_utf8_lossy(&output.stderr).trim())),
+            Err(e) => errors.push(format!("{}: {}", tool, e)),
+        }
+    }
+
+    Err(errors.join("; "))
+}
+
+/// Colle `text` puis restaure le presse-papiers. Renvoie le chemin qui a envoyé Ctrl+V.
+pub fn paste(text: &str) -> Result<&'static str, String> {
+    let generation = match replace_clipboard(text) {
+        Ok(generation) => Some(generation),
+        Err(e) => {
+            log::warn!("[INPUT] {}, falling back to command-line tools", e);
+            set_clipboard_cli(text).map_err(|cli| format!("{}; {}", e, cli))?;
+            None
+        }
+    };
+
+    std::thread::sleep(Duration::from_millis(50));
+
+    match send_paste_keys() {
+        Ok(path) => {
+            if let Some(generation) = generation {
+                schedule_restore(generation);
+            }
+            Ok(path)
+        }
+        Err(e) => {
+            cancel_restore();
+            Err(format!("No way to send Ctrl+V ({}), text left in the clipboard", e))
+        }
+    }
+}
+
+// ============================================================================
+// Diagnostic
+// ============================================================================
+
+fn find_in_path(program: &str) -> Option<PathBuf> {
+    std::env::var_os("PATH")?
+        .to_str()?
+        .split(':')
+        .map(|dir| PathBuf::from(dir).join(program))
+        .find(|path| path.is_file())
+}
+
+fn check(name: &str, result: Result<String, String>) -> InsertionCheck {
+    let (ok, detail) = match result {
+        Ok(detail) => (true, detail),
+        Err(detail) => (false, detail),
+    };
+    InsertionCheck { name: name.to_string(), ok, detail }
+}
+
+/// Droit d'écriture sur /dev/uinput, sans créer de clavier virtuel
+fn probe_uinput() -> Result<String, String> {
+    if KEYBOARD.lock().map_err(|e| e.to_string())?.is_some() {
+        return Ok("Virtual keyboard ready".to_string());
+    }
+    OpenOptions::new()
+        .write(true)
+        .custom_flags(libc::O_NONBLOCK)
+        .open("/dev/uinput")
+        .map(|_| "/dev/uinput writable, virtual keyboard created on first use".to_string())
+        .map_err(|e| format!("Cannot open /dev/uinput: {} (a udev rule granting access to the user is needed)", e))
+}
+
+/// Teste chaque chemin d'insertion sans rien taper dans l'application active
+pub fn diagnose() -> TextInsertionReport {
+    let wayland = is_wayland();
+
+    let mut checks = vec![
+        check(
+            "xtest",
+            Xtest::connect().map(|xtest| format!("{} free keycodes for characters missing from the layout", xtest.spare_keycodes().len())),
+        ),
+        check("uinput", probe_uinput()),
+        check(
+            "clipboard",
+            with_clipboard(|clipboard, _| match clipboard.get_text() {
+                Ok(_) | Err(arboard::Error::ContentNotAvailable) => Ok("Read and restore available".to_string()),
+                Err(e) => Err(e.to_string()),
+            }),
+        ),
+    ];
+    for tool in ["wl-copy", "wtype", "ydotool", "xclip", "xdotool"] {
+        checks.push(check(
+            tool,
+            find_in_path(tool).map(|path| path.display().to_string()).ok_or_else(|| "Not installed".to_string()),
+        ));
+    }
+
+    let works = |name: &str| checks.iter().any(|c| c.name == name && c.ok);
+    let typing = works("xtest").then(|| "xtest".to_string());
+    let clipboard = works("clipboard") || works(if wayland { "wl-copy" } else { "xclip" });
+    let key_paths: &[&str] = if wayland { &["uinput", "wtype", "ydotool"] } else { &["xtest", "uinput", "xdotool"] };
+    let paste = key_paths
+        .iter()
+        .find(|path| clipboard && works(path))
+        .map(|path| path.to_string());
+
+    TextInsertionReport {
+        session: if wayland { "wayland" } else { "x11" }.to_string(),
+        typing,
+        paste,
+        checks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_char_keysym() {
+        assert_eq!(char_keysym('a'), Some(0x61));
+        assert_eq!(char_keysym('é'), Some(0xe9));
+        assert_eq!(char_keysym('€'), Some(0x0100_20ac));
+        assert_eq!(char_keysym('\n'), Some(XK_RETURN));
+        assert_eq!(char_keysym('\r'), None);
+    }
+
+    #[test]
+    fn test_uinput_ioctl_numbers_match_kernel_headers() {
+        assert_eq!(UI_DEV_CREATE, 0x5501);
+        assert_eq!(UI_DEV_SETUP, 0x405c_5503);
+        assert_eq!(UI_SET_KEYBIT, 0x4004_5565);
+    }
+}
@@ -10,7 +10,7 @@ const PROMPT_EMAIL: &str = "Tu es un assistant d'écriture professionnelle. Tran
 
 const PROMPT_CODE: &str = "Tu es un assistant technique. Formate ce texte en documentation de code ou commentaire technique. Utilise la terminologie appropriée. Structure clairement. Retourne uniquement le texte formaté.";
 
-const PROMPT_NOTES: &str = "Tu es un assistant de prise de notes. Organise ce texte en notes structurées avec puces si approprié. Garde les points essentiels, supprime le superflu. Retourne uniquement les notes formatées.";
+const PROMPT_NOTES: &str = "Tu es un assistant de prise de notes. Organise ce texte en notes structurées en Markdown (titres #, puces -, listes numérotées, cases - [ ]) si approprié. Conserve la structure Markdown déjà présente. Garde les points essentiels, supprime le superflu. Retourne uniquement les notes formatées.";
 
 fn get_prompt(llm_mode: LlmMode, dictation_mode: DictationMode) -> &'static str {
     match llm_mode {
@@ -26,10 +26,12 @@ fn get_prompt(llm_mode: LlmMode, dictation_mode: DictationMode) -> &'static str
     }
 }
 
+/// `custom_prompt` (profil d'application) remplace le prompt du mode
 pub async fn process(
     text: &str,
     llm_mode: LlmMode,
     dictation_mode: DictationMode,
+    custom_prompt: Option<&str>,
     api_key: &str,
 ) -> Result<String, String> {
     // If LLM mode is Off, return text as-is
@@ -37,7 +39,7 @@ pub async fn process(
         return Ok(text.to_string());
     }
 
-    let prompt = get_prompt(llm_mode, dictation_mode);
+    let prompt = custom_prompt.unwrap_or_else(|| get_prompt(llm_mode, dictation_mode));
     let user_message = format!("Texte: {}", text);
 
     match groq_client::send_completion(api_key, prompt, &user_message).await {
@@ -0,0 +1,572 @@
+//! Per-language rule sets for text normalization
+//!
+//! A lexicon lists the spoken number words of one language along with the
+//! words that give them a meaning (currencies, months, hours...) and the
+//! local typography: French writes "312 €" and "15 %" with a no-break space
+//! (U+00A0) and groups thousands with a narrow no-break space (U+202F).
+
+use NumberWord::{Hundred, Hundreds, Scale, Teen, Tens, Unit, Zero};
+
+/// Role of a number word in the grammar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum NumberWord {
+    Zero,
+    /// 1 to 9
+    Unit(u64),
+    /// Fills the tens and units at once: 10-19, and 21-29 in Spanish ("veintidós")
+    Teen(u64),
+    /// 20, 30... 90
+    Tens(u64),
+    /// "cent", "hundred": multiplies what precedes it
+    Hundred { needs_count: bool },
+    /// Single word hundreds: "doscientos"
+    Hundreds(u64),
+    /// "mille", "million"... : closes a group
+    Scale { value: u64, needs_count: bool },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum OrdinalStyle {
+    /// "vingtième" → "20e", "premier" → "1er"
+    French,
+    /// "twenty-first" → "21st"
+    English,
+    /// "fünfzehnte" → "15."
+    German,
+    /// "primero" → "1.º", "primera" → "1.ª"
+    Spanish,
+    /// "undicesimo" → "11º"
+    Italian,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum TimeStyle {
+    /// "quinze heures trente" → "15 h 30"
+    French,
+    /// "three thirty pm" → "3:30 pm", "five o'clock" → "5:00"
+    English,
+    /// "acht Uhr dreißig" → "8:30 Uhr"
+    German,
+    /// No spoken time pattern
+    None,
+}
+
+pub(super) struct Lexicon {
+    /// ISO 639-1 code
+    pub language: &'static str,
+    pub numbers: &'static [(&'static str, NumberWord)],
+    /// "et" in "vingt et un", "and" in "one hundred and two"
+    pub connectors: &'static [&'static str],
+    /// Number words written as one word ("dreihundertzwölf", "trecentododici")
+    pub compounds: bool,
+    /// "dreiundzwanzig": units before tens, joined by a connector
+    pub units_first: bool,
+    /// "soixante-dix", "quatre-vingts"
+    pub vigesimal: bool,
+    pub ordinals: OrdinalStyle,
+    pub group_separator: &'static str,
+    pub decimal_separator: &'static str,
+    /// Spoken decimal point: "deux virgule cinq" → "2,5"
+    pub decimal_points: &'static [&'static str],
+    /// Currency words and their symbol
+    pub currencies: &'static [(&'static str, &'static str)],
+    pub cents: &'static [&'static str],
+    /// "$12" rather than "12 $"
+    pub symbol_before: bool,
+    pub currency_space: &'static str,
+    /// Ordered so that a phrase is listed before any phrase it contains
+    pub percent: &'static [&'static str],
+    pub percent_space: &'static str,
+    pub months: &'static [&'static str],
+    /// Month words that are also common words ("march", "may"): only read as a
+    /// month when the phrase clearly is a date (ordinal day or year)
+    pub ambiguous_months: &'static [&'static str],
+    /// "quince de marzo"
+    pub date_connectors: &'static [&'static str],
+    /// "March 15" rather than "15 mars"
+    pub month_first: bool,
+    pub time: TimeStyle,
+    pub hour_words: &'static [&'static str],
+    /// Phone numbers start with a spoken zero
+    pub zero_words: &'static [&'static str],
+    /// "06 12 34 56 78" rather than "0612345678"
+    pub phone_pairs: bool,
+    /// Spoken separators of e-mail addresses and URLs, longest phrase first
+    pub address_words: &'static [(&'static str, &'static str)],
+    /// Function words that never name a host or a mailbox ("I said dot com")
+    pub address_stopwords: &'static [&'static str],
+}
+
+/// Top-level domains accepted at the end of a spoken address
+pub(super) const TOP_LEVEL_DOMAINS: &[&str] = &[
+    "com", "org", "net", "fr", "de", "es", "it", "eu", "io", "dev", "app", "co", "uk", "ch", "be", "ca", "info",
+];
+
+// ============================================================================
+// Français
+// ============================================================================
+
+static FRENCH: Lexicon = Lexicon {
+    language: "fr",
+    numbers: &[
+        ("zéro", Zero),
+        ("un", Unit(1)),
+        ("une", Unit(1)),
+        ("deux", Unit(2)),
+        ("trois", Unit(3)),
+        ("quatre", Unit(4)),
+        ("cinq", Unit(5)),
+        ("six", Unit(6)),
+        ("sept", Unit(7)),
+        ("huit", Unit(8)),
+        ("neuf", Unit(9)),
+        ("dix", Teen(10)),
+        ("onze", Teen(11)),
+        ("douze", Teen(12)),
+        ("treize", Teen(13)),
+        ("quatorze", Teen(14)),
+        ("quinze", Teen(15)),
+        ("seize", Teen(16)),
+        ("vingt", Tens(20)),
+        ("vingts", Tens(20)),
+        ("trente", Tens(30)),
+        ("quarante", Tens(40)),
+        ("cinquante", Tens(50)),
+        ("soixante", Tens(60)),
+        ("cent", Hundred { needs_count: false }),
+        ("cents", Hundred { needs_count: false }),
+        ("mille", Scale { value: 1_000, needs_count: false }),
+        ("million", Scale { value: 1_000_000, needs_count: true }),
+        ("millions", Scale { value: 1_000_000, needs_count: true }),
+        ("milliard", Scale { value: 1_000_000_000, needs_count: true }),
+        ("milliards", Scale { value: 1_000_000_000, needs_count: true }),
+    ],
+    connectors: &["et"],
+    compounds: false,
+    units_first: false,
+    vigesimal: true,
+    ordinals: OrdinalStyle::French,
+    group_separator: "\u{202F}",
+    decimal_separator: ",",
+    decimal_points: &["virgule"],
+    currencies: &[("euro", "€"), ("euros", "€"), ("dollar", "$"), ("dollars", "$")],
+    cents: &["centime", "centimes"],
+    symbol_before: false,
+    currency_space: "\u{00A0}",
+    percent: &["pour cent", "pourcent", "pourcents"],
+    percent_space: "\u{00A0}",
+    months: &[
+        "janvier", "février", "mars", "avril", "mai", "juin", "juillet", "août", "septembre", "octobre", "novembre",
+        "décembre",
+    ],
+    ambiguous_months: &[],
+    date_connectors: &[],
+    month_first: false,
+    time: TimeStyle::French,
+    hour_words: &["heure", "heures"],
+    zero_words: &["zéro"],
+    phone_pairs: true,
+    address_words: &[
+        ("arobase", "@"),
+        ("arrobase", "@"),
+        ("point", "."),
+        ("tiret du bas", "_"),
+        ("tiret bas", "_"),
+        ("tiret", "-"),
+        ("slash", "/"),
+    ],
+    address_stopwords: &[
+        "le", "la", "les", "un", "une", "des", "de", "du", "à", "au", "aux", "et", "ou", "je", "tu", "il", "elle",
+        "on", "nous", "vous", "ils", "elles", "moi", "me", "ce", "c'est", "est",
+    ],
+};
+
+// ============================================================================
+// English
+// ============================================================================
+
+static ENGLISH: Lexicon = Lexicon {
+    language: "en",
+    numbers: &[
+        ("zero", Zero),
+        ("one", Unit(1)),
+        ("two", Unit(2)),
+        ("three", Unit(3)),
+        ("four", Unit(4)),
+        ("five", Unit(5)),
+        ("six", Unit(6)),
+        ("seven", Unit(7)),
+        ("eight", Unit(8)),
+        ("nine", Unit(9)),
+        ("ten", Teen(10)),
+        ("eleven", Teen(11)),
+        ("twelve", Teen(12)),
+        ("thirteen", Teen(13)),
+        ("fourteen", Teen(14)),
+        ("fifteen", Teen(15)),
+        ("sixteen", Teen(16)),
+        ("seventeen", Teen(17)),
+        ("eighteen", Teen(18)),
+        ("nineteen", Teen(19)),
+        ("twenty", Tens(20)),
+        ("thirty", Tens(30)),
+        ("forty", Tens(40)),
+        ("fifty", Tens(50)),
+        ("sixty", Tens(60)),
+        ("seventy", Tens(70)),
+        ("eighty", Tens(80)),
+        ("ninety", Tens(90)),
+        // "a hundred people" stays in words
+        ("hundred", Hundred { needs_count: true }),
+        ("thousand", Scale { value: 1_000, needs_count: true }),
+        ("million", Scale { value: 1_000_000, needs_count: true }),
+        ("billion", Scale { value: 1_000_000_000, needs_count: true }),
+    ],
+    connectors: &["and"],
+    compounds: false,
+    units_first: false,
+    vigesimal: false,
+    ordinals: OrdinalStyle::English,
+    group_separator: ",",
+    decimal_separator: ".",
+    decimal_points: &["point"],
+    currencies: &[("dollar", "$"), ("dollars", "$"), ("euro", "€"), ("euros", "€")],
+    cents: &["cent", "cents"],
+    symbol_before: true,
+    currency_space: "",
+    percent: &["per cent", "percent"],
+    percent_space: "",
+    months: &[
+        "january", "february", "march", "april", "may", "june", "july", "august", "september", "october", "november",
+        "december",
+    ],
+    ambiguous_months: &["march", "may", "august"],
+    date_connectors: &["of"],
+    month_first: true,
+    time: TimeStyle::English,
+    hour_words: &["o'clock"],
+    zero_words: &["zero"],
+    phone_pairs: false,
+    address_words: &[
+        ("at", "@"),
+        ("dot", "."),
+        ("underscore", "_"),
+        ("dash", "-"),
+        ("hyphen", "-"),
+        ("slash", "/"),
+    ],
+    address_stopwords: &[
+        "i", "you", "he", "she", "we", "they", "me", "him", "her", "us", "them", "my", "your", "the", "a", "an",
+        "to", "of", "and", "or", "is", "was", "said", "this", "that",
+    ],
+};
+
+// ============================================================================
+// Deutsch
+// ============================================================================
+
+static GERMAN: Lexicon = Lexicon {
+    language: "de",
+    numbers: &[
+        ("null", Zero),
+        ("ein", Unit(1)),
+        ("eins", Unit(1)),
+        ("eine", Unit(1)),
+        ("zwei", Unit(2)),
+        ("drei", Unit(3)),
+        ("vier", Unit(4)),
+        ("fünf", Unit(5)),
+        ("sechs", Unit(6)),
+        ("sieben", Unit(7)),
+        ("acht", Unit(8)),
+        ("neun", Unit(9)),
+        ("zehn", Teen(10)),
+        ("elf", Teen(11)),
+        ("zwölf", Teen(12)),
+        ("dreizehn", Teen(13)),
+        ("vierzehn", Teen(14)),
+        ("fünfzehn", Teen(15)),
+        ("sechzehn", Teen(16)),
+        ("siebzehn", Teen(17)),
+        ("achtzehn", Teen(18)),
+        ("neunzehn", Teen(19)),
+        ("zwanzig", Tens(20)),
+        ("dreißig", Tens(30)),
+        ("vierzig", Tens(40)),
+        ("fünfzig", Tens(50)),
+        ("sechzig", Tens(60)),
+        ("siebzig", Tens(70)),
+        ("achtzig", Tens(80)),
+        ("neunzig", Tens(90)),
+        ("hundert", Hundred { needs_count: false }),
+        ("tausend", Scale { value: 1_000, needs_count: false }),
+        ("million", Scale { value: 1_000_000, needs_count: true }),
+        ("millionen", Scale { value: 1_000_000, needs_count: true }),
+        ("milliarde", Scale { value: 1_000_000_000, needs_count: true }),
+        ("milliarden", Scale { value: 1_000_000_000, needs_count: true }),
+    ],
+    connectors: &["und"],
+    compounds: true,
+    units_first: true,
+    vigesimal: false,
+    ordinals: OrdinalStyle::German,
+    group_separator: ".",
+    decimal_separator: ",",
+    decimal_points: &["komma"],
+    currencies: &[("euro", "€"), ("dollar", "$")],
+    cents: &["cent"],
+    symbol_before: false,
+    currency_space: " ",
+    percent: &["prozent"],
+    percent_space: " ",
+    months: &[
+        "januar", "februar", "märz", "april", "mai", "juni", "juli", "august", "september", "oktober", "november",
+        "dezember",
+    ],
+    ambiguous_months: &["mai", "august"],
+    date_connectors: &[],
+    month_first: false,
+    time: TimeStyle::German,
+    hour_words: &["uhr"],
+    zero_words: &["null"],
+    phone_pairs: false,
+    address_words: &[
+        ("at", "@"),
+        ("ät", "@"),
+        ("punkt", "."),
+        ("unterstrich", "_"),
+        ("bindestrich", "-"),
+        ("schrägstrich", "/"),
+        ("slash", "/"),
+    ],
+    address_stopwords: &[
+        "ich", "du", "er", "sie", "es", "wir", "ihr", "mich", "mir", "der", "die", "das", "ein", "eine", "und",
+        "oder", "zu", "mit", "ist", "hallo",
+    ],
+};
+
+// ============================================================================
+// Español
+// ============================================================================
+
+static SPANISH: Lexicon = Lexicon {
+    language: "es",
+    numbers: &[
+        ("cero", Zero),
+        ("un", Unit(1)),
+        ("uno", Unit(1)),
+        ("una", Unit(1)),
+        ("dos", Unit(2)),
+        ("tres", Unit(3)),
+        ("cuatro", Unit(4)),
+        ("cinco", Unit(5)),
+        ("seis", Unit(6)),
+        ("siete", Unit(7)),
+        ("ocho", Unit(8)),
+        ("nueve", Unit(9)),
+        ("diez", Teen(10)),
+        ("once", Teen(11)),
+        ("doce", Teen(12)),
+        ("trece", Teen(13)),
+        ("catorce", Teen(14)),
+        ("quince", Teen(15)),
+        ("dieciséis", Teen(16)),
+        ("diecisiete", Teen(17)),
+        ("dieciocho", Teen(18)),
+        ("diecinueve", Teen(19)),
+        ("veinte", Tens(20)),
+        ("veintiuno", Teen(21)),
+        ("veintiún", Teen(21)),
+        ("veintiuna", Teen(21)),
+        ("veintidós", Teen(22)),
+        ("veintitrés", Teen(23)),
+        ("veinticuatro", Teen(24)),
+        ("veinticinco", Teen(25)),
+        ("veintiséis", Teen(26)),
+        ("veintisiete", Teen(27)),
+        ("veintiocho", Teen(28)),
+        ("veintinueve", Teen(29)),
+        ("treinta", Tens(30)),
+        ("cuarenta", Tens(40)),
+        ("cincuenta", Tens(50)),
+        ("sesenta", Tens(60)),
+        ("setenta", Tens(70)),
+        ("ochenta", Tens(80)),
+        ("noventa", Tens(90)),
+        ("cien", Hundred { needs_count: false }),
+        ("ciento", Hundred { needs_count: false }),
+        ("doscientos", Hundreds(200)),
+        ("doscientas", Hundreds(200)),
+        ("trescientos", Hundreds(300)),
+        ("trescientas", Hundreds(300)),
+        ("cuatrocientos", Hundreds(400)),
+        ("cuatrocientas", Hundreds(400)),
+        ("quinientos", Hundreds(500)),
+        ("quinientas", Hundreds(500)),
+        ("seiscientos", Hundreds(600)),
+        ("seiscientas", Hundreds(600)),
+        ("setecientos", Hundreds(700)),
+        ("setecientas", Hundreds(700)),
+        ("ochocientos", Hundreds(800)),
+        ("ochocientas", Hundreds(800)),
+        ("novecientos", Hundreds(900)),
+        ("novecientas", Hundreds(900)),
+        ("mil", Scale { value: 1_000, needs_count: false }),
+        ("millón", Scale { value: 1_000_000, needs_count: true }),
+        ("millones", Scale { value: 1_000_000, needs_count: true }),
+    ],
+    connectors: &["y"],
+    compounds: false,
+    units_first: false,
+    vigesimal: false,
+    ordinals: OrdinalStyle::Spanish,
+    group_separator: ".",
+    decimal_separator: ",",
+    decimal_points: &["coma"],
+    currencies: &[("euro", "€"), ("euros", "€"), ("dólar", "$"), ("dólares", "$")],
+    cents: &["céntimo", "céntimos"],
+    symbol_before: false,
+    currency_space: " ",
+    percent: &["por ciento"],
+    percent_space: " ",
+    months: &[
+        "enero", "febrero", "marzo", "abril", "mayo", "junio", "julio", "agosto", "septiembre", "octubre", "noviembre",
+        "diciembre",
+    ],
+    ambiguous_months: &[],
+    date_connectors: &["de"],
+    month_first: false,
+    time: TimeStyle::None,
+    hour_words: &[],
+    zero_words: &["cero"],
+    phone_pairs: false,
+    address_words: &[
+        ("arroba", "@"),
+        ("punto", "."),
+        ("guion bajo", "_"),
+        ("guion", "-"),
+        ("barra", "/"),
+    ],
+    address_stopwords: &[
+        "yo", "tú", "él", "ella", "me", "te", "se", "el", "la", "los", "las", "un", "una", "de", "del", "y", "o",
+        "a", "que", "es",
+    ],
+};
+
+// ============================================================================
+// Italiano
+// ============================================================================
+
+static ITALIAN: Lexicon = Lexicon {
+    language: "it",
+    numbers: &[
+        ("zero", Zero),
+        ("un", Unit(1)),
+        ("uno", Unit(1)),
+        ("una", Unit(1)),
+        ("due", Unit(2)),
+        ("tre", Unit(3)),
+        ("tré", Unit(3)),
+        ("quattro", Unit(4)),
+        ("cinque", Unit(5)),
+        ("sei", Unit(6)),
+        ("sette", Unit(7)),
+        ("otto", Unit(8)),
+        ("nove", Unit(9)),
+        ("dieci", Teen(10)),
+        ("undici", Teen(11)),
+        ("dodici", Teen(12)),
+        ("tredici", Teen(13)),
+        ("quattordici", Teen(14)),
+        ("quindici", Teen(15)),
+        ("sedici", Teen(16)),
+        ("diciassette", Teen(17)),
+        ("diciotto", Teen(18)),
+        ("diciannove", Teen(19)),
+        ("venti", Tens(20)),
+        ("trenta", Tens(30)),
+        ("quaranta", Tens(40)),
+        ("cinquanta", Tens(50)),
+        ("sessanta", Tens(60)),
+        ("settanta", Tens(70)),
+        ("ottanta", Tens(80)),
+        ("novanta", Tens(90)),
+        // Elided tens before "uno" and "otto": "ventuno", "trentotto"
+        ("vent", Tens(20)),
+        ("trent", Tens(30)),
+        ("quarant", Tens(40)),
+        ("cinquant", Tens(50)),
+        ("sessant", Tens(60)),
+        ("settant", Tens(70)),
+        ("ottant", Tens(80)),
+        ("novant", Tens(90)),
+        ("cento", Hundred { needs_count: false }),
+        ("mille", Scale { value: 1_000, needs_count: false }),
+        ("mila", Scale { value: 1_000, needs_count: true }),
+        ("milione", Scale { value: 1_000_000, needs_count: true }),
+        ("milioni", Scale { value: 1_000_000, needs_count: true }),
+        ("miliardo", Scale { value: 1_000_000_000, needs_count: true }),
+        ("miliardi", Scale { value: 1_000_000_000, needs_count: true }),
+    ],
+    connectors: &[],
+    compounds: true,
+    units_first: false,
+    vigesimal: false,
+    ordinals: OrdinalStyle::Italian,
+    group_separator: ".",
+    decimal_separator: ",",
+    decimal_points: &["virgola"],
+    currencies: &[("euro", "€"), ("dollaro", "$"), ("dollari", "$")],
+    cents: &["centesimo", "centesimi"],
+    symbol_before: false,
+    currency_space: " ",
+    percent: &["per cento", "percento"],
+    percent_space: "",
+    months: &[
+        "gennaio", "febbraio", "marzo", "aprile", "maggio", "giugno", "luglio", "agosto", "settembre", "ottobre",
+        "novembre", "dicembre",
+    ],
+    ambiguous_months: &[],
+    date_connectors: &[],
+    month_first: false,
+    time: TimeStyle::None,
+    hour_words: &[],
+    zero_words: &["zero"],
+    phone_pairs: false,
+    address_words: &[
+        ("chiocciola", "@"),
+        ("punto", "."),
+        ("trattino basso", "_"),
+        ("underscore", "_"),
+        ("trattino", "-"),
+        ("barra", "/"),
+        ("slash", "/"),
+    ],
+    address_stopwords: &[
+        "io", "tu", "lui", "lei", "mi", "ti", "si", "il", "lo", "la", "gli", "le", "un", "una", "di", "del", "e",
+        "o", "a", "che", "è", "ciao",
+    ],
+};
+
+static LEXICONS: [&Lexicon; 5] = [&FRENCH, &ENGLISH, &GERMAN, &SPANISH, &ITALIAN];
+
+/// Lexicon for a language code ("fr", "en-US") or name ("english").
+/// Unlike command packs there is no fallback: unknown languages are left as-is.
+pub(super) fn for_language(language: &str) -> Option<&'static Lexicon> {
+    let code = language
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase();
+    let code = match code.as_str() {
+        "english" => "en",
+        "french" => "fr",
+        "german" => "de",
+        "spanish" => "es",
+        "italian" => "it",
+        other => other,
+    };
+
+    LEXICONS.iter().find(|lexicon| lexicon.language == code).copied()
+}
@@ -0,0 +1,583 @@
+//! Inverse text normalization for Phonon
+//!
+//! Rewrites spoken forms into their written form after transcription and
+//! before voice commands: "trois cent douze euros le quinze mars" becomes
+//! "312 € le 15 mars". Cardinals, ordinals, currencies, percentages, dates,
+//! times, phone numbers, e-mail addresses and URLs are handled with
+//! per-language rule sets (see `lexicons`). Deterministic, no LLM involved.
+
+mod lexicons;
+mod numbers;
+
+use crate::types::DictationMode;
+use lexicons::{Lexicon, TimeStyle, TOP_LEVEL_DOMAINS};
+use numbers::{format_cardinal, format_ordinal, parse_cardinal, parse_ordinal};
+
+/// Spoken meridiems after an English time ("three thirty pm")
+const MERIDIEMS: &[&str] = &["am", "pm", "a.m", "p.m"];
+
+/// Categories rewritten in a dictation mode
+#[derive(Debug, Clone, Copy)]
+struct Rules {
+    cardinals: bool,
+    ordinals: bool,
+    currencies: bool,
+    percentages: bool,
+    dates: bool,
+    times: bool,
+    phones: bool,
+    emails: bool,
+    urls: bool,
+}
+
+impl Rules {
+    fn for_mode(mode: DictationMode) -> Self {
+        match mode {
+            // Le code garde ses mots : seuls les nombres et les adresses sont écrits
+            DictationMode::Code => Self {
+                cardinals: true,
+                ordinals: false,
+                currencies: false,
+                percentages: true,
+                dates: false,
+                times: false,
+                phones: false,
+                emails: true,
+                urls: true,
+            },
+            DictationMode::General | DictationMode::Email | DictationMode::Notes => Self {
+                cardinals: true,
+                ordinals: true,
+                currencies: true,
+                percentages: true,
+                dates: true,
+                times: true,
+                phones: true,
+                emails: true,
+                urls: true,
+            },
+        }
+    }
+}
+
+/// One whitespace-separated word, split from its surrounding punctuation
+struct Word<'a> {
+    /// Whitespace before the word
+    space: &'a str,
+    lead: &'a str,
+    core: &'a str,
+    trail: &'a str,
+    lower: String,
+}
+
+fn tokenize(text: &str) -> (Vec<Word<'_>>, &str) {
+    let mut words = Vec::new();
+    let mut rest = text;
+
+    loop {
+        let space_len = rest.len() - rest.trim_start().len();
+        let (space, after) = rest.split_at(space_len);
+        if after.is_empty() {
+            return (words, space);
+        }
+        let token_len = after.find(char::is_whitespace).unwrap_or(after.len());
+        let (token, next) = after.split_at(token_len);
+
+        let core_start = token.find(char::is_alphanumeric).unwrap_or(token.len());
+        let core_end = token
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_alphanumeric())
+            .map_or(core_start, |(pos, c)| pos + c.len_utf8());
+        let core = &token[core_start..core_end];
+
+        words.push(Word {
+            space,
+            lead: &token[..core_start],
+            core,
+            trail: &token[core_end..],
+            lower: core.to_lowercase(),
+        });
+        rest = next;
+    }
+}
+
+/// Rewrites spoken numbers, dates, amounts and addresses of `text`.
+/// Languages without a rule set are returned unchanged.
+pub fn normalize(text: &str, language: &str, mode: DictationMode) -> String {
+    let Some(lexicon) = lexicons::for_language(language) else {
+        return text.to_string();
+    };
+    let rules = Rules::for_mode(mode);
+    let (words, trailing_space) = tokenize(text);
+
+    let mut result = String::with_capacity(text.len());
+    let mut index = 0;
+    while index < words.len() {
+        let word = &words[index];
+        result.push_str(word.space);
+
+        let window = phrase_window(&words, index);
+        match rewrite(&window, lexicon, &rules) {
+            Some((written, consumed)) => {
+                result.push_str(word.lead);
+                result.push_str(&written);
+                result.push_str(words[index + consumed - 1].trail);
+                index += consumed;
+            }
+            None => {
+                result.push_str(word.lead);
+                result.push_str(word.core);
+                result.push_str(word.trail);
+                index += 1;
+            }
+        }
+    }
+    result.push_str(trailing_space);
+
+    result
+}
+
+/// Lowercase words from `start` that can be rewritten together:
+/// punctuation between two words ends the phrase
+fn phrase_window<'a>(words: &'a [Word], start: usize) -> Vec<&'a str> {
+    let mut window = Vec::new();
+    for (offset, word) in words[start..].iter().enumerate() {
+        if word.core.is_empty() || (offset > 0 && !word.lead.is_empty()) {
+            break;
+        }
+        window.push(word.lower.as_str());
+        if !word.trail.is_empty() {
+            break;
+        }
+    }
+    window
+}
+
+/// Written form of the phrase starting the window, and the number of words it replaces
+fn rewrite(window: &[&str], lexicon: &Lexicon, rules: &Rules) -> Option<(String, usize)> {
+    if window.is_empty() {
+        return None;
+    }
+
+    if rules.emails || rules.urls {
+        if let Some(found) = address(window, lexicon, rules) {
+            return Some(found);
+        }
+    }
+    if rules.phones {
+        if let Some(found) = phone_number(window, lexicon) {
+            return Some(found);
+        }
+    }
+    if rules.dates && lexicon.month_first {
+        if let Some(found) = month_first_date(window, lexicon) {
+            return Some(found);
+        }
+    }
+
+    number(window, lexicon, rules)
+}
+
+/// A number and the unit following it, if any
+fn number(window: &[&str], lexicon: &Lexicon, rules: &Rules) -> Option<(String, usize)> {
+    let literal = window[0].chars().all(|c| c.is_ascii_digit()).then(|| window[0].parse::<u64>().ok());
+    let cardinal = match literal {
+        Some(value) => value.map(|value| (value, 1)),
+        None => parse_cardinal(window, lexicon),
+    };
+    let ordinal = if rules.ordinals && literal.is_none() {
+        parse_ordinal(window, lexicon)
+    } else {
+        None
+    };
+
+    // "vingt et unième" is longer read as an ordinal than as "vingt"
+    if let Some((value, len, gender)) = ordinal.filter(|o| cardinal.is_none_or(|(_, c)| o.1 > c)) {
+        let before_month = rules.dates && followed_by_month(&window[len..], lexicon, true);
+        if before_month || value >= 10 || len > 1 {
+            return Some((format_ordinal(value, gender, lexicon), len));
+        }
+        return None;
+    }
+
+    let (value, len) = cardinal?;
+    let rest = &window[len..];
+    let digits = match literal {
+        Some(_) => value.to_string(),
+        None => format_cardinal(value, lexicon),
+    };
+
+    // "deux virgule cinq" → "2,5", read before "virgule" can be taken for punctuation
+    if let Some((decimals, extra)) = decimal_part(rest, lexicon) {
+        let digits = format!("{}{}{}", digits, lexicon.decimal_separator, decimals);
+        let len = len + extra;
+        return match unit(&digits, &window[len..], lexicon, rules) {
+            Some((written, extra)) => Some((written, len + extra)),
+            None => rules.cardinals.then_some((digits, len)),
+        };
+    }
+
+    if rules.times {
+        if let Some((written, extra)) = time(value, rest, lexicon) {
+            return Some((written, len + extra));
+        }
+    }
+    if let Some((written, extra)) = unit(&digits, rest, lexicon, rules) {
+        return Some((written, len + extra));
+    }
+    if literal.is_some() {
+        return None;
+    }
+    if rules.dates && (1..=31).contains(&value) && followed_by_month(rest, lexicon, false) {
+        return Some((digits, len));
+    }
+    // "un chat", "one day": a lone word below ten stays in words
+    if rules.cardinals && (value >= 10 || len > 1) {
+        return Some((digits, len));
+    }
+    None
+}
+
+/// Currency or percent sign after a written number, and the number of words it replaces
+fn unit(digits: &str, rest: &[&str], lexicon: &Lexicon, rules: &Rules) -> Option<(String, usize)> {
+    if rules.currencies {
+        if let Some(found) = currency(digits, rest, lexicon) {
+            return Some(found);
+        }
+    }
+    if rules.percentages {
+        if let Some(extra) = lexicon.percent.iter().find_map(|phrase| phrase_at(rest, phrase)) {
+            return Some((format!("{}{}%", digits, lexicon.percent_space), extra));
+        }
+    }
+    None
+}
+
+/// Digits after a spoken decimal point, read as one number ("virgule quatorze")
+/// or digit by digit ("point zero five"), and the number of words they replace
+fn decimal_part(rest: &[&str], lexicon: &Lexicon) -> Option<(String, usize)> {
+    if !rest.first().is_some_and(|word| lexicon.decimal_points.contains(word)) {
+        return None;
+    }
+    let mut decimals = String::new();
+    let mut consumed = 1;
+    while let Some(word) = rest.get(consumed) {
+        if !word.is_empty() && word.chars().all(|c| c.is_ascii_digit()) {
+            decimals.push_str(word);
+            consumed += 1;
+            continue;
+        }
+        match parse_cardinal(&rest[consumed..], lexicon).filter(|(value, _)| *value < 1000) {
+            Some((value, len)) => {
+                decimals.push_str(&value.to_string());
+                consumed += len;
+            }
+            None => break,
+        }
+    }
+    (!decimals.is_empty()).then_some((decimals, consumed))
+}
+
+/// Number of words of `phrase` if `words` starts with it
+fn phrase_at(words: &[&str], phrase: &str) -> Option<usize> {
+    let parts: Vec<&str> = phrase.split(' ').collect();
+    (words.len() >= parts.len() && words.iter().zip(&parts).all(|(word, part)| word == part)).then_some(parts.len())
+}
+
+/// Whether a month follows the day. After a plain cardinal, an ambiguous month
+/// word ("zwei August") only reads as a date when a year follows it.
+fn followed_by_month(rest: &[&str], lexicon: &Lexicon, after_ordinal: bool) -> bool {
+    let rest = match rest.first() {
+        Some(first) if lexicon.date_connectors.contains(first) => &rest[1..],
+        _ => rest,
+    };
+    match rest.first() {
+        Some(word) if lexicon.ambiguous_months.contains(word) => after_ordinal || year(&rest[1..], lexicon).is_some(),
+        Some(word) => lexicon.months.contains(word),
+        None => false,
+    }
+}
+
+/// Year read as a number ("two thousand five") or as two pairs ("nineteen eighty-four")
+fn year(words: &[&str], lexicon: &Lexicon) -> Option<(u64, usize)> {
+    match parse_cardinal(words, lexicon) {
+        Some((value, len)) if value >= 1000 => Some((value, len)),
+        Some((high, len)) if (10..100).contains(&high) => parse_cardinal(&words[len..], lexicon)
+            .filter(|(low, _)| (10..100).contains(low))
+            .map(|(low, low_len)| (high * 100 + low, len + low_len)),
+        _ => None,
+    }
+}
+
+/// "quinze heures trente" → "15 h 30", "acht Uhr" → "8 Uhr", "three thirty pm" → "3:30 pm"
+fn time(hour: u64, rest: &[&str], lexicon: &Lexicon) -> Option<(String, usize)> {
+    let minutes = |words: &[&str]| parse_cardinal(words, lexicon).filter(|(m, _)| (1..=59).contains(m));
+
+    match lexicon.time {
+        TimeStyle::French | TimeStyle::German => {
+            if hour > 24 || !rest.first().is_some_and(|word| lexicon.hour_words.contains(word)) {
+                return None;
+            }
+            let minute = minutes(&rest[1..]);
+            let written = match (lexicon.time, minute) {
+                (TimeStyle::French, Some((m, _))) => format!("{}\u{00A0}h\u{00A0}{:02}", hour, m),
+                (TimeStyle::French, None) => format!("{}\u{00A0}h", hour),
+                (_, Some((m, _))) => format!("{}:{:02} Uhr", hour, m),
+                (_, None) => format!("{} Uhr", hour),
+            };
+            Some((written, 1 + minute.map_or(0, |(_, len)| len)))
+        }
+        TimeStyle::English => {
+            if !(1..=12).contains(&hour) {
+                return None;
+            }
+            if rest.first().is_some_and(|word| lexicon.hour_words.contains(word)) {
+                return Some((format!("{}:00", hour), 1));
+            }
+            // "three oh five pm"
+            let (minute, len) = match rest.first() {
+                Some(&"oh") => parse_cardinal(&rest[1..], lexicon)
+                    .filter(|(m, _)| (1..=9).contains(m))
+                    .map(|(m, len)| (Some(m), len + 1))?,
+                _ => minutes(rest).map_or((None, 0), |(m, len)| (Some(m), len)),
+            };
+            let meridiem = rest.get(len).filter(|word| MERIDIEMS.contains(word))?;
+            let written = match minute {
+                Some(m) => format!("{}:{:02} {}", hour, m, meridiem),
+                None => format!("{} {}", hour, meridiem),
+            };
+            Some((written, len + 1))
+        }
+        TimeStyle::None => None,
+    }
+}
+
+/// "douze euros cinquante" → "12,50 €", "twelve dollars" → "$12"
+fn currency(digits: &str, rest: &[&str], lexicon: &Lexicon) -> Option<(String, usize)> {
+    let first = rest.first()?;
+    let (_, symbol) = lexicon.currencies.iter().find(|(word, _)| word == first)?;
+
+    let mut consumed = 1;
+    let mut amount = digits.to_string();
+    let after = &rest[1..];
+    let skip = usize::from(after.first().is_some_and(|word| lexicon.connectors.contains(word)));
+    if let Some((cents, len)) = parse_cardinal(&after[skip..], lexicon).filter(|(c, _)| (1..100).contains(c)) {
+        let cents_word = after.get(skip + len).is_some_and(|word| lexicon.cents.contains(word));
+        amount = format!("{}{}{:02}", amount, lexicon.decimal_separator, cents);
+        consumed += skip + len + usize::from(cents_word);
+    }
+
+    let written = if lexicon.symbol_before {
+        format!("{}{}", symbol, amount)
+    } else {
+        format!("{}{}{}", amount, lexicon.currency_space, symbol)
+    };
+    Some((written, consumed))
+}
+
+/// "march fifteenth twenty twenty-four" → "March 15, 2024"
+fn month_first_date(window: &[&str], lexicon: &Lexicon) -> Option<(String, usize)> {
+    if !lexicon.months.contains(&window[0]) {
+        return None;
+    }
+    let rest = &window[1..];
+    let (day, ordinal) = match parse_ordinal(rest, lexicon) {
+        Some((value, len, _)) => ((value, len), true),
+        None => (parse_cardinal(rest, lexicon)?, false),
+    };
+    if !(1..=31).contains(&day.0) {
+        return None;
+    }
+
+    let mut month = window[0].to_string();
+    month[..1].make_ascii_uppercase();
+    let mut written = format!("{} {}", month, day.0);
+    let mut consumed = 1 + day.1;
+
+    let year = year(&window[consumed..], lexicon);
+    // "I will march ten miles": a verb, not a date
+    if lexicon.ambiguous_months.contains(&window[0]) && !ordinal && year.is_none() {
+        return None;
+    }
+    if let Some((value, len)) = year {
+        written.push_str(&format!(", {}", value));
+        consumed += len;
+    }
+
+    Some((written, consumed))
+}
+
+/// Spoken zero followed by digit groups: "zéro six douze trente-quatre..."
+fn phone_number(window: &[&str], lexicon: &Lexicon) -> Option<(String, usize)> {
+    if !lexicon.zero_words.contains(&window[0]) {
+        return None;
+    }
+
+    let mut digits = String::from("0");
+    let mut consumed = 1;
+    while let Some((group, len)) = parse_cardinal(&window[consumed..], lexicon).filter(|(g, _)| *g < 100) {
+        digits.push_str(&group.to_string());
+        consumed += len;
+    }
+    if digits.len() < 6 {
+        return None;
+    }
+
+    if lexicon.phone_pairs && digits.len().is_multiple_of(2) {
+        let pairs: Vec<&str> = (0..digits.len()).step_by(2).map(|i| &digits[i..i + 2]).collect();
+        return Some((pairs.join(" "), consumed));
+    }
+    Some((digits, consumed))
+}
+
+/// Spoken e-mail address or URL: "jean point dupont arobase exemple point fr"
+fn address(window: &[&str], lexicon: &Lexicon, rules: &Rules) -> Option<(String, usize)> {
+    let separator = |at: usize| {
+        lexicon
+            .address_words
+            .iter()
+            .find_map(|(phrase, symbol)| phrase_at(&window[at..], phrase).map(|len| (*symbol, len)))
+    };
+    let is_label = |at: usize| {
+        window.get(at).is_some_and(|word| word.chars().all(char::is_alphanumeric)) && separator(at).is_none()
+    };
+
+    if !is_label(0) {
+        return None;
+    }
+    let mut written = window[0].to_string();
+    let mut consumed = 1;
+    while let Some((symbol, len)) = separator(consumed) {
+        if !is_label(consumed + len) {
+            break;
+        }
+        written.push_str(symbol);
+        written.push_str(window[consumed + len]);
+        consumed += len + 1;
+    }
+
+    let (host, path) = match written.split_once('/') {
+        Some((host, path)) => (host, Some(path)),
+        None => (written.as_str(), None),
+    };
+    let domain = match host.split('@').collect::<Vec<_>>().as_slice() {
+        [domain] if rules.urls => *domain,
+        [_, domain] if rules.emails && path.is_none() => *domain,
+        _ => return None,
+    };
+    let top_level = domain.rsplit_once('.').map(|(_, tld)| tld)?;
+    if !TOP_LEVEL_DOMAINS.contains(&top_level) {
+        return None;
+    }
+
+    // A bare "<word> dot <tld>" is usually speech ("I said dot com", "fini point de vue"):
+    // the name before the TLD must follow "@", "www" or another dotted label
+    if !host.contains('@') && domain.matches('.').count() < 2 {
+        return None;
+    }
+    let labels: Vec<&str> = host.split(['.', '@']).collect();
+    if labels[..labels.len() - 1].iter().any(|label| lexicon.address_stopwords.contains(label)) {
+        return None;
+    }
+    Some((written, consumed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fr(text: &str) -> String {
+        normalize(text, "fr", DictationMode::General)
+    }
+
+    fn en(text: &str) -> String {
+        normalize(text, "en", DictationMode::General)
+    }
+
+    #[test]
+    fn test_amount_and_date() {
+        assert_eq!(fr("trois cent douze euros le quinze mars"), "312\u{00A0}€ le 15 mars");
+        assert_eq!(fr("douze euros cinquante"), "12,50\u{00A0}€");
+        assert_eq!(en("twelve dollars and fifty cents"), "$12.50");
+        assert_eq!(fr("le premier mai deux mille vingt-quatre"), "le 1er mai 2024");
+        assert_eq!(en("on march third nineteen eighty-four"), "on March 3, 1984");
+        assert_eq!(en("see you may fifth"), "see you May 5");
+        assert_eq!(normalize("quince de marzo", "es", DictationMode::General), "15 de marzo");
+    }
+
+    #[test]
+    fn test_small_numbers_stay_in_words() {
+        assert_eq!(fr("un chat et deux chiens"), "un chat et deux chiens");
+        assert_eq!(en("one day I will win twenty-two races"), "one day I will win 22 races");
+        assert_eq!(fr("il est arrivé premier"), "il est arrivé premier");
+        assert_eq!(fr("le vingtième siècle"), "le 20e siècle");
+    }
+
+    #[test]
+    fn test_percent_and_time() {
+        assert_eq!(fr("cinq pour cent"), "5\u{00A0}%");
+        assert_eq!(en("twenty percent"), "20%");
+        assert_eq!(fr("rendez-vous à quinze heures trente"), "rendez-vous à 15\u{00A0}h\u{00A0}30");
+        assert_eq!(en("at three thirty pm"), "at 3:30 pm");
+        assert_eq!(en("at five o'clock"), "at 5:00");
+        assert_eq!(normalize("um acht Uhr dreißig", "de", DictationMode::General), "um 8:30 Uhr");
+    }
+
+    #[test]
+    fn test_phone_email_url() {
+        assert_eq!(
+            fr("appelle le zéro six douze trente-quatre cinquante-six soixante-dix-huit"),
+            "appelle le 06 12 34 56 78"
+        );
+        assert_eq!(
+            fr("écris à jean point dupont arobase exemple point fr merci"),
+            "écris à jean.dupont@exemple.fr merci"
+        );
+        assert_eq!(en("contact me at john dot doe at gmail dot com"), "contact me at john.doe@gmail.com");
+        assert_eq!(en("see www dot example dot com slash docs"), "see www.example.com/docs");
+        assert_eq!(fr("au point mort"), "au point mort");
+    }
+
+    #[test]
+    fn test_plain_speech_is_not_an_address() {
+        assert_eq!(fr("c'est fini point de vue"), "c'est fini point de vue");
+        assert_eq!(normalize("Hallo Punkt De", "de", DictationMode::General), "Hallo Punkt De");
+        assert_eq!(normalize("ciao punto it", "it", DictationMode::General), "ciao punto it");
+        assert_eq!(en("I said dot com"), "I said dot com");
+        assert_eq!(en("send it to me at home dot com"), "send it to me at home dot com");
+    }
+
+    #[test]
+    fn test_ambiguous_month_words() {
+        assert_eq!(en("I will march ten miles"), "I will march 10 miles");
+        assert_eq!(en("you may one day"), "you may one day");
+        assert_eq!(en("march tenth"), "March 10");
+        assert_eq!(en("march ten twenty twenty-four"), "March 10, 2024");
+    }
+
+    #[test]
+    fn test_decimals() {
+        assert_eq!(fr("deux virgule cinq"), "2,5");
+        assert_eq!(fr("il mesure un virgule quatre-vingts mètre"), "il mesure 1,80 mètre");
+        assert_eq!(fr("trois virgule quatorze pour cent"), "3,14\u{00A0}%");
+        assert_eq!(fr("zéro virgule zéro cinq"), "0,05");
+        assert_eq!(en("two point five"), "2.5");
+        assert_eq!(normalize("zwei Komma fünf", "de", DictationMode::General), "2,5");
+        // "virgule" sans chiffre après reste un mot (commande de ponctuation)
+        assert_eq!(fr("deux virgule puis trois"), "deux virgule puis trois");
+    }
+
+    #[test]
+    fn test_punctuation_is_kept_and_splits_numbers() {
+        assert_eq!(fr("Total : vingt-deux, trente."), "Total : 22, 30.");
+        assert_eq!(fr("(quinze pour cent)"), "(15\u{00A0}%)");
+    }
+
+    #[test]
+    fn test_mode_and_language_toggles() {
+        assert_eq!(normalize("douze euros", "fr", DictationMode::Code), "12 euros");
+        assert_eq!(normalize("douze euros", "nl", DictationMode::General), "douze euros");
+        assert_eq!(normalize("trecentododici euro", "it-IT", DictationMode::Notes), "312 €");
+    }
+}
@@ -0,0 +1,484 @@
+//! Spoken numbers: cardinal and ordinal parsing, digit formatting
+
+use super::lexicons::{Lexicon, NumberWord, OrdinalStyle};
+
+/// Longest words run tried as one number ("quatre-vingt-dix-sept mille trois cent...")
+const MAX_NUMBER_WORDS: usize = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Gender {
+    Masculine,
+    Feminine,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Part {
+    Number(NumberWord),
+    Connector,
+}
+
+/// Last filled position of the number being read
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Slot {
+    Start,
+    Unit,
+    Teen,
+    Tens,
+    Hundred,
+    Scale,
+}
+
+struct Reader<'a> {
+    lexicon: &'a Lexicon,
+    total: u64,
+    current: u64,
+    slot: Slot,
+    last_scale: u64,
+    connector: bool,
+    zero: bool,
+}
+
+impl<'a> Reader<'a> {
+    fn new(lexicon: &'a Lexicon) -> Self {
+        Self {
+            lexicon,
+            total: 0,
+            current: 0,
+            slot: Slot::Start,
+            last_scale: u64::MAX,
+            connector: false,
+            zero: false,
+        }
+    }
+
+    fn value(&self) -> u64 {
+        self.total + self.current
+    }
+
+    /// A number can end here: something was read and no connector is dangling
+    fn complete(&self) -> bool {
+        self.slot != Slot::Start && !self.connector
+    }
+
+    /// Adds one part; false if it cannot continue the number
+    fn push(&mut self, part: Part) -> bool {
+        if self.zero {
+            return false;
+        }
+        let Part::Number(word) = part else {
+            if self.slot == Slot::Start || self.connector {
+                return false;
+            }
+            self.connector = true;
+            return true;
+        };
+        let open = matches!(self.slot, Slot::Start | Slot::Hundred | Slot::Scale);
+        let tail = self.current % 100;
+
+        let slot = match word {
+            NumberWord::Zero if self.slot == Slot::Start => {
+                self.zero = true;
+                Slot::Unit
+            }
+            NumberWord::Zero => return false,
+            NumberWord::Unit(unit) => {
+                // "dix-sept", "soixante-dix-neuf", "quatre-vingt-dix-sept"
+                let after_ten = self.slot == Slot::Teen && self.lexicon.vigesimal && tail % 20 == 10 && unit >= 7;
+                if !(open || self.slot == Slot::Tens || after_ten) {
+                    return false;
+                }
+                self.current += unit;
+                Slot::Unit
+            }
+            NumberWord::Teen(teen) => {
+                // "soixante-quinze", "quatre-vingt-douze"
+                let vigesimal = self.slot == Slot::Tens && self.lexicon.vigesimal && (tail == 60 || tail == 80) && teen < 20;
+                if !(open || vigesimal) {
+                    return false;
+                }
+                self.current += teen;
+                Slot::Teen
+            }
+            NumberWord::Tens(tens) => {
+                if open {
+                    self.current += tens;
+                    Slot::Tens
+                } else if self.slot == Slot::Unit && self.lexicon.vigesimal && tail == 4 && tens == 20 && !self.connector {
+                    // "quatre-vingts"
+                    self.current += 76;
+                    Slot::Tens
+                } else if self.slot == Slot::Unit && self.lexicon.units_first && self.connector && tail < 10 {
+                    // "dreiundzwanzig"
+                    self.current += tens;
+                    Slot::Teen
+                } else {
+                    return false;
+                }
+            }
+            NumberWord::Hundred { needs_count } => {
+                if self.current == 0 && !needs_count && matches!(self.slot, Slot::Start | Slot::Scale) {
+                    self.current = 100;
+                } else if self.current > 0 && self.current < 100 && self.slot != Slot::Hundred {
+                    self.current *= 100;
+                } else {
+                    return false;
+                }
+                Slot::Hundred
+            }
+            NumberWord::Hundreds(hundreds) => {
+                if self.current != 0 || !matches!(self.slot, Slot::Start | Slot::Scale) {
+                    return false;
+                }
+                self.current = hundreds;
+                Slot::Hundred
+            }
+            NumberWord::Scale { value, needs_count } => {
+                if value >= self.last_scale {
+                    return false;
+                }
+                if self.current == 0 {
+                    if needs_count || self.slot != Slot::Start {
+                        return false;
+                    }
+                    self.current = 1;
+                }
+                self.total += self.current * value;
+                self.current = 0;
+                self.last_scale = value;
+                Slot::Scale
+            }
+        };
+
+        self.slot = slot;
+        self.connector = false;
+        true
+    }
+}
+
+/// Splits a word into number parts: "vingt-deux", "dreihundertzwölf"
+fn split_word(word: &str, lexicon: &Lexicon) -> Option<Vec<Part>> {
+    let mut parts = Vec::new();
+    for piece in word.split('-') {
+        if piece.is_empty() {
+            return None;
+        }
+        if let Some(part) = lookup(piece, lexicon) {
+            parts.push(part);
+        } else if !lexicon.compounds || !segment(piece, lexicon, &mut parts) {
+            return None;
+        }
+    }
+    Some(parts)
+}
+
+fn lookup(piece: &str, lexicon: &Lexicon) -> Option<Part> {
+    if lexicon.connectors.contains(&piece) {
+        return Some(Part::Connector);
+    }
+    lexicon
+        .numbers
+        .iter()
+        .find(|(word, _)| *word == piece)
+        .map(|(_, number)| Part::Number(*number))
+}
+
+/// Cuts a compound into lexicon entries, longest prefix first
+fn segment(rest: &str, lexicon: &Lexicon, parts: &mut Vec<Part>) -> bool {
+    if rest.is_empty() {
+        return true;
+    }
+
+    let mut candidates: Vec<(&str, Part)> = lexicon
+        .numbers
+        .iter()
+        .map(|(word, number)| (*word, Part::Number(*number)))
+        .chain(lexicon.connectors.iter().map(|word| (*word, Part::Connector)))
+        .filter(|(word, _)| rest.starts_with(word))
+        .collect();
+    candidates.sort_by_key(|(word, _)| std::cmp::Reverse(word.len()));
+
+    for (word, part) in candidates {
+        parts.push(part);
+        if segment(&rest[word.len()..], lexicon, parts) {
+            return true;
+        }
+        parts.pop();
+    }
+    false
+}
+
+/// Reads the longest cardinal at the start of `words` (lowercase).
+/// Returns its value and the number of words it spans.
+pub(super) fn parse_cardinal(words: &[&str], lexicon: &Lexicon) -> Option<(u64, usize)> {
+    let mut reader = Reader::new(lexicon);
+    let mut best = None;
+
+    for (index, word) in words.iter().take(MAX_NUMBER_WORDS).enumerate() {
+        let Some(parts) = split_word(word, lexicon) else {
+            break;
+        };
+        if !parts.into_iter().all(|part| reader.push(part)) {
+            break;
+        }
+        if reader.complete() {
+            best = Some((reader.value(), index + 1));
+        }
+    }
+
+    best
+}
+
+/// Reads the longest ordinal at the start of `words`: a cardinal whose last
+/// word is in ordinal form ("vingt et unième", "twenty-first")
+pub(super) fn parse_ordinal(words: &[&str], lexicon: &Lexicon) -> Option<(u64, usize, Gender)> {
+    let limit = words.len().min(MAX_NUMBER_WORDS);
+
+    for len in (1..=limit).rev() {
+        let last = words[len - 1];
+        // "vingt-et-unième": only the last piece is in ordinal form
+        let (head, tail) = match last.rfind('-') {
+            Some(pos) => (&last[..=pos], &last[pos + 1..]),
+            None => ("", last),
+        };
+
+        for (cardinal, gender) in cardinal_forms(tail, lexicon.ordinals) {
+            let mut candidate: Vec<String> = words[..len - 1].iter().map(|w| w.to_string()).collect();
+            candidate.push(format!("{}{}", head, cardinal));
+            let candidate: Vec<&str> = candidate.iter().map(String::as_str).collect();
+            if let Some((value, consumed)) = parse_cardinal(&candidate, lexicon) {
+                if consumed == len && value > 0 {
+                    return Some((value, len, gender));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Possible cardinal spellings of an ordinal word
+fn cardinal_forms(word: &str, style: OrdinalStyle) -> Vec<(String, Gender)> {
+    use Gender::{Feminine, Masculine};
+    let irregular = |table: &[(&str, &str, Gender)]| {
+        table
+            .iter()
+            .filter(|(ordinal, _, _)| *ordinal == word)
+            .map(|(_, cardinal, gender)| (cardinal.to_string(), *gender))
+            .collect::<Vec<_>>()
+    };
+
+    match style {
+        OrdinalStyle::French => {
+            let forms = irregular(&[
+                ("premier", "un", Masculine),
+                ("première", "un", Feminine),
+                ("premiers", "un", Masculine),
+                ("premières", "un", Feminine),
+            ]);
+            if !forms.is_empty() {
+                return forms;
+            }
+            let Some(stem) = word.strip_suffix("ième").or_else(|| word.strip_suffix("ièmes")) else {
+                return Vec::new();
+            };
+            let stem = match stem {
+                "cinqu" => "cinq",
+                "neuv" => "neuf",
+                other => other,
+            };
+            vec![(stem.to_string(), Masculine), (format!("{}e", stem), Masculine)]
+        }
+        OrdinalStyle::English => {
+            let forms = irregular(&[
+                ("first", "one", Masculine),
+                ("second", "two", Masculine),
+                ("third", "three", Masculine),
+                ("fifth", "five", Masculine),
+                ("eighth", "eight", Masculine),
+                ("ninth", "nine", Masculine),
+                ("twelfth", "twelve", Masculine),
+            ]);
+            if !forms.is_empty() {
+                return forms;
+            }
+            if let Some(stem) = word.strip_suffix("ieth") {
+                return vec![(format!("{}y", stem), Masculine)];
+            }
+            word.strip_suffix("th")
+                .map(|stem| vec![(stem.to_string(), Masculine)])
+                .unwrap_or_default()
+        }
+        OrdinalStyle::German => {
+            // Declined endings: "erste", "ersten", "erster"...
+            let Some(stem) = ["e", "er", "en", "es", "em"].iter().find_map(|ending| word.strip_suffix(ending)) else {
+                return Vec::new();
+            };
+            let mut forms = Vec::new();
+            for (ordinal, cardinal) in [("erst", "eins"), ("dritt", "drei"), ("siebt", "sieben"), ("acht", "acht")] {
+                if let Some(prefix) = stem.strip_suffix(ordinal) {
+                    forms.push((format!("{}{}", prefix, cardinal), Masculine));
+                }
+            }
+            if let Some(base) = stem.strip_suffix("st") {
+                forms.push((base.to_string(), Masculine));
+            }
+            if let Some(base) = stem.strip_suffix('t') {
+                forms.push((base.to_string(), Masculine));
+            }
+            forms
+        }
+        OrdinalStyle::Spanish | OrdinalStyle::Italian => {
+            let table: &[&str] = if style == OrdinalStyle::Spanish {
+                &[
+                    "primer", "segund", "tercer", "cuart", "quint", "sext", "séptim", "octav", "noven", "décim",
+                ]
+            } else {
+                &["prim", "second", "terz", "quart", "quint", "sest", "settim", "ottav", "non", "decim"]
+            };
+            let units = if style == OrdinalStyle::Spanish {
+                ["uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve", "diez"]
+            } else {
+                ["uno", "due", "tre", "quattro", "cinque", "sei", "sette", "otto", "nove", "dieci"]
+            };
+            let gender = if word.ends_with('a') || word.ends_with('e') { Feminine } else { Masculine };
+
+            // "primero", "primera", and the Spanish apocopes "primer", "tercer"
+            let stem = word.strip_suffix(['o', 'a', 'i', 'e']);
+            let apocope = style == OrdinalStyle::Spanish && matches!(word, "primer" | "tercer");
+            if let Some(index) = table.iter().position(|ordinal| stem == Some(ordinal) || apocope && *ordinal == word) {
+                return vec![(units[index].to_string(), gender)];
+            }
+            if style == OrdinalStyle::Spanish {
+                return Vec::new();
+            }
+            // "undicesimo" → "undici", "ventesimo" → "venti", "centesimo" → "cento"
+            let Some(stem) = ["esimo", "esima", "esimi", "esime"].iter().find_map(|s| word.strip_suffix(s)) else {
+                return Vec::new();
+            };
+            ["", "i", "e", "a", "o"]
+                .iter()
+                .map(|vowel| (format!("{}{}", stem, vowel), gender))
+                .collect()
+        }
+    }
+}
+
+/// Digits, grouped by thousands from 10 000 on ("2024" but "12 500")
+pub(super) fn format_cardinal(value: u64, lexicon: &Lexicon) -> String {
+    let digits = value.to_string();
+    if value < 10_000 {
+        return digits;
+    }
+
+    let mut grouped = String::new();
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            grouped.push_str(lexicon.group_separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+pub(super) fn format_ordinal(value: u64, gender: Gender, lexicon: &Lexicon) -> String {
+    let number = format_cardinal(value, lexicon);
+    match lexicon.ordinals {
+        OrdinalStyle::French => match (value, gender) {
+            (1, Gender::Masculine) => "1er".to_string(),
+            (1, Gender::Feminine) => "1re".to_string(),
+            _ => format!("{}e", number),
+        },
+        OrdinalStyle::English => {
+            let suffix = match (value % 100, value % 10) {
+                (11..=13, _) => "th",
+                (_, 1) => "st",
+                (_, 2) => "nd",
+                (_, 3) => "rd",
+                _ => "th",
+            };
+            format!("{}{}", number, suffix)
+        }
+        OrdinalStyle::German => format!("{}.", number),
+        OrdinalStyle::Spanish => match gender {
+            Gender::Masculine => format!("{}.º", number),
+            Gender::Feminine => format!("{}.ª", number),
+        },
+        OrdinalStyle::Italian => match gender {
+            Gender::Masculine => format!("{}º", number),
+            Gender::Feminine => format!("{}ª", number),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::lexicons::for_language;
+    use super::*;
+
+    fn cardinal(text: &str, language: &str) -> Option<(u64, usize)> {
+        let words: Vec<&str> = text.split(' ').collect();
+        parse_cardinal(&words, for_language(language).unwrap())
+    }
+
+    fn ordinal(text: &str, language: &str) -> Option<String> {
+        let lexicon = for_language(language).unwrap();
+        let words: Vec<&str> = text.split(' ').collect();
+        parse_ordinal(&words, lexicon).map(|(value, _, gender)| format_ordinal(value, gender, lexicon))
+    }
+
+    #[test]
+    fn test_french_cardinals() {
+        assert_eq!(cardinal("trois cent douze euros", "fr"), Some((312, 3)));
+        assert_eq!(cardinal("vingt et un", "fr"), Some((21, 3)));
+        assert_eq!(cardinal("soixante-dix-sept", "fr"), Some((77, 1)));
+        assert_eq!(cardinal("quatre-vingt-dix-neuf", "fr"), Some((99, 1)));
+        assert_eq!(cardinal("quatre-vingts", "fr"), Some((80, 1)));
+        assert_eq!(cardinal("soixante et onze", "fr"), Some((71, 3)));
+        assert_eq!(cardinal("deux mille vingt-quatre", "fr"), Some((2024, 3)));
+        assert_eq!(cardinal("mille neuf cent quatre-vingt-quatre", "fr"), Some((1984, 4)));
+        assert_eq!(cardinal("trois millions deux cent mille", "fr"), Some((3_200_000, 5)));
+        // Deux nombres distincts
+        assert_eq!(cardinal("six douze", "fr"), Some((6, 1)));
+        assert_eq!(cardinal("vingt et le", "fr"), Some((20, 1)));
+        assert_eq!(cardinal("millions", "fr"), None);
+    }
+
+    #[test]
+    fn test_other_language_cardinals() {
+        assert_eq!(cardinal("one hundred and twelve", "en"), Some((112, 4)));
+        assert_eq!(cardinal("twenty-five thousand", "en"), Some((25_000, 2)));
+        assert_eq!(cardinal("hundred", "en"), None);
+        assert_eq!(cardinal("dreihundertzwölf", "de"), Some((312, 1)));
+        assert_eq!(cardinal("dreiundzwanzig", "de"), Some((23, 1)));
+        assert_eq!(cardinal("achtung", "de"), None);
+        assert_eq!(cardinal("trescientos doce", "es"), Some((312, 2)));
+        assert_eq!(cardinal("treinta y dos", "es"), Some((32, 3)));
+        assert_eq!(cardinal("trecentododici", "it"), Some((312, 1)));
+        assert_eq!(cardinal("duemilaventiquattro", "it"), Some((2024, 1)));
+        assert_eq!(cardinal("novembre", "it"), None);
+    }
+
+    #[test]
+    fn test_ordinals() {
+        assert_eq!(ordinal("premier", "fr"), Some("1er".to_string()));
+        assert_eq!(ordinal("première", "fr"), Some("1re".to_string()));
+        assert_eq!(ordinal("vingt et unième", "fr"), Some("21e".to_string()));
+        assert_eq!(ordinal("cinquième", "fr"), Some("5e".to_string()));
+        assert_eq!(ordinal("twenty-first", "en"), Some("21st".to_string()));
+        assert_eq!(ordinal("twelfth", "en"), Some("12th".to_string()));
+        assert_eq!(ordinal("ninetieth", "en"), Some("90th".to_string()));
+        assert_eq!(ordinal("fünfzehnten", "de"), Some("15.".to_string()));
+        assert_eq!(ordinal("dritte", "de"), Some("3.".to_string()));
+        assert_eq!(ordinal("primera", "es"), Some("1.ª".to_string()));
+        assert_eq!(ordinal("undicesimo", "it"), Some("11º".to_string()));
+        assert_eq!(ordinal("second", "fr"), None);
+    }
+
+    #[test]
+    fn test_grouping() {
+        let french = for_language("fr").unwrap();
+        assert_eq!(format_cardinal(2024, french), "2024");
+        assert_eq!(format_cardinal(1_250_000, french), "1\u{202F}250\u{202F}000");
+        assert_eq!(format_cardinal(12_500, for_language("en").unwrap()), "12,500");
+    }
+}
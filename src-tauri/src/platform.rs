@@ -1,3 +1,4 @@
+use serde::Serialize;
 use std::process::{Command, Stdio};
 
 /// Ouvre une application par son nom
@@ -67,10 +68,10 @@ pub fn open_app(app_name: &str) {
 
 /// Tape du texte en utilisant le presse-papier (pour le streaming incrémental)
 pub fn type_text_incremental(text: &str) {
-    use std::io::Write;
-
     #[cfg(target_os = "macos")]
     {
+        use std::io::Write;
+
         match Command::new("pbcopy")
             .stdin(Stdio::piped())
             .spawn()
@@ -106,6 +107,8 @@ $.CGEventPost($.kCGHIDEventTap, up);
 
     #[cfg(target_os = "windows")]
     {
+        use std::io::Write;
+
         match Command::new("cmd")
             .args(["/C", "clip"])
             .stdin(Stdio::piped())
@@ -137,45 +140,21 @@ $.CGEventPost($.kCGHIDEventTap, up);
 
     #[cfg(target_os = "linux")]
     {
-        let wayland = std::env::var("WAYLAND_DISPLAY").is_ok();
-        if wayland {
-            match Command::new("wl-copy")
-                .stdin(Stdio::piped())
-                .spawn()
-            {
-                Ok(mut child) => {
-                    if let Some(mut stdin) = child.stdin.take() {
-                        let _ = stdin.write_all(text.as_bytes());
-                    }
-                    let _ = child.wait();
-                }
-                Err(_) => return,
-            }
-            std::thread::sleep(std::time::Duration::from_millis(30));
-            let _ = Command::new("wtype").args(["-M", "ctrl", "v", "-m", "ctrl"]).output();
-        } else {
-            match Command::new("xclip")
-                .args(["-selection", "clipboard"])
-                .stdin(Stdio::piped())
-                .spawn()
-            {
-                Ok(mut child) => {
-                    if let Some(mut stdin) = child.stdin.take() {
-                        let _ = stdin.write_all(text.as_bytes());
-                    }
-                    let _ = child.wait();
-                }
-                Err(_) => return,
-            }
-            std::thread::sleep(std::time::Duration::from_millis(30));
-            let _ = Command::new("xdotool").args(["key", "--clearmodifiers", "ctrl+v"]).output();
+        // X11 : frappe directe, le presse-papiers n'est pas touché
+        match crate::linux_input::type_text(text) {
+            Ok(()) => return,
+            Err(e) => log::debug!("[TYPE] Direct typing unavailable: {}", e),
+        }
+        if let Err(e) = crate::linux_input::paste(text) {
+            log::warn!("[TYPE] {}", e);
         }
     }
 }
 
 /// Colle le texte à la position du curseur
 pub fn paste_text(text: &str) {
-    log::debug!("[PASTE] paste_text called with: '{}'", &text[..text.len().min(50)]);
+    let preview: String = text.chars().take(50).collect();
+    log::debug!("[PASTE] paste_text called with: '{}'", crate::privacy::redact(&preview));
 
     #[cfg(target_os = "macos")]
     {
@@ -321,93 +300,54 @@ pub fn paste_text(text: &str) {
 
     #[cfg(target_os = "linux")]
     {
-        use std::io::Write;
-
-        let wayland = std::env::var("WAYLAND_DISPLAY").is_ok();
-
-        if wayland {
-            match Command::new("wl-copy")
-                .stdin(Stdio::piped())
-                .spawn()
-            {
-                Ok(mut child) => {
-                    if let Some(mut stdin) = child.stdin.take() {
-                        if let Err(e) = stdin.write_all(text.as_bytes()) {
-                            log::error!("[PASTE] Failed to write to wl-copy: {}", e);
-                            return;
-                        }
-                    }
-                    let _ = child.wait();
-                }
-                Err(e) => {
-                    log::error!("[PASTE] Failed to spawn wl-copy: {}. Install with 'sudo apt install wl-clipboard'", e);
-                    return;
-                }
-            }
+        match crate::linux_input::paste(text) {
+            Ok(path) => log::info!("[PASTE] Text pasted via {}", path),
+            Err(e) => log::warn!("[PASTE] {}. Use Ctrl+V manually, see the text insertion diagnostic in Settings.", e),
+        }
+    }
 
-            std::thread::sleep(std::time::Duration::from_millis(50));
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        log::warn!("[PASTE] Paste not implemented for this platform - text copied to clipboard");
+    }
+}
 
-            match Command::new("wtype")
-                .args(["-M", "ctrl", "v", "-m", "ctrl"])
-                .output()
-            {
-                Ok(output) if output.status.success() => {
-                    log::info!("[PASTE] Text pasted via wtype");
-                }
-                _ => {
-                    match Command::new("ydotool")
-                        .args(["key", "29:1", "47:1", "47:0", "29:0"])
-                        .output()
-                    {
-                        Ok(output) if output.status.success() => {
-                            log::info!("[PASTE] Text pasted via ydotool");
-                        }
-                        _ => {
-                            log::warn!("[PASTE] Text copied to clipboard. Use Ctrl+V manually.");
-                        }
-                    }
-                }
-            }
-        } else {
-            match Command::new("xclip")
-                .args(["-selection", "clipboard"])
-                .stdin(Stdio::piped())
-                .spawn()
-            {
-                Ok(mut child) => {
-                    if let Some(mut stdin) = child.stdin.take() {
-                        if let Err(e) = stdin.write_all(text.as_bytes()) {
-                            log::error!("[PASTE] Failed to write to xclip: {}", e);
-                            return;
-                        }
-                    }
-                    let _ = child.wait();
-                }
-                Err(e) => {
-                    log::error!("[PASTE] Failed to spawn xclip: {}. Install with 'sudo apt install xclip'", e);
-                    return;
-                }
-            }
+/// Un chemin d'insertion testé par le diagnostic
+#[derive(Debug, Clone, Serialize)]
+pub struct InsertionCheck {
+    pub name: String,
+    pub ok: bool,
+    pub detail: String,
+}
 
-            std::thread::sleep(std::time::Duration::from_millis(50));
+/// Chemins d'insertion de texte utilisables sur la session courante
+#[derive(Debug, Clone, Serialize)]
+pub struct TextInsertionReport {
+    /// "x11", "wayland", "macos" ou "windows"
+    pub session: String,
+    /// Frappe directe sans presse-papiers (streaming), si disponible
+    pub typing: Option<String>,
+    /// Chemin qui envoie le raccourci de collage
+    pub paste: Option<String>,
+    pub checks: Vec<InsertionCheck>,
+}
 
-            match Command::new("xdotool")
-                .args(["key", "--clearmodifiers", "ctrl+v"])
-                .output()
-            {
-                Ok(output) if output.status.success() => {
-                    log::info!("[PASTE] Text pasted via xdotool");
-                }
-                Ok(_) | Err(_) => {
-                    log::warn!("[PASTE] Text copied to clipboard. Use Ctrl+V manually.");
-                }
-            }
-        }
+/// Diagnostique l'insertion de texte sans rien taper dans l'application active
+pub fn diagnose_text_insertion() -> TextInsertionReport {
+    #[cfg(target_os = "linux")]
+    {
+        crate::linux_input::diagnose()
     }
 
-    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    #[cfg(not(target_os = "linux"))]
     {
-        log::warn!("[PASTE] Paste not implemented for this platform - text copied to clipboard");
+        let paste = if cfg!(target_os = "macos") { "System Events" } else { "SendInput" };
+        TextInsertionReport {
+            session: std::env::consts::OS.to_string(),
+            typing: None,
+            paste: Some(paste.to_string()),
+            checks: Vec::new(),
+        }
     }
 }
 
@@ -674,6 +614,444 @@ pub fn send_keyboard_shortcut(key: &str) {
     }
 }
 
+/// Modificateur d'une combinaison de touches
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyModifier {
+    Ctrl,
+    Alt,
+    Shift,
+    /// Cmd sur macOS, touche Windows / Super ailleurs
+    Super,
+}
+
+/// Touche non imprimable d'une combinaison
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NamedKey {
+    Enter,
+    Tab,
+    Escape,
+    Backspace,
+    Delete,
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Space,
+    F(u8),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Char(char),
+    Named(NamedKey),
+}
+
+/// Combinaison de touches ("ctrl+shift+k", "primary+z", "enter"...)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyCombo {
+    pub modifiers: Vec<KeyModifier>,
+    pub key: Key,
+}
+
+impl KeyCombo {
+    /// Parse une combinaison. `primary` (ou `cmdorctrl`) vaut Cmd sur macOS et Ctrl ailleurs.
+    pub fn parse(combo: &str) -> Result<Self, String> {
+        let parts: Vec<String> = combo
+            .split('+')
+            .map(|p| p.trim().to_lowercase())
+            .collect();
+        let (key_part, modifier_parts) = parts
+            .split_last()
+            .filter(|(key, _)| !key.is_empty())
+            .ok_or_else(|| format!("Empty key combination '{}'", combo))?;
+
+        let mut modifiers = Vec::new();
+        for part in modifier_parts {
+            let modifier = match part.as_str() {
+                "ctrl" | "control" => KeyModifier::Ctrl,
+                "alt" | "option" => KeyModifier::Alt,
+                "shift" => KeyModifier::Shift,
+                "super" | "cmd" | "command" | "meta" | "win" => KeyModifier::Super,
+                "primary" | "cmdorctrl" if cfg!(target_os = "macos") => KeyModifier::Super,
+                "primary" | "cmdorctrl" => KeyModifier::Ctrl,
+                other => return Err(format!("Unknown modifier '{}' in '{}'", other, combo)),
+            };
+            if !modifiers.contains(&modifier) {
+                modifiers.push(modifier);
+            }
+        }
+
+        let key = match key_part.as_str() {
+            "enter" | "return" => Key::Named(NamedKey::Enter),
+            "tab" => Key::Named(NamedKey::Tab),
+            "escape" | "esc" => Key::Named(NamedKey::Escape),
+            "backspace" => Key::Named(NamedKey::Backspace),
+            "delete" | "del" => Key::Named(NamedKey::Delete),
+            "up" => Key::Named(NamedKey::Up),
+            "down" => Key::Named(NamedKey::Down),
+            "left" => Key::Named(NamedKey::Left),
+            "right" => Key::Named(NamedKey::Right),
+            "home" => Key::Named(NamedKey::Home),
+            "end" => Key::Named(NamedKey::End),
+            "pageup" => Key::Named(NamedKey::PageUp),
+            "pagedown" => Key::Named(NamedKey::PageDown),
+            "space" => Key::Named(NamedKey::Space),
+            other => {
+                let mut chars = other.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if c.is_ascii_alphanumeric() => Key::Char(c),
+                    _ => match other.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                        Some(n) if (1..=12).contains(&n) => Key::Named(NamedKey::F(n)),
+                        _ => return Err(format!("Unknown key '{}' in '{}'", other, combo)),
+                    },
+                }
+            }
+        };
+
+        Ok(Self { modifiers, key })
+    }
+
+    /// Syntaxe xdotool ("ctrl+shift+Return")
+    #[cfg(target_os = "linux")]
+    fn xdotool_name(&self) -> String {
+        let mut parts: Vec<String> = self
+            .modifiers
+            .iter()
+            .map(|m| match m {
+                KeyModifier::Ctrl => "ctrl",
+                KeyModifier::Alt => "alt",
+                KeyModifier::Shift => "shift",
+                KeyModifier::Super => "super",
+            }.to_string())
+            .collect();
+        parts.push(self.keysym());
+        parts.join("+")
+    }
+
+    /// Nom de keysym X11, compris par xdotool et wtype
+    #[cfg(target_os = "linux")]
+    fn keysym(&self) -> String {
+        match self.key {
+            Key::Char(c) => c.to_string(),
+            Key::Named(named) => match named {
+                NamedKey::Enter => "Return".to_string(),
+                NamedKey::Tab => "Tab".to_string(),
+                NamedKey::Escape => "Escape".to_string(),
+                NamedKey::Backspace => "BackSpace".to_string(),
+                NamedKey::Delete => "Delete".to_string(),
+                NamedKey::Up => "Up".to_string(),
+                NamedKey::Down => "Down".to_string(),
+                NamedKey::Left => "Left".to_string(),
+                NamedKey::Right => "Right".to_string(),
+                NamedKey::Home => "Home".to_string(),
+                NamedKey::End => "End".to_string(),
+                NamedKey::PageUp => "Prior".to_string(),
+                NamedKey::PageDown => "Next".to_string(),
+                NamedKey::Space => "space".to_string(),
+                NamedKey::F(n) => format!("F{}", n),
+            },
+        }
+    }
+}
+
+/// Envoie une combinaison de touches à l'application au premier plan
+pub fn send_key_combo(combo: &KeyCombo) {
+    log::info!("[SHORTCUT] Sending key combo: {:?}", combo);
+
+    #[cfg(target_os = "macos")]
+    {
+        let modifiers: Vec<&str> = combo
+            .modifiers
+            .iter()
+            .map(|m| match m {
+                KeyModifier::Ctrl => "control down",
+                KeyModifier::Alt => "option down",
+                KeyModifier::Shift => "shift down",
+                KeyModifier::Super => "command down",
+            })
+            .collect();
+        let stroke = match combo.key {
+            Key::Char(c) => format!("keystroke \"{}\"", c),
+            Key::Named(named) => {
+                let code = match named {
+                    NamedKey::Enter => 36,
+                    NamedKey::Tab => 48,
+                    NamedKey::Escape => 53,
+                    NamedKey::Backspace => 51,
+                    NamedKey::Delete => 117,
+                    NamedKey::Up => 126,
+                    NamedKey::Down => 125,
+                    NamedKey::Left => 123,
+                    NamedKey::Right => 124,
+                    NamedKey::Home => 115,
+                    NamedKey::End => 119,
+                    NamedKey::PageUp => 116,
+                    NamedKey::PageDown => 121,
+                    NamedKey::Space => 49,
+                    NamedKey::F(n) => [122, 120, 99, 118, 96, 97, 98, 100, 101, 109, 103, 111][(n as usize - 1).min(11)],
+                };
+                format!("key code {}", code)
+            }
+        };
+        let script = if modifiers.is_empty() {
+            format!("tell application \"System Events\" to {}", stroke)
+        } else {
+            format!("tell application \"System Events\" to {} using {{{}}}", stroke, modifiers.join(", "))
+        };
+        let _ = Command::new("osascript").args(["-e", &script]).output();
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use windows::Win32::UI::Input::KeyboardAndMouse::{
+            SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYBD_EVENT_FLAGS,
+            KEYEVENTF_KEYUP, VIRTUAL_KEY,
+        };
+
+        let key_vk = match combo.key {
+            Key::Char(c) => c.to_ascii_uppercase() as u16,
+            Key::Named(named) => match named {
+                NamedKey::Enter => 0x0D,
+                NamedKey::Tab => 0x09,
+                NamedKey::Escape => 0x1B,
+                NamedKey::Backspace => 0x08,
+                NamedKey::Delete => 0x2E,
+                NamedKey::Up => 0x26,
+                NamedKey::Down => 0x28,
+                NamedKey::Left => 0x25,
+                NamedKey::Right => 0x27,
+                NamedKey::Home => 0x24,
+                NamedKey::End => 0x23,
+                NamedKey::PageUp => 0x21,
+                NamedKey::PageDown => 0x22,
+                NamedKey::Space => 0x20,
+                NamedKey::F(n) => 0x70 + (n as u16 - 1),
+            },
+        };
+        let mut sequence: Vec<u16> = combo
+            .modifiers
+            .iter()
+            .map(|m| match m {
+                KeyModifier::Ctrl => 0x11,
+                KeyModifier::Alt => 0x12,
+                KeyModifier::Shift => 0x10,
+                KeyModifier::Super => 0x5B,
+            })
+            .collect();
+        sequence.push(key_vk);
+
+        let key_input = |vk: u16, flags: KEYBD_EVENT_FLAGS| INPUT {
+            r#type: INPUT_KEYBOARD,
+            Anonymous: INPUT_0 { ki: KEYBDINPUT { wVk: VIRTUAL_KEY(vk), wScan: 0, dwFlags: flags, time: 0, dwExtraInfo: 0 } },
+        };
+        // Appui dans l'ordre, relâchement dans l'ordre inverse
+        let mut inputs: Vec<INPUT> = sequence.iter().map(|&vk| key_input(vk, KEYBD_EVENT_FLAGS(0))).collect();
+        inputs.extend(sequence.iter().rev().map(|&vk| key_input(vk, KEYEVENTF_KEYUP)));
+        unsafe { SendInput(&inputs, std::mem::size_of::<INPUT>() as i32) };
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let wayland = std::env::var("WAYLAND_DISPLAY").is_ok();
+        if wayland {
+            let mut args: Vec<String> = Vec::new();
+            let wtype_modifiers: Vec<&str> = combo
+                .modifiers
+                .iter()
+                .map(|m| match m {
+                    KeyModifier::Ctrl => "ctrl",
+                    KeyModifier::Alt => "alt",
+                    KeyModifier::Shift => "shift",
+                    KeyModifier::Super => "logo",
+                })
+                .collect();
+            for m in &wtype_modifiers {
+                args.extend(["-M".to_string(), m.to_string()]);
+            }
+            args.extend(["-k".to_string(), combo.keysym()]);
+            for m in wtype_modifiers.iter().rev() {
+                args.extend(["-m".to_string(), m.to_string()]);
+            }
+            let _ = Command::new("wtype").args(&args).output();
+        } else {
+            let _ = Command::new("xdotool")
+                .args(["key", "--clearmodifiers", &combo.xdotool_name()])
+                .output();
+        }
+    }
+}
+
+/// Lance un programme sans attendre sa fin (commandes vocales utilisateur).
+/// Pas de shell : chaque élément de `argv` est passé tel quel, rien n'est interprété.
+pub fn run_command(argv: &[String]) {
+    let Some((program, args)) = argv.split_first() else {
+        return;
+    };
+    log::info!("[SHELL] Running: {}", crate::privacy::redact(&argv.join(" ")));
+
+    let result = Command::new(program).args(args).stdout(Stdio::null()).stderr(Stdio::null()).spawn();
+
+    match result {
+        // Récupère le processus en arrière-plan pour ne pas laisser de zombie
+        Ok(mut child) => {
+            std::thread::spawn(move || {
+                let _ = child.wait();
+            });
+        }
+        Err(e) => log::error!("[SHELL] Failed to run command: {}", e),
+    }
+}
+
+/// Application au premier plan, pour choisir le profil de dictée
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FocusedApp {
+    /// Nom du processus ("code", "thunderbird")
+    pub process: String,
+    /// Classe de fenêtre X11 (WM_CLASS), app_id Wayland ou bundle id macOS
+    pub class: String,
+}
+
+/// Sortie d'une commande terminée avec succès, `None` si vide ou en échec
+#[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
+fn command_stdout(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).stderr(Stdio::null()).output().ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !stdout.is_empty()).then_some(stdout)
+}
+
+/// Détecte l'application au premier plan.
+/// Wayland n'a pas de protocole commun : Hyprland, Sway et KWin (kdotool) sont
+/// interrogés, puis X11 (xdotool, xprop), qui couvre aussi les fenêtres XWayland.
+pub fn focused_app() -> Option<FocusedApp> {
+    #[cfg(target_os = "macos")]
+    {
+        let script = r#"tell application "System Events"
+            set frontApp to first application process whose frontmost is true
+            return (name of frontApp) & linefeed & (bundle identifier of frontApp)
+        end tell"#;
+        let output = command_stdout("osascript", &["-e", script])?;
+        let mut lines = output.lines();
+        Some(FocusedApp {
+            process: lines.next().unwrap_or_default().to_string(),
+            class: lines.next().unwrap_or_default().to_string(),
+        })
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+        let mut pid = 0u32;
+        unsafe {
+            let hwnd = GetForegroundWindow();
+            GetWindowThreadProcessId(hwnd, Some(&mut pid as *mut u32));
+        }
+        if pid == 0 {
+            return None;
+        }
+        // "Code.exe","1234","Console","1","120 000 K"
+        let output = command_stdout("tasklist", &["/FI", &format!("PID eq {}", pid), "/FO", "CSV", "/NH"])?;
+        let image = output.split(',').next()?.trim_matches('"');
+        Some(FocusedApp {
+            process: image.trim_end_matches(".exe").to_string(),
+            class: String::new(),
+        })
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let wayland = std::env::var("WAYLAND_DISPLAY").is_ok();
+        let detected = if wayland {
+            focused_app_hyprland()
+                .or_else(focused_app_sway)
+                .or_else(focused_app_kwin)
+                .or_else(focused_app_x11)
+        } else {
+            focused_app_x11()
+        };
+        if detected.is_none() {
+            log::debug!("[FOCUS] No focused window detected (wayland={})", wayland);
+        }
+        detected
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        None
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn process_name(pid: u64) -> String {
+    std::fs::read_to_string(format!("/proc/{}/comm", pid))
+        .map(|comm| comm.trim().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(target_os = "linux")]
+fn focused_app_hyprland() -> Option<FocusedApp> {
+    let output = command_stdout("hyprctl", &["activewindow", "-j"])?;
+    let window: serde_json::Value = serde_json::from_str(&output).ok()?;
+    let class = window["class"].as_str()?.to_string();
+    let process = window["pid"].as_u64().map(process_name).unwrap_or_default();
+    Some(FocusedApp { process, class })
+}
+
+#[cfg(target_os = "linux")]
+fn focused_app_sway() -> Option<FocusedApp> {
+    fn find_focused(node: &serde_json::Value) -> Option<&serde_json::Value> {
+        if node["focused"].as_bool() == Some(true) && node["pid"].is_u64() {
+            return Some(node);
+        }
+        ["nodes", "floating_nodes"]
+            .iter()
+            .filter_map(|key| node[*key].as_array())
+            .flatten()
+            .find_map(find_focused)
+    }
+
+    let output = command_stdout("swaymsg", &["-t", "get_tree", "-r"])?;
+    let tree: serde_json::Value = serde_json::from_str(&output).ok()?;
+    let window = find_focused(&tree)?;
+    // app_id pour les clients Wayland, classe X11 pour XWayland
+    let class = window["app_id"]
+        .as_str()
+        .or_else(|| window["window_properties"]["class"].as_str())
+        .unwrap_or_default()
+        .to_string();
+    let process = window["pid"].as_u64().map(process_name).unwrap_or_default();
+    Some(FocusedApp { process, class })
+}
+
+#[cfg(target_os = "linux")]
+fn focused_app_kwin() -> Option<FocusedApp> {
+    let window = command_stdout("kdotool", &["getactivewindow"])?;
+    let class = command_stdout("kdotool", &["getwindowclassname", &window]).unwrap_or_default();
+    let process = command_stdout("kdotool", &["getwindowpid", &window])
+        .and_then(|pid| pid.parse().ok())
+        .map(process_name)
+        .unwrap_or_default();
+    Some(FocusedApp { process, class })
+}
+
+#[cfg(target_os = "linux")]
+fn focused_app_x11() -> Option<FocusedApp> {
+    let window = command_stdout("xdotool", &["getactivewindow"])?;
+    let process = command_stdout("xdotool", &["getwindowpid", &window])
+        .and_then(|pid| pid.parse().ok())
+        .map(process_name)
+        .unwrap_or_default();
+    // WM_CLASS(STRING) = "code", "Code" : on garde la classe (second nom)
+    let class = command_stdout("xprop", &["-id", &window, "WM_CLASS"])
+        .and_then(|line| line.rsplit('"').nth(1).map(str::to_string))
+        .unwrap_or_default();
+    Some(FocusedApp { process, class })
+}
+
 /// Create a new note in Apple Notes (macOS only)
 #[cfg(target_os = "macos")]
 pub fn apple_notes_create(title: &str, body: &str) -> Result<(), String> {
@@ -718,8 +1096,12 @@ pub fn obsidian_create(vault_path: &str, title: &str, body: &str) -> Result<(),
         .collect();
     let file_path = vault.join(format!("{}.md", safe_title.trim()));
 
-    let content = format!("# {}\n\n{}\n\n---\n*Cree par Phonon le {}*\n",
-        title, body, chrono::Local::now().format("%Y-%m-%d %H:%M"));
+    // Markdown dicté en mode Notes : conservé tel quel, sans dupliquer son titre
+    let body = body.replace("\r\n", "\n");
+    let body = body.trim_end();
+    let heading = if body.starts_with("# ") { String::new() } else { format!("# {}\n\n", title) };
+    let content = format!("{}{}\n\n---\n*Cree par Phonon le {}*\n",
+        heading, body, chrono::Local::now().format("%Y-%m-%d %H:%M"));
 
     std::fs::write(&file_path, content)
         .map_err(|e| format!("Failed to write note: {}", e))?;
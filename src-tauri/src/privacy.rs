@@ -0,0 +1,124 @@
+//! Mode confidentialité : aucun texte dicté ne doit atteindre le disque.
+//!
+//! Quand il est actif, l'historique, l'historique de traduction et le journal
+//! de l'application ne sont plus alimentés, les stats ne retiennent plus les
+//! langues, et tout texte passé aux macros `log::` doit transiter par `redact`.
+
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Miroir de `AppSettings::privacy_mode`, lisible sans verrou depuis
+/// n'importe quel thread (audio, raccourcis, storage)
+static PRIVACY_MODE: AtomicBool = AtomicBool::new(false);
+
+pub fn set_enabled(enabled: bool) {
+    let previous = PRIVACY_MODE.swap(enabled, Ordering::SeqCst);
+    if previous != enabled {
+        log::info!("[PRIVACY] Privacy mode {}", if enabled { "enabled" } else { "disabled" });
+    }
+}
+
+pub fn is_enabled() -> bool {
+    PRIVACY_MODE.load(Ordering::SeqCst)
+}
+
+/// Retourne le texte tel quel, ou un marqueur sans contenu en mode confidentialité.
+/// À utiliser pour tout texte utilisateur formaté dans un message de log.
+pub fn redact(text: &str) -> Cow<'_, str> {
+    if is_enabled() {
+        Cow::Owned(mask(text))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+fn mask(text: &str) -> String {
+    format!("<redacted {} chars>", text.chars().count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{TranscriptionResult, TranslationEntry};
+    use std::sync::{Mutex, MutexGuard};
+
+    const SECRET: &str = "mot de passe ultra secret";
+
+    /// Sérialise les tests qui touchent au drapeau global
+    static PRIVACY_TEST_LOCK: Mutex<()> = Mutex::new(());
+
+    /// Active le mode confidentialité et restaure l'état précédent au drop,
+    /// même si le test échoue
+    struct PrivacyGuard {
+        previous: bool,
+        _serial: MutexGuard<'static, ()>,
+    }
+
+    impl PrivacyGuard {
+        fn enable() -> Self {
+            let serial = PRIVACY_TEST_LOCK.lock().unwrap_or_else(|p| p.into_inner());
+            let previous = is_enabled();
+            set_enabled(true);
+            Self { previous, _serial: serial }
+        }
+    }
+
+    impl Drop for PrivacyGuard {
+        fn drop(&mut self) {
+            set_enabled(self.previous);
+        }
+    }
+
+    fn snapshot(name: &str) -> Option<Vec<u8>> {
+        std::fs::read(crate::storage::get_app_data_dir().join(name)).ok()
+    }
+
+    #[test]
+    fn test_mask_hides_content() {
+        let masked = mask(SECRET);
+        assert!(!masked.contains("secret"));
+        assert_eq!(masked, "<redacted 25 chars>");
+    }
+
+    #[test]
+    fn test_privacy_mode_writes_nothing() {
+        let store = crate::storage::temp_store("privacy");
+        let _data_dir = crate::storage::redirect_data_dir(store.parent().unwrap());
+        let _privacy = PrivacyGuard::enable();
+        assert!(!redact(SECRET).contains("secret"));
+
+        crate::storage::history::add_transcription(TranscriptionResult {
+            text: SECRET.to_string(),
+            confidence: 1.0,
+            duration_seconds: 1.0,
+            processing_time_ms: 10,
+            detected_language: Some("fr".to_string()),
+            timestamp: 0,
+            model_used: None,
+            tokens: Vec::new(),
+        })
+        .unwrap();
+        crate::storage::translation_history::add_translation(TranslationEntry {
+            source_text: SECRET.to_string(),
+            translated_text: SECRET.to_string(),
+            source_language: None,
+            target_language: "en".to_string(),
+            char_count: SECRET.len(),
+            translation_time_ms: 10,
+            provider: "groq".to_string(),
+            timestamp: 0,
+        })
+        .unwrap();
+
+        crate::storage::app_log::append_log("error", "translation", SECRET);
+        crate::storage::stats::record_transcription(5, 1.0, Some("fr")).unwrap();
+
+        assert_eq!(snapshot("history.json"), None);
+        assert_eq!(snapshot("translation_history.json"), None);
+        assert_eq!(snapshot("app_log.json"), None);
+        // Les totaux restent comptés, sans la langue dictée
+        let stats = crate::storage::stats::load_stats();
+        assert_eq!(stats.total_transcriptions, 1);
+        assert!(stats.languages_used.is_empty());
+    }
+}
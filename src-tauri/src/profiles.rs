@@ -0,0 +1,168 @@
+//! Profils par application
+//!
+//! Au début de chaque dictée, l'application au premier plan choisit un profil
+//! (`AppSettings::app_profiles`) qui remplace le mode de dictée, le moteur, la
+//! langue, le LLM, les commandes vocales et la méthode de collage.
+
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::platform::{self, FocusedApp};
+use crate::state::AppState;
+use crate::types::{AppProfile, AppSettings, PasteMethod};
+
+/// Profil de la dictée en cours, `None` : réglages globaux
+static ACTIVE_PROFILE: Mutex<Option<AppProfile>> = Mutex::new(None);
+
+/// Choisit le profil de l'application au premier plan.
+/// À appeler avant d'afficher une fenêtre de Phonon, qui prendrait le focus.
+pub fn activate(app: &AppHandle) {
+    let state: tauri::State<'_, AppState> = app.state();
+    let settings = match state.settings.read() {
+        Ok(settings) => settings.clone(),
+        Err(e) => {
+            log::warn!("[PROFILE] Failed to read settings: {}", e);
+            return;
+        }
+    };
+
+    let focused = if settings.app_profiles_enabled { platform::focused_app() } else { None };
+    set_active_profile(select_profile(&settings, focused.as_ref()));
+}
+
+/// Profil correspondant à l'application au premier plan, `None` sans profil
+/// (ou si les profils sont désactivés) : retour aux réglages globaux
+fn select_profile(settings: &AppSettings, focused: Option<&FocusedApp>) -> Option<AppProfile> {
+    if !settings.app_profiles_enabled {
+        return None;
+    }
+    let focused = focused?;
+    let profile = settings
+        .app_profiles
+        .iter()
+        .find(|p| p.matches(&focused.process, &focused.class));
+    log::info!(
+        "[PROFILE] Focused app '{}' ({}): profile {:?}",
+        focused.process,
+        focused.class,
+        profile.map(|p| p.name.as_str())
+    );
+    profile.cloned()
+}
+
+fn set_active_profile(profile: Option<AppProfile>) {
+    crate::tray::set_active_profile(profile.as_ref().map(|p| p.name.as_str()));
+    if let Ok(mut active) = ACTIVE_PROFILE.lock() {
+        *active = profile;
+    }
+}
+
+/// Charge le moteur et la langue du profil actif (ou revient à ceux des réglages).
+/// Peut recharger un modèle : à appeler hors du thread des raccourcis.
+pub fn prepare_engine(app: &AppHandle) {
+    let state: tauri::State<'_, AppState> = app.state();
+    let Ok(settings) = state.settings.read().map(|s| s.clone()) else {
+        return;
+    };
+    let effective = effective_settings(&settings);
+
+    let loaded = state.engine.read().ok().and_then(|engine| engine.as_ref().map(|e| e.engine_type()));
+    if loaded.is_some_and(|loaded| loaded != effective.engine_type) {
+        log::info!("[PROFILE] Switching engine to {:?}", effective.engine_type);
+        if let Err(e) = state.switch_engine_type(effective.engine_type) {
+            log::warn!("[PROFILE] Failed to switch engine to {:?}: {}", effective.engine_type, e);
+        }
+    }
+
+    // Seul Whisper change de langue sans recharger de modèle
+    let language = (!effective.auto_detect_language).then(|| effective.transcription_language.clone());
+    let Ok(mut engine) = state.engine.write() else {
+        return;
+    };
+    if let Some(engine) = engine.as_mut() {
+        engine.set_language(language);
+    }
+}
+
+fn active_profile() -> Option<AppProfile> {
+    ACTIVE_PROFILE.lock().ok().and_then(|active| active.clone())
+}
+
+/// Réglages de la dictée en cours, profil actif appliqué
+pub fn effective_settings(settings: &AppSettings) -> AppSettings {
+    let mut effective = settings.clone();
+    if let Some(profile) = active_profile() {
+        profile.apply(&mut effective);
+    }
+    effective
+}
+
+/// Méthode de collage du profil actif, sinon celle des réglages
+pub fn paste_method(settings: &AppSettings) -> PasteMethod {
+    active_profile()
+        .and_then(|profile| profile.paste_method)
+        .unwrap_or(if settings.auto_paste_enabled { PasteMethod::Paste } else { PasteMethod::ClipboardOnly })
+}
+
+/// Prompt LLM du profil actif, qui remplace celui du mode
+pub fn llm_prompt() -> Option<String> {
+    active_profile()
+        .and_then(|profile| profile.llm_prompt)
+        .filter(|prompt| !prompt.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DictationMode;
+
+    #[test]
+    fn test_default_profiles_match_focused_app() {
+        let settings = AppSettings::default();
+        let find = |process: &str, class: &str| {
+            settings
+                .app_profiles
+                .iter()
+                .find(|p| p.matches(process, class))
+                .map(|p| p.name.as_str())
+        };
+        assert_eq!(find("code", "Code"), Some("Code"));
+        assert_eq!(find("gnome-terminal-server", "Gnome-terminal"), Some("Terminal"));
+        assert_eq!(find("thunderbird", "thunderbird"), Some("Email"));
+        assert_eq!(find("obsidian", "obsidian"), Some("Notes"));
+        assert_eq!(find("firefox", "firefox"), None);
+    }
+
+    #[test]
+    fn test_profile_overrides_only_set_fields() {
+        let mut settings = AppSettings::default();
+        let terminal = settings.app_profiles[0].clone();
+        terminal.apply(&mut settings);
+        assert_eq!(settings.dictation_mode, DictationMode::Code);
+        assert_eq!(terminal.paste_method, Some(PasteMethod::TerminalPaste));
+        assert_eq!(settings.transcription_language, AppSettings::default().transcription_language);
+        assert_eq!(settings.voice_commands_enabled, AppSettings::default().voice_commands_enabled);
+    }
+
+    #[test]
+    fn test_switching_to_app_without_profile_restores_settings() {
+        let settings = AppSettings {
+            app_profiles_enabled: true,
+            auto_paste_enabled: true,
+            ..AppSettings::default()
+        };
+        let app = |process: &str| FocusedApp { process: process.to_string(), class: process.to_string() };
+
+        set_active_profile(select_profile(&settings, Some(&app("code"))));
+        assert_eq!(effective_settings(&settings).dictation_mode, DictationMode::Code);
+
+        set_active_profile(select_profile(&settings, Some(&app("firefox"))));
+        assert!(active_profile().is_none());
+        assert_eq!(effective_settings(&settings).dictation_mode, settings.dictation_mode);
+        assert_eq!(paste_method(&settings), PasteMethod::Paste);
+
+        set_active_profile(select_profile(&settings, Some(&app("code"))));
+        set_active_profile(select_profile(&settings, None));
+        assert!(active_profile().is_none());
+    }
+}
@@ -1,30 +1,33 @@
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::{mpsc, Mutex};
 use tauri::{Emitter, Manager};
 use tauri_plugin_clipboard_manager::ClipboardExt;
-use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut, ShortcutState};
+use tauri_plugin_global_shortcut::{Shortcut, ShortcutState};
 
-use crate::audio::AudioCapture;
-use crate::hotkeys::parse_hotkey;
-use crate::platform::{copy_selected_text, paste_text, type_text_incremental};
+use crate::audio::{AudioCapture, InputSelection, RecordedAudio, SilenceDetector};
+use crate::hotkeys::{self, HotkeyAction};
+use crate::platform::{copy_selected_text, paste_text, send_key_combo, type_text_incremental, KeyCombo};
 use crate::state::AppState;
 use crate::storage;
 use crate::tray::{set_tray_recording, set_tray_state, TrayState};
+use crate::types::PasteMethod;
+use crate::voice_commands::Token;
 
 /// Taux d'échantillonnage requis par le modèle
 const TARGET_SAMPLE_RATE: u32 = 16000;
 
-// Raccourcis globaux
-static PTT_SHORTCUT: Mutex<Option<Shortcut>> = Mutex::new(None);
-static TRANSLATE_SHORTCUT: Mutex<Option<Shortcut>> = Mutex::new(None);
-static VOICE_ACTION_SHORTCUT: Mutex<Option<Shortcut>> = Mutex::new(None);
-
 // État global pour le push-to-talk
 static IS_PTT_ACTIVE: AtomicBool = AtomicBool::new(false);
 static IS_TRANSLATE_ACTIVE: AtomicBool = AtomicBool::new(false);
 static IS_VOICE_ACTION_ACTIVE: AtomicBool = AtomicBool::new(false);
 static SELECTED_TEXT_FOR_ACTION: Mutex<String> = Mutex::new(String::new());
 
+// Dictée mains libres (raccourci de bascule)
+static IS_HANDS_FREE: AtomicBool = AtomicBool::new(false);
+static HANDS_FREE_SESSION: AtomicU64 = AtomicU64::new(0);
+/// Silence, en secondes, qui termine la dictée mains libres ; 0 : pas d'arrêt automatique
+static HANDS_FREE_SILENCE_SECS: AtomicU32 = AtomicU32::new(0);
+
 // Channel pour envoyer les données audio du thread d'enregistrement
 static PTT_AUDIO_SENDER: Mutex<Option<mpsc::Sender<PttCommand>>> = Mutex::new(None);
 static PTT_AUDIO_RECEIVER: Mutex<Option<mpsc::Receiver<PttResult>>> = Mutex::new(None);
@@ -36,17 +39,18 @@ static STREAMING_TEXT: Mutex<String> = Mutex::new(String::new());
 enum PttCommand {
     Start,
     Stop,
-    GetSnapshot,
+    /// Audio capturé depuis l'échantillon `offset`
+    GetSnapshot { offset: usize },
 }
 
 #[derive(Debug)]
 enum PttResult {
-    AudioComplete { audio: Vec<f32>, sample_rate: u32 },
+    AudioComplete(RecordedAudio),
     AudioSnapshot { audio: Vec<f32>, sample_rate: u32 },
 }
 
 /// Initialise le thread audio pour le push-to-talk
-pub fn init_ptt_audio_thread() {
+pub fn init_ptt_audio_thread(app: tauri::AppHandle) {
     let (cmd_tx, cmd_rx) = mpsc::channel::<PttCommand>();
     let (result_tx, result_rx) = mpsc::channel::<PttResult>();
 
@@ -60,14 +64,21 @@ pub fn init_ptt_audio_thread() {
     std::thread::spawn(move || {
         log::info!("PTT audio thread started");
         let mut capture: Option<AudioCapture> = None;
+        // Détecteur de silence et position déjà analysée (arrêt automatique mains libres)
+        let mut silence: Option<(SilenceDetector, usize)> = None;
 
         loop {
-            match cmd_rx.recv() {
+            match cmd_rx.recv_timeout(std::time::Duration::from_millis(100)) {
                 Ok(PttCommand::Start) => {
                     log::info!("PTT: Starting audio capture");
-                    match AudioCapture::new(None) {
+                    silence = None;
+                    let state: tauri::State<'_, AppState> = app.state();
+                    let selection = state.settings.read()
+                        .map(|s| InputSelection::from_settings(&s))
+                        .unwrap_or_default();
+                    match AudioCapture::new(&selection) {
                         Ok(mut cap) => {
-                            if let Err(e) = cap.start(None) {
+                            if let Err(e) = cap.start(&selection) {
                                 log::error!("Failed to start audio capture: {}", e);
                                 continue;
                             }
@@ -78,18 +89,19 @@ pub fn init_ptt_audio_thread() {
                         }
                     }
                 }
-                Ok(PttCommand::GetSnapshot) => {
+                Ok(PttCommand::GetSnapshot { offset }) => {
                     if let Some(ref cap) = capture {
-                        let (audio, sample_rate) = cap.get_audio_snapshot();
+                        let (audio, sample_rate) = cap.get_audio_since(offset);
                         let _ = result_tx.send(PttResult::AudioSnapshot { audio, sample_rate });
                     }
                 }
                 Ok(PttCommand::Stop) => {
                     log::info!("PTT: Stopping audio capture");
+                    silence = None;
                     if let Some(mut cap) = capture.take() {
                         match cap.stop() {
-                            Ok((audio, sample_rate)) => {
-                                let _ = result_tx.send(PttResult::AudioComplete { audio, sample_rate });
+                            Ok(recorded) => {
+                                let _ = result_tx.send(PttResult::AudioComplete(recorded));
                             }
                             Err(e) => {
                                 log::error!("Failed to stop audio capture: {}", e);
@@ -97,7 +109,29 @@ pub fn init_ptt_audio_thread() {
                         }
                     }
                 }
-                Err(_) => {
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    if let Some(ref mut cap) = capture {
+                        if let Some(event) = cap.maintain() {
+                            let _ = app.emit("recording-limit-warning", event);
+                        }
+                        if let Some(event) = cap.poll_device() {
+                            let _ = app.emit("audio-device-changed", event);
+                        }
+
+                        let silence_limit = HANDS_FREE_SILENCE_SECS.load(Ordering::SeqCst);
+                        if silence_limit > 0 {
+                            let (detector, offset) =
+                                silence.get_or_insert_with(|| (SilenceDetector::new(cap.sample_rate()), 0));
+                            let (audio, _) = cap.get_audio_since(*offset);
+                            *offset += audio.len();
+                            if detector.push(&audio) >= silence_limit as f32 {
+                                log::info!("[HANDS_FREE] {}s of silence, stopping dictation", silence_limit);
+                                stop_if_active(&app);
+                            }
+                        }
+                    }
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
                     log::info!("PTT audio thread: channel closed, exiting");
                     break;
                 }
@@ -122,14 +156,27 @@ fn start_ptt_recording() {
 fn start_streaming_transcription(app: &tauri::AppHandle) {
     log::info!("[STREAMING] Starting streaming transcription");
 
-    let settings = storage::config::load_settings();
+    let settings = crate::profiles::effective_settings(&storage::config::load_settings());
     if !settings.streaming_enabled {
         log::info!("[STREAMING] Streaming disabled in settings");
         return;
     }
 
+    // Une commande vocale ou un nombre peut être coupé entre deux passes
+    // ("commande" | "efface", "trois cent" | "douze") : le texte n'est alors
+    // tapé qu'à la fin, après analyse de toute la dictée
+    let type_live = !settings.voice_commands_enabled
+        && !settings.text_normalization.enabled_for(settings.dictation_mode);
+    if !type_live {
+        log::info!("[STREAMING] Voice commands or normalization enabled, text is inserted when push-to-talk ends");
+    }
+
     const STREAMING_INTERVAL_MS: u64 = 1000;
     let mut last_text_len = 0;
+    // Seul l'audio nouveau est lu et ré-échantillonné à chaque tour ;
+    // `captured` compte les échantillons bruts déjà lus
+    let mut captured: usize = 0;
+    let mut audio_16k: Vec<f32> = Vec::new();
 
     while IS_PTT_ACTIVE.load(Ordering::SeqCst) {
         std::thread::sleep(std::time::Duration::from_millis(STREAMING_INTERVAL_MS));
@@ -140,7 +187,7 @@ fn start_streaming_transcription(app: &tauri::AppHandle) {
 
         if let Ok(guard) = PTT_AUDIO_SENDER.lock() {
             if let Some(ref sender) = *guard {
-                let _ = sender.send(PttCommand::GetSnapshot);
+                let _ = sender.send(PttCommand::GetSnapshot { offset: captured });
             }
         }
 
@@ -161,11 +208,7 @@ fn start_streaming_transcription(app: &tauri::AppHandle) {
             Some(data) => data,
             None => continue,
         };
-
-        let duration = audio_data.len() as f32 / sample_rate as f32;
-        if duration < 1.0 {
-            continue;
-        }
+        captured += audio_data.len();
 
         // Audio processing: pre-resample
         let audio_data = {
@@ -178,19 +221,24 @@ fn start_streaming_transcription(app: &tauri::AppHandle) {
             result
         };
 
-        let resampled = if sample_rate != TARGET_SAMPLE_RATE {
-            crate::audio::resampling::resample_audio(&audio_data, sample_rate, TARGET_SAMPLE_RATE)
+        if sample_rate != TARGET_SAMPLE_RATE {
+            audio_16k.extend(crate::audio::resampling::resample_audio(&audio_data, sample_rate, TARGET_SAMPLE_RATE));
         } else {
-            audio_data
-        };
+            audio_16k.extend(audio_data);
+        }
+
+        let duration = audio_16k.len() as f32 / TARGET_SAMPLE_RATE as f32;
+        if duration < 1.0 {
+            continue;
+        }
 
         // Audio processing: post-resample
         let (resampled, has_speech) = {
             let state: tauri::State<'_, AppState> = app.state();
             let result = if let Ok(mut proc) = state.audio_processor.write() {
-                proc.process_post_resample(&resampled)
+                proc.process_post_resample(&audio_16k)
             } else {
-                (resampled, true)
+                (audio_16k.clone(), true)
             };
             result
         };
@@ -199,16 +247,9 @@ fn start_streaming_transcription(app: &tauri::AppHandle) {
             continue;
         }
 
+        // Même point d'entrée que la transcription finale : moteurs de secours et santé
         let state: tauri::State<'_, AppState> = app.state();
-        let engine_guard = match state.engine.read() {
-            Ok(guard) => guard,
-            Err(_) => continue,
-        };
-        let engine = match engine_guard.as_ref() {
-            Some(e) => e,
-            None => continue,
-        };
-        let result = match engine.transcribe(&resampled, TARGET_SAMPLE_RATE) {
+        let result = match state.transcribe(&resampled, TARGET_SAMPLE_RATE) {
             Ok(r) => r,
             Err(e) => {
                 log::warn!("[STREAMING] Transcription error: {}", e);
@@ -220,7 +261,7 @@ fn start_streaming_transcription(app: &tauri::AppHandle) {
             continue;
         }
 
-        log::info!("[STREAMING] Transcribed: '{}'", result.text);
+        log::info!("[STREAMING] Transcribed: '{}'", crate::privacy::redact(&result.text));
 
         #[derive(serde::Serialize, Clone)]
         struct StreamingChunk {
@@ -236,7 +277,16 @@ fn start_streaming_transcription(app: &tauri::AppHandle) {
         let _ = app.emit("transcription-chunk", chunk);
 
         let current_text = result.text.trim();
-        if current_text.len() > last_text_len {
+        let language = result.detected_language.as_deref().unwrap_or(&settings.transcription_language);
+
+        // "commande stop" : on arrête sans taper la commande
+        if settings.voice_commands_enabled && crate::voice_commands::contains_stop_command(current_text, language) {
+            log::info!("[STREAMING] Stop command heard, ending push-to-talk");
+            stop_if_active(app);
+            break;
+        }
+
+        if type_live && current_text.len() > last_text_len {
             let new_text = &current_text[last_text_len..];
             if !new_text.trim().is_empty() {
                 type_text_incremental(new_text);
@@ -265,11 +315,11 @@ fn stop_ptt_and_paste(app: &tauri::AppHandle) {
         }
     }
 
-    let (audio_data, sample_rate) = if let Ok(guard) = PTT_AUDIO_RECEIVER.lock() {
+    let recorded = if let Ok(guard) = PTT_AUDIO_RECEIVER.lock() {
         if let Some(ref receiver) = *guard {
             loop {
                 match receiver.recv_timeout(std::time::Duration::from_secs(2)) {
-                    Ok(PttResult::AudioComplete { audio, sample_rate }) => break (audio, sample_rate),
+                    Ok(PttResult::AudioComplete(recorded)) => break recorded,
                     Ok(PttResult::AudioSnapshot { .. }) => continue,
                     Err(e) => {
                         log::error!("Failed to receive audio data: {}", e);
@@ -286,73 +336,66 @@ fn stop_ptt_and_paste(app: &tauri::AppHandle) {
         return;
     };
 
-    if audio_data.is_empty() {
+    if recorded.audio.is_empty() {
         log::warn!("Audio buffer is empty");
         return;
     }
 
-    let duration = audio_data.len() as f32 / sample_rate as f32;
-    log::info!("PTT captured {:.2}s of audio ({} samples at {}Hz)", duration, audio_data.len(), sample_rate);
+    let sample_rate = recorded.sample_rate;
+    let duration = recorded.duration_secs();
+    log::info!("PTT captured {:.2}s of audio ({} samples at {}Hz)", duration, recorded.audio.len(), sample_rate);
 
     if duration < 0.3 {
         log::warn!("Recording too short");
         return;
     }
 
-    // Reset and apply audio processing: pre-resample (noise suppression)
-    let audio_data = {
-        let state: tauri::State<'_, AppState> = app.state();
-        let result = if let Ok(mut processor) = state.audio_processor.write() {
-            processor.reset();
+    let state: tauri::State<'_, AppState> = app.state();
+    if let Ok(mut processor) = state.audio_processor.write() {
+        processor.reset();
+    }
+
+    let result = if duration > crate::commands::transcription::SEGMENTED_TRANSCRIPTION_THRESHOLD_SECS {
+        match crate::commands::transcription::transcribe_segmented(&state, &recorded) {
+            Ok(r) => r,
+            Err(e) => {
+                log::error!("Transcription failed: {}", e);
+                return;
+            }
+        }
+    } else {
+        // Audio processing: pre-resample (noise suppression)
+        let audio_data = recorded.audio.to_vec();
+        let audio_data = if let Ok(mut processor) = state.audio_processor.write() {
             processor.process_pre_resample(&audio_data, sample_rate)
         } else {
             audio_data
         };
-        result
-    };
 
-    let resampled_audio = if sample_rate != TARGET_SAMPLE_RATE {
-        crate::audio::resampling::resample_audio(&audio_data, sample_rate, TARGET_SAMPLE_RATE)
-    } else {
-        audio_data
-    };
+        let resampled_audio = if sample_rate != TARGET_SAMPLE_RATE {
+            crate::audio::resampling::resample_audio(&audio_data, sample_rate, TARGET_SAMPLE_RATE)
+        } else {
+            audio_data
+        };
 
-    // Audio processing: post-resample (limiter + AGC + VAD)
-    let (resampled_audio, has_speech) = {
-        let state: tauri::State<'_, AppState> = app.state();
-        let result = if let Ok(mut processor) = state.audio_processor.write() {
+        // Audio processing: post-resample (limiter + AGC + VAD)
+        let (resampled_audio, has_speech) = if let Ok(mut processor) = state.audio_processor.write() {
             processor.process_post_resample(&resampled_audio)
         } else {
             (resampled_audio, true)
         };
-        result
-    };
-
-    if !has_speech {
-        log::info!("PTT: no speech detected, skipping");
-        return;
-    }
 
-    let state: tauri::State<'_, AppState> = app.state();
-    let engine_guard = match state.engine.read() {
-        Ok(guard) => guard,
-        Err(e) => {
-            log::error!("Failed to lock engine: {}", e);
-            return;
-        }
-    };
-    let engine = match engine_guard.as_ref() {
-        Some(e) => e,
-        None => {
-            log::error!("Whisper engine not initialized");
+        if !has_speech {
+            log::info!("PTT: no speech detected, skipping");
             return;
         }
-    };
-    let result = match engine.transcribe(&resampled_audio, TARGET_SAMPLE_RATE) {
-        Ok(r) => r,
-        Err(e) => {
-            log::error!("Transcription failed: {}", e);
-            return;
+
+        match state.transcribe(&resampled_audio, TARGET_SAMPLE_RATE) {
+            Ok(r) => r,
+            Err(e) => {
+                log::error!("Transcription failed: {}", e);
+                return;
+            }
         }
     };
 
@@ -361,7 +404,7 @@ fn stop_ptt_and_paste(app: &tauri::AppHandle) {
         return;
     }
 
-    log::info!("Transcribed: '{}'", result.text);
+    log::info!("Transcribed: '{}'", crate::privacy::redact(&result.text));
 
     #[derive(serde::Serialize, Clone)]
     struct TranscriptionChunk {
@@ -384,20 +427,99 @@ fn stop_ptt_and_paste(app: &tauri::AppHandle) {
     ));
 
     let final_text = result.text.trim();
-    if had_streaming && final_text.len() > streaming_text.len() {
-        let remaining = &final_text[streaming_text.len()..];
-        if !remaining.trim().is_empty() {
-            type_text_incremental(remaining.trim());
+    // Texte restant à insérer : ce que le streaming n'a pas encore tapé
+    let pending = if had_streaming {
+        final_text.get(streaming_text.len()..).unwrap_or_default()
+    } else {
+        final_text
+    };
+
+    let settings = match state.settings.read() {
+        Ok(settings) => crate::profiles::effective_settings(&settings),
+        Err(e) => {
+            log::error!("Failed to read settings: {}", e);
+            return;
         }
-    } else if !had_streaming {
-        paste_text(&result.text);
-    }
+    };
+    let paste_method = crate::profiles::paste_method(&settings);
+
+    let language = result
+        .detected_language
+        .clone()
+        .unwrap_or_else(|| settings.transcription_language.clone());
+
+    // Nombres, dates, montants et adresses écrits avant les commandes vocales
+    let pending = if settings.text_normalization.enabled_for(settings.dictation_mode) {
+        crate::normalization::normalize(pending, &language, settings.dictation_mode)
+    } else {
+        pending.to_string()
+    };
+
+    // Texte et actions dans l'ordre où ils ont été dictés
+    let (tokens, snippets) = if settings.voice_commands_enabled {
+        let snippets = storage::snippets::effective_snippets();
+        let parsed = crate::voice_commands::parse(
+            &pending,
+            &crate::voice_commands::ParseContext {
+                mode: settings.dictation_mode,
+                snippets: &snippets,
+                system_commands_enabled: settings.system_commands_enabled,
+                language: &language,
+                code_language: settings.code_language,
+            },
+        );
+        if !parsed.actions.is_empty() {
+            log::info!(
+                "[PTT] Voice commands detected: {}",
+                crate::privacy::redact(&format!("{:?}", parsed.actions))
+            );
+        }
+        (parsed.tokens, snippets)
+    } else {
+        let text = if had_streaming { pending.trim() } else { &pending };
+        (vec![Token::Text(text.to_string())], Vec::new())
+    };
+
+    crate::voice_commands::execute_tokens(app, &tokens, &snippets, &settings, |text| {
+        if text.trim().is_empty() {
+            return;
+        }
+        if had_streaming {
+            type_text_incremental(text);
+        } else {
+            insert_text(app, text, paste_method);
+        }
+    });
 
     if let Ok(mut text) = STREAMING_TEXT.lock() {
         text.clear();
     }
 }
 
+/// Insère le texte dicté selon la méthode du profil actif
+fn insert_text(app: &tauri::AppHandle, text: &str, method: PasteMethod) {
+    match method {
+        PasteMethod::Paste => paste_text(text),
+        PasteMethod::TerminalPaste => {
+            if let Err(e) = app.clipboard().write_text(text.to_string()) {
+                log::error!("[PTT] Failed to copy text: {}", e);
+                return;
+            }
+            // Cmd+V colle aussi dans les terminaux macOS
+            let combo = if cfg!(target_os = "macos") { "primary+v" } else { "ctrl+shift+v" };
+            if let Ok(combo) = KeyCombo::parse(combo) {
+                std::thread::sleep(std::time::Duration::from_millis(50));
+                send_key_combo(&combo);
+            }
+        }
+        PasteMethod::ClipboardOnly => {
+            if let Err(e) = app.clipboard().write_text(text.to_string()) {
+                log::error!("[PTT] Failed to copy text: {}", e);
+            }
+        }
+    }
+}
+
 /// Lit le texte du presse-papiers, le traduit et le colle
 fn translate_clipboard_and_paste(app: &tauri::AppHandle) {
     log::info!("[TRANSLATE] translate_clipboard_and_paste() called");
@@ -617,7 +739,7 @@ fn stop_voice_action_and_execute(app: &tauri::AppHandle) {
         if let Some(ref receiver) = *guard {
             loop {
                 match receiver.recv_timeout(std::time::Duration::from_secs(2)) {
-                    Ok(PttResult::AudioComplete { audio, sample_rate }) => break (audio, sample_rate),
+                    Ok(PttResult::AudioComplete(recorded)) => break (recorded.audio.to_vec(), recorded.sample_rate),
                     Ok(PttResult::AudioSnapshot { .. }) => continue,
                     Err(e) => {
                         log::error!("[VOICE_ACTION] Failed to receive audio: {}", e);
@@ -688,25 +810,7 @@ fn stop_voice_action_and_execute(app: &tauri::AppHandle) {
     }
 
     let state: tauri::State<'_, crate::state::AppState> = app.state();
-    let engine_guard = match state.engine.read() {
-        Ok(guard) => guard,
-        Err(_) => {
-            set_tray_state(TrayState::Idle);
-            let _ = app.emit("voice-action-status", "idle");
-            return;
-        }
-    };
-
-    let engine = match engine_guard.as_ref() {
-        Some(e) => e,
-        None => {
-            set_tray_state(TrayState::Idle);
-            let _ = app.emit("voice-action-status", "idle");
-            return;
-        }
-    };
-
-    let transcription = match engine.transcribe(&resampled, TARGET_SAMPLE_RATE) {
+    let transcription = match state.transcribe(&resampled, TARGET_SAMPLE_RATE) {
         Ok(r) => r.text,
         Err(e) => {
             log::error!("[VOICE_ACTION] Transcription failed: {}", e);
@@ -716,15 +820,13 @@ fn stop_voice_action_and_execute(app: &tauri::AppHandle) {
         }
     };
 
-    drop(engine_guard);
-
     if transcription.is_empty() {
         set_tray_state(TrayState::Idle);
         let _ = app.emit("voice-action-status", "idle");
         return;
     }
 
-    log::info!("[VOICE_ACTION] Instruction: '{}'", transcription);
+    log::info!("[VOICE_ACTION] Instruction: '{}'", crate::privacy::redact(&transcription));
 
     let api_key = match crate::commands::llm::get_groq_api_key_internal() {
         Some(key) => key,
@@ -784,122 +886,144 @@ Retourne UNIQUEMENT le résultat, sans explication ni commentaire."#;
 /// Configure le global shortcut handler et enregistre les raccourcis
 pub fn setup_shortcuts(app: &mut tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     let settings = storage::config::load_settings();
+    hotkeys::apply(app.handle(), &settings);
+    Ok(())
+}
 
-    // Raccourci push-to-talk
-    let ptt_hotkey = settings.hotkey_push_to_talk.clone();
-    let ptt_shortcut = parse_hotkey(&ptt_hotkey)
-        .unwrap_or_else(|| Shortcut::new(Some(Modifiers::CONTROL | Modifiers::SHIFT), Code::Space));
+/// Termine la dictée en cours (relâchement de la touche, second appui sur la
+/// bascule, silence, durée maximale ou "commande stop")
+pub fn stop_if_active(app: &tauri::AppHandle) {
+    IS_HANDS_FREE.store(false, Ordering::SeqCst);
+    HANDS_FREE_SILENCE_SECS.store(0, Ordering::SeqCst);
+    if IS_PTT_ACTIVE.swap(false, Ordering::SeqCst) {
+        set_tray_recording(false);
+        let _ = app.emit("recording-status", "processing");
+        let handle = app.clone();
+        std::thread::spawn(move || {
+            stop_ptt_and_paste(&handle);
+            let _ = handle.emit("recording-status", "idle");
+            // Hide floating window after a delay
+            std::thread::sleep(std::time::Duration::from_secs(3));
+            if let Some(window) = handle.get_webview_window("floating") {
+                let _ = window.hide();
+            }
+        });
+    }
+}
 
-    if let Ok(mut guard) = PTT_SHORTCUT.lock() {
-        *guard = Some(ptt_shortcut);
+/// Démarre une dictée (push-to-talk ou mains libres) : enregistrement, fenêtre
+/// flottante et streaming. Renvoie `false` si une dictée est déjà en cours.
+fn start_dictation(app: &tauri::AppHandle) -> bool {
+    if IS_PTT_ACTIVE.swap(true, Ordering::SeqCst) {
+        return false;
     }
-    match app.global_shortcut().register(ptt_shortcut) {
-        Ok(_) => log::info!("[PTT] Shortcut '{}' registered successfully!", ptt_hotkey),
-        Err(e) => log::error!("[PTT] ERROR registering shortcut: {:?}", e),
+    if let Ok(mut text) = STREAMING_TEXT.lock() {
+        text.clear();
     }
-
-    // Raccourci traduction (si activé)
-    if settings.translation_enabled {
-        let translate_hotkey = settings.hotkey_translate.clone();
-        if let Some(translate_shortcut) = parse_hotkey(&translate_hotkey) {
-            if let Ok(mut guard) = TRANSLATE_SHORTCUT.lock() {
-                *guard = Some(translate_shortcut);
-            }
-            match app.global_shortcut().register(translate_shortcut) {
-                Ok(_) => log::info!("[TRANSLATE] Shortcut '{}' registered!", translate_hotkey),
-                Err(e) => log::error!("[TRANSLATE] ERROR registering shortcut: {:?}", e),
-            }
+    // Avant d'afficher la fenêtre flottante, qui prendrait le focus
+    crate::profiles::activate(app);
+    set_tray_recording(true);
+    start_ptt_recording();
+    // Show floating window centered at top of screen during PTT
+    if let Some(window) = app.get_webview_window("floating") {
+        if let Ok(Some(monitor)) = window.primary_monitor() {
+            let screen_width = monitor.size().width as i32;
+            let window_width = 380; // matches tauri.conf.json
+            let x = (screen_width - window_width) / 2;
+            let y = 40; // slight offset from top
+            let _ = window.set_position(tauri::Position::Physical(
+                tauri::PhysicalPosition { x, y },
+            ));
         }
+        let _ = window.show();
     }
+    let _ = app.emit("recording-status", "recording");
 
-    // Raccourci Voice Action
-    let voice_action_hotkey = settings.hotkey_voice_action.clone();
-    if let Some(voice_action_shortcut) = parse_hotkey(&voice_action_hotkey) {
-        if let Ok(mut guard) = VOICE_ACTION_SHORTCUT.lock() {
-            *guard = Some(voice_action_shortcut);
-        }
-        match app.global_shortcut().register(voice_action_shortcut) {
-            Ok(_) => log::info!("[VOICE_ACTION] Shortcut '{}' registered!", voice_action_hotkey),
-            Err(e) => log::error!("[VOICE_ACTION] ERROR registering shortcut: {:?}", e),
-        }
+    let handle = app.clone();
+    std::thread::spawn(move || {
+        crate::profiles::prepare_engine(&handle);
+        start_streaming_transcription(&handle);
+    });
+    true
+}
+
+#[derive(serde::Serialize, Clone)]
+struct HandsFreeProgress {
+    elapsed_secs: u32,
+    max_secs: u32,
+}
+
+/// Raccourci de bascule : dictée sans garder la touche enfoncée, jusqu'au
+/// prochain appui, au silence (si activé) ou à la durée maximale
+fn start_hands_free(app: &tauri::AppHandle) {
+    let state: tauri::State<'_, AppState> = app.state();
+    let Ok(settings) = state.settings.read().map(|s| s.clone()) else {
+        return;
+    };
+    if !start_dictation(app) {
+        return;
     }
 
-    Ok(())
+    let session = HANDS_FREE_SESSION.fetch_add(1, Ordering::SeqCst) + 1;
+    IS_HANDS_FREE.store(true, Ordering::SeqCst);
+    let silence_secs = if settings.hands_free_auto_stop { settings.hands_free_silence_secs.max(1) } else { 0 };
+    HANDS_FREE_SILENCE_SECS.store(silence_secs, Ordering::SeqCst);
+    log::info!("[HANDS_FREE] Dictation started (auto-stop after silence: {}s)", silence_secs);
+
+    // Garde-fou : la durée restante est affichée, la dictée s'arrête à la limite
+    let max_secs = settings.hands_free_max_secs.max(10);
+    let handle = app.clone();
+    std::thread::spawn(move || {
+        let started = std::time::Instant::now();
+        while HANDS_FREE_SESSION.load(Ordering::SeqCst) == session && IS_HANDS_FREE.load(Ordering::SeqCst) {
+            let elapsed_secs = started.elapsed().as_secs() as u32;
+            let _ = handle.emit("hands-free-progress", HandsFreeProgress { elapsed_secs, max_secs });
+            if elapsed_secs >= max_secs {
+                log::warn!("[HANDS_FREE] Maximum duration of {}s reached, stopping dictation", max_secs);
+                stop_if_active(&handle);
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_secs(1));
+        }
+    });
 }
 
 /// Handler pour les événements de raccourcis globaux
 pub fn handle_shortcut(app: &tauri::AppHandle, shortcut: &Shortcut, event: &tauri_plugin_global_shortcut::ShortcutEvent) {
-    let is_ptt = PTT_SHORTCUT.lock().ok()
-        .and_then(|guard| guard.as_ref().map(|s| *s == *shortcut))
-        .unwrap_or(false);
-    let is_translate = TRANSLATE_SHORTCUT.lock().ok()
-        .and_then(|guard| guard.as_ref().map(|s| *s == *shortcut))
-        .unwrap_or(false);
-    let is_voice_action = VOICE_ACTION_SHORTCUT.lock().ok()
-        .and_then(|guard| guard.as_ref().map(|s| *s == *shortcut))
-        .unwrap_or(false);
-
-    if is_ptt {
-        match event.state() {
-            ShortcutState::Pressed => {
-                if !IS_PTT_ACTIVE.swap(true, Ordering::SeqCst) {
-                    if let Ok(mut text) = STREAMING_TEXT.lock() {
-                        text.clear();
-                    }
-                    set_tray_recording(true);
-                    start_ptt_recording();
-                    // Show floating window centered at top of screen during PTT
-                    if let Some(window) = app.get_webview_window("floating") {
-                        if let Ok(Some(monitor)) = window.primary_monitor() {
-                            let screen_width = monitor.size().width as i32;
-                            let window_width = 380; // matches tauri.conf.json
-                            let x = (screen_width - window_width) / 2;
-                            let y = 40; // slight offset from top
-                            let _ = window.set_position(tauri::Position::Physical(
-                                tauri::PhysicalPosition { x, y },
-                            ));
-                        }
-                        let _ = window.show();
-                    }
-                    let _ = app.emit("recording-status", "recording");
+    let Some(action) = hotkeys::action_for(shortcut) else { return };
 
-                    let handle = app.clone();
-                    std::thread::spawn(move || {
-                        start_streaming_transcription(&handle);
-                    });
-                }
+    match action {
+        HotkeyAction::PushToTalk => match event.state() {
+            ShortcutState::Pressed => {
+                start_dictation(app);
             }
             ShortcutState::Released => {
-                if IS_PTT_ACTIVE.swap(false, Ordering::SeqCst) {
-                    set_tray_recording(false);
-                    let _ = app.emit("recording-status", "processing");
+                stop_if_active(app);
+            }
+        },
+        HotkeyAction::ToggleRecord => {
+            if let ShortcutState::Pressed = event.state() {
+                if IS_PTT_ACTIVE.load(Ordering::SeqCst) {
+                    stop_if_active(app);
+                } else {
+                    start_hands_free(app);
+                }
+            }
+        }
+        HotkeyAction::Translate => {
+            if let ShortcutState::Released = event.state() {
+                if !IS_TRANSLATE_ACTIVE.swap(true, Ordering::SeqCst) {
                     let handle = app.clone();
                     std::thread::spawn(move || {
-                        stop_ptt_and_paste(&handle);
-                        let _ = handle.emit("recording-status", "idle");
-                        // Hide floating window after a delay
-                        std::thread::sleep(std::time::Duration::from_secs(3));
-                        if let Some(window) = handle.get_webview_window("floating") {
-                            let _ = window.hide();
-                        }
+                        translate_clipboard_and_paste(&handle);
+                        IS_TRANSLATE_ACTIVE.store(false, Ordering::SeqCst);
                     });
+                } else {
+                    log::warn!("[TRANSLATE] Translation already in progress, ignoring");
                 }
             }
         }
-    } else if is_translate {
-        if let ShortcutState::Released = event.state() {
-            if !IS_TRANSLATE_ACTIVE.swap(true, Ordering::SeqCst) {
-                let handle = app.clone();
-                std::thread::spawn(move || {
-                    translate_clipboard_and_paste(&handle);
-                    IS_TRANSLATE_ACTIVE.store(false, Ordering::SeqCst);
-                });
-            } else {
-                log::warn!("[TRANSLATE] Translation already in progress, ignoring");
-            }
-        }
-    } else if is_voice_action {
-        match event.state() {
+        HotkeyAction::VoiceAction => match event.state() {
             ShortcutState::Pressed => {
                 if !IS_VOICE_ACTION_ACTIVE.swap(true, Ordering::SeqCst) {
                     let handle = app.clone();
@@ -916,6 +1040,6 @@ pub fn handle_shortcut(app: &tauri::AppHandle, shortcut: &Shortcut, event: &taur
                     });
                 }
             }
-        }
+        },
     }
 }
@@ -1,17 +1,23 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
-use tauri::{AppHandle, Manager};
+use tauri::{AppHandle, Emitter, Manager};
 
 use crate::audio::AudioProcessor;
+use crate::engines::health::{self, EngineHealth, HEALTH_CHECK_INTERVAL};
 use crate::engines::{ModelManager, ParakeetCoreMLEngine, ParakeetEngine, SpeechEngine, VoskEngine, WhisperEngine};
-use crate::storage::config;
-use crate::types::{AppSettings, EngineType, ModelSize, ParakeetModelSize, VoskLanguage};
+use crate::profiles;
+use crate::storage::{config, managed};
+use crate::types::{AppSettings, EngineType, ModelSize, ParakeetModelSize, TranscriptionResult, VoskLanguage, WhisperDecodeOptions};
 
 pub struct AppState {
     pub is_recording: Arc<RwLock<bool>>,
     pub settings: Arc<RwLock<AppSettings>>,
     pub sample_rate: Arc<RwLock<u32>>,
     pub engine: Arc<RwLock<Option<Box<dyn SpeechEngine>>>>,
+    /// Moteurs de secours, chargés au premier échec du moteur actif
+    pub fallback_engines: Arc<RwLock<Vec<Box<dyn SpeechEngine>>>>,
+    /// Dernier état connu de chaque moteur de la chaîne
+    pub engine_health: Arc<RwLock<Vec<EngineHealth>>>,
     pub model_manager: Arc<ModelManager>,
     pub resource_path: PathBuf,
     pub audio_buffer: Arc<RwLock<Option<(Vec<f32>, u32)>>>,
@@ -88,119 +94,331 @@ impl AppState {
             bundled_models_path,
         );
 
-        // Helper to load Whisper engine
-        let load_whisper = |model_manager: &ModelManager, settings: &AppSettings| -> Option<Box<dyn SpeechEngine>> {
-            if let Some(model_path) = model_manager.get_model_path(settings.whisper_model) {
+        // Moteur configuré, puis la chaîne de secours s'il ne se charge pas
+        let mut engine: Option<Box<dyn SpeechEngine>> = None;
+        let fallbacks = health::fallback_order(settings.engine_type, &settings.engine_fallback_chain, managed::is_engine_allowed);
+        for engine_type in std::iter::once(settings.engine_type).chain(fallbacks) {
+            match Self::load_engine(&model_manager, &resource_path, &settings, engine_type) {
+                Ok(loaded) => {
+                    if engine_type != settings.engine_type {
+                        log::warn!("Falling back to {:?} engine", engine_type);
+                    }
+                    engine = Some(loaded);
+                    break;
+                }
+                Err(e) => log::error!("Failed to initialize {:?} engine: {}", engine_type, e),
+            }
+        }
+
+        Ok(Self {
+            is_recording: Arc::new(RwLock::new(false)),
+            settings: Arc::new(RwLock::new(settings)),
+            sample_rate: Arc::new(RwLock::new(16000)),
+            engine: Arc::new(RwLock::new(engine)),
+            fallback_engines: Arc::new(RwLock::new(Vec::new())),
+            engine_health: Arc::new(RwLock::new(Vec::new())),
+            model_manager: Arc::new(model_manager),
+            resource_path,
+            audio_buffer: Arc::new(RwLock::new(None)),
+            audio_processor: Arc::new(RwLock::new(AudioProcessor::new())),
+        })
+    }
+
+    /// Charge un moteur d'après les réglages (modèle, langue), sans repli
+    fn load_engine(
+        model_manager: &ModelManager,
+        resource_path: &Path,
+        settings: &AppSettings,
+        engine_type: EngineType,
+    ) -> Result<Box<dyn SpeechEngine>, String> {
+        match engine_type {
+            EngineType::Whisper => {
+                let model_path = model_manager
+                    .get_model_path(settings.whisper_model)
+                    .ok_or_else(|| format!("Whisper model {:?} not available", settings.whisper_model))?;
                 let lang = if settings.auto_detect_language {
                     None
                 } else {
                     Some(settings.transcription_language.clone())
                 };
-
-                match WhisperEngine::new(&model_path, lang, settings.whisper_model) {
-                    Ok(engine) => {
-                        log::info!("Whisper engine initialized with model {:?}", settings.whisper_model);
-                        Some(Box::new(engine))
-                    }
-                    Err(e) => {
-                        log::error!("Failed to initialize Whisper engine: {}", e);
-                        None
-                    }
-                }
-            } else {
-                log::warn!("Whisper model {:?} not available", settings.whisper_model);
-                None
+                let engine = WhisperEngine::new(&model_path, lang, settings.whisper_model)?;
+                log::info!("Whisper engine initialized with model {:?}", settings.whisper_model);
+                Ok(Box::new(engine))
             }
-        };
-
-        // Load engine based on configured type
-        let engine: Option<Box<dyn SpeechEngine>> = match settings.engine_type {
-            EngineType::Whisper => load_whisper(&model_manager, &settings),
             EngineType::Parakeet => {
                 // On macOS, use CoreML-based Parakeet engine
                 #[cfg(target_os = "macos")]
                 {
-                    // Find sidecar path
-                    let sidecar_path = Self::find_parakeet_sidecar(&resource_path);
-                    if let Some(path) = sidecar_path {
-                        match ParakeetCoreMLEngine::new(path) {
-                            Ok(engine) => {
-                                log::info!("Parakeet CoreML engine initialized");
-                                Some(Box::new(engine))
-                            }
-                            Err(e) => {
-                                log::error!("Failed to initialize Parakeet CoreML engine: {}, falling back to Whisper", e);
-                                load_whisper(&model_manager, &settings)
-                            }
-                        }
-                    } else {
-                        log::warn!("Parakeet CoreML sidecar not found, falling back to Whisper");
-                        load_whisper(&model_manager, &settings)
-                    }
+                    let sidecar_path = Self::find_parakeet_sidecar(resource_path)
+                        .ok_or_else(|| "Parakeet CoreML sidecar not found".to_string())?;
+                    let engine = ParakeetCoreMLEngine::new(sidecar_path)?;
+                    log::info!("Parakeet CoreML engine initialized");
+                    Ok(Box::new(engine))
                 }
                 // On other platforms, use ONNX-based Parakeet engine
                 #[cfg(not(target_os = "macos"))]
                 {
-                    if let Some(model_path) = model_manager.get_parakeet_model_path(settings.parakeet_model) {
-                        match ParakeetEngine::new(&model_path, settings.parakeet_model.into()) {
-                            Ok(engine) => {
-                                log::info!("Parakeet engine initialized with model {:?}", settings.parakeet_model);
-                                Some(Box::new(engine))
-                            }
-                            Err(e) => {
-                                log::error!("Failed to initialize Parakeet engine: {}, falling back to Whisper", e);
-                                load_whisper(&model_manager, &settings)
-                            }
-                        }
-                    } else {
-                        log::warn!("Parakeet model {:?} not available, falling back to Whisper", settings.parakeet_model);
-                        load_whisper(&model_manager, &settings)
-                    }
+                    let _ = resource_path;
+                    let model_path = model_manager
+                        .get_parakeet_model_path(settings.parakeet_model)
+                        .ok_or_else(|| format!("Parakeet model {:?} not available", settings.parakeet_model))?;
+                    let engine = ParakeetEngine::new(&model_path, settings.parakeet_model.into())?;
+                    log::info!("Parakeet engine initialized with model {:?}", settings.parakeet_model);
+                    Ok(Box::new(engine))
                 }
             }
             EngineType::Vosk => {
                 // Find Vosk model matching configured language
-                let vosk_lang = settings.vosk_language
-                    .or_else(|| VoskLanguage::from_language_code(&settings.transcription_language));
+                let lang = settings.vosk_language
+                    .or_else(|| VoskLanguage::from_language_code(&settings.transcription_language))
+                    .ok_or_else(|| "No Vosk language configured".to_string())?;
+                let model_path = model_manager
+                    .get_vosk_model_path(lang)
+                    .ok_or_else(|| format!("Vosk model for {:?} not available", lang))?;
+                let engine = VoskEngine::new(&model_path, lang)?;
+                log::info!("Vosk engine initialized for language {:?}", lang);
+                Ok(Box::new(engine))
+            }
+        }
+    }
 
-                if let Some(lang) = vosk_lang {
-                    if let Some(model_path) = model_manager.get_vosk_model_path(lang) {
-                        match VoskEngine::new(&model_path, lang) {
-                            Ok(engine) => {
-                                log::info!("Vosk engine initialized for language {:?}", lang);
-                                Some(Box::new(engine))
-                            }
-                            Err(e) => {
-                                log::error!("Failed to initialize Vosk engine: {}", e);
-                                None
-                            }
+    /// Vérifie qu'un moteur non chargé pourrait l'être (modèle téléchargé)
+    fn check_available(&self, settings: &AppSettings, engine_type: EngineType) -> Result<(), String> {
+        let available = match engine_type {
+            EngineType::Whisper => self.model_manager.get_model_path(settings.whisper_model).is_some(),
+            #[cfg(target_os = "macos")]
+            EngineType::Parakeet => Self::find_parakeet_sidecar(&self.resource_path).is_some(),
+            #[cfg(not(target_os = "macos"))]
+            EngineType::Parakeet => self.model_manager.get_parakeet_model_path(settings.parakeet_model).is_some(),
+            EngineType::Vosk => settings.vosk_language
+                .or_else(|| VoskLanguage::from_language_code(&settings.transcription_language))
+                .and_then(|lang| self.model_manager.get_vosk_model_path(lang))
+                .is_some(),
+        };
+        if available {
+            Ok(())
+        } else {
+            Err("Model not downloaded".to_string())
+        }
+    }
+
+    /// Transcrit avec le moteur actif puis, s'il échoue (daemon arrêté, erreur
+    /// ONNX...), avec chaque moteur de la chaîne de secours.
+    /// `model_used` du résultat indique le moteur qui a effectivement transcrit.
+    pub fn transcribe(&self, audio: &[f32], sample_rate: u32) -> Result<TranscriptionResult, String> {
+        self.transcribe_with_options(audio, sample_rate, None)
+    }
+
+    /// Comme `transcribe`, avec des options de décodage Whisper propres à la
+    /// tâche (fichier, segment) ; `None` : celles des réglages et du profil actif
+    pub fn transcribe_with_options(
+        &self,
+        audio: &[f32],
+        sample_rate: u32,
+        options: Option<&WhisperDecodeOptions>,
+    ) -> Result<TranscriptionResult, String> {
+        let settings = {
+            let settings = self.settings.read().map_err(|e| e.to_string())?;
+            profiles::effective_settings(&settings)
+        };
+        let options = options.unwrap_or(&settings.whisper_decode);
+        let mut errors = Vec::new();
+
+        let primary = {
+            let engine = self.engine.read().map_err(|e| e.to_string())?;
+            match engine.as_ref() {
+                Some(engine) => match engine.transcribe_with_options(audio, sample_rate, options) {
+                    Ok(result) => return Ok(result),
+                    Err(e) => {
+                        log::warn!("[ENGINE] {} transcription failed: {}", engine.name(), e);
+                        self.record_health(EngineHealth::new(engine.engine_type(), true, true, Some(e.clone())));
+                        errors.push(format!("{}: {}", engine.name(), e));
+                        engine.engine_type()
+                    }
+                },
+                None => settings.engine_type,
+            }
+        };
+
+        let fallbacks = health::fallback_order(primary, &settings.engine_fallback_chain, managed::is_engine_allowed);
+        for engine_type in fallbacks {
+            match self.transcribe_with_fallback(&settings, engine_type, audio, sample_rate, options) {
+                Ok(result) => {
+                    log::warn!("[ENGINE] Transcription recovered with fallback engine {:?}", engine_type);
+                    return Ok(result);
+                }
+                Err(e) => {
+                    log::warn!("[ENGINE] Fallback {:?} failed: {}", engine_type, e);
+                    errors.push(format!("{:?}: {}", engine_type, e));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            return Err("No transcription engine available. Please download a model first.".to_string());
+        }
+        Err(format!("All transcription engines failed ({})", errors.join("; ")))
+    }
+
+    fn transcribe_with_fallback(
+        &self,
+        settings: &AppSettings,
+        engine_type: EngineType,
+        audio: &[f32],
+        sample_rate: u32,
+        options: &WhisperDecodeOptions,
+    ) -> Result<TranscriptionResult, String> {
+        let mut engines = self.fallback_engines.write().map_err(|e| e.to_string())?;
+        let index = match engines.iter().position(|engine| engine.engine_type() == engine_type) {
+            Some(index) => index,
+            None => {
+                log::info!("[ENGINE] Loading fallback engine {:?}", engine_type);
+                let engine = Self::load_engine(&self.model_manager, &self.resource_path, settings, engine_type)
+                    .inspect_err(|e| self.record_health(EngineHealth::new(engine_type, false, false, Some(e.clone()))))?;
+                engines.push(engine);
+                engines.len() - 1
+            }
+        };
+
+        let engine = &mut engines[index];
+        engine.set_language((!settings.auto_detect_language).then(|| settings.transcription_language.clone()));
+        let result = engine.transcribe_with_options(audio, sample_rate, options);
+        self.record_health(EngineHealth::new(engine_type, false, true, result.as_ref().err().cloned()));
+        result
+    }
+
+    fn record_health(&self, status: EngineHealth) {
+        if let Ok(mut health) = self.engine_health.write() {
+            health.retain(|h| h.engine_type != status.engine_type);
+            health.push(status);
+        }
+    }
+
+    /// Oublie un moteur de secours (modèle changé) : il sera rechargé à la demande
+    fn unload_fallback(&self, engine_type: EngineType) {
+        if let Ok(mut engines) = self.fallback_engines.write() {
+            engines.retain(|engine| engine.engine_type() != engine_type);
+        }
+    }
+
+    /// Vérifie chaque moteur de la chaîne. Un moteur actif défaillant est
+    /// rechargé ; un moteur de secours défaillant est déchargé.
+    pub fn check_engine_health(&self) -> Vec<EngineHealth> {
+        let Ok(settings) = self.settings.read().map(|s| profiles::effective_settings(&s)) else {
+            return Vec::new();
+        };
+        let mut report = Vec::new();
+
+        let primary = self.engine.read().ok()
+            .and_then(|engine| engine.as_ref().map(|e| (e.engine_type(), e.health_check())));
+        let primary_type = match primary {
+            Some((engine_type, Ok(()))) => {
+                report.push(EngineHealth::new(engine_type, true, true, None));
+                engine_type
+            }
+            Some((engine_type, Err(e))) => {
+                log::warn!("[ENGINE] {:?} engine unhealthy: {}, reloading", engine_type, e);
+                let error = match Self::load_engine(&self.model_manager, &self.resource_path, &settings, engine_type) {
+                    Ok(engine) => {
+                        if let Ok(mut slot) = self.engine.write() {
+                            *slot = Some(engine);
                         }
-                    } else {
-                        log::warn!("Vosk model for {:?} not available", lang);
+                        log::info!("[ENGINE] {:?} engine reloaded", engine_type);
                         None
                     }
-                } else {
-                    log::warn!("No Vosk language configured");
-                    None
-                }
+                    Err(reload) => Some(format!("{} (reload failed: {})", e, reload)),
+                };
+                report.push(EngineHealth::new(engine_type, true, error.is_none(), error));
+                engine_type
+            }
+            None => {
+                let error = self.check_available(&settings, settings.engine_type).err()
+                    .unwrap_or_else(|| "Not loaded".to_string());
+                report.push(EngineHealth::new(settings.engine_type, true, false, Some(error)));
+                settings.engine_type
             }
         };
 
-        Ok(Self {
-            is_recording: Arc::new(RwLock::new(false)),
-            settings: Arc::new(RwLock::new(settings)),
-            sample_rate: Arc::new(RwLock::new(16000)),
-            engine: Arc::new(RwLock::new(engine)),
-            model_manager: Arc::new(model_manager),
-            resource_path,
-            audio_buffer: Arc::new(RwLock::new(None)),
-            audio_processor: Arc::new(RwLock::new(AudioProcessor::new())),
-        })
+        let fallbacks = health::fallback_order(primary_type, &settings.engine_fallback_chain, managed::is_engine_allowed);
+        if let Ok(mut engines) = self.fallback_engines.write() {
+            engines.retain(|engine| fallbacks.contains(&engine.engine_type()));
+            for engine_type in fallbacks {
+                let status = match engines.iter().position(|e| e.engine_type() == engine_type) {
+                    Some(index) => match engines[index].health_check() {
+                        Ok(()) => EngineHealth::new(engine_type, false, true, None),
+                        Err(e) => {
+                            log::warn!("[ENGINE] Fallback {:?} unhealthy: {}, unloading", engine_type, e);
+                            engines.remove(index);
+                            EngineHealth::new(engine_type, false, false, Some(e))
+                        }
+                    },
+                    None => EngineHealth::new(engine_type, false, false, self.check_available(&settings, engine_type).err()),
+                };
+                report.push(status);
+            }
+        }
+
+        if let Ok(mut health) = self.engine_health.write() {
+            *health = report.clone();
+        }
+        report
+    }
+
+    /// Vérifie périodiquement les moteurs ; le résultat est émis via "engine-health-updated"
+    pub fn start_health_checks(app: AppHandle) {
+        std::thread::spawn(move || loop {
+            std::thread::sleep(HEALTH_CHECK_INTERVAL);
+            let state: tauri::State<'_, AppState> = app.state();
+            let report = state.check_engine_health();
+            let _ = app.emit("engine-health-updated", &report);
+        });
+    }
+
+    /// Chaque moteur et modèle installé, avec les réglages qui le sélectionnent.
+    /// Vosk est limité à la langue de transcription quand elle est connue.
+    pub fn installed_engine_variants(&self, settings: &AppSettings) -> Vec<(EngineType, String, AppSettings)> {
+        let mut variants = Vec::new();
+
+        for size in self.model_manager.available_models() {
+            let mut variant = settings.clone();
+            variant.whisper_model = size;
+            variants.push((EngineType::Whisper, format!("Whisper {}", size.display_name()), variant));
+        }
+
+        #[cfg(target_os = "macos")]
+        if Self::find_parakeet_sidecar(&self.resource_path).is_some() {
+            variants.push((EngineType::Parakeet, "Parakeet CoreML".to_string(), settings.clone()));
+        }
+        #[cfg(not(target_os = "macos"))]
+        for model in self.model_manager.available_parakeet_models() {
+            let mut variant = settings.clone();
+            variant.parakeet_model = model;
+            variants.push((EngineType::Parakeet, model.display_name().to_string(), variant));
+        }
+
+        let language = settings.vosk_language
+            .or_else(|| VoskLanguage::from_language_code(&settings.transcription_language));
+        for lang in self.model_manager.available_vosk_models() {
+            if language.is_some_and(|l| l != lang) {
+                continue;
+            }
+            let mut variant = settings.clone();
+            variant.vosk_language = Some(lang);
+            variants.push((EngineType::Vosk, format!("Vosk {}", lang.display_name()), variant));
+        }
+
+        variants.retain(|(engine_type, _, _)| managed::is_engine_allowed(*engine_type));
+        variants
+    }
+
+    /// Charge un moteur indépendant du moteur actif (évaluation comparative)
+    pub fn load_standalone_engine(&self, settings: &AppSettings, engine_type: EngineType) -> Result<Box<dyn SpeechEngine>, String> {
+        Self::load_engine(&self.model_manager, &self.resource_path, settings, engine_type)
     }
 
     /// Find the Parakeet CoreML sidecar binary
     #[cfg(target_os = "macos")]
-    fn find_parakeet_sidecar(resource_path: &PathBuf) -> Option<PathBuf> {
+    fn find_parakeet_sidecar(resource_path: &Path) -> Option<PathBuf> {
         // Determine target triple
         #[cfg(target_arch = "x86_64")]
         let target = "x86_64-apple-darwin";
@@ -253,6 +471,7 @@ impl AppState {
             .ok_or_else(|| format!("Model {:?} not available", model_size))?;
 
         let new_engine = WhisperEngine::new(&model_path, language, model_size)?;
+        self.unload_fallback(EngineType::Whisper);
 
         let mut engine = self.engine.write().map_err(|e| e.to_string())?;
         *engine = Some(Box::new(new_engine));
@@ -270,6 +489,7 @@ impl AppState {
                 .ok_or_else(|| "Parakeet CoreML sidecar not found".to_string())?;
 
             let new_engine = ParakeetCoreMLEngine::new(sidecar_path)?;
+            self.unload_fallback(EngineType::Parakeet);
 
             let mut engine = self.engine.write().map_err(|e| e.to_string())?;
             *engine = Some(Box::new(new_engine));
@@ -285,6 +505,7 @@ impl AppState {
                 .ok_or_else(|| format!("Parakeet model {:?} not available", _model_size))?;
 
             let new_engine = ParakeetEngine::new(&model_path, _model_size.into())?;
+            self.unload_fallback(EngineType::Parakeet);
 
             let mut engine = self.engine.write().map_err(|e| e.to_string())?;
             *engine = Some(Box::new(new_engine));
@@ -301,6 +522,7 @@ impl AppState {
             .ok_or_else(|| format!("Vosk model for {:?} not available", language))?;
 
         let new_engine = VoskEngine::new(&model_path, language)?;
+        self.unload_fallback(EngineType::Vosk);
 
         let mut engine = self.engine.write().map_err(|e| e.to_string())?;
         *engine = Some(Box::new(new_engine));
@@ -1,9 +1,11 @@
 use serde::{Deserialize, Serialize};
-use std::fs;
 use std::path::PathBuf;
+use std::sync::Mutex;
 
 const MAX_LOG_ENTRIES: usize = 500;
 
+pub(super) static APP_LOG_LOCK: Mutex<()> = Mutex::new(());
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LogEntry {
     pub timestamp: i64,
@@ -17,7 +19,7 @@ pub struct AppLogData {
     pub entries: Vec<LogEntry>,
 }
 
-fn log_path() -> PathBuf {
+pub(super) fn log_path() -> PathBuf {
     super::get_app_data_dir().join("app_log.json")
 }
 
@@ -26,29 +28,25 @@ pub fn log_path_string() -> String {
 }
 
 fn load_log_data() -> AppLogData {
-    let path = log_path();
-    if path.exists() {
-        let content = fs::read_to_string(&path).unwrap_or_default();
-        serde_json::from_str(&content).unwrap_or_default()
-    } else {
-        AppLogData::default()
-    }
+    super::read_json(&log_path())
 }
 
 fn save_log_data(data: &AppLogData) -> Result<(), String> {
-    super::ensure_app_data_dir().map_err(|e| e.to_string())?;
-    let path = log_path();
-    let content = serde_json::to_string_pretty(data).map_err(|e| e.to_string())?;
-    fs::write(&path, content).map_err(|e| e.to_string())
+    super::write_private_json(&log_path(), data)
 }
 
+/// Ajoute une entrée au journal de l'application ; rien n'est écrit en mode confidentialité
 pub fn append_log(level: &str, category: &str, message: &str) {
+    if crate::privacy::is_enabled() {
+        return;
+    }
     let entry = LogEntry {
         timestamp: chrono::Utc::now().timestamp(),
         level: level.to_string(),
         category: category.to_string(),
         message: message.to_string(),
     };
+    let _guard = super::lock_store(&APP_LOG_LOCK);
     let mut data = load_log_data();
     data.entries.insert(0, entry);
     data.entries.truncate(MAX_LOG_ENTRIES);
@@ -62,5 +60,6 @@ pub fn get_logs(limit: Option<usize>) -> Vec<LogEntry> {
 }
 
 pub fn clear_logs() -> Result<(), String> {
+    let _guard = super::lock_store(&APP_LOG_LOCK);
     save_log_data(&AppLogData::default())
 }
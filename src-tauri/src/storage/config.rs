@@ -1,6 +1,38 @@
+//! `config.json` : réglages versionnés, migrés et validés au chargement
+//!
+//! Le fichier est lu en JSON brut, migré jusqu'à `SETTINGS_SCHEMA_VERSION`,
+//! puis désérialisé champ par champ : une valeur invalide ne remet à zéro que
+//! son propre champ, jamais tout le fichier. Le fichier d'origine est alors
+//! conservé en `config.json.invalid-<timestamp>` avant d'être réécrit.
+//! La configuration d'équipe (`managed`) est fusionnée sous le fichier.
+
+use crate::hotkeys::parse_hotkey;
 use crate::types::AppSettings;
-use std::fs;
+use serde::Serialize;
+use serde_json::{Map, Value};
 use std::path::PathBuf;
+use std::sync::Mutex;
+
+static CONFIG_LOCK: Mutex<()> = Mutex::new(());
+
+/// Version courante du schéma de `config.json`
+pub const SETTINGS_SCHEMA_VERSION: u32 = 1;
+
+/// Migration d'indice n : version n -> n + 1, sur le JSON brut
+const MIGRATIONS: [fn(&mut Map<String, Value>); SETTINGS_SCHEMA_VERSION as usize] = [migrate_v0_to_v1];
+
+/// Champ rejeté ou corrigé lors du chargement, de l'import ou d'une mise à jour
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SettingsIssue {
+    pub field: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for SettingsIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
 
 fn config_path() -> PathBuf {
     super::get_app_data_dir().join("config.json")
@@ -8,17 +40,263 @@ fn config_path() -> PathBuf {
 
 pub fn load_settings() -> AppSettings {
     let path = config_path();
-    if path.exists() {
-        let content = fs::read_to_string(&path).unwrap_or_default();
-        serde_json::from_str(&content).unwrap_or_default()
-    } else {
-        AppSettings::default()
+    let value: Value = super::read_json(&path);
+    let user_file = !value.is_null();
+
+    // Configuration d'équipe sous les réglages de l'utilisateur
+    let (mut settings, issues) = parse_settings(super::managed::overlay(value));
+    for issue in super::managed::enforce(&mut settings) {
+        log::warn!("[SETTINGS] {} (team configuration)", issue);
     }
+    if user_file && !issues.is_empty() {
+        for issue in &issues {
+            log::warn!("[SETTINGS] {}", issue);
+        }
+        repair(&path, &settings);
+    }
+    settings
 }
 
 pub fn save_settings(settings: &AppSettings) -> Result<(), String> {
-    super::ensure_app_data_dir().map_err(|e| e.to_string())?;
-    let path = config_path();
-    let content = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
-    fs::write(&path, content).map_err(|e| e.to_string())
+    let _guard = super::lock_store(&CONFIG_LOCK);
+    let mut settings = settings.clone();
+    settings.schema_version = SETTINGS_SCHEMA_VERSION;
+    let mut value = serde_json::to_value(&settings).map_err(|e| e.to_string())?;
+    if let Value::Object(map) = &mut value {
+        super::managed::strip(map);
+    }
+    super::write_json(&config_path(), &value)
+}
+
+/// Sauvegarde le fichier d'origine puis le remplace par les réglages corrigés,
+/// pour ne signaler chaque problème qu'une fois
+fn repair(path: &std::path::Path, settings: &AppSettings) {
+    let backup = path.with_file_name(format!(
+        "config.json.invalid-{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    match std::fs::copy(path, &backup) {
+        Ok(_) => log::warn!("[SETTINGS] Original config backed up to {:?}", backup),
+        Err(e) => {
+            log::error!("[SETTINGS] Failed to back up {:?}, leaving it untouched: {}", path, e);
+            return;
+        }
+    }
+    if let Err(e) = save_settings(settings) {
+        log::error!("[SETTINGS] Failed to rewrite repaired config: {}", e);
+    }
+}
+
+/// Migre, désérialise et valide un JSON de réglages (fichier ou import).
+/// Les champs illisibles ou invalides reprennent leur valeur par défaut.
+pub fn parse_settings(value: Value) -> (AppSettings, Vec<SettingsIssue>) {
+    let Value::Object(mut map) = value else {
+        let issue = SettingsIssue {
+            field: "*".to_string(),
+            message: "settings must be a JSON object".to_string(),
+        };
+        return (AppSettings::default(), vec![issue]);
+    };
+
+    migrate(&mut map);
+    let (mut settings, mut issues) = deserialize_lenient(map);
+    issues.extend(sanitize(&mut settings));
+    (settings, issues)
+}
+
+/// Applique les migrations manquantes. Un fichier écrit par une version plus
+/// récente est lu tel quel : les champs inconnus sont ignorés.
+fn migrate(map: &mut Map<String, Value>) {
+    let version = map
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .unwrap_or(0) as u32;
+
+    if version > SETTINGS_SCHEMA_VERSION {
+        log::warn!(
+            "[SETTINGS] config.json has schema v{} (newer than v{}), unknown fields are ignored",
+            version,
+            SETTINGS_SCHEMA_VERSION
+        );
+        return;
+    }
+
+    for (from, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        migration(map);
+        log::info!("[SETTINGS] Migrated settings schema v{} -> v{}", from, from + 1);
+    }
+    map.insert("schema_version".to_string(), Value::from(SETTINGS_SCHEMA_VERSION));
+}
+
+/// v0 : fichiers sans `schema_version`. Le format des champs n'a pas changé ;
+/// la clé Groq en clair est retirée par `credentials::migrate_plaintext_settings`.
+fn migrate_v0_to_v1(_map: &mut Map<String, Value>) {}
+
+/// Désérialise les champs un par un par-dessus les valeurs par défaut
+fn deserialize_lenient(map: Map<String, Value>) -> (AppSettings, Vec<SettingsIssue>) {
+    let candidate = Value::Object(map.clone());
+    if let Ok(settings) = serde_json::from_value(candidate) {
+        return (settings, Vec::new());
+    }
+
+    let mut merged = match serde_json::to_value(AppSettings::default()) {
+        Ok(Value::Object(defaults)) => defaults,
+        _ => return (AppSettings::default(), Vec::new()),
+    };
+    let mut issues = Vec::new();
+    for (key, value) in map {
+        let previous = merged.insert(key.clone(), value.clone());
+        if let Err(e) = serde_json::from_value::<AppSettings>(Value::Object(merged.clone())) {
+            match previous {
+                Some(previous) => merged.insert(key.clone(), previous),
+                None => merged.remove(&key),
+            };
+            issues.push(SettingsIssue {
+                field: key,
+                message: format!("invalid value {} ({}), default used", value, e),
+            });
+        }
+    }
+
+    let settings = serde_json::from_value(Value::Object(merged)).unwrap_or_default();
+    (settings, issues)
+}
+
+/// Valide les réglages sans les modifier
+pub fn validate(settings: &AppSettings) -> Vec<SettingsIssue> {
+    check(&mut settings.clone(), false)
+}
+
+/// Remet à leur valeur par défaut les champs invalides
+pub fn sanitize(settings: &mut AppSettings) -> Vec<SettingsIssue> {
+    check(settings, true)
+}
+
+fn check(settings: &mut AppSettings, fix: bool) -> Vec<SettingsIssue> {
+    let defaults = AppSettings::default();
+    let mut issues = Vec::new();
+
+    let hotkeys = [
+        ("hotkey_push_to_talk", &mut settings.hotkey_push_to_talk, &defaults.hotkey_push_to_talk),
+        ("hotkey_toggle_record", &mut settings.hotkey_toggle_record, &defaults.hotkey_toggle_record),
+        ("hotkey_translate", &mut settings.hotkey_translate, &defaults.hotkey_translate),
+        ("hotkey_voice_action", &mut settings.hotkey_voice_action, &defaults.hotkey_voice_action),
+    ];
+    for (field, value, default) in hotkeys {
+        // Vide : action désactivée
+        if value.trim().is_empty() {
+            continue;
+        }
+        if let Err(e) = parse_hotkey(value) {
+            issues.push(SettingsIssue { field: field.to_string(), message: e });
+            if fix {
+                value.clone_from(default);
+            }
+        }
+    }
+
+    let languages = [
+        ("transcription_language", &mut settings.transcription_language, &defaults.transcription_language, true),
+        ("translation_target_language", &mut settings.translation_target_language, &defaults.translation_target_language, false),
+    ];
+    for (field, value, default, allow_auto) in languages {
+        let valid = is_language_code(value) || (allow_auto && value.as_str() == "auto");
+        if !valid {
+            issues.push(SettingsIssue {
+                field: field.to_string(),
+                message: format!("'{}' is not a language code (expected e.g. \"fr\", \"en\")", value),
+            });
+            if fix {
+                value.clone_from(default);
+            }
+        }
+    }
+    for (index, profile) in settings.app_profiles.iter_mut().enumerate() {
+        if let Some(language) = &profile.transcription_language {
+            if language != "auto" && !is_language_code(language) {
+                issues.push(SettingsIssue {
+                    field: format!("app_profiles[{}].transcription_language", index),
+                    message: format!("'{}' is not a language code", language),
+                });
+                if fix {
+                    profile.transcription_language = None;
+                }
+            }
+        }
+    }
+
+    let ranges = [
+        ("subtitles_font_size", &mut settings.subtitles_font_size, defaults.subtitles_font_size, 12, 48),
+        ("hands_free_silence_secs", &mut settings.hands_free_silence_secs, defaults.hands_free_silence_secs, 1, 30),
+        ("hands_free_max_secs", &mut settings.hands_free_max_secs, defaults.hands_free_max_secs, 10, 3600),
+        ("parakeet_beam_size", &mut settings.parakeet_beam_size, defaults.parakeet_beam_size, 1, 8),
+        ("whisper_decode.beam_size", &mut settings.whisper_decode.beam_size, defaults.whisper_decode.beam_size, 1, 8),
+        ("whisper_decode.best_of", &mut settings.whisper_decode.best_of, defaults.whisper_decode.best_of, 1, 8),
+        ("whisper_decode.n_threads", &mut settings.whisper_decode.n_threads, defaults.whisper_decode.n_threads, 0, 64),
+    ];
+    for (field, value, default, min, max) in ranges {
+        if !(min..=max).contains(value) {
+            issues.push(SettingsIssue {
+                field: field.to_string(),
+                message: format!("{} is out of range ({}-{})", value, min, max),
+            });
+            if fix {
+                *value = default;
+            }
+        }
+    }
+
+    issues
+}
+
+/// Code ISO 639 : 2 ou 3 lettres minuscules ("fr", "haw")
+fn is_language_code(code: &str) -> bool {
+    (2..=3).contains(&code.len()) && code.bytes().all(|b| b.is_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_invalid_field_keeps_the_rest() {
+        let (settings, issues) = parse_settings(json!({
+            "hotkey_push_to_talk": "Ctrl+Alt+P",
+            "subtitles_font_size": "big",
+            "translation_target_language": "de",
+        }));
+
+        assert_eq!(settings.hotkey_push_to_talk, "Ctrl+Alt+P");
+        assert_eq!(settings.translation_target_language, "de");
+        assert_eq!(settings.subtitles_font_size, AppSettings::default().subtitles_font_size);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].field, "subtitles_font_size");
+    }
+
+    #[test]
+    fn test_unversioned_file_is_migrated() {
+        let (settings, issues) = parse_settings(json!({ "theme": "dark" }));
+        assert!(issues.is_empty());
+        assert_eq!(settings.schema_version, SETTINGS_SCHEMA_VERSION);
+        assert_eq!(settings.theme, "dark");
+    }
+
+    #[test]
+    fn test_sanitize_resets_invalid_values() {
+        let mut settings = AppSettings {
+            hotkey_voice_action: "Ctrl+Nope".to_string(),
+            transcription_language: "French".to_string(),
+            subtitles_font_size: 200,
+            ..AppSettings::default()
+        };
+        let issues = sanitize(&mut settings);
+        let fields: Vec<&str> = issues.iter().map(|i| i.field.as_str()).collect();
+
+        assert_eq!(fields, ["hotkey_voice_action", "transcription_language", "subtitles_font_size"]);
+        assert_eq!(settings.hotkey_voice_action, AppSettings::default().hotkey_voice_action);
+        assert_eq!(settings.transcription_language, "fr");
+        assert_eq!(settings.subtitles_font_size, 20);
+        assert!(validate(&settings).is_empty());
+    }
 }
@@ -0,0 +1,342 @@
+//! Stockage des secrets des fournisseurs (clé API Groq, endpoints compatibles
+//! OpenAI, ...)
+//!
+//! Les secrets vivent dans le trousseau de l'OS. Sans trousseau (Linux sans
+//! Secret Service, conteneur, ...), ils sont écrits dans `credentials.json`,
+//! chiffré en AES-256-GCM avec une clé aléatoire stockée à côté dans
+//! `credentials.key` (permissions 0600). Ce repli protège contre une fuite du
+//! dossier de config (sauvegarde, partage), pas contre un accès local au compte.
+//! Aucun secret n'est jamais écrit dans `config.json`.
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use keyring::Entry;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const SERVICE_NAME: &str = "wakascribe";
+const FILE_VERSION: u32 = 1;
+
+/// Compte de la clé API Groq (même nom qu'avant la migration)
+pub const GROQ_API_KEY: &str = "groq_api_key";
+
+static CREDENTIALS_LOCK: Mutex<()> = Mutex::new(());
+
+/// Backend où sont rangés les secrets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CredentialBackend {
+    Keyring,
+    EncryptedFile,
+}
+
+/// État du stockage des secrets exposé à l'interface
+#[derive(Debug, Clone, Serialize)]
+pub struct CredentialStoreStatus {
+    pub backend: CredentialBackend,
+    pub keyring_error: Option<String>,
+    pub groq_api_key_stored: bool,
+}
+
+/// Abstraction commune au trousseau et au fichier chiffré
+trait CredentialStore {
+    fn get(&self, account: &str) -> Result<Option<String>, String>;
+    fn set(&self, account: &str, secret: &str) -> Result<(), String>;
+    fn delete(&self, account: &str) -> Result<(), String>;
+}
+
+struct KeyringStore;
+
+impl KeyringStore {
+    fn entry(account: &str) -> Result<Entry, String> {
+        Entry::new(SERVICE_NAME, account).map_err(|e| format!("Keyring error: {}", e))
+    }
+}
+
+impl CredentialStore for KeyringStore {
+    fn get(&self, account: &str) -> Result<Option<String>, String> {
+        match Self::entry(account)?.get_password() {
+            Ok(secret) => Ok(Some(secret)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(format!("Keyring error: {}", e)),
+        }
+    }
+
+    fn set(&self, account: &str, secret: &str) -> Result<(), String> {
+        Self::entry(account)?
+            .set_password(secret)
+            .map_err(|e| format!("Failed to store credential in keyring: {}", e))
+    }
+
+    fn delete(&self, account: &str) -> Result<(), String> {
+        match Self::entry(account)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(format!("Keyring error: {}", e)),
+        }
+    }
+}
+
+/// Contenu chiffré de `credentials.json`
+#[derive(Debug, Serialize, Deserialize)]
+struct CredentialsFile {
+    version: u32,
+    nonce: String,
+    ciphertext: String,
+}
+
+struct EncryptedFileStore {
+    path: PathBuf,
+    key_path: PathBuf,
+}
+
+impl EncryptedFileStore {
+    fn new(dir: &Path) -> Self {
+        Self {
+            path: dir.join("credentials.json"),
+            key_path: dir.join("credentials.key"),
+        }
+    }
+
+    fn load(&self) -> Result<BTreeMap<String, String>, String> {
+        if !self.path.exists() {
+            return Ok(BTreeMap::new());
+        }
+        let content = std::fs::read_to_string(&self.path)
+            .map_err(|e| format!("Failed to read {:?}: {}", self.path, e))?;
+        let file: CredentialsFile = serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid credentials file: {}", e))?;
+        let key = std::fs::read_to_string(&self.key_path)
+            .map_err(|e| format!("Credentials file found but key is missing: {}", e))?;
+        let plaintext = open(key.trim(), &file)?;
+        serde_json::from_str(&plaintext).map_err(|e| format!("Invalid credentials file: {}", e))
+    }
+
+    fn save(&self, secrets: &BTreeMap<String, String>) -> Result<(), String> {
+        if secrets.is_empty() {
+            let _ = std::fs::remove_file(&self.path);
+            return Ok(());
+        }
+        let key = self.load_or_create_key()?;
+        let plaintext = serde_json::to_string(secrets).map_err(|e| e.to_string())?;
+        let content = serde_json::to_string_pretty(&seal(&key, &plaintext)?).map_err(|e| e.to_string())?;
+        super::write_atomic(&self.path, content.as_bytes())
+    }
+
+    fn load_or_create_key(&self) -> Result<String, String> {
+        if let Ok(key) = std::fs::read_to_string(&self.key_path) {
+            return Ok(key.trim().to_string());
+        }
+        let key = BASE64.encode(Aes256Gcm::generate_key(OsRng));
+        write_private_file(&self.key_path, key.as_bytes())?;
+        log::info!("[CREDENTIALS] New file key created at {:?}", self.key_path);
+        Ok(key)
+    }
+}
+
+impl CredentialStore for EncryptedFileStore {
+    fn get(&self, account: &str) -> Result<Option<String>, String> {
+        Ok(self.load()?.remove(account))
+    }
+
+    fn set(&self, account: &str, secret: &str) -> Result<(), String> {
+        let mut secrets = self.load()?;
+        secrets.insert(account.to_string(), secret.to_string());
+        self.save(&secrets)
+    }
+
+    fn delete(&self, account: &str) -> Result<(), String> {
+        let mut secrets = self.load()?;
+        if secrets.remove(account).is_some() {
+            self.save(&secrets)?;
+        }
+        Ok(())
+    }
+}
+
+fn file_store() -> EncryptedFileStore {
+    EncryptedFileStore::new(&super::get_app_data_dir())
+}
+
+/// Le trousseau répond-il ? (`NoEntry` signifie qu'il est joignable)
+fn keyring_error() -> Option<String> {
+    KeyringStore.get("__probe__").err()
+}
+
+/// Lit un secret : trousseau d'abord, puis fichier chiffré
+pub fn get(account: &str) -> Option<String> {
+    let _guard = super::lock_store(&CREDENTIALS_LOCK);
+    let from_keyring = KeyringStore.get(account).unwrap_or_else(|e| {
+        log::debug!("[CREDENTIALS] {}", e);
+        None
+    });
+    from_keyring
+        .or_else(|| match file_store().get(account) {
+            Ok(secret) => secret,
+            Err(e) => {
+                log::error!("[CREDENTIALS] {}", e);
+                None
+            }
+        })
+        .filter(|secret| !secret.is_empty())
+}
+
+/// Enregistre un secret dans le trousseau, ou dans le fichier chiffré s'il est indisponible
+pub fn set(account: &str, secret: &str) -> Result<CredentialBackend, String> {
+    let _guard = super::lock_store(&CREDENTIALS_LOCK);
+    let file = file_store();
+    match KeyringStore.set(account, secret) {
+        Ok(()) => {
+            // Une seule copie : on retire l'éventuel secret du repli
+            if let Err(e) = file.delete(account) {
+                log::warn!("[CREDENTIALS] Failed to remove file copy of '{}': {}", account, e);
+            }
+            Ok(CredentialBackend::Keyring)
+        }
+        Err(e) => {
+            log::warn!("[CREDENTIALS] {}; falling back to encrypted file", e);
+            file.set(account, secret)?;
+            Ok(CredentialBackend::EncryptedFile)
+        }
+    }
+}
+
+/// Supprime un secret des deux backends
+pub fn delete(account: &str) -> Result<(), String> {
+    let _guard = super::lock_store(&CREDENTIALS_LOCK);
+    let keyring_result = KeyringStore.delete(account);
+    file_store().delete(account)?;
+    if let Err(e) = keyring_result {
+        log::warn!("[CREDENTIALS] {}", e);
+    }
+    Ok(())
+}
+
+pub fn status() -> CredentialStoreStatus {
+    let keyring_error = keyring_error();
+    let backend = if keyring_error.is_none() {
+        CredentialBackend::Keyring
+    } else {
+        CredentialBackend::EncryptedFile
+    };
+
+    CredentialStoreStatus {
+        backend,
+        keyring_error,
+        groq_api_key_stored: get(GROQ_API_KEY).is_some(),
+    }
+}
+
+/// Déplace les secrets encore en clair dans `config.json` vers le stockage
+/// sécurisé, puis les efface du fichier. Appelé au démarrage.
+pub fn migrate_plaintext_settings() {
+    let mut settings = super::config::load_settings();
+    let Some(key) = settings.groq_api_key.take() else { return };
+
+    if !key.is_empty() {
+        match set(GROQ_API_KEY, &key) {
+            Ok(backend) => log::info!("[CREDENTIALS] Groq API key migrated from config.json ({:?})", backend),
+            Err(e) => {
+                log::error!("[CREDENTIALS] Failed to migrate Groq API key, keeping it in config.json: {}", e);
+                return;
+            }
+        }
+    }
+
+    if let Err(e) = super::config::save_settings(&settings) {
+        log::error!("[CREDENTIALS] Failed to scrub config.json: {}", e);
+    }
+}
+
+fn seal(key_b64: &str, plaintext: &str) -> Result<CredentialsFile, String> {
+    let cipher = cipher_from_b64(key_b64)?;
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_bytes())
+        .map_err(|_| "Encryption failed".to_string())?;
+    Ok(CredentialsFile {
+        version: FILE_VERSION,
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(ciphertext),
+    })
+}
+
+fn open(key_b64: &str, file: &CredentialsFile) -> Result<String, String> {
+    let cipher = cipher_from_b64(key_b64)?;
+    let nonce_bytes = BASE64.decode(&file.nonce).map_err(|e| e.to_string())?;
+    if nonce_bytes.len() != 12 {
+        return Err("Invalid nonce length".to_string());
+    }
+    let ciphertext = BASE64.decode(&file.ciphertext).map_err(|e| e.to_string())?;
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(&nonce_bytes), ciphertext.as_ref())
+        .map_err(|_| "Decryption failed (wrong key or tampered file)".to_string())?;
+    String::from_utf8(plaintext).map_err(|e| e.to_string())
+}
+
+fn cipher_from_b64(key_b64: &str) -> Result<Aes256Gcm, String> {
+    let bytes = BASE64.decode(key_b64).map_err(|e| format!("Invalid credentials key: {}", e))?;
+    if bytes.len() != 32 {
+        return Err("Invalid credentials key length".to_string());
+    }
+    Ok(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&bytes)))
+}
+
+/// Crée un fichier lisible uniquement par l'utilisateur courant
+fn write_private_file(path: &Path, content: &[u8]) -> Result<(), String> {
+    use std::io::Write;
+
+    super::ensure_app_data_dir().map_err(|e| e.to_string())?;
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(path)
+        .map_err(|e| format!("Failed to create {:?}: {}", path, e))?;
+    file.write_all(content)
+        .and_then(|_| file.sync_all())
+        .map_err(|e| format!("Failed to write {:?}: {}", path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_store(name: &str) -> EncryptedFileStore {
+        let dir = std::env::temp_dir().join(format!("phonon-credentials-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        EncryptedFileStore::new(&dir)
+    }
+
+    #[test]
+    fn test_encrypted_file_store_roundtrip() {
+        let store = temp_store("roundtrip");
+        store.set(GROQ_API_KEY, "gsk_secret").unwrap();
+        store.set("openai_compatible:local", "sk-other").unwrap();
+
+        assert_eq!(store.get(GROQ_API_KEY).unwrap().as_deref(), Some("gsk_secret"));
+        let content = std::fs::read_to_string(&store.path).unwrap();
+        assert!(!content.contains("gsk_secret"), "secret must not be stored in clear");
+
+        store.delete(GROQ_API_KEY).unwrap();
+        assert_eq!(store.get(GROQ_API_KEY).unwrap(), None);
+        assert_eq!(store.get("openai_compatible:local").unwrap().as_deref(), Some("sk-other"));
+    }
+
+    #[test]
+    fn test_encrypted_file_rejects_wrong_key() {
+        let store = temp_store("wrong-key");
+        store.set(GROQ_API_KEY, "gsk_secret").unwrap();
+        std::fs::write(&store.key_path, BASE64.encode([7u8; 32])).unwrap();
+
+        assert!(store.get(GROQ_API_KEY).is_err());
+    }
+}
@@ -0,0 +1,436 @@
+//! Chiffrement au repos des stores JSON (historique, snippets, logs, ...)
+//!
+//! Les fichiers chiffrés sont des enveloppes JSON AES-256-GCM. La clé de
+//! données est conservée dans le trousseau de l'OS (crate `keyring`), jamais
+//! sur disque. `config.json` reste en clair : il contient l'état
+//! d'activation du chiffrement lui-même.
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use keyring::Entry;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+const SERVICE_NAME: &str = "wakascribe";
+const ACCOUNT_NAME: &str = "storage_data_key";
+const ENVELOPE_VERSION: u32 = 1;
+
+/// Chiffrement activé pour les prochaines écritures (miroir de `AppSettings`)
+static ENCRYPTION_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Cache du jeu de clés pour éviter un accès trousseau à chaque écriture
+static KEY_SET: Mutex<Option<KeySet>> = Mutex::new(None);
+
+/// Enveloppe écrite sur disque à la place du JSON en clair
+#[derive(Debug, Serialize, Deserialize)]
+struct EncryptedEnvelope {
+    phonon_encrypted: u32,
+    key_id: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// Clés de données stockées dans le trousseau.
+/// Les anciennes clés sont conservées pendant une rotation pour pouvoir
+/// relire un fichier pas encore ré-chiffré si l'app est interrompue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct KeySet {
+    current: String,
+    keys: BTreeMap<String, String>,
+}
+
+/// État du chiffrement exposé à l'interface
+#[derive(Debug, Clone, Serialize)]
+pub struct EncryptionStatus {
+    pub enabled: bool,
+    pub key_available: bool,
+    pub key_id: Option<String>,
+    pub encrypted_files: Vec<String>,
+    pub plaintext_files: Vec<String>,
+    /// Fichiers chiffrés qu'aucune clé disponible ne permet de relire
+    pub unreadable_files: Vec<String>,
+}
+
+/// Échec de lecture d'un store
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum DecodeError {
+    /// Enveloppe invalide : le fichier est corrompu
+    Corrupt(String),
+    /// Enveloppe valide mais clé indisponible (trousseau verrouillé, clé
+    /// absente ou remplacée) : le fichier est intact et doit être conservé
+    Undecryptable(String),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Corrupt(e) => write!(f, "{}", e),
+            DecodeError::Undecryptable(e) => write!(f, "Cannot decrypt: {}", e),
+        }
+    }
+}
+
+/// Accès au jeu de clés : trousseau de l'OS, ou clé injectée dans les tests
+trait KeyStore {
+    fn load(&self) -> Result<Option<KeySet>, String>;
+    fn save(&self, key_set: &KeySet) -> Result<(), String>;
+}
+
+/// Trousseau de l'OS, avec cache pour éviter un accès à chaque écriture
+struct KeyringKeyStore;
+
+impl KeyringKeyStore {
+    fn entry() -> Result<Entry, String> {
+        Entry::new(SERVICE_NAME, ACCOUNT_NAME).map_err(|e| format!("Keyring error: {}", e))
+    }
+}
+
+impl KeyStore for KeyringKeyStore {
+    fn load(&self) -> Result<Option<KeySet>, String> {
+        if let Ok(guard) = KEY_SET.lock() {
+            if let Some(ref key_set) = *guard {
+                return Ok(Some(key_set.clone()));
+            }
+        }
+
+        match Self::entry()?.get_password() {
+            Ok(raw) => {
+                let key_set: KeySet = serde_json::from_str(&raw)
+                    .map_err(|e| format!("Invalid key set in keyring: {}", e))?;
+                if let Ok(mut guard) = KEY_SET.lock() {
+                    *guard = Some(key_set.clone());
+                }
+                Ok(Some(key_set))
+            }
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(format!("Keyring error: {}", e)),
+        }
+    }
+
+    fn save(&self, key_set: &KeySet) -> Result<(), String> {
+        let raw = serde_json::to_string(key_set).map_err(|e| e.to_string())?;
+        Self::entry()?
+            .set_password(&raw)
+            .map_err(|e| format!("Failed to store data key in keyring: {}", e))?;
+        if let Ok(mut guard) = KEY_SET.lock() {
+            *guard = Some(key_set.clone());
+        }
+        Ok(())
+    }
+}
+
+pub fn is_enabled() -> bool {
+    ENCRYPTION_ENABLED.load(Ordering::SeqCst)
+}
+
+/// Initialise l'état depuis les settings au démarrage (sans migration)
+pub fn init(enabled: bool) {
+    ENCRYPTION_ENABLED.store(enabled, Ordering::SeqCst);
+}
+
+/// Active ou désactive le chiffrement et migre les fichiers existants.
+/// À l'activation, les fichiers en clair sont chiffrés ; à la désactivation,
+/// ils sont ré-écrits en clair.
+pub fn set_enabled(enabled: bool) -> Result<(), String> {
+    if enabled {
+        // Crée la clé si besoin avant de basculer, pour échouer tôt sans trousseau
+        load_or_create_key_set(&KeyringKeyStore)?;
+    }
+    ENCRYPTION_ENABLED.store(enabled, Ordering::SeqCst);
+    migrate_all()
+}
+
+/// Génère une nouvelle clé de données et ré-chiffre tous les stores avec.
+pub fn rotate_key() -> Result<String, String> {
+    if !is_enabled() {
+        return Err("Storage encryption is not enabled".to_string());
+    }
+    rotate_stores(&KeyringKeyStore, &super::private_stores())
+}
+
+fn rotate_stores(keys: &dyn KeyStore, stores: &[(PathBuf, &'static Mutex<()>)]) -> Result<String, String> {
+    let mut key_set = load_or_create_key_set(keys)?;
+    let mut new_id = new_key_id();
+    // Deux rotations dans la même milliseconde ne doivent pas écraser une clé encore utilisée
+    while key_set.keys.contains_key(&new_id) {
+        new_id.push('b');
+    }
+    key_set.keys.insert(new_id.clone(), BASE64.encode(Aes256Gcm::generate_key(OsRng)));
+    key_set.current = new_id.clone();
+    keys.save(&key_set)?;
+
+    migrate_stores(keys, true, stores)?;
+
+    // Tous les fichiers utilisent la nouvelle clé : on peut oublier les anciennes
+    key_set.keys.retain(|id, _| *id == new_id);
+    keys.save(&key_set)?;
+
+    log::info!("[ENCRYPTION] Data key rotated to {}", new_id);
+    Ok(new_id)
+}
+
+pub fn status() -> EncryptionStatus {
+    let key_set = KeyringKeyStore.load().ok().flatten();
+    let mut encrypted_files = Vec::new();
+    let mut plaintext_files = Vec::new();
+
+    for (path, _) in super::private_stores() {
+        if let Ok(content) = std::fs::read_to_string(&path) {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            if is_envelope(&content) {
+                encrypted_files.push(name);
+            } else {
+                plaintext_files.push(name);
+            }
+        }
+    }
+
+    EncryptionStatus {
+        enabled: is_enabled(),
+        key_available: key_set.is_some(),
+        key_id: key_set.map(|k| k.current),
+        encrypted_files,
+        plaintext_files,
+        unreadable_files: super::unreadable_stores(),
+    }
+}
+
+/// Retourne le JSON en clair d'un fichier, qu'il soit chiffré ou non
+pub(crate) fn decode(content: &str) -> Result<String, DecodeError> {
+    decode_with(&KeyringKeyStore, content)
+}
+
+fn decode_with(keys: &dyn KeyStore, content: &str) -> Result<String, DecodeError> {
+    if !is_envelope(content) {
+        return Ok(content.to_string());
+    }
+
+    let corrupt = |e: String| DecodeError::Corrupt(e);
+    let envelope: EncryptedEnvelope = serde_json::from_str(content)
+        .map_err(|e| corrupt(format!("Invalid encrypted envelope: {}", e)))?;
+    let nonce_bytes = BASE64.decode(&envelope.nonce).map_err(|e| corrupt(e.to_string()))?;
+    if nonce_bytes.len() != 12 {
+        return Err(corrupt("Invalid nonce length".to_string()));
+    }
+    let ciphertext = BASE64.decode(&envelope.ciphertext).map_err(|e| corrupt(e.to_string()))?;
+
+    let key_set = keys
+        .load()
+        .map_err(DecodeError::Undecryptable)?
+        .ok_or_else(|| DecodeError::Undecryptable("Encrypted file found but no data key in keyring".to_string()))?;
+    let key_b64 = key_set
+        .keys
+        .get(&envelope.key_id)
+        .ok_or_else(|| DecodeError::Undecryptable(format!("Unknown data key '{}'", envelope.key_id)))?;
+    let cipher = cipher_from_b64(key_b64).map_err(DecodeError::Undecryptable)?;
+
+    // Échec d'authentification : mauvaise clé aussi bien que fichier altéré,
+    // on conserve le fichier dans le doute
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(&nonce_bytes), ciphertext.as_ref())
+        .map_err(|_| DecodeError::Undecryptable("Decryption failed (wrong key or tampered file)".to_string()))?;
+
+    String::from_utf8(plaintext).map_err(|e| corrupt(e.to_string()))
+}
+
+/// Chiffre le JSON si le chiffrement est activé, sinon le retourne tel quel
+pub(crate) fn encode(plaintext: &str) -> Result<String, String> {
+    encode_with(&KeyringKeyStore, is_enabled(), plaintext)
+}
+
+fn encode_with(keys: &dyn KeyStore, enabled: bool, plaintext: &str) -> Result<String, String> {
+    if !enabled {
+        return Ok(plaintext.to_string());
+    }
+
+    let key_set = load_or_create_key_set(keys)?;
+    let key_b64 = key_set
+        .keys
+        .get(&key_set.current)
+        .ok_or_else(|| "Current data key missing from key set".to_string())?;
+    let cipher = cipher_from_b64(key_b64)?;
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_bytes())
+        .map_err(|_| "Encryption failed".to_string())?;
+
+    let envelope = EncryptedEnvelope {
+        phonon_encrypted: ENVELOPE_VERSION,
+        key_id: key_set.current.clone(),
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(ciphertext),
+    };
+    serde_json::to_string(&envelope).map_err(|e| e.to_string())
+}
+
+fn is_envelope(content: &str) -> bool {
+    content.trim_start().starts_with("{\"phonon_encrypted\"")
+}
+
+/// Ré-écrit chaque store privé avec l'état de chiffrement et la clé courants
+fn migrate_all() -> Result<(), String> {
+    migrate_stores(&KeyringKeyStore, is_enabled(), &super::private_stores())
+}
+
+fn migrate_stores(keys: &dyn KeyStore, enabled: bool, stores: &[(PathBuf, &'static Mutex<()>)]) -> Result<(), String> {
+    for (path, lock) in stores {
+        let _guard = super::lock_store(lock);
+        migrate_file(keys, enabled, path)?;
+    }
+    Ok(())
+}
+
+fn migrate_file(keys: &dyn KeyStore, enabled: bool, path: &PathBuf) -> Result<(), String> {
+    if !path.exists() {
+        return Ok(());
+    }
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    let plaintext = decode_with(keys, &content).map_err(|e| format!("{:?}: {}", path, e))?;
+    let output = encode_with(keys, enabled, &plaintext)?;
+    if output != content {
+        super::write_atomic(path, output.as_bytes())?;
+        log::info!("[ENCRYPTION] Migrated {:?} (encrypted: {})", path, enabled);
+    }
+    Ok(())
+}
+
+fn cipher_from_b64(key_b64: &str) -> Result<Aes256Gcm, String> {
+    let bytes = BASE64.decode(key_b64).map_err(|e| format!("Invalid data key: {}", e))?;
+    if bytes.len() != 32 {
+        return Err("Invalid data key length".to_string());
+    }
+    Ok(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&bytes)))
+}
+
+fn new_key_id() -> String {
+    format!("k{}", chrono::Utc::now().format("%Y%m%d%H%M%S%3f"))
+}
+
+fn load_or_create_key_set(keys: &dyn KeyStore) -> Result<KeySet, String> {
+    if let Some(key_set) = keys.load()? {
+        return Ok(key_set);
+    }
+
+    let id = new_key_id();
+    let mut key_map = BTreeMap::new();
+    key_map.insert(id.clone(), BASE64.encode(Aes256Gcm::generate_key(OsRng)));
+    let key_set = KeySet { current: id, keys: key_map };
+    keys.save(&key_set)?;
+    log::info!("[ENCRYPTION] New data key created in keyring");
+    Ok(key_set)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Jeu de clés en mémoire à la place du trousseau
+    #[derive(Default)]
+    struct TestKeys(Mutex<Option<KeySet>>);
+
+    impl KeyStore for TestKeys {
+        fn load(&self) -> Result<Option<KeySet>, String> {
+            Ok(self.0.lock().unwrap().clone())
+        }
+
+        fn save(&self, key_set: &KeySet) -> Result<(), String> {
+            *self.0.lock().unwrap() = Some(key_set.clone());
+            Ok(())
+        }
+    }
+
+    static LOCK_A: Mutex<()> = Mutex::new(());
+    static LOCK_B: Mutex<()> = Mutex::new(());
+
+    fn temp_stores(name: &str) -> Vec<(PathBuf, &'static Mutex<()>)> {
+        let dir = std::env::temp_dir().join(format!("phonon-encryption-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        vec![(dir.join("history.json"), &LOCK_A), (dir.join("snippets.json"), &LOCK_B)]
+    }
+
+    fn key_id(content: &str) -> String {
+        serde_json::from_str::<EncryptedEnvelope>(content).unwrap().key_id
+    }
+
+    #[test]
+    fn test_encode_decode_roundtrip() {
+        let keys = TestKeys::default();
+        let plaintext = r#"{"transcriptions":["bonjour"]}"#;
+
+        let encrypted = encode_with(&keys, true, plaintext).unwrap();
+        assert!(is_envelope(&encrypted));
+        assert!(!encrypted.contains("bonjour"));
+        assert_eq!(decode_with(&keys, &encrypted).unwrap(), plaintext);
+
+        assert_eq!(encode_with(&keys, false, plaintext).unwrap(), plaintext);
+
+        let other_keys = TestKeys::default();
+        encode_with(&other_keys, true, "{}").unwrap();
+        assert!(matches!(decode_with(&other_keys, &encrypted), Err(DecodeError::Undecryptable(_))));
+        assert!(matches!(decode_with(&TestKeys::default(), &encrypted), Err(DecodeError::Undecryptable(_))));
+    }
+
+    #[test]
+    fn test_plaintext_file_readable_while_enabled() {
+        let keys = TestKeys::default();
+        encode_with(&keys, true, "{}").unwrap();
+        let plaintext = r#"{"snippets":[]}"#;
+        assert_eq!(decode_with(&keys, plaintext).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn test_migrate_stores_both_directions() {
+        let keys = TestKeys::default();
+        let stores = temp_stores("migrate");
+        let contents = [r#"{"a":1}"#, r#"{"b":2}"#];
+        for ((path, _), content) in stores.iter().zip(contents) {
+            std::fs::write(path, content).unwrap();
+        }
+
+        migrate_stores(&keys, true, &stores).unwrap();
+        for ((path, _), content) in stores.iter().zip(contents) {
+            let encrypted = std::fs::read_to_string(path).unwrap();
+            assert!(is_envelope(&encrypted));
+            assert_eq!(decode_with(&keys, &encrypted).unwrap(), content);
+        }
+
+        migrate_stores(&keys, false, &stores).unwrap();
+        for ((path, _), content) in stores.iter().zip(contents) {
+            assert_eq!(std::fs::read_to_string(path).unwrap(), content);
+        }
+    }
+
+    #[test]
+    fn test_rotate_key_reencrypts_stores() {
+        let keys = TestKeys::default();
+        let stores = temp_stores("rotate");
+        for (path, _) in &stores {
+            std::fs::write(path, encode_with(&keys, true, r#"{"x":1}"#).unwrap()).unwrap();
+        }
+        let old_id = keys.load().unwrap().unwrap().current;
+
+        let new_id = rotate_stores(&keys, &stores).unwrap();
+        assert_ne!(new_id, old_id);
+
+        let key_set = keys.load().unwrap().unwrap();
+        assert_eq!(key_set.current, new_id);
+        assert_eq!(key_set.keys.len(), 1, "old key must be dropped once every store is migrated");
+        for (path, _) in &stores {
+            let content = std::fs::read_to_string(path).unwrap();
+            assert_eq!(key_id(&content), new_id);
+            assert_eq!(decode_with(&keys, &content).unwrap(), r#"{"x":1}"#);
+        }
+    }
+}
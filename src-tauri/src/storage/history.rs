@@ -1,35 +1,40 @@
 use crate::types::{HistoryData, TranscriptionResult};
-use std::fs;
 use std::path::PathBuf;
+use std::sync::Mutex;
 
 const MAX_HISTORY: usize = 50;
 
-fn history_path() -> PathBuf {
+pub(super) static HISTORY_LOCK: Mutex<()> = Mutex::new(());
+
+pub(super) fn history_path() -> PathBuf {
     super::get_app_data_dir().join("history.json")
 }
 
 pub fn load_history() -> HistoryData {
-    let path = history_path();
-    if path.exists() {
-        let content = fs::read_to_string(&path).unwrap_or_default();
-        serde_json::from_str(&content).unwrap_or_default()
-    } else {
-        HistoryData::default()
-    }
+    super::read_json(&history_path())
+}
+
+/// Comme `load_history`, en erreur si l'historique chiffré ne peut pas être relu
+pub fn try_load_history() -> Result<HistoryData, String> {
+    super::read_json_checked(&history_path())
 }
 
 pub fn save_history(data: &HistoryData) -> Result<(), String> {
-    super::ensure_app_data_dir().map_err(|e| e.to_string())?;
-    let path = history_path();
-    let content = serde_json::to_string_pretty(data).map_err(|e| e.to_string())?;
-    fs::write(&path, content).map_err(|e| e.to_string())
+    let _guard = super::lock_store(&HISTORY_LOCK);
+    super::write_private_json(&history_path(), data)
 }
 
-pub fn add_transcription(result: TranscriptionResult) -> Result<(), String> {
+pub fn add_transcription(mut result: TranscriptionResult) -> Result<(), String> {
+    if crate::privacy::is_enabled() {
+        return Ok(());
+    }
+    // Les horodatages par token alourdiraient l'historique sans être affichés
+    result.tokens.clear();
+    let _guard = super::lock_store(&HISTORY_LOCK);
     let mut data = load_history();
     data.transcriptions.insert(0, result);
     data.transcriptions.truncate(MAX_HISTORY);
-    save_history(&data)
+    super::write_private_json(&history_path(), &data)
 }
 
 pub fn clear_history() -> Result<(), String> {
@@ -0,0 +1,360 @@
+//! Configuration d'équipe gérée par l'administrateur (lecture seule)
+//!
+//! Fichier JSON système (`/etc/phonon/managed.json` sous Linux, voir
+//! `managed_config_path`) lu une fois au démarrage :
+//!
+//! ```json
+//! {
+//!   "settings": { "privacy_mode": true, "transcription_language": "fr" },
+//!   "locked": ["privacy_mode"],
+//!   "allowed_engines": ["whisper", "parakeet"],
+//!   "groq_disabled": true,
+//!   "dictionary": ["Wakastellar"],
+//!   "snippets": [{ "id": "adresse", "name": "Adresse", "trigger": "adresse", "content": "..." }]
+//! }
+//! ```
+//!
+//! `settings` sert de valeurs par défaut sous `config.json` ; les champs de
+//! `locked` y sont imposés. Le dictionnaire et les snippets d'équipe
+//! s'ajoutent à ceux de l'utilisateur sans être écrits dans ses fichiers.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use super::config::{self, SettingsIssue, SETTINGS_SCHEMA_VERSION};
+use crate::types::{AppSettings, EngineType, LlmProvider, Snippet};
+
+/// Préfixe des identifiants de snippets d'équipe (non modifiables)
+const TEAM_SNIPPET_PREFIX: &str = "team:";
+
+static MANAGED: OnceLock<ManagedConfig> = OnceLock::new();
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct ManagedConfig {
+    /// Valeurs par défaut, sous les réglages de l'utilisateur
+    settings: Map<String, Value>,
+    /// Champs de `settings` imposés à l'utilisateur
+    locked: Vec<String>,
+    /// Moteurs autorisés ; vide : tous
+    allowed_engines: Vec<EngineType>,
+    /// Désactive le fournisseur cloud Groq (clé API, LLM, traduction)
+    groq_disabled: bool,
+    dictionary: Vec<String>,
+    snippets: Vec<Snippet>,
+    /// Fichier d'origine, renseigné au chargement
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+/// Réglages gérés exposés à l'interface
+#[derive(Debug, Clone, Serialize)]
+pub struct ManagedStatus {
+    pub active: bool,
+    pub path: Option<String>,
+    /// Champs dont la valeur par défaut vient de l'équipe
+    pub defaulted_fields: Vec<String>,
+    /// Champs imposés, non modifiables
+    pub locked_fields: Vec<String>,
+    pub allowed_engines: Vec<EngineType>,
+    pub groq_disabled: bool,
+    pub dictionary: Vec<String>,
+    pub snippet_ids: Vec<String>,
+}
+
+fn managed_config_path() -> PathBuf {
+    if let Some(path) = std::env::var_os("PHONON_MANAGED_CONFIG") {
+        return PathBuf::from(path);
+    }
+    #[cfg(target_os = "macos")]
+    {
+        PathBuf::from("/Library/Application Support/Phonon/managed.json")
+    }
+    #[cfg(target_os = "windows")]
+    {
+        let program_data = std::env::var_os("ProgramData").unwrap_or_else(|| "C:\\ProgramData".into());
+        PathBuf::from(program_data).join("Phonon").join("managed.json")
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        PathBuf::from("/etc/phonon/managed.json")
+    }
+}
+
+fn managed() -> &'static ManagedConfig {
+    MANAGED.get_or_init(|| {
+        let path = managed_config_path();
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return ManagedConfig::default(),
+            Err(e) => {
+                log::error!("[MANAGED] Failed to read {:?}: {}", path, e);
+                return ManagedConfig::default();
+            }
+        };
+        match serde_json::from_str::<ManagedConfig>(&content) {
+            Ok(mut managed) => {
+                for issue in managed.prune_invalid() {
+                    log::error!("[MANAGED] {:?}: ignoring {}", path, issue);
+                }
+                log::info!(
+                    "[MANAGED] Team configuration loaded from {:?} ({} default(s), {} locked)",
+                    path,
+                    managed.settings.len(),
+                    managed.locked.len()
+                );
+                managed.path = Some(path);
+                managed
+            }
+            Err(e) => {
+                log::error!("[MANAGED] Invalid team configuration {:?}: {}", path, e);
+                ManagedConfig::default()
+            }
+        }
+    })
+}
+
+impl ManagedConfig {
+    /// Retire les valeurs invalides et les verrous sans valeur
+    fn prune_invalid(&mut self) -> Vec<SettingsIssue> {
+        let mut candidate = self.settings.clone();
+        candidate.insert("schema_version".to_string(), Value::from(SETTINGS_SCHEMA_VERSION));
+        let (_, issues) = config::parse_settings(Value::Object(candidate));
+        for issue in &issues {
+            let field = issue.field.split('[').next().unwrap_or(&issue.field);
+            self.settings.remove(field);
+        }
+
+        let settings = &self.settings;
+        let mut issues = issues;
+        self.locked.retain(|field| {
+            let known = settings.contains_key(field);
+            if !known {
+                issues.push(SettingsIssue {
+                    field: field.clone(),
+                    message: "locked without a value in \"settings\"".to_string(),
+                });
+            }
+            known
+        });
+        issues
+    }
+
+    /// Valeurs par défaut de l'équipe, puis réglages utilisateur, puis champs verrouillés
+    fn overlay(&self, user: Value) -> Value {
+        let mut merged = self.settings.clone();
+        if let Value::Object(user) = user {
+            merged.extend(user);
+        }
+        for field in &self.locked {
+            if let Some(value) = self.settings.get(field) {
+                merged.insert(field.clone(), value.clone());
+            }
+        }
+        Value::Object(merged)
+    }
+
+    /// Champs à ne pas écrire dans `config.json` : verrouillés, ou égaux à la
+    /// valeur de l'équipe (qui pourra ainsi évoluer)
+    fn strip(&self, user: &mut Map<String, Value>) {
+        for (field, value) in &self.settings {
+            if self.locked.contains(field) || user.get(field) == Some(value) {
+                user.remove(field);
+            }
+        }
+    }
+
+    fn check(&self, settings: &AppSettings) -> Vec<SettingsIssue> {
+        let mut issues = Vec::new();
+        if let Ok(Value::Object(map)) = serde_json::to_value(settings) {
+            for field in &self.locked {
+                if map.get(field) != self.settings.get(field) {
+                    issues.push(SettingsIssue {
+                        field: field.clone(),
+                        message: "managed by your organization".to_string(),
+                    });
+                }
+            }
+        }
+        if !self.allowed_engines.is_empty() && !self.allowed_engines.contains(&settings.engine_type) {
+            issues.push(SettingsIssue {
+                field: "engine_type".to_string(),
+                message: format!("{:?} is not allowed by your organization", settings.engine_type),
+            });
+        }
+        if self.groq_disabled && settings.llm_provider == LlmProvider::Groq {
+            issues.push(SettingsIssue {
+                field: "llm_provider".to_string(),
+                message: "Groq is disabled by your organization".to_string(),
+            });
+        }
+        issues
+    }
+
+    fn enforce(&self, settings: &mut AppSettings) -> Vec<SettingsIssue> {
+        let issues = self.check(settings);
+        if issues.is_empty() {
+            return issues;
+        }
+
+        if !self.locked.is_empty() {
+            if let Ok(user) = serde_json::to_value(&*settings) {
+                let locked: Map<String, Value> = self.locked.iter()
+                    .filter_map(|field| self.settings.get(field).map(|v| (field.clone(), v.clone())))
+                    .collect();
+                let mut merged = user;
+                if let Value::Object(map) = &mut merged {
+                    map.extend(locked);
+                }
+                match serde_json::from_value(merged) {
+                    Ok(enforced) => *settings = enforced,
+                    Err(e) => log::error!("[MANAGED] Failed to apply locked settings: {}", e),
+                }
+            }
+        }
+        if let Some(engine) = self.allowed_engines.first() {
+            if !self.allowed_engines.contains(&settings.engine_type) {
+                settings.engine_type = *engine;
+            }
+        }
+        if self.groq_disabled && settings.llm_provider == LlmProvider::Groq {
+            settings.llm_provider = LlmProvider::Local;
+        }
+        issues
+    }
+}
+
+/// Superpose la configuration d'équipe au JSON de `config.json` (`Null` si absent)
+pub fn overlay(user: Value) -> Value {
+    let managed = managed();
+    if managed.path.is_none() {
+        return user;
+    }
+    managed.overlay(user)
+}
+
+/// Retire du JSON à sauvegarder ce qui vient de la configuration d'équipe
+pub fn strip(user: &mut Map<String, Value>) {
+    managed().strip(user)
+}
+
+/// Champs modifiés en violation de la configuration d'équipe
+pub fn check(settings: &AppSettings) -> Vec<SettingsIssue> {
+    managed().check(settings)
+}
+
+/// Rétablit les champs verrouillés, le moteur et le fournisseur autorisés
+pub fn enforce(settings: &mut AppSettings) -> Vec<SettingsIssue> {
+    managed().enforce(settings)
+}
+
+/// Moteur autorisé par la configuration d'équipe (tous si aucune liste)
+pub fn is_engine_allowed(engine_type: EngineType) -> bool {
+    let allowed = &managed().allowed_engines;
+    allowed.is_empty() || allowed.contains(&engine_type)
+}
+
+pub fn is_groq_disabled() -> bool {
+    managed().groq_disabled
+}
+
+/// Mots du dictionnaire d'équipe
+pub fn dictionary() -> &'static [String] {
+    &managed().dictionary
+}
+
+/// Snippets d'équipe, avec un identifiant réservé
+pub fn snippets() -> Vec<Snippet> {
+    managed()
+        .snippets
+        .iter()
+        .map(|snippet| Snippet {
+            id: format!("{}{}", TEAM_SNIPPET_PREFIX, snippet.id),
+            ..snippet.clone()
+        })
+        .collect()
+}
+
+pub fn is_team_snippet(id: &str) -> bool {
+    id.starts_with(TEAM_SNIPPET_PREFIX)
+}
+
+pub fn status() -> ManagedStatus {
+    let managed = managed();
+    ManagedStatus {
+        active: managed.path.is_some(),
+        path: managed.path.as_ref().map(|p| p.to_string_lossy().to_string()),
+        defaulted_fields: managed.settings.keys().cloned().collect(),
+        locked_fields: managed.locked.clone(),
+        allowed_engines: managed.allowed_engines.clone(),
+        groq_disabled: managed.groq_disabled,
+        dictionary: managed.dictionary.clone(),
+        snippet_ids: snippets().into_iter().map(|s| s.id).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn team() -> ManagedConfig {
+        let mut managed: ManagedConfig = serde_json::from_value(json!({
+            "settings": {
+                "privacy_mode": true,
+                "transcription_language": "de",
+                "subtitles_font_size": "huge",
+            },
+            "locked": ["privacy_mode", "theme"],
+            "allowed_engines": ["parakeet"],
+            "groq_disabled": true,
+        }))
+        .unwrap();
+        let issues = managed.prune_invalid();
+        assert_eq!(issues.len(), 2, "{:?}", issues);
+        managed
+    }
+
+    #[test]
+    fn test_overlay_defaults_user_then_locked() {
+        let managed = team();
+        let merged = managed.overlay(json!({ "privacy_mode": false, "transcription_language": "en" }));
+        assert_eq!(merged["privacy_mode"], json!(true));
+        assert_eq!(merged["transcription_language"], json!("en"));
+
+        let merged = managed.overlay(Value::Null);
+        assert_eq!(merged["transcription_language"], json!("de"));
+    }
+
+    #[test]
+    fn test_strip_keeps_only_user_choices() {
+        let managed = team();
+        let Value::Object(mut user) = json!({
+            "privacy_mode": true,
+            "transcription_language": "de",
+            "theme": "dark",
+        }) else { panic!() };
+        managed.strip(&mut user);
+        assert_eq!(Value::Object(user), json!({ "theme": "dark" }));
+    }
+
+    #[test]
+    fn test_enforce_locked_engine_and_provider() {
+        let managed = team();
+        let mut settings = AppSettings {
+            privacy_mode: false,
+            engine_type: EngineType::Whisper,
+            llm_provider: LlmProvider::Groq,
+            ..AppSettings::default()
+        };
+        assert_eq!(managed.check(&settings).len(), 3);
+
+        managed.enforce(&mut settings);
+        assert!(settings.privacy_mode);
+        assert_eq!(settings.engine_type, EngineType::Parakeet);
+        assert_eq!(settings.llm_provider, LlmProvider::Local);
+        assert!(managed.check(&settings).is_empty());
+    }
+}
@@ -1,14 +1,40 @@
 pub mod app_log;
 pub mod config;
+pub mod credentials;
 pub mod dictionary;
+pub mod encryption;
 pub mod history;
+pub mod managed;
+pub mod settings_export;
 pub mod snippets;
 pub mod stats;
 pub mod translation_history;
 
-use std::path::PathBuf;
+use encryption::DecodeError;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+/// Stores chiffrés qu'aucune clé disponible ne permet de relire. Ils restent
+/// en place et ne sont plus écrits tant qu'une lecture n'a pas réussi, pour
+/// ne pas remplacer les données par un store vide.
+static UNREADABLE_STORES: Mutex<BTreeSet<PathBuf>> = Mutex::new(BTreeSet::new());
+
+#[cfg(test)]
+thread_local! {
+    /// Tests : dossier de données redirigé vers un répertoire temporaire
+    static DATA_DIR_OVERRIDE: std::cell::RefCell<Option<PathBuf>> = const { std::cell::RefCell::new(None) };
+}
 
 pub fn get_app_data_dir() -> PathBuf {
+    #[cfg(test)]
+    if let Some(dir) = DATA_DIR_OVERRIDE.with(|d| d.borrow().clone()) {
+        return dir;
+    }
     let base = dirs::data_dir().unwrap_or_else(|| PathBuf::from("."));
     base.join("com.wakastellar.wakascribe")
 }
@@ -18,3 +44,250 @@ pub fn ensure_app_data_dir() -> std::io::Result<PathBuf> {
     std::fs::create_dir_all(&dir)?;
     Ok(dir)
 }
+
+/// Verrouille un store pour toute la durée d'un load-modify-save.
+/// Un verrou empoisonné (panic dans un autre thread) est récupéré : le fichier
+/// sur disque reste cohérent grâce à l'écriture atomique.
+pub(crate) fn lock_store(lock: &'static Mutex<()>) -> MutexGuard<'static, ()> {
+    lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Charge un fichier JSON, chiffré ou non.
+/// - Fichier absent : valeur par défaut.
+/// - Fichier illisible ou corrompu : il est renommé en `<nom>.corrupt-<timestamp>`
+///   avant de retourner la valeur par défaut, pour ne jamais écraser les données
+///   de l'utilisateur à la prochaine sauvegarde.
+/// - Fichier chiffré sans clé disponible : il reste en place, la valeur par
+///   défaut est retournée et les écritures sont refusées (voir `unreadable_error`).
+pub(crate) fn read_json<T: DeserializeOwned + Default>(path: &Path) -> T {
+    read_json_with(path, encryption::decode)
+}
+
+fn read_json_with<T: DeserializeOwned + Default>(
+    path: &Path,
+    decode: impl Fn(&str) -> Result<String, DecodeError>,
+) -> T {
+    if !path.exists() {
+        return T::default();
+    }
+
+    let parsed = fs::read_to_string(path)
+        .map_err(|e| DecodeError::Corrupt(e.to_string()))
+        .and_then(|content| decode(&content))
+        .and_then(|content| serde_json::from_str(&content).map_err(|e| DecodeError::Corrupt(e.to_string())));
+
+    match parsed {
+        Ok(data) => {
+            set_unreadable(path, false);
+            data
+        }
+        Err(DecodeError::Undecryptable(e)) => {
+            log::error!("[STORAGE] Cannot decrypt {:?}: {}. File kept, writes suspended", path, e);
+            set_unreadable(path, true);
+            T::default()
+        }
+        Err(DecodeError::Corrupt(e)) => {
+            log::error!("[STORAGE] Failed to parse {:?}: {}", path, e);
+            backup_corrupt_file(path);
+            set_unreadable(path, false);
+            T::default()
+        }
+    }
+}
+
+/// Comme `read_json`, mais en erreur si le fichier ne peut pas être déchiffré
+pub(crate) fn read_json_checked<T: DeserializeOwned + Default>(path: &Path) -> Result<T, String> {
+    let data = read_json(path);
+    match unreadable_error(path) {
+        Some(e) => Err(e),
+        None => Ok(data),
+    }
+}
+
+fn set_unreadable(path: &Path, unreadable: bool) {
+    let mut stores = UNREADABLE_STORES.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if unreadable {
+        stores.insert(path.to_path_buf());
+    } else {
+        stores.remove(path);
+    }
+}
+
+/// Erreur à remonter à l'interface si le dernier chargement de `path` n'a pas pu le déchiffrer
+pub(crate) fn unreadable_error(path: &Path) -> Option<String> {
+    let stores = UNREADABLE_STORES.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    stores.contains(path).then(|| {
+        format!(
+            "{} is encrypted and cannot be decrypted (keyring locked or data key missing); it was left untouched",
+            path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+        )
+    })
+}
+
+/// Noms des stores actuellement illisibles
+pub(crate) fn unreadable_stores() -> Vec<String> {
+    let stores = UNREADABLE_STORES.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    stores
+        .iter()
+        .filter_map(|path| path.file_name().map(|n| n.to_string_lossy().to_string()))
+        .collect()
+}
+
+/// Écrit un fichier JSON de façon atomique : fichier temporaire + fsync + rename.
+/// Un crash pendant l'écriture laisse l'ancien fichier intact.
+pub(crate) fn write_json<T: Serialize>(path: &Path, data: &T) -> Result<(), String> {
+    if let Some(e) = unreadable_error(path) {
+        return Err(e);
+    }
+    ensure_app_data_dir().map_err(|e| e.to_string())?;
+    let content = serde_json::to_string_pretty(data).map_err(|e| e.to_string())?;
+    write_atomic(path, content.as_bytes())
+}
+
+/// Comme `write_json`, mais chiffre le contenu si le chiffrement au repos est
+/// activé. À utiliser pour tout store contenant du texte dicté.
+pub(crate) fn write_private_json<T: Serialize>(path: &Path, data: &T) -> Result<(), String> {
+    if let Some(e) = unreadable_error(path) {
+        return Err(e);
+    }
+    ensure_app_data_dir().map_err(|e| e.to_string())?;
+    let content = serde_json::to_string_pretty(data).map_err(|e| e.to_string())?;
+    let content = encryption::encode(&content)?;
+    write_atomic(path, content.as_bytes())
+}
+
+/// Stores contenant des données privées, avec leur verrou (migration et rotation)
+fn private_stores() -> Vec<(PathBuf, &'static Mutex<()>)> {
+    vec![
+        (history::history_path(), &history::HISTORY_LOCK),
+        (translation_history::translation_history_path(), &translation_history::TRANSLATION_HISTORY_LOCK),
+        (app_log::log_path(), &app_log::APP_LOG_LOCK),
+        (snippets::snippets_path(), &snippets::SNIPPETS_LOCK),
+        (dictionary::dictionary_path(), &dictionary::DICTIONARY_LOCK),
+        (stats::stats_path(), &stats::STATS_LOCK),
+    ]
+}
+
+fn write_atomic(path: &Path, content: &[u8]) -> Result<(), String> {
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| format!("Invalid storage path: {:?}", path))?;
+    let tmp_path = path.with_file_name(format!(".{}.tmp", file_name));
+
+    {
+        let mut file = fs::File::create(&tmp_path)
+            .map_err(|e| format!("Failed to create {:?}: {}", tmp_path, e))?;
+        file.write_all(content)
+            .map_err(|e| format!("Failed to write {:?}: {}", tmp_path, e))?;
+        file.sync_all()
+            .map_err(|e| format!("Failed to sync {:?}: {}", tmp_path, e))?;
+    }
+
+    fs::rename(&tmp_path, path).map_err(|e| {
+        let _ = fs::remove_file(&tmp_path);
+        format!("Failed to replace {:?}: {}", path, e)
+    })
+}
+
+fn backup_corrupt_file(path: &Path) {
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("store.json");
+    let backup_path = path.with_file_name(format!(
+        "{}.corrupt-{}",
+        file_name,
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+
+    match fs::rename(path, &backup_path) {
+        Ok(_) => log::warn!("[STORAGE] Corrupt file backed up to {:?}", backup_path),
+        Err(e) => log::error!("[STORAGE] Failed to back up corrupt file {:?}: {}", path, e),
+    }
+}
+
+/// Fichier `store.json` dans un répertoire temporaire vide, propre au test
+#[cfg(test)]
+pub(crate) fn temp_store(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("phonon-storage-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir.join("store.json")
+}
+
+/// Redirige `get_app_data_dir()` du thread courant jusqu'au drop du guard
+#[cfg(test)]
+pub(crate) struct DataDirGuard;
+
+#[cfg(test)]
+pub(crate) fn redirect_data_dir(dir: &Path) -> DataDirGuard {
+    DATA_DIR_OVERRIDE.with(|d| *d.borrow_mut() = Some(dir.to_path_buf()));
+    DataDirGuard
+}
+
+#[cfg(test)]
+impl Drop for DataDirGuard {
+    fn drop(&mut self) {
+        DATA_DIR_OVERRIDE.with(|d| *d.borrow_mut() = None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+    struct Sample {
+        values: Vec<u32>,
+    }
+
+    #[test]
+    fn test_write_then_read_roundtrip() {
+        let path = temp_store("roundtrip");
+        let data = Sample { values: vec![1, 2, 3] };
+        write_atomic(&path, serde_json::to_string(&data).unwrap().as_bytes()).unwrap();
+        assert_eq!(read_json::<Sample>(&path), data);
+        assert!(!path.with_file_name(".store.json.tmp").exists());
+    }
+
+    #[test]
+    fn test_corrupt_file_is_backed_up() {
+        let path = temp_store("corrupt");
+        fs::write(&path, "{ not json").unwrap();
+
+        let data: Sample = read_json(&path);
+        assert_eq!(data, Sample::default());
+        assert!(!path.exists(), "corrupt file must be moved away, not reused");
+
+        let backups: Vec<_> = fs::read_dir(path.parent().unwrap())
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().starts_with("store.json.corrupt-"))
+            .collect();
+        assert_eq!(backups.len(), 1);
+        assert_eq!(fs::read_to_string(backups[0].path()).unwrap(), "{ not json");
+    }
+
+    #[test]
+    fn test_undecryptable_file_is_kept_and_not_overwritten() {
+        let path = temp_store("undecryptable");
+        let envelope = r#"{"phonon_encrypted":1,"key_id":"gone","nonce":"","ciphertext":""}"#;
+        fs::write(&path, envelope).unwrap();
+
+        let locked = |_: &str| Err(DecodeError::Undecryptable("keyring locked".to_string()));
+        let data: Sample = read_json_with(&path, locked);
+        assert_eq!(data, Sample::default());
+        assert_eq!(fs::read_to_string(&path).unwrap(), envelope, "file must stay in place");
+        assert!(unreadable_error(&path).is_some());
+        assert!(write_json(&path, &Sample { values: vec![1] }).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), envelope);
+
+        // Clé de nouveau disponible : lecture et écritures reprennent
+        let unlocked = |_: &str| Ok(r#"{"values":[7]}"#.to_string());
+        let data: Sample = read_json_with(&path, unlocked);
+        assert_eq!(data.values, vec![7]);
+        assert!(unreadable_error(&path).is_none());
+    }
+}
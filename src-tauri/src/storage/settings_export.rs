@@ -0,0 +1,311 @@
+//! Export/import d'une configuration partageable : réglages (modèles de
+//! commandes et prompts des profils compris), dictionnaire et snippets.
+//!
+//! Les secrets et les réglages propres à la machine (micros, fenêtre,
+//! chemins locaux, chiffrement) ne sont jamais exportés, et conservent leur
+//! valeur locale à l'import.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::path::Path;
+
+use super::config::{self, SettingsIssue, SETTINGS_SCHEMA_VERSION};
+use super::{dictionary, snippets};
+use crate::types::{AppSettings, Snippet};
+
+const EXPORT_FORMAT: &str = "phonon-settings";
+const EXPORT_VERSION: u32 = 1;
+
+/// Champs exclus de l'export et préservés à l'import
+const LOCAL_FIELDS: &[&str] = &[
+    "schema_version",
+    "groq_api_key",
+    "microphone_id",
+    "preferred_microphones",
+    "microphone_channel",
+    "system_audio_device",
+    "floating_window_position",
+    "onboarding_completed",
+    "tour_completed",
+    "storage_encryption_enabled",
+];
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SettingsExport {
+    format: String,
+    version: u32,
+    schema_version: u32,
+    exported_at: String,
+    settings: Map<String, Value>,
+    #[serde(default)]
+    dictionary: Vec<String>,
+    #[serde(default)]
+    snippets: Vec<Snippet>,
+}
+
+/// Bilan d'un import, affiché à l'utilisateur
+#[derive(Debug, Clone, Serialize)]
+pub struct SettingsImportSummary {
+    pub issues: Vec<SettingsIssue>,
+    pub words_added: usize,
+    pub snippets_added: usize,
+    pub snippets_updated: usize,
+}
+
+/// Écrit la configuration courante dans `path`
+pub fn export(path: &Path) -> Result<(), String> {
+    let settings = match serde_json::to_value(config::load_settings()) {
+        Ok(Value::Object(map)) => strip_local_fields(map),
+        _ => return Err("Failed to serialize settings".to_string()),
+    };
+
+    let export = SettingsExport {
+        format: EXPORT_FORMAT.to_string(),
+        version: EXPORT_VERSION,
+        schema_version: SETTINGS_SCHEMA_VERSION,
+        exported_at: chrono::Local::now().to_rfc3339(),
+        settings,
+        dictionary: dictionary::load_dictionary().words,
+        snippets: snippets::load_snippets().snippets,
+    };
+
+    let content = serde_json::to_string_pretty(&export).map_err(|e| e.to_string())?;
+    super::write_atomic(path, content.as_bytes())?;
+    log::info!("[SETTINGS] Configuration exported to {:?}", path);
+    Ok(())
+}
+
+/// Export lu et validé, pas encore écrit : le dictionnaire et les snippets ne
+/// sont fusionnés par `commit` qu'une fois les réglages acceptés et sauvegardés
+pub struct PendingImport {
+    pub settings: AppSettings,
+    issues: Vec<SettingsIssue>,
+    dictionary: Vec<String>,
+    snippets: Vec<Snippet>,
+}
+
+impl PendingImport {
+    pub fn commit(self) -> Result<SettingsImportSummary, String> {
+        let mut dictionary_data = dictionary::load_dictionary();
+        let words_added = merge_words(&mut dictionary_data.words, self.dictionary);
+        if words_added > 0 {
+            dictionary::save_dictionary(&dictionary_data)?;
+        }
+
+        let mut snippets_data = snippets::load_snippets();
+        let (snippets_added, snippets_updated) = merge_snippets(&mut snippets_data.snippets, self.snippets);
+        if snippets_added + snippets_updated > 0 {
+            snippets::save_snippets(&snippets_data)?;
+        }
+
+        log::info!(
+            "[SETTINGS] Configuration imported: {} issue(s), {} word(s), {} new / {} updated snippet(s)",
+            self.issues.len(),
+            words_added,
+            snippets_added,
+            snippets_updated
+        );
+
+        Ok(SettingsImportSummary {
+            issues: self.issues,
+            words_added,
+            snippets_added,
+            snippets_updated,
+        })
+    }
+}
+
+/// Lit un export et prépare les réglages à appliquer par-dessus `current`.
+/// Rien n'est écrit avant `PendingImport::commit`.
+pub fn import(path: &Path, current: &AppSettings) -> Result<PendingImport, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    let export: SettingsExport = serde_json::from_str(&content)
+        .map_err(|e| format!("Not a Phonon settings export: {}", e))?;
+    if export.format != EXPORT_FORMAT {
+        return Err(format!("Unsupported file format '{}'", export.format));
+    }
+    if export.version > EXPORT_VERSION {
+        return Err(format!(
+            "This export was made by a newer version of Phonon (format v{})",
+            export.version
+        ));
+    }
+
+    let (settings, issues) = merge_settings(current, export.settings, export.schema_version)?;
+    log::info!("[SETTINGS] Read configuration export {:?}", path);
+
+    Ok(PendingImport {
+        settings,
+        issues,
+        dictionary: export.dictionary,
+        snippets: export.snippets,
+    })
+}
+
+fn strip_local_fields(mut settings: Map<String, Value>) -> Map<String, Value> {
+    for field in LOCAL_FIELDS {
+        settings.remove(*field);
+    }
+    if let Some(Value::Object(integrations)) = settings.get_mut("integrations") {
+        integrations.remove("obsidian_vault_path");
+    }
+    settings
+}
+
+/// Superpose les réglages importés (migrés depuis leur version) aux réglages locaux
+fn merge_settings(
+    current: &AppSettings,
+    imported: Map<String, Value>,
+    schema_version: u32,
+) -> Result<(AppSettings, Vec<SettingsIssue>), String> {
+    let mut merged = match serde_json::to_value(current) {
+        Ok(Value::Object(map)) => map,
+        _ => return Err("Failed to serialize current settings".to_string()),
+    };
+
+    for (key, value) in strip_local_fields(imported) {
+        match (merged.get_mut(&key), value) {
+            // Intégrations : le chemin du vault reste celui de la machine
+            (Some(Value::Object(local)), Value::Object(incoming)) if key == "integrations" => {
+                local.extend(incoming);
+            }
+            (_, value) => {
+                merged.insert(key, value);
+            }
+        }
+    }
+    // Les migrations partent de la version de l'export, pas de la version locale
+    merged.insert("schema_version".to_string(), Value::from(schema_version));
+
+    let (mut settings, issues) = config::parse_settings(Value::Object(merged));
+    settings.groq_api_key = None;
+    Ok((settings, issues))
+}
+
+fn merge_words(words: &mut Vec<String>, imported: Vec<String>) -> usize {
+    let before = words.len();
+    for word in imported {
+        let word = word.trim().to_string();
+        if !word.is_empty() && !words.contains(&word) {
+            words.push(word);
+        }
+    }
+    words.len() - before
+}
+
+/// Un snippet importé remplace le snippet local de même déclencheur
+fn merge_snippets(snippets: &mut Vec<Snippet>, imported: Vec<Snippet>) -> (usize, usize) {
+    let (mut added, mut updated) = (0, 0);
+    for snippet in imported {
+        match snippets.iter_mut().find(|s| s.trigger.eq_ignore_ascii_case(&snippet.trigger)) {
+            Some(existing) => {
+                if existing.name != snippet.name || existing.content != snippet.content {
+                    existing.name = snippet.name;
+                    existing.content = snippet.content;
+                    updated += 1;
+                }
+            }
+            None => {
+                let mut snippet = snippet;
+                if snippets.iter().any(|s| s.id == snippet.id) {
+                    snippet.id = format!("{}-{}", snippet.id, chrono::Utc::now().timestamp_millis());
+                }
+                snippets.push(snippet);
+                added += 1;
+            }
+        }
+    }
+    (added, updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn snippet(id: &str, trigger: &str, content: &str) -> Snippet {
+        Snippet {
+            id: id.to_string(),
+            name: trigger.to_string(),
+            trigger: trigger.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_export_excludes_secrets_and_local_fields() {
+        let settings = AppSettings {
+            groq_api_key: Some("gsk_secret".to_string()),
+            microphone_id: Some("USB Mic".to_string()),
+            ..AppSettings::default()
+        };
+        let Value::Object(map) = serde_json::to_value(&settings).unwrap() else { panic!() };
+        let exported = Value::Object(strip_local_fields(map)).to_string();
+
+        assert!(!exported.contains("gsk_secret"));
+        assert!(!exported.contains("USB Mic"));
+        assert!(!exported.contains("obsidian_vault_path"));
+        assert!(exported.contains("hotkey_push_to_talk"));
+    }
+
+    #[test]
+    fn test_import_keeps_local_fields() {
+        let mut current = AppSettings {
+            microphone_id: Some("USB Mic".to_string()),
+            ..AppSettings::default()
+        };
+        current.integrations.obsidian_vault_path = Some("/home/me/vault".to_string());
+        let Value::Object(imported) = json!({
+            "microphone_id": "Other Mic",
+            "theme": "dark",
+            "subtitles_font_size": 999,
+            "integrations": { "obsidian_enabled": true },
+        }) else { panic!() };
+
+        let (settings, issues) = merge_settings(&current, imported, 0).unwrap();
+
+        assert_eq!(settings.microphone_id.as_deref(), Some("USB Mic"));
+        assert_eq!(settings.theme, "dark");
+        assert!(settings.integrations.obsidian_enabled);
+        assert_eq!(settings.integrations.obsidian_vault_path.as_deref(), Some("/home/me/vault"));
+        assert_eq!(settings.subtitles_font_size, 20);
+        assert_eq!(issues.len(), 1);
+    }
+
+    #[test]
+    fn test_merge_dictionary_and_snippets() {
+        let mut words = vec!["Phonon".to_string()];
+        assert_eq!(merge_words(&mut words, vec!["Phonon".to_string(), "Tauri".to_string()]), 1);
+
+        let mut snippets = vec![snippet("1", "sig", "Cordialement")];
+        let imported = vec![snippet("9", "SIG", "Bien à vous"), snippet("1", "addr", "1 rue de Paris")];
+        assert_eq!(merge_snippets(&mut snippets, imported), (1, 1));
+        assert_eq!(snippets[0].content, "Bien à vous");
+        assert_ne!(snippets[1].id, "1");
+    }
+
+    #[test]
+    fn test_import_writes_nothing_before_commit() {
+        let dir = crate::storage::temp_store("settings-import").parent().unwrap().to_path_buf();
+        let _data_dir = crate::storage::redirect_data_dir(&dir);
+
+        let file = dir.join("export.json");
+        export(&file).unwrap();
+        let mut content: Value = serde_json::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+        content["settings"]["theme"] = json!("dark");
+        content["dictionary"] = json!(["Tauri"]);
+        content["snippets"] = serde_json::to_value(vec![snippet("1", "sig", "Cordialement")]).unwrap();
+        std::fs::write(&file, content.to_string()).unwrap();
+
+        let pending = import(&file, &AppSettings::default()).unwrap();
+        assert_eq!(pending.settings.theme, "dark");
+        assert!(!dir.join("dictionary.json").exists());
+        assert!(!dir.join("snippets.json").exists());
+
+        let summary = pending.commit().unwrap();
+        assert_eq!((summary.words_added, summary.snippets_added), (1, 1));
+        assert!(dictionary::load_dictionary().words.contains(&"Tauri".to_string()));
+        assert_eq!(snippets::load_snippets().snippets.len(), 1);
+    }
+}
@@ -1,29 +1,28 @@
 use crate::types::{DailyStats, UsageStats};
-use std::fs;
 use std::path::PathBuf;
+use std::sync::Mutex;
 
-fn stats_path() -> PathBuf {
+pub(super) static STATS_LOCK: Mutex<()> = Mutex::new(());
+
+pub(super) fn stats_path() -> PathBuf {
     super::get_app_data_dir().join("stats.json")
 }
 
 pub fn load_stats() -> UsageStats {
-    let path = stats_path();
-    if path.exists() {
-        let content = fs::read_to_string(&path).unwrap_or_default();
-        serde_json::from_str(&content).unwrap_or_default()
-    } else {
-        UsageStats::default()
-    }
+    super::read_json(&stats_path())
+}
+
+pub fn try_load_stats() -> Result<UsageStats, String> {
+    super::read_json_checked(&stats_path())
 }
 
 pub fn save_stats(stats: &UsageStats) -> Result<(), String> {
-    super::ensure_app_data_dir().map_err(|e| e.to_string())?;
-    let path = stats_path();
-    let content = serde_json::to_string_pretty(stats).map_err(|e| e.to_string())?;
-    fs::write(&path, content).map_err(|e| e.to_string())
+    let _guard = super::lock_store(&STATS_LOCK);
+    super::write_private_json(&stats_path(), stats)
 }
 
 pub fn record_transcription(word_count: u64, duration_secs: f64, language: Option<&str>) -> Result<(), String> {
+    let _guard = super::lock_store(&STATS_LOCK);
     let mut stats = load_stats();
 
     // Update totals
@@ -38,11 +37,11 @@ pub fn record_transcription(word_count: u64, duration_secs: f64, language: Optio
     daily.transcriptions += 1;
     daily.duration_secs += duration_secs;
 
-    // Update language stats
-    if let Some(lang) = language {
+    // Update language stats (pas en mode confidentialité)
+    if let Some(lang) = language.filter(|_| !crate::privacy::is_enabled()) {
         let count = stats.languages_used.entry(lang.to_string()).or_insert(0);
         *count += 1;
     }
 
-    save_stats(&stats)
+    super::write_private_json(&stats_path(), &stats)
 }
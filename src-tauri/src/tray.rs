@@ -19,6 +19,9 @@ static ICON_RECORDING: Mutex<Option<Image<'static>>> = Mutex::new(None);
 static ICON_TRANSLATING: Mutex<Option<Image<'static>>> = Mutex::new(None);
 static ICON_VOICE_ACTION: Mutex<Option<Image<'static>>> = Mutex::new(None);
 
+// Ligne du menu indiquant le profil d'application actif
+static PROFILE_ITEM: Mutex<Option<MenuItem<tauri::Wry>>> = Mutex::new(None);
+
 /// État du tray icon
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TrayState {
@@ -55,6 +58,27 @@ pub fn set_tray_state(state: TrayState) {
     }
 }
 
+/// Affiche le profil d'application actif dans le menu et l'infobulle du tray
+pub fn set_active_profile(name: Option<&str>) {
+    let label = match name {
+        Some(name) => format!("Profil : {}", name),
+        None => "Profil : réglages globaux".to_string(),
+    };
+
+    if let Ok(guard) = PROFILE_ITEM.lock() {
+        if let Some(ref item) = *guard {
+            if let Err(e) = item.set_text(&label) {
+                log::warn!("[TRAY] Failed to update profile item: {:?}", e);
+            }
+        }
+    }
+    if let Ok(guard) = TRAY_ICON.lock() {
+        if let Some(ref tray) = *guard {
+            let _ = tray.set_tooltip(Some(format!("Phonon — {}", label)));
+        }
+    }
+}
+
 /// Raccourci pour compatibilité
 pub fn set_tray_recording(recording: bool) {
     set_tray_state(if recording { TrayState::Recording } else { TrayState::Idle });
@@ -99,6 +123,10 @@ fn create_tray_menu(app: &tauri::App) -> Result<Menu<tauri::Wry>, tauri::Error>
     let updates = MenuItem::with_id(app, "updates", "Rechercher des mises à jour...", true, None::<&str>)?;
     let paste_last = MenuItem::with_id(app, "paste_last", "Coller dernière transcription", true, Some("Option+Cmd+V"))?;
     let last_transcript = MenuItem::with_id(app, "last_transcript_preview", "Aucune transcription", false, None::<&str>)?;
+    let profile = MenuItem::with_id(app, "active_profile", "Profil : réglages globaux", false, None::<&str>)?;
+    if let Ok(mut guard) = PROFILE_ITEM.lock() {
+        *guard = Some(profile.clone());
+    }
     let shortcuts = MenuItem::with_id(app, "shortcuts", "Raccourcis clavier", true, None::<&str>)?;
     let subtitles = MenuItem::with_id(app, "subtitles", "Sous-titres en direct", true, None::<&str>)?;
 
@@ -126,6 +154,8 @@ fn create_tray_menu(app: &tauri::App) -> Result<Menu<tauri::Wry>, tauri::Error>
         &PredefinedMenuItem::separator(app)?,
         &paste_last, &last_transcript,
         &PredefinedMenuItem::separator(app)?,
+        &profile,
+        &PredefinedMenuItem::separator(app)?,
         &shortcuts, &subtitles, &mic_submenu, &lang_submenu,
         &PredefinedMenuItem::separator(app)?,
         &help, &feedback,
@@ -112,45 +112,148 @@ impl Default for DictationMode {
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+/// Langage cible du mode Code : flèches, indentation et modèles insérés
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
+pub enum CodeLanguage {
+    /// Modèles de `CommandTemplates`
+    #[default]
+    Generic,
+    Rust,
+    Python,
+    TypeScript,
+}
+
+/// Modèles Whisper (GGML) de whisper.cpp : multilingues, anglais seul (`.en`)
+/// et quantifiés (q5/q8, plus légers pour une précision proche)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
 pub enum ModelSize {
     Tiny,
+    TinyEn,
+    TinyQ5_1,
+    Base,
+    BaseEn,
+    BaseQ5_1,
     Small,
+    SmallEn,
+    SmallQ5_1,
+    SmallQ8_0,
     Medium,
+    MediumEn,
+    MediumQ5_0,
+    MediumQ8_0,
+    LargeV3,
+    LargeV3Q5_0,
+    LargeV3Turbo,
+    LargeV3TurboQ5_0,
+    LargeV3TurboQ8_0,
+}
+
+/// Caractéristiques d'un modèle Whisper du catalogue
+struct ModelSpec {
+    /// Nom du fichier sans `ggml-` ni `.bin` ("small.en-q5_1")
+    stem: &'static str,
+    name: &'static str,
+    size_mb: u64,
+    /// Mémoire nécessaire à l'inférence, estimée d'après whisper.cpp
+    ram_mb: u64,
+    /// Vitesse relative à large-v3 sur CPU
+    relative_speed: f32,
 }
 
 impl ModelSize {
-    pub fn file_name(&self) -> &'static str {
-        match self {
-            ModelSize::Tiny => "ggml-tiny.bin",
-            ModelSize::Small => "ggml-small.bin",
-            ModelSize::Medium => "ggml-medium.bin",
-        }
+    pub const ALL: [ModelSize; 19] = [
+        ModelSize::Tiny,
+        ModelSize::TinyEn,
+        ModelSize::TinyQ5_1,
+        ModelSize::Base,
+        ModelSize::BaseEn,
+        ModelSize::BaseQ5_1,
+        ModelSize::Small,
+        ModelSize::SmallEn,
+        ModelSize::SmallQ5_1,
+        ModelSize::SmallQ8_0,
+        ModelSize::Medium,
+        ModelSize::MediumEn,
+        ModelSize::MediumQ5_0,
+        ModelSize::MediumQ8_0,
+        ModelSize::LargeV3,
+        ModelSize::LargeV3Q5_0,
+        ModelSize::LargeV3Turbo,
+        ModelSize::LargeV3TurboQ5_0,
+        ModelSize::LargeV3TurboQ8_0,
+    ];
+
+    fn spec(&self) -> ModelSpec {
+        let (stem, name, size_mb, ram_mb, relative_speed) = match self {
+            ModelSize::Tiny => ("tiny", "Tiny", 75, 273, 32.0),
+            ModelSize::TinyEn => ("tiny.en", "Tiny English", 75, 273, 32.0),
+            ModelSize::TinyQ5_1 => ("tiny-q5_1", "Tiny q5", 31, 230, 32.0),
+            ModelSize::Base => ("base", "Base", 142, 388, 16.0),
+            ModelSize::BaseEn => ("base.en", "Base English", 142, 388, 16.0),
+            ModelSize::BaseQ5_1 => ("base-q5_1", "Base q5", 57, 300, 16.0),
+            ModelSize::Small => ("small", "Small", 466, 852, 6.0),
+            ModelSize::SmallEn => ("small.en", "Small English", 466, 852, 6.0),
+            ModelSize::SmallQ5_1 => ("small-q5_1", "Small q5", 181, 570, 6.0),
+            ModelSize::SmallQ8_0 => ("small-q8_0", "Small q8", 252, 640, 6.0),
+            ModelSize::Medium => ("medium", "Medium", 1500, 2100, 2.0),
+            ModelSize::MediumEn => ("medium.en", "Medium English", 1500, 2100, 2.0),
+            ModelSize::MediumQ5_0 => ("medium-q5_0", "Medium q5", 514, 1110, 2.0),
+            ModelSize::MediumQ8_0 => ("medium-q8_0", "Medium q8", 785, 1380, 2.0),
+            ModelSize::LargeV3 => ("large-v3", "Large v3", 3100, 3900, 1.0),
+            ModelSize::LargeV3Q5_0 => ("large-v3-q5_0", "Large v3 q5", 1080, 1880, 1.0),
+            ModelSize::LargeV3Turbo => ("large-v3-turbo", "Large v3 Turbo", 1620, 2100, 8.0),
+            ModelSize::LargeV3TurboQ5_0 => ("large-v3-turbo-q5_0", "Large v3 Turbo q5", 547, 1030, 8.0),
+            ModelSize::LargeV3TurboQ8_0 => ("large-v3-turbo-q8_0", "Large v3 Turbo q8", 834, 1320, 8.0),
+        };
+        ModelSpec { stem, name, size_mb, ram_mb, relative_speed }
     }
 
-    pub fn download_url(&self) -> &'static str {
-        match self {
-            ModelSize::Tiny => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-tiny.bin",
-            ModelSize::Small => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-small.bin",
-            ModelSize::Medium => "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-medium.bin",
-        }
+    pub fn file_name(&self) -> String {
+        format!("ggml-{}.bin", self.spec().stem)
+    }
+
+    pub fn download_url(&self) -> String {
+        format!(
+            "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/{}",
+            self.file_name()
+        )
     }
 
     pub fn size_bytes(&self) -> u64 {
-        match self {
-            ModelSize::Tiny => 75_000_000,
-            ModelSize::Small => 466_000_000,
-            ModelSize::Medium => 1_500_000_000,
-        }
+        self.spec().size_mb * 1_000_000
     }
 
-    pub fn display_name(&self) -> &'static str {
-        match self {
-            ModelSize::Tiny => "Tiny (75 MB)",
-            ModelSize::Small => "Small (466 MB)",
-            ModelSize::Medium => "Medium (1.5 GB)",
-        }
+    /// Mémoire estimée pour charger le modèle et transcrire
+    pub fn ram_bytes(&self) -> u64 {
+        self.spec().ram_mb * 1_000_000
+    }
+
+    /// Vitesse relative à large-v3 (8 : huit fois plus rapide)
+    pub fn relative_speed(&self) -> f32 {
+        self.spec().relative_speed
+    }
+
+    /// Modèle `.en` : anglais uniquement, plus précis en anglais à taille égale
+    pub fn is_english_only(&self) -> bool {
+        self.spec().stem.contains(".en")
+    }
+
+    /// Quantification ("q5_0", "q8_0"...), `None` : pleine précision
+    pub fn quantization(&self) -> Option<&'static str> {
+        let stem = self.spec().stem;
+        stem.rfind("-q").map(|i| &stem[i + 1..])
+    }
+
+    pub fn display_name(&self) -> String {
+        let size_mb = self.spec().size_mb;
+        let size = if size_mb >= 1000 {
+            format!("{:.1} GB", size_mb as f64 / 1000.0)
+        } else {
+            format!("{} MB", size_mb)
+        };
+        format!("{} ({})", self.spec().name, size)
     }
 }
 
@@ -169,6 +272,15 @@ pub enum EngineType {
     Vosk,
 }
 
+/// Type d'entrée audio : micro (cpal) ou audio système (loopback)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioDeviceKind {
+    #[default]
+    Microphone,
+    SystemAudio,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum ParakeetModelSize {
@@ -298,11 +410,25 @@ pub struct TranscriptionResult {
     pub timestamp: i64,
     #[serde(default)]
     pub model_used: Option<String>,
+    /// Horodatage par token (Parakeet), en secondes depuis le début de l'audio
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tokens: Vec<TokenTimestamp>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenTimestamp {
+    pub token: String,
+    pub start: f32,
+    pub end: f32,
+    pub confidence: f32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct AppSettings {
+    /// Version du schéma de `config.json` (0 : fichier antérieur au versionnage)
+    #[serde(default)]
+    pub schema_version: u32,
     pub microphone_id: Option<String>,
     pub hotkey_push_to_talk: String,
     pub hotkey_toggle_record: String,
@@ -339,7 +465,18 @@ pub struct AppSettings {
     pub vosk_language: Option<VoskLanguage>,
     #[serde(default)]
     pub parakeet_model: ParakeetModelSize,
+    /// Largeur du faisceau du décodage TDT de Parakeet (1 : glouton)
+    #[serde(default = "default_parakeet_beam_size")]
+    pub parakeet_beam_size: u32,
+    /// Moteurs essayés dans l'ordre quand le moteur actif échoue
+    #[serde(default = "default_engine_fallback_chain")]
+    pub engine_fallback_chain: Vec<EngineType>,
+    /// Options de décodage Whisper (faisceau, température, prompt...)
     #[serde(default)]
+    pub whisper_decode: WhisperDecodeOptions,
+    /// Ancien emplacement de la clé Groq, lu uniquement pour la migrer vers
+    /// `storage::credentials` ; jamais réécrit dans `config.json`
+    #[serde(default, skip_serializing)]
     pub groq_api_key: Option<String>,
     #[serde(default)]
     pub llm_provider: LlmProvider,
@@ -359,6 +496,47 @@ pub struct AppSettings {
     pub stats_tracking_enabled: bool,
     #[serde(default)]
     pub integrations: IntegrationConfig,
+    /// Chiffre historique, snippets, logs et stats sur disque (clé dans le trousseau)
+    #[serde(default)]
+    pub storage_encryption_enabled: bool,
+    /// Aucun texte dicté dans l'historique, les logs, les stats ou les fichiers temporaires
+    #[serde(default)]
+    pub privacy_mode: bool,
+    /// Micros par ordre de préférence ; remplace `microphone_id` quand non vide
+    #[serde(default)]
+    pub preferred_microphones: Vec<String>,
+    /// Canal d'entrée à capturer (0 = premier) ; `None` mixe tous les canaux
+    #[serde(default)]
+    pub microphone_channel: Option<u16>,
+    /// Enregistre aussi l'audio système, mixé au micro (appels)
+    #[serde(default)]
+    pub mix_system_audio: bool,
+    /// Source système à mixer (`system:...`), `None` : sortie par défaut
+    #[serde(default)]
+    pub system_audio_device: Option<String>,
+    /// Textes insérés par "commande signature", "commande fonction"...
+    #[serde(default)]
+    pub command_templates: CommandTemplates,
+    #[serde(default)]
+    pub code_language: CodeLanguage,
+    /// Écriture des nombres, dates, montants et adresses dictés, par mode
+    #[serde(default)]
+    pub text_normalization: TextNormalization,
+    /// Applique le profil de l'application au premier plan au début de chaque dictée
+    #[serde(default)]
+    pub app_profiles_enabled: bool,
+    /// Le premier profil correspondant l'emporte
+    #[serde(default = "default_app_profiles")]
+    pub app_profiles: Vec<AppProfile>,
+    /// Dictée mains libres (raccourci de bascule) : arrêt automatique après un silence
+    #[serde(default)]
+    pub hands_free_auto_stop: bool,
+    /// Silence, en secondes, qui termine une dictée mains libres
+    #[serde(default = "default_hands_free_silence_secs")]
+    pub hands_free_silence_secs: u32,
+    /// Durée maximale d'une dictée mains libres, en secondes, décomptée à l'écran
+    #[serde(default = "default_hands_free_max_secs")]
+    pub hands_free_max_secs: u32,
 }
 
 fn default_true() -> bool {
@@ -381,9 +559,26 @@ fn default_subtitles_font_size() -> u32 {
     20
 }
 
+fn default_hands_free_silence_secs() -> u32 {
+    3
+}
+
+fn default_hands_free_max_secs() -> u32 {
+    300
+}
+
+fn default_parakeet_beam_size() -> u32 {
+    1
+}
+
+fn default_engine_fallback_chain() -> Vec<EngineType> {
+    vec![EngineType::Whisper, EngineType::Parakeet]
+}
+
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
+            schema_version: crate::storage::config::SETTINGS_SCHEMA_VERSION,
             microphone_id: None,
             hotkey_push_to_talk: "Control+Space".to_string(),
             hotkey_toggle_record: "Control+Shift+R".to_string(),
@@ -409,6 +604,9 @@ impl Default for AppSettings {
             engine_type: EngineType::default(),
             vosk_language: None,
             parakeet_model: ParakeetModelSize::default(),
+            parakeet_beam_size: default_parakeet_beam_size(),
+            engine_fallback_chain: default_engine_fallback_chain(),
+            whisper_decode: WhisperDecodeOptions::default(),
             groq_api_key: None,
             llm_provider: LlmProvider::default(),
             local_llm_model: LocalLlmModel::default(),
@@ -419,15 +617,257 @@ impl Default for AppSettings {
             subtitles_font_size: 20,
             stats_tracking_enabled: true,
             integrations: IntegrationConfig::default(),
+            storage_encryption_enabled: false,
+            privacy_mode: false,
+            preferred_microphones: Vec::new(),
+            microphone_channel: None,
+            mix_system_audio: false,
+            system_audio_device: None,
+            command_templates: CommandTemplates::default(),
+            code_language: CodeLanguage::default(),
+            text_normalization: TextNormalization::default(),
+            app_profiles_enabled: false,
+            app_profiles: default_app_profiles(),
+            hands_free_auto_stop: false,
+            hands_free_silence_secs: 3,
+            hands_free_max_secs: 300,
+        }
+    }
+}
+
+/// Modèles de texte des commandes d'insertion (emails, code)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CommandTemplates {
+    pub signature: String,
+    pub greeting: String,
+    pub function: String,
+    pub comment: String,
+}
+
+impl Default for CommandTemplates {
+    fn default() -> Self {
+        Self {
+            signature: "Cordialement,\n".to_string(),
+            greeting: "Bonjour,\n\n".to_string(),
+            function: "function name() {\n    \n}".to_string(),
+            comment: "// ".to_string(),
+        }
+    }
+}
+
+/// Normalisation du texte ("trois cent douze euros" → "312 €") activée par mode de dictée.
+/// Désactivée par défaut : le texte dicté des utilisateurs existants ne change pas.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TextNormalization {
+    pub general: bool,
+    pub email: bool,
+    pub code: bool,
+    pub notes: bool,
+}
+
+impl TextNormalization {
+    pub fn enabled_for(&self, mode: DictationMode) -> bool {
+        match mode {
+            DictationMode::General => self.general,
+            DictationMode::Email => self.email,
+            DictationMode::Code => self.code,
+            DictationMode::Notes => self.notes,
+        }
+    }
+}
+
+/// Insertion du texte dicté dans l'application au premier plan
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PasteMethod {
+    /// Presse-papiers puis Ctrl+V (Cmd+V sur macOS)
+    #[default]
+    Paste,
+    /// Presse-papiers puis Ctrl+Maj+V, pour les terminaux
+    TerminalPaste,
+    /// Copié dans le presse-papiers, sans coller
+    ClipboardOnly,
+}
+
+/// Nombre maximal de caractères du segment précédent repris dans le prompt
+/// (le prompt Whisper est limité à environ 224 jetons)
+const CONTEXT_PROMPT_CHARS: usize = 400;
+
+/// Options de décodage Whisper : précision contre vitesse.
+/// Les valeurs par défaut sont celles de whisper.cpp en décodage glouton.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WhisperDecodeOptions {
+    /// Largeur du faisceau ; 1 : décodage glouton
+    pub beam_size: u32,
+    /// Candidats tirés en décodage glouton quand la température est non nulle
+    pub best_of: u32,
+    /// Température de départ (0 : déterministe)
+    pub temperature: f32,
+    /// Pas de température pour redécoder un segment peu probable ; 0 : pas de repli
+    pub temperature_increment: f32,
+    /// Log-probabilité moyenne en dessous de laquelle le segment est redécodé
+    pub logprob_threshold: f32,
+    /// Texte donné au décodeur comme contexte (vocabulaire, noms propres, style)
+    pub initial_prompt: String,
+    /// Garde le contexte d'une fenêtre de 30s à l'autre et d'un segment à l'autre
+    pub carry_context: bool,
+    /// Traduit vers l'anglais au lieu de transcrire
+    pub translate: bool,
+    /// Threads d'inférence ; 0 : automatique (coeurs physiques, max 8)
+    pub n_threads: u32,
+    pub suppress_blank: bool,
+    /// Supprime les jetons non vocaux (musique, sous-titres...)
+    pub suppress_non_speech: bool,
+}
+
+impl Default for WhisperDecodeOptions {
+    fn default() -> Self {
+        Self {
+            beam_size: 1,
+            best_of: 1,
+            temperature: 0.0,
+            temperature_increment: 0.2,
+            logprob_threshold: -1.0,
+            initial_prompt: String::new(),
+            carry_context: false,
+            translate: false,
+            n_threads: 0,
+            suppress_blank: true,
+            suppress_non_speech: true,
+        }
+    }
+}
+
+impl WhisperDecodeOptions {
+    pub fn thread_count(&self) -> i32 {
+        if self.n_threads > 0 {
+            return self.n_threads as i32;
+        }
+        std::thread::available_parallelism()
+            .map(|n| (n.get() / 2).clamp(1, 8) as i32)
+            .unwrap_or(4)
+    }
+
+    /// Options du segment suivant : la fin du texte précédent complète le prompt
+    pub fn with_previous_text(&self, previous: &str) -> Self {
+        let previous = previous.trim();
+        let mut options = self.clone();
+        if !self.carry_context || previous.is_empty() {
+            return options;
         }
+
+        let tail = match previous.char_indices().rev().nth(CONTEXT_PROMPT_CHARS - 1) {
+            // Couper sur un mot entier
+            Some((start, _)) => {
+                let tail = &previous[start..];
+                tail.split_once(' ').map_or(tail, |(_, rest)| rest)
+            }
+            None => previous,
+        };
+        options.initial_prompt = if self.initial_prompt.trim().is_empty() {
+            tail.to_string()
+        } else {
+            format!("{} {}", self.initial_prompt.trim(), tail)
+        };
+        options
     }
 }
 
+/// Réglages appliqués quand l'application au premier plan correspond.
+/// Les champs `None` gardent la valeur des réglages globaux.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct AppProfile {
+    pub name: String,
+    /// Noms de processus ou classes de fenêtre (WM_CLASS, app_id Wayland, bundle id macOS),
+    /// comparés sans casse à une partie du nom
+    pub apps: Vec<String>,
+    pub dictation_mode: Option<DictationMode>,
+    pub engine_type: Option<EngineType>,
+    pub transcription_language: Option<String>,
+    pub llm_mode: Option<LlmMode>,
+    /// Remplace le prompt du mode LLM
+    pub llm_prompt: Option<String>,
+    pub voice_commands_enabled: Option<bool>,
+    pub paste_method: Option<PasteMethod>,
+    /// Remplace toutes les options de décodage Whisper
+    pub whisper_decode: Option<WhisperDecodeOptions>,
+}
+
+impl AppProfile {
+    fn preset(name: &str, apps: &[&str], dictation_mode: DictationMode) -> Self {
+        Self {
+            name: name.to_string(),
+            apps: apps.iter().map(|a| a.to_string()).collect(),
+            dictation_mode: Some(dictation_mode),
+            ..Self::default()
+        }
+    }
+
+    /// Vrai si le processus ou la classe de fenêtre contient un des noms du profil
+    pub fn matches(&self, process: &str, class: &str) -> bool {
+        let process = process.to_lowercase();
+        let class = class.to_lowercase();
+        self.apps
+            .iter()
+            .map(|app| app.trim().to_lowercase())
+            .filter(|app| !app.is_empty())
+            .any(|app| process.contains(&app) || class.contains(&app))
+    }
+
+    /// Réglages effectifs : ceux du profil, sinon les réglages globaux
+    pub fn apply(&self, settings: &mut AppSettings) {
+        if let Some(mode) = self.dictation_mode {
+            settings.dictation_mode = mode;
+        }
+        if let Some(engine_type) = self.engine_type {
+            settings.engine_type = engine_type;
+        }
+        if let Some(ref language) = self.transcription_language {
+            settings.transcription_language = language.clone();
+            settings.auto_detect_language = language == "auto";
+        }
+        if let Some(llm_mode) = self.llm_mode {
+            settings.llm_mode = llm_mode;
+            settings.llm_enabled = llm_mode != LlmMode::Off;
+        }
+        if let Some(enabled) = self.voice_commands_enabled {
+            settings.voice_commands_enabled = enabled;
+        }
+        if let Some(ref options) = self.whisper_decode {
+            settings.whisper_decode = options.clone();
+        }
+    }
+}
+
+/// Profils proposés par défaut : terminal, éditeurs de code, mail et notes
+fn default_app_profiles() -> Vec<AppProfile> {
+    vec![
+        AppProfile {
+            paste_method: Some(PasteMethod::TerminalPaste),
+            ..AppProfile::preset(
+                "Terminal",
+                &["gnome-terminal", "konsole", "alacritty", "kitty", "wezterm", "foot", "xterm", "terminator", "tilix"],
+                DictationMode::Code,
+            )
+        },
+        AppProfile::preset("Code", &["code", "codium", "jetbrains", "zed", "neovide", "sublime"], DictationMode::Code),
+        AppProfile::preset("Email", &["thunderbird", "evolution", "geary", "outlook", "com.apple.mail"], DictationMode::Email),
+        AppProfile::preset("Notes", &["obsidian", "joplin", "logseq", "com.apple.notes"], DictationMode::Notes),
+    ]
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AudioDevice {
     pub id: String,
     pub name: String,
     pub is_default: bool,
+    /// Nombre de canaux d'entrée (interfaces multi-canaux)
+    pub channels: u16,
+    pub kind: AudioDeviceKind,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -495,3 +935,41 @@ pub struct IntegrationConfig {
     #[serde(default)]
     pub obsidian_vault_path: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_whisper_model_catalogue() {
+        assert_eq!(ModelSize::Tiny.file_name(), "ggml-tiny.bin");
+        assert_eq!(ModelSize::SmallEn.file_name(), "ggml-small.en.bin");
+        assert_eq!(ModelSize::LargeV3TurboQ5_0.file_name(), "ggml-large-v3-turbo-q5_0.bin");
+        assert_eq!(ModelSize::LargeV3TurboQ5_0.quantization(), Some("q5_0"));
+        assert_eq!(ModelSize::LargeV3Turbo.quantization(), None);
+        assert!(ModelSize::MediumEn.is_english_only());
+        assert!(!ModelSize::LargeV3.is_english_only());
+
+        // Les réglages existants restent lisibles
+        let size: ModelSize = serde_json::from_str("\"medium\"").unwrap();
+        assert_eq!(size, ModelSize::Medium);
+        assert_eq!(serde_json::to_string(&ModelSize::LargeV3TurboQ8_0).unwrap(), "\"large_v3_turbo_q8_0\"");
+    }
+
+    #[test]
+    fn test_whisper_context_prompt_keeps_tail_of_previous_text() {
+        let options = WhisperDecodeOptions {
+            carry_context: true,
+            initial_prompt: "Phonon, Tauri.".to_string(),
+            ..Default::default()
+        };
+        let previous = "mot ".repeat(200);
+        let next = options.with_previous_text(&previous);
+        assert!(next.initial_prompt.starts_with("Phonon, Tauri. mot"));
+        assert!(next.initial_prompt.len() <= "Phonon, Tauri. ".len() + CONTEXT_PROMPT_CHARS);
+
+        // Sans report de contexte, le prompt est inchangé
+        let plain = WhisperDecodeOptions::default().with_previous_text("bonjour");
+        assert_eq!(plain.initial_prompt, "");
+    }
+}
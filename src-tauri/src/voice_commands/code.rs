@@ -0,0 +1,308 @@
+//! Code dictation: spoken symbols, identifier casing and per-language templates
+//!
+//! Used in `DictationMode::Code` only. English phrases ("open brace", "camel
+//! case") work whatever the dictation language, since most developers say
+//! them in English; each language may add its own on top.
+
+use super::packs::SpacingRule::{self, Attached, CloseWithSpace, Newline, OpenCall, OpenNoSpace, Spaced};
+use super::parser::find_phrase;
+use crate::types::CodeLanguage;
+
+/// Placeholder for one indentation level, expanded once whitespace is cleaned
+/// (the cleanup drops spaces after a newline)
+pub(super) const INDENT: char = '\t';
+
+/// Replacement of a spoken symbol
+#[derive(Debug, Clone, Copy)]
+pub(super) enum Symbol {
+    Text(&'static str),
+    /// "->" or "=>" depending on the code language
+    Arrow,
+    /// Newline followed by one indentation level
+    IndentedNewline,
+}
+
+pub(super) struct SymbolMapping {
+    pub command: &'static str,
+    pub symbol: Symbol,
+    pub spacing: SpacingRule,
+}
+
+const fn symbol(command: &'static str, text: &'static str, spacing: SpacingRule) -> SymbolMapping {
+    SymbolMapping { command, symbol: Symbol::Text(text), spacing }
+}
+
+/// Ordered so that a phrase is always listed before any phrase it contains
+static ENGLISH_SYMBOLS: &[SymbolMapping] = &[
+    SymbolMapping { command: "new line indent", symbol: Symbol::IndentedNewline, spacing: Newline },
+    symbol("open brace", "{", Spaced),
+    symbol("close brace", "}", Spaced),
+    symbol("open bracket", "[", OpenCall),
+    symbol("close bracket", "]", CloseWithSpace),
+    symbol("open paren", "(", OpenCall),
+    symbol("close paren", ")", CloseWithSpace),
+    symbol("triple equals", "===", Spaced),
+    symbol("double equals", "==", Spaced),
+    symbol("not equals", "!=", Spaced),
+    symbol("plus equals", "+=", Spaced),
+    symbol("minus equals", "-=", Spaced),
+    symbol("less or equal", "<=", Spaced),
+    symbol("greater or equal", ">=", Spaced),
+    symbol("less than", "<", Spaced),
+    symbol("greater than", ">", Spaced),
+    symbol("equals", "=", Spaced),
+    symbol("fat arrow", "=>", Spaced),
+    symbol("thin arrow", "->", Spaced),
+    SymbolMapping { command: "arrow", symbol: Symbol::Arrow, spacing: Spaced },
+    symbol("double colon", "::", Attached),
+    symbol("double and", "&&", Spaced),
+    symbol("double pipe", "||", Spaced),
+    symbol("pipe", "|", Spaced),
+    symbol("plus", "+", Spaced),
+    symbol("minus", "-", Spaced),
+    symbol("dot", ".", Attached),
+    symbol("underscore", "_", Attached),
+    symbol("ampersand", "&", OpenNoSpace),
+    symbol("bang", "!", OpenNoSpace),
+    symbol("hash", "#", OpenNoSpace),
+    symbol("dollar sign", "$", OpenNoSpace),
+    symbol("at sign", "@", OpenNoSpace),
+];
+
+static FRENCH_SYMBOLS: &[SymbolMapping] = &[
+    SymbolMapping { command: "à la ligne indentée", symbol: Symbol::IndentedNewline, spacing: Newline },
+    symbol("accolade ouvrante", "{", Spaced),
+    symbol("accolade fermante", "}", Spaced),
+    symbol("crochet ouvrant", "[", OpenCall),
+    symbol("crochet fermant", "]", CloseWithSpace),
+    symbol("égal égal", "==", Spaced),
+    symbol("différent de", "!=", Spaced),
+    symbol("égal", "=", Spaced),
+    SymbolMapping { command: "flèche", symbol: Symbol::Arrow, spacing: Spaced },
+];
+
+static GERMAN_SYMBOLS: &[SymbolMapping] = &[
+    symbol("geschweifte klammer auf", "{", Spaced),
+    symbol("geschweifte klammer zu", "}", Spaced),
+    symbol("gleich gleich", "==", Spaced),
+    symbol("ungleich", "!=", Spaced),
+    symbol("gleich", "=", Spaced),
+    SymbolMapping { command: "pfeil", symbol: Symbol::Arrow, spacing: Spaced },
+];
+
+static SPANISH_SYMBOLS: &[SymbolMapping] = &[
+    symbol("abrir llave", "{", Spaced),
+    symbol("cerrar llave", "}", Spaced),
+    symbol("igual igual", "==", Spaced),
+    symbol("distinto de", "!=", Spaced),
+    symbol("igual", "=", Spaced),
+    SymbolMapping { command: "flecha", symbol: Symbol::Arrow, spacing: Spaced },
+];
+
+static ITALIAN_SYMBOLS: &[SymbolMapping] = &[
+    symbol("apri graffa", "{", Spaced),
+    symbol("chiudi graffa", "}", Spaced),
+    symbol("uguale uguale", "==", Spaced),
+    symbol("diverso da", "!=", Spaced),
+    symbol("uguale", "=", Spaced),
+    SymbolMapping { command: "freccia", symbol: Symbol::Arrow, spacing: Spaced },
+];
+
+/// Symbols of a command pack language, then the English ones
+pub(super) fn symbols(language: &str) -> impl Iterator<Item = &'static SymbolMapping> {
+    let local: &[SymbolMapping] = match language {
+        "fr" => FRENCH_SYMBOLS,
+        "de" => GERMAN_SYMBOLS,
+        "es" => SPANISH_SYMBOLS,
+        "it" => ITALIAN_SYMBOLS,
+        _ => &[],
+    };
+    local.iter().chain(ENGLISH_SYMBOLS)
+}
+
+/// Vrai si `phrase` (en minuscules) est un symbole ou une casse du mode Code
+pub(super) fn is_code_phrase(phrase: &str) -> bool {
+    [ENGLISH_SYMBOLS, FRENCH_SYMBOLS, GERMAN_SYMBOLS, SPANISH_SYMBOLS, ITALIAN_SYMBOLS]
+        .iter()
+        .any(|table| table.iter().any(|m| m.command == phrase))
+        || CASINGS.iter().any(|(command, _)| *command == phrase)
+}
+
+impl Symbol {
+    pub(super) fn text(self, code_language: CodeLanguage) -> &'static str {
+        match self {
+            Symbol::Text(text) => text,
+            Symbol::Arrow => match code_language {
+                CodeLanguage::TypeScript => "=>",
+                CodeLanguage::Generic | CodeLanguage::Rust | CodeLanguage::Python => "->",
+            },
+            Symbol::IndentedNewline => "\n\t",
+        }
+    }
+}
+
+/// One indentation level of the code language
+pub(super) fn indent(code_language: CodeLanguage) -> &'static str {
+    match code_language {
+        CodeLanguage::TypeScript => "  ",
+        CodeLanguage::Generic | CodeLanguage::Rust | CodeLanguage::Python => "    ",
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Casing {
+    Camel,
+    Pascal,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+}
+
+/// Ordered so that a phrase is always listed before any phrase it contains
+const CASINGS: &[(&str, Casing)] = &[
+    ("camel case", Casing::Camel),
+    ("pascal case", Casing::Pascal),
+    ("screaming snake case", Casing::ScreamingSnake),
+    ("screaming snake", Casing::ScreamingSnake),
+    ("constant case", Casing::ScreamingSnake),
+    ("snake case", Casing::Snake),
+    ("kebab case", Casing::Kebab),
+];
+
+/// Ends an identifier before the end of the sentence: "camel case user id end case is"
+const CASING_END: &[&str] = &["end case", "fin casse"];
+
+impl Casing {
+    fn join(self, words: &[String]) -> String {
+        let capitalized = || {
+            words.iter().map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                    .unwrap_or_default()
+            })
+        };
+
+        match self {
+            Casing::Camel => {
+                let mut result = words.first().cloned().unwrap_or_default();
+                result.extend(capitalized().skip(1));
+                result
+            }
+            Casing::Pascal => capitalized().collect(),
+            Casing::Snake => words.join("_"),
+            Casing::ScreamingSnake => words.join("_").to_uppercase(),
+            Casing::Kebab => words.join("-"),
+        }
+    }
+}
+
+/// "camel case user id" → "userId": the casing applies to the following words,
+/// up to punctuation, a symbol, "end case" or the end of the text
+pub(super) fn apply_casing(text: &str) -> String {
+    let mut text = text.to_string();
+
+    loop {
+        let lower = text.to_lowercase();
+        let Some((pos, phrase, casing)) = CASINGS
+            .iter()
+            .filter_map(|(phrase, casing)| find_phrase(&lower, phrase).map(|pos| (pos, *phrase, *casing)))
+            .min_by_key(|(pos, _, _)| *pos)
+        else {
+            return text;
+        };
+
+        let after = &text[pos + phrase.len()..];
+        let (words, consumed) = identifier_words(after);
+        let identifier = casing.join(&words);
+        let rest = after[consumed..].to_string();
+        text.truncate(pos);
+        text.push_str(&identifier);
+        text.push_str(&rest);
+    }
+}
+
+/// Lowercase words of the identifier at the start of `text`, and the length they span
+fn identifier_words(text: &str) -> (Vec<String>, usize) {
+    let mut words = Vec::new();
+    let mut end = 0;
+
+    loop {
+        let rest = &text[end..];
+        let word_start = end + rest.len() - rest.trim_start_matches(' ').len();
+        let rest = &text[word_start..];
+
+        let rest_lower = rest.to_lowercase();
+        if let Some(terminator) = CASING_END.iter().find(|t| find_phrase(&rest_lower, t) == Some(0)) {
+            return (words, word_start + terminator.len());
+        }
+
+        let word_len = rest.find(|c: char| !c.is_alphanumeric()).unwrap_or(rest.len());
+        if word_len == 0 {
+            return (words, end);
+        }
+        words.push(rest[..word_len].to_lowercase());
+        end = word_start + word_len;
+        if !text[end..].starts_with(' ') {
+            return (words, end);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum CodeTemplate {
+    Function,
+    Comment,
+    Class,
+    Loop,
+    Condition,
+}
+
+/// Built-in template, `None` for `Generic` function and comment
+/// (taken from the user's `CommandTemplates`)
+pub(super) fn template(code_language: CodeLanguage, template: CodeTemplate) -> Option<&'static str> {
+    let text = match (code_language, template) {
+        (CodeLanguage::Generic, CodeTemplate::Function | CodeTemplate::Comment) => return None,
+        (CodeLanguage::Rust, CodeTemplate::Function) => "fn name() {\n    \n}",
+        (CodeLanguage::Rust, CodeTemplate::Comment) => "// ",
+        (CodeLanguage::Rust, CodeTemplate::Class) => "struct Name {\n    \n}",
+        (CodeLanguage::Rust, CodeTemplate::Loop) => "for item in items {\n    \n}",
+        (CodeLanguage::Rust, CodeTemplate::Condition) => "if condition {\n    \n}",
+        (CodeLanguage::Python, CodeTemplate::Function) => "def name():\n    ",
+        (CodeLanguage::Python, CodeTemplate::Comment) => "# ",
+        (CodeLanguage::Python, CodeTemplate::Class) => "class Name:\n    ",
+        (CodeLanguage::Python, CodeTemplate::Loop) => "for item in items:\n    ",
+        (CodeLanguage::Python, CodeTemplate::Condition) => "if condition:\n    ",
+        (CodeLanguage::TypeScript, CodeTemplate::Function) => "function name() {\n  \n}",
+        (CodeLanguage::TypeScript, CodeTemplate::Comment) => "// ",
+        (CodeLanguage::TypeScript, CodeTemplate::Class) => "class Name {\n  \n}",
+        (CodeLanguage::TypeScript, CodeTemplate::Loop) => "for (const item of items) {\n  \n}",
+        (CodeLanguage::TypeScript, CodeTemplate::Condition) => "if (condition) {\n  \n}",
+        (CodeLanguage::Generic, CodeTemplate::Class) => "class Name {\n    \n}",
+        (CodeLanguage::Generic, CodeTemplate::Loop) => "for (item in items) {\n    \n}",
+        (CodeLanguage::Generic, CodeTemplate::Condition) => "if (condition) {\n    \n}",
+    };
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_casing() {
+        assert_eq!(apply_casing("let camel case user id = 5"), "let userId = 5");
+        assert_eq!(apply_casing("pascal case http client."), "HttpClient.");
+        assert_eq!(apply_casing("screaming snake case max size end case is"), "MAX_SIZE is");
+        assert_eq!(apply_casing("self.snake case user name"), "self.user_name");
+        assert_eq!(apply_casing("kebab case main menu"), "main-menu");
+    }
+
+    #[test]
+    fn test_templates() {
+        assert_eq!(template(CodeLanguage::Generic, CodeTemplate::Function), None);
+        assert_eq!(template(CodeLanguage::Python, CodeTemplate::Comment), Some("# "));
+        assert_eq!(template(CodeLanguage::Rust, CodeTemplate::Class), Some("struct Name {\n    \n}"));
+    }
+}
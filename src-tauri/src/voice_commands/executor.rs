@@ -1,18 +1,98 @@
 //! Voice command action executor
 
-use super::Action;
-use crate::platform;
-use crate::types::Snippet;
-
-/// Execute a list of voice command actions
-pub fn execute_actions(actions: &[Action], snippets: &[Snippet]) {
-    for action in actions {
-        execute_action(action, snippets);
+use super::code::{self, CodeTemplate};
+use super::{Action, Token};
+use crate::platform::{self, KeyCombo};
+use crate::types::{AppSettings, Snippet};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+/// Temps laissé à l'application cible pour lire le presse-papiers avant sa restauration
+const CLIPBOARD_RESTORE_DELAY: Duration = Duration::from_millis(500);
+
+/// Execute a token stream in spoken order: text segments go through
+/// `insert_text`, actions run between them
+pub fn execute_tokens(
+    app: &AppHandle,
+    tokens: &[Token],
+    snippets: &[Snippet],
+    settings: &AppSettings,
+    insert_text: impl Fn(&str),
+) {
+    for token in tokens {
+        match token {
+            Token::Text(text) => insert_text(text),
+            Token::Action(action) => execute_action(app, action, snippets, settings),
+        }
     }
 }
 
-fn execute_action(action: &Action, snippets: &[Snippet]) {
+fn execute_action(app: &AppHandle, action: &Action, snippets: &[Snippet], settings: &AppSettings) {
+    // Dictée lancée depuis l'interface : les touches arriveraient dans Phonon lui-même
+    if sends_keys(action) && phonon_has_focus(app) {
+        log::info!(
+            "[VOICE_CMD] Skipping {} while the Phonon window has focus",
+            crate::privacy::redact(&format!("{:?}", action))
+        );
+        return;
+    }
+
+    let templates = &settings.command_templates;
+
     match action {
+        Action::Delete => {
+            // Supprime le mot précédent (ou la sélection)
+            press(if cfg!(target_os = "macos") { "alt+backspace" } else { "ctrl+backspace" });
+        }
+        Action::Undo => {
+            press("primary+z");
+        }
+        Action::ClearAll => {
+            press("primary+a");
+            press("backspace");
+        }
+        Action::Uppercase => {
+            uppercase_selection(app);
+        }
+        Action::Copy => {
+            press("primary+c");
+        }
+        Action::Stop => {
+            crate::ptt::stop_if_active(app);
+            // Enregistrement lancé depuis l'interface : arrêté par le frontend
+            let _ = app.emit("voice-stop-requested", ());
+        }
+        Action::InsertSignature => {
+            platform::paste_text(&templates.signature);
+        }
+        Action::InsertGreeting => {
+            platform::paste_text(&templates.greeting);
+        }
+        Action::InsertFunction => {
+            let language_template = code::template(settings.code_language, CodeTemplate::Function);
+            platform::paste_text(language_template.unwrap_or(&templates.function));
+        }
+        Action::InsertComment => {
+            let language_template = code::template(settings.code_language, CodeTemplate::Comment);
+            platform::paste_text(language_template.unwrap_or(&templates.comment));
+        }
+        Action::InsertClass | Action::InsertLoop | Action::InsertCondition => {
+            let template = match action {
+                Action::InsertClass => CodeTemplate::Class,
+                Action::InsertLoop => CodeTemplate::Loop,
+                _ => CodeTemplate::Condition,
+            };
+            if let Some(text) = code::template(settings.code_language, template) {
+                platform::paste_text(text);
+            }
+        }
+        Action::InsertBullet => {
+            platform::paste_text("- ");
+        }
+        Action::InsertTitle => {
+            platform::paste_text("# ");
+        }
         Action::OpenApp(app_name) => {
             platform::open_app(app_name);
         }
@@ -42,9 +122,86 @@ fn execute_action(action: &Action, snippets: &[Snippet]) {
         Action::FormatUnderline => {
             platform::send_keyboard_shortcut("u");
         }
-        // Other actions are handled by the frontend or not yet implemented
-        _ => {
-            log::debug!("Action {:?} not executed server-side", action);
+        Action::Shortcut(combo) => {
+            platform::send_key_combo(combo);
+        }
+        Action::Shell(argv) => {
+            platform::run_command(argv);
+        }
+    }
+}
+
+/// Actions qui agissent par touches synthétisées (raccourcis, collage)
+/// sur l'application au premier plan
+fn sends_keys(action: &Action) -> bool {
+    matches!(
+        action,
+        Action::Delete
+            | Action::Undo
+            | Action::ClearAll
+            | Action::Uppercase
+            | Action::Copy
+            | Action::InsertSignature
+            | Action::InsertGreeting
+            | Action::InsertFunction
+            | Action::InsertComment
+            | Action::InsertClass
+            | Action::InsertLoop
+            | Action::InsertCondition
+            | Action::InsertBullet
+            | Action::InsertTitle
+            | Action::InsertSnippet(_)
+            | Action::FormatBold
+            | Action::FormatItalic
+            | Action::FormatUnderline
+            | Action::Shortcut(_)
+    )
+}
+
+fn phonon_has_focus(app: &AppHandle) -> bool {
+    app.webview_windows()
+        .values()
+        .any(|window| window.is_focused().unwrap_or(false))
+}
+
+/// Envoie une combinaison fixe (toujours valide)
+fn press(spec: &str) {
+    match KeyCombo::parse(spec) {
+        Ok(combo) => platform::send_key_combo(&combo),
+        Err(e) => log::error!("[VOICE_CMD] {}", e),
+    }
+}
+
+/// Copie la sélection, la passe en majuscules et la colle à sa place,
+/// puis rend à l'utilisateur le contenu de son presse-papiers
+fn uppercase_selection(app: &AppHandle) {
+    let clipboard = app.clipboard();
+    let saved_text = clipboard.read_text().ok();
+    let saved_image = match saved_text {
+        Some(_) => None,
+        None => clipboard.read_image().ok().map(|image| image.to_owned()),
+    };
+
+    // Presse-papiers vidé avant : sans sélection, on ne recolle pas l'ancien contenu
+    let _ = clipboard.write_text(String::new());
+    platform::copy_selected_text();
+
+    match clipboard.read_text() {
+        Ok(text) if !text.is_empty() => {
+            platform::paste_text(&text.to_uppercase());
+            // L'application cible lit le presse-papiers après avoir reçu le collage
+            std::thread::sleep(CLIPBOARD_RESTORE_DELAY);
         }
+        Ok(_) => log::info!("[VOICE_CMD] Uppercase: no selection"),
+        Err(e) => log::warn!("[VOICE_CMD] Uppercase: failed to read clipboard: {}", e),
+    }
+
+    let restored = match (saved_text, saved_image) {
+        (Some(text), _) => clipboard.write_text(text),
+        (None, Some(image)) => clipboard.write_image(&image),
+        (None, None) => clipboard.clear(),
+    };
+    if let Err(e) = restored {
+        log::warn!("[VOICE_CMD] Uppercase: failed to restore clipboard: {}", e);
     }
 }
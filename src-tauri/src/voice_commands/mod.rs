@@ -2,9 +2,17 @@
 //!
 //! This module handles parsing of voice commands for punctuation,
 //! editing actions, and contextual commands based on dictation mode.
+//! Phrases come from per-language packs (fr, en, de, es, it) and from the
+//! user command file (see `user_commands`). Code mode adds spoken symbols,
+//! identifier casing and per-language templates (see `code`); Notes mode writes
+//! its structure as Markdown (see `notes`).
 
+mod code;
 mod executor;
+mod notes;
+mod packs;
 mod parser;
+pub mod user_commands;
 
-pub use executor::execute_actions;
-pub use parser::{parse, Action, ParseResult};
+pub use executor::execute_tokens;
+pub use parser::{contains_stop_command, parse, Action, ParseContext, ParseResult, Token};
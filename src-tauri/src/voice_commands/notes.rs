@@ -0,0 +1,207 @@
+//! Notes dictation: spoken structure written as Markdown
+//!
+//! Used in `DictationMode::Notes` only. Headings, lists, checkboxes, spans,
+//! links and code blocks become Markdown in the text itself, so the note keeps
+//! its structure wherever it is pasted or sent (Obsidian, Apple Notes).
+
+use super::code::INDENT;
+use super::packs::PunctuationMapping;
+use super::packs::SpacingRule::{self, Attached, CloseWithSpace, Newline, OpenNoSpace};
+
+/// Placeholder for a numbered list item, replaced by "1. ", "2. "… once the
+/// text is cleaned (the tables embed it as `\u{F0000}`)
+const NUMBER: char = '\u{F0000}';
+
+/// Nested list indentation, valid under both "- " and "1. " items
+const LIST_INDENT: &str = "    ";
+
+const fn md(command: &'static str, replacement: &'static str, spacing: SpacingRule) -> PunctuationMapping {
+    PunctuationMapping { command, replacement, spacing }
+}
+
+/// Ordered so that a phrase is always listed before any phrase it contains
+static FRENCH_MARKDOWN: &[PunctuationMapping] = &[
+    md("commande fin bloc de code", "\n```\n", Newline),
+    md("commande bloc de code", "\n```\n", Newline),
+    md("commande titre trois", "\n\n### ", Newline),
+    md("commande titre deux", "\n\n## ", Newline),
+    md("commande sous-titre", "\n\n## ", Newline),
+    md("commande sous titre", "\n\n## ", Newline),
+    md("commande titre", "\n\n# ", Newline),
+    md("commande sous-puce", "\n\t- ", Newline),
+    md("commande sous puce", "\n\t- ", Newline),
+    md("commande puce", "\n- ", Newline),
+    md("commande sous-numéro", "\n\t\u{F0000}", Newline),
+    md("commande sous numéro", "\n\t\u{F0000}", Newline),
+    md("commande numéro", "\n\u{F0000}", Newline),
+    md("commande case cochée", "\n- [x] ", Newline),
+    md("commande case à cocher", "\n- [ ] ", Newline),
+    md("commande fin gras", "**", CloseWithSpace),
+    md("commande gras", "**", OpenNoSpace),
+    md("commande fin italique", "*", CloseWithSpace),
+    md("commande italique", "*", OpenNoSpace),
+    md("commande fin lien", ")", CloseWithSpace),
+    md("commande adresse", "](", Attached),
+    md("commande lien", "[", OpenNoSpace),
+];
+
+static ENGLISH_MARKDOWN: &[PunctuationMapping] = &[
+    md("command end code block", "\n```\n", Newline),
+    md("command code block", "\n```\n", Newline),
+    md("command heading three", "\n\n### ", Newline),
+    md("command heading two", "\n\n## ", Newline),
+    md("command subheading", "\n\n## ", Newline),
+    md("command heading", "\n\n# ", Newline),
+    md("command title", "\n\n# ", Newline),
+    md("command sub bullet", "\n\t- ", Newline),
+    md("command bullet", "\n- ", Newline),
+    md("command sub number", "\n\t\u{F0000}", Newline),
+    md("command number", "\n\u{F0000}", Newline),
+    md("command checked box", "\n- [x] ", Newline),
+    md("command checkbox", "\n- [ ] ", Newline),
+    md("command end bold", "**", CloseWithSpace),
+    md("command bold", "**", OpenNoSpace),
+    md("command end italic", "*", CloseWithSpace),
+    md("command italic", "*", OpenNoSpace),
+    md("command end link", ")", CloseWithSpace),
+    md("command url", "](", Attached),
+    md("command link", "[", OpenNoSpace),
+];
+
+static GERMAN_MARKDOWN: &[PunctuationMapping] = &[
+    md("befehl codeblock ende", "\n```\n", Newline),
+    md("befehl codeblock", "\n```\n", Newline),
+    md("befehl überschrift drei", "\n\n### ", Newline),
+    md("befehl überschrift zwei", "\n\n## ", Newline),
+    md("befehl unterüberschrift", "\n\n## ", Newline),
+    md("befehl überschrift", "\n\n# ", Newline),
+    md("befehl unterpunkt", "\n\t- ", Newline),
+    md("befehl aufzählung", "\n- ", Newline),
+    md("befehl unternummer", "\n\t\u{F0000}", Newline),
+    md("befehl nummer", "\n\u{F0000}", Newline),
+    md("befehl erledigt", "\n- [x] ", Newline),
+    md("befehl kontrollkästchen", "\n- [ ] ", Newline),
+    md("befehl fett ende", "**", CloseWithSpace),
+    md("befehl fett", "**", OpenNoSpace),
+    md("befehl kursiv ende", "*", CloseWithSpace),
+    md("befehl kursiv", "*", OpenNoSpace),
+    md("befehl link ende", ")", CloseWithSpace),
+    md("befehl adresse", "](", Attached),
+    md("befehl link", "[", OpenNoSpace),
+];
+
+static SPANISH_MARKDOWN: &[PunctuationMapping] = &[
+    md("comando fin bloque de código", "\n```\n", Newline),
+    md("comando fin bloque de codigo", "\n```\n", Newline),
+    md("comando bloque de código", "\n```\n", Newline),
+    md("comando bloque de codigo", "\n```\n", Newline),
+    md("comando título tres", "\n\n### ", Newline),
+    md("comando título dos", "\n\n## ", Newline),
+    md("comando subtítulo", "\n\n## ", Newline),
+    md("comando título", "\n\n# ", Newline),
+    md("comando titulo", "\n\n# ", Newline),
+    md("comando subviñeta", "\n\t- ", Newline),
+    md("comando viñeta", "\n- ", Newline),
+    md("comando subnúmero", "\n\t\u{F0000}", Newline),
+    md("comando número", "\n\u{F0000}", Newline),
+    md("comando numero", "\n\u{F0000}", Newline),
+    md("comando casilla marcada", "\n- [x] ", Newline),
+    md("comando casilla", "\n- [ ] ", Newline),
+    md("comando fin negrita", "**", CloseWithSpace),
+    md("comando negrita", "**", OpenNoSpace),
+    md("comando fin cursiva", "*", CloseWithSpace),
+    md("comando cursiva", "*", OpenNoSpace),
+    md("comando fin enlace", ")", CloseWithSpace),
+    md("comando dirección", "](", Attached),
+    md("comando enlace", "[", OpenNoSpace),
+];
+
+static ITALIAN_MARKDOWN: &[PunctuationMapping] = &[
+    md("comando fine blocco di codice", "\n```\n", Newline),
+    md("comando blocco di codice", "\n```\n", Newline),
+    md("comando titolo tre", "\n\n### ", Newline),
+    md("comando titolo due", "\n\n## ", Newline),
+    md("comando sottotitolo", "\n\n## ", Newline),
+    md("comando titolo", "\n\n# ", Newline),
+    md("comando sottoelenco", "\n\t- ", Newline),
+    md("comando elenco", "\n- ", Newline),
+    md("comando sottonumero", "\n\t\u{F0000}", Newline),
+    md("comando numero", "\n\u{F0000}", Newline),
+    md("comando casella spuntata", "\n- [x] ", Newline),
+    md("comando casella", "\n- [ ] ", Newline),
+    md("comando fine grassetto", "**", CloseWithSpace),
+    md("comando grassetto", "**", OpenNoSpace),
+    md("comando fine corsivo", "*", CloseWithSpace),
+    md("comando corsivo", "*", OpenNoSpace),
+    md("comando fine link", ")", CloseWithSpace),
+    md("comando indirizzo", "](", Attached),
+    md("comando link", "[", OpenNoSpace),
+];
+
+/// Markdown phrases of a command pack language
+pub(super) fn markdown(language: &str) -> &'static [PunctuationMapping] {
+    match language {
+        "en" => ENGLISH_MARKDOWN,
+        "de" => GERMAN_MARKDOWN,
+        "es" => SPANISH_MARKDOWN,
+        "it" => ITALIAN_MARKDOWN,
+        _ => FRENCH_MARKDOWN,
+    }
+}
+
+/// Vrai si `phrase` (en minuscules) est une commande Markdown du mode Notes
+pub(super) fn is_notes_phrase(phrase: &str) -> bool {
+    [FRENCH_MARKDOWN, ENGLISH_MARKDOWN, GERMAN_MARKDOWN, SPANISH_MARKDOWN, ITALIAN_MARKDOWN]
+        .iter()
+        .any(|table| table.iter().any(|m| m.command == phrase))
+}
+
+/// Number list items and expand nesting, once whitespace is cleaned.
+/// A bullet at the same level, or any other line, restarts the numbering.
+pub(super) fn finish(text: &str) -> String {
+    let mut counters: Vec<usize> = Vec::new();
+    let mut lines = Vec::new();
+
+    for line in text.split('\n') {
+        let item = line.trim_start_matches(INDENT);
+        let depth = line.len() - item.len();
+
+        let item = if let Some(rest) = item.strip_prefix(NUMBER) {
+            counters.resize(depth + 1, 0);
+            counters[depth] += 1;
+            format!("{}. {}", counters[depth], rest.trim_start())
+        } else {
+            if item.starts_with("- ") {
+                counters.truncate(depth);
+            } else {
+                counters.clear();
+            }
+            item.to_string()
+        };
+        lines.push(format!("{}{}", LIST_INDENT.repeat(depth), item));
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finish_numbers_nested_lists() {
+        let text = "\u{F0000}one\n\t- detail\n\t\u{F0000}sub\n\u{F0000}two\ntext\n\u{F0000}again";
+        assert_eq!(finish(text), "1. one\n    - detail\n    1. sub\n2. two\ntext\n1. again");
+    }
+
+    #[test]
+    fn test_tables_list_longer_phrases_first() {
+        for table in [FRENCH_MARKDOWN, ENGLISH_MARKDOWN, GERMAN_MARKDOWN, SPANISH_MARKDOWN, ITALIAN_MARKDOWN] {
+            for (i, earlier) in table.iter().enumerate() {
+                for later in &table[i + 1..] {
+                    assert!(!later.command.contains(earlier.command), "{} hides {}", earlier.command, later.command);
+                }
+            }
+        }
+    }
+}
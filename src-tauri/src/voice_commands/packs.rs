@@ -0,0 +1,394 @@
+//! Per-language voice command packs
+//!
+//! Each pack holds the spoken phrases for one language along with its
+//! typographic conventions. French punctuation embeds the narrow no-break
+//! space (U+202F) before `?`, `!` and `;`, a no-break space (U+00A0) before
+//! `:` and inside guillemets; other languages attach punctuation to the word.
+
+use super::Action;
+use SpacingRule::{CloseWithSpace, Newline, OpenNoSpace};
+
+/// Spacing behavior for punctuation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum SpacingRule {
+    /// Remove space before, add space after (e.g., ".", ",", ";", ":", "?", "!")
+    CloseWithSpace,
+    /// Keep space before, remove space after (e.g., "(", "<<", "¿")
+    OpenNoSpace,
+    /// Remove space before, no space after but newline (e.g., "\n")
+    Newline,
+    /// One space on each side (code operators: "==", "->", "{")
+    Spaced,
+    /// No space on either side (code: ".", "::", "_")
+    Attached,
+    /// Attached to a preceding identifier ("main(", "items["), spaced after an operator
+    OpenCall,
+}
+
+/// Punctuation command with its replacement and spacing rule
+pub(super) struct PunctuationMapping {
+    pub command: &'static str,
+    /// Inserted text, including any locale-specific space (e.g. "\u{202F}?" in French)
+    pub replacement: &'static str,
+    pub spacing: SpacingRule,
+}
+
+/// All spoken phrases of one language
+pub(super) struct CommandPack {
+    /// ISO 639-1 code
+    pub language: &'static str,
+    /// Ordered so that a phrase is always listed before any phrase it contains
+    pub punctuation: &'static [PunctuationMapping],
+    /// Whole-word triggers followed by an application name
+    pub app_triggers: &'static [&'static str],
+    pub edit: &'static [(&'static str, Action)],
+    pub email: &'static [(&'static str, Action)],
+    pub code: &'static [(&'static str, Action)],
+    pub system: &'static [(&'static str, Action)],
+    pub format: &'static [(&'static str, Action)],
+    /// Prefixes of snippet insertion ("insère <trigger>")
+    pub snippet_prefixes: &'static [&'static str],
+    /// Volume command, followed by an optional connector and a number
+    pub volume_command: &'static str,
+    pub volume_connectors: &'static [&'static str],
+}
+
+const fn punct(command: &'static str, replacement: &'static str, spacing: SpacingRule) -> PunctuationMapping {
+    PunctuationMapping { command, replacement, spacing }
+}
+
+// ============================================================================
+// Français
+// ============================================================================
+
+static FRENCH: CommandPack = CommandPack {
+    language: "fr",
+    punctuation: &[
+        punct("point d'interrogation", "\u{202F}?", CloseWithSpace),
+        punct("point d'exclamation", "\u{202F}!", CloseWithSpace),
+        punct("nouveau paragraphe", "\n\n", Newline),
+        punct("ouvrir parenthèse", "(", OpenNoSpace),
+        punct("fermer parenthèse", ")", CloseWithSpace),
+        punct("ouvrir guillemets", "\u{00AB}\u{00A0}", OpenNoSpace),
+        punct("fermer guillemets", "\u{00A0}\u{00BB}", CloseWithSpace),
+        punct("point virgule", "\u{202F};", CloseWithSpace),
+        punct("deux points", "\u{00A0}:", CloseWithSpace),
+        punct("à la ligne", "\n", Newline),
+        punct("virgule", ",", CloseWithSpace),
+        punct("point", ".", CloseWithSpace),
+    ],
+    // Imperative forms only, to avoid conflicts with "ouvrir parenthèse"
+    app_triggers: &["ouvre", "lance", "mets", "démarre", "demarre"],
+    edit: &[
+        ("commande tout effacer", Action::ClearAll),
+        ("commande majuscules", Action::Uppercase),
+        ("commande annuler", Action::Undo),
+        ("commande efface", Action::Delete),
+        ("commande copier", Action::Copy),
+        ("commande stop", Action::Stop),
+    ],
+    email: &[
+        ("commande formule politesse", Action::InsertGreeting),
+        ("commande signature", Action::InsertSignature),
+    ],
+    code: &[
+        ("commande commentaire", Action::InsertComment),
+        ("commande fonction", Action::InsertFunction),
+        ("commande classe", Action::InsertClass),
+        ("commande boucle", Action::InsertLoop),
+        ("commande condition", Action::InsertCondition),
+    ],
+    system: &[
+        ("commande screenshot", Action::Screenshot),
+        ("commande capture ecran", Action::Screenshot),
+        ("commande capture écran", Action::Screenshot),
+        ("commande verrouille", Action::LockScreen),
+        ("commande ne pas déranger", Action::ToggleDND),
+        ("commande ne pas deranger", Action::ToggleDND),
+    ],
+    format: &[
+        ("mets en gras", Action::FormatBold),
+        ("en gras", Action::FormatBold),
+        ("mets en italique", Action::FormatItalic),
+        ("en italique", Action::FormatItalic),
+        ("commande souligne", Action::FormatUnderline),
+        ("commande souligné", Action::FormatUnderline),
+    ],
+    snippet_prefixes: &["insere", "insère"],
+    volume_command: "commande volume",
+    volume_connectors: &["à", "a"],
+};
+
+// ============================================================================
+// English
+// ============================================================================
+
+static ENGLISH: CommandPack = CommandPack {
+    language: "en",
+    punctuation: &[
+        punct("exclamation point", "!", CloseWithSpace),
+        punct("exclamation mark", "!", CloseWithSpace),
+        punct("question mark", "?", CloseWithSpace),
+        punct("new paragraph", "\n\n", Newline),
+        punct("open parenthesis", "(", OpenNoSpace),
+        punct("close parenthesis", ")", CloseWithSpace),
+        punct("open quote", "\u{201C}", OpenNoSpace),
+        punct("close quote", "\u{201D}", CloseWithSpace),
+        punct("semicolon", ";", CloseWithSpace),
+        punct("new line", "\n", Newline),
+        punct("full stop", ".", CloseWithSpace),
+        punct("colon", ":", CloseWithSpace),
+        punct("comma", ",", CloseWithSpace),
+        punct("period", ".", CloseWithSpace),
+    ],
+    // "open" and "start" are too common in prose
+    app_triggers: &["launch"],
+    edit: &[
+        ("command clear all", Action::ClearAll),
+        ("command uppercase", Action::Uppercase),
+        ("command undo", Action::Undo),
+        ("command delete", Action::Delete),
+        ("command copy", Action::Copy),
+        ("command stop", Action::Stop),
+    ],
+    email: &[
+        ("command greeting", Action::InsertGreeting),
+        ("command signature", Action::InsertSignature),
+    ],
+    code: &[
+        ("command comment", Action::InsertComment),
+        ("command function", Action::InsertFunction),
+        ("command class", Action::InsertClass),
+        ("command loop", Action::InsertLoop),
+        ("command condition", Action::InsertCondition),
+    ],
+    system: &[
+        ("command screenshot", Action::Screenshot),
+        ("command lock screen", Action::LockScreen),
+        ("command do not disturb", Action::ToggleDND),
+    ],
+    format: &[
+        ("command bold", Action::FormatBold),
+        ("command italic", Action::FormatItalic),
+        ("command underline", Action::FormatUnderline),
+    ],
+    snippet_prefixes: &["insert"],
+    volume_command: "command volume",
+    volume_connectors: &["to"],
+};
+
+// ============================================================================
+// Deutsch
+// ============================================================================
+
+static GERMAN: CommandPack = CommandPack {
+    language: "de",
+    punctuation: &[
+        punct("anführungszeichen auf", "\u{201E}", OpenNoSpace),
+        punct("anführungszeichen zu", "\u{201C}", CloseWithSpace),
+        punct("ausrufezeichen", "!", CloseWithSpace),
+        punct("fragezeichen", "?", CloseWithSpace),
+        punct("neuer absatz", "\n\n", Newline),
+        punct("klammer auf", "(", OpenNoSpace),
+        punct("klammer zu", ")", CloseWithSpace),
+        punct("doppelpunkt", ":", CloseWithSpace),
+        punct("semikolon", ";", CloseWithSpace),
+        punct("neue zeile", "\n", Newline),
+        punct("komma", ",", CloseWithSpace),
+        punct("punkt", ".", CloseWithSpace),
+    ],
+    app_triggers: &["starte", "öffne"],
+    edit: &[
+        ("befehl alles löschen", Action::ClearAll),
+        ("befehl großbuchstaben", Action::Uppercase),
+        ("befehl rückgängig", Action::Undo),
+        ("befehl löschen", Action::Delete),
+        ("befehl kopieren", Action::Copy),
+        ("befehl stopp", Action::Stop),
+        ("befehl stop", Action::Stop),
+    ],
+    email: &[
+        ("befehl grußformel", Action::InsertGreeting),
+        ("befehl signatur", Action::InsertSignature),
+    ],
+    code: &[
+        ("befehl kommentar", Action::InsertComment),
+        ("befehl funktion", Action::InsertFunction),
+        ("befehl klasse", Action::InsertClass),
+        ("befehl schleife", Action::InsertLoop),
+        ("befehl bedingung", Action::InsertCondition),
+    ],
+    system: &[
+        ("befehl bildschirmfoto", Action::Screenshot),
+        ("befehl screenshot", Action::Screenshot),
+        ("befehl sperren", Action::LockScreen),
+        ("befehl nicht stören", Action::ToggleDND),
+    ],
+    format: &[
+        ("befehl fett", Action::FormatBold),
+        ("befehl kursiv", Action::FormatItalic),
+        ("befehl unterstreichen", Action::FormatUnderline),
+    ],
+    snippet_prefixes: &["einfügen"],
+    volume_command: "befehl lautstärke",
+    volume_connectors: &["auf"],
+};
+
+// ============================================================================
+// Español
+// ============================================================================
+
+static SPANISH: CommandPack = CommandPack {
+    language: "es",
+    punctuation: &[
+        punct("signo de interrogación", "?", CloseWithSpace),
+        punct("signo de exclamación", "!", CloseWithSpace),
+        punct("abrir interrogación", "\u{00BF}", OpenNoSpace),
+        punct("cerrar interrogación", "?", CloseWithSpace),
+        punct("abrir exclamación", "\u{00A1}", OpenNoSpace),
+        punct("cerrar exclamación", "!", CloseWithSpace),
+        punct("nuevo párrafo", "\n\n", Newline),
+        punct("abrir paréntesis", "(", OpenNoSpace),
+        punct("cerrar paréntesis", ")", CloseWithSpace),
+        punct("abrir comillas", "\u{00AB}", OpenNoSpace),
+        punct("cerrar comillas", "\u{00BB}", CloseWithSpace),
+        punct("punto y coma", ";", CloseWithSpace),
+        punct("dos puntos", ":", CloseWithSpace),
+        punct("nueva línea", "\n", Newline),
+        punct("coma", ",", CloseWithSpace),
+        punct("punto", ".", CloseWithSpace),
+    ],
+    app_triggers: &["abre", "lanza", "inicia"],
+    edit: &[
+        ("comando borrar todo", Action::ClearAll),
+        ("comando mayúsculas", Action::Uppercase),
+        ("comando deshacer", Action::Undo),
+        ("comando borrar", Action::Delete),
+        ("comando copiar", Action::Copy),
+        ("comando parar", Action::Stop),
+    ],
+    email: &[
+        ("comando fórmula de cortesía", Action::InsertGreeting),
+        ("comando saludo", Action::InsertGreeting),
+        ("comando firma", Action::InsertSignature),
+    ],
+    code: &[
+        ("comando comentario", Action::InsertComment),
+        ("comando función", Action::InsertFunction),
+        ("comando funcion", Action::InsertFunction),
+        ("comando clase", Action::InsertClass),
+        ("comando bucle", Action::InsertLoop),
+        ("comando condición", Action::InsertCondition),
+        ("comando condicion", Action::InsertCondition),
+    ],
+    system: &[
+        ("comando captura de pantalla", Action::Screenshot),
+        ("comando bloquear", Action::LockScreen),
+        ("comando no molestar", Action::ToggleDND),
+    ],
+    format: &[
+        ("comando negrita", Action::FormatBold),
+        ("comando cursiva", Action::FormatItalic),
+        ("comando subrayado", Action::FormatUnderline),
+    ],
+    snippet_prefixes: &["inserta", "insertar"],
+    volume_command: "comando volumen",
+    volume_connectors: &["al", "a"],
+};
+
+// ============================================================================
+// Italiano
+// ============================================================================
+
+static ITALIAN: CommandPack = CommandPack {
+    language: "it",
+    punctuation: &[
+        punct("punto interrogativo", "?", CloseWithSpace),
+        punct("punto esclamativo", "!", CloseWithSpace),
+        punct("nuovo paragrafo", "\n\n", Newline),
+        punct("apri parentesi", "(", OpenNoSpace),
+        punct("chiudi parentesi", ")", CloseWithSpace),
+        punct("apri virgolette", "\u{00AB}", OpenNoSpace),
+        punct("chiudi virgolette", "\u{00BB}", CloseWithSpace),
+        punct("punto e virgola", ";", CloseWithSpace),
+        punct("due punti", ":", CloseWithSpace),
+        punct("a capo", "\n", Newline),
+        punct("virgola", ",", CloseWithSpace),
+        punct("punto", ".", CloseWithSpace),
+    ],
+    // "apri" is left out: it would swallow "apri parentesi"
+    app_triggers: &["lancia", "avvia"],
+    edit: &[
+        ("comando cancella tutto", Action::ClearAll),
+        ("comando maiuscole", Action::Uppercase),
+        ("comando annulla", Action::Undo),
+        ("comando cancella", Action::Delete),
+        ("comando copia", Action::Copy),
+        ("comando stop", Action::Stop),
+    ],
+    email: &[
+        ("comando formula di cortesia", Action::InsertGreeting),
+        ("comando firma", Action::InsertSignature),
+    ],
+    code: &[
+        ("comando commento", Action::InsertComment),
+        ("comando funzione", Action::InsertFunction),
+        ("comando classe", Action::InsertClass),
+        ("comando ciclo", Action::InsertLoop),
+        ("comando condizione", Action::InsertCondition),
+    ],
+    system: &[
+        ("comando screenshot", Action::Screenshot),
+        ("comando cattura schermo", Action::Screenshot),
+        ("comando blocca", Action::LockScreen),
+        ("comando non disturbare", Action::ToggleDND),
+    ],
+    format: &[
+        ("comando grassetto", Action::FormatBold),
+        ("comando corsivo", Action::FormatItalic),
+        ("comando sottolineato", Action::FormatUnderline),
+    ],
+    snippet_prefixes: &["inserisci"],
+    volume_command: "comando volume",
+    volume_connectors: &["al", "a"],
+};
+
+static PACKS: &[&CommandPack] = &[&FRENCH, &ENGLISH, &GERMAN, &SPANISH, &ITALIAN];
+
+/// Pack for a transcription or detected language ("en", "en-US", "english"...).
+/// Unknown or "auto" languages fall back to French, the historical default.
+pub(super) fn for_language(language: &str) -> &'static CommandPack {
+    let code = language
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase();
+    let code = match code.as_str() {
+        "english" => "en",
+        "french" => "fr",
+        "german" => "de",
+        "spanish" => "es",
+        "italian" => "it",
+        other => other,
+    };
+
+    PACKS
+        .iter()
+        .find(|pack| pack.language == code)
+        .copied()
+        .unwrap_or(&FRENCH)
+}
+
+/// Vrai si `phrase` (en minuscules) est une commande d'un des packs
+pub(super) fn is_builtin_phrase(phrase: &str) -> bool {
+    PACKS.iter().any(|pack| {
+        pack.punctuation.iter().any(|m| m.command == phrase)
+            || pack.app_triggers.contains(&phrase)
+            || pack.snippet_prefixes.contains(&phrase)
+            || pack.volume_command == phrase
+            || [pack.edit, pack.email, pack.code, pack.system, pack.format]
+                .iter()
+                .any(|table| table.iter().any(|(command, _)| *command == phrase))
+    })
+}
@@ -3,7 +3,12 @@
 //! Parses transcribed text to detect and replace punctuation commands,
 //! extract editing actions, and handle contextual commands based on dictation mode.
 
-use crate::types::DictationMode;
+use super::code;
+use super::notes;
+use super::packs::{self, CommandPack, PunctuationMapping, SpacingRule};
+use super::user_commands::{self, UserCommands};
+use crate::platform::KeyCombo;
+use crate::types::{CodeLanguage, DictationMode};
 use crate::types::Snippet;
 
 /// Actions that can be triggered by voice commands
@@ -29,6 +34,12 @@ pub enum Action {
     InsertFunction,
     /// Insert comment (Code mode)
     InsertComment,
+    /// Insert class or struct (Code mode)
+    InsertClass,
+    /// Insert loop (Code mode)
+    InsertLoop,
+    /// Insert condition (Code mode)
+    InsertCondition,
     /// Insert bullet point (Notes mode)
     InsertBullet,
     /// Insert title/heading (Notes mode)
@@ -51,6 +62,19 @@ pub enum Action {
     FormatItalic,
     /// Format selection as underline
     FormatUnderline,
+    /// Send a key combination (user command)
+    Shortcut(KeyCombo),
+    /// Run a program in the background, without a shell: program then arguments (user command)
+    Shell(Vec<String>),
+}
+
+/// One element of the parsed stream, in spoken order
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    /// Text to insert, with its leading space if it follows earlier text
+    Text(String),
+    /// Action to run at this point of the dictation
+    Action(Action),
 }
 
 /// Result of parsing voice commands from text
@@ -58,104 +82,40 @@ pub enum Action {
 pub struct ParseResult {
     /// The processed text with punctuation commands replaced
     pub text: String,
-    /// Actions extracted from command phrases
+    /// Actions extracted from command phrases, in spoken order
     pub actions: Vec<Action>,
+    /// Text segments and actions interleaved in spoken order
+    pub tokens: Vec<Token>,
 }
 
-/// Spacing behavior for punctuation
-#[derive(Debug, Clone, Copy, PartialEq)]
-enum SpacingRule {
-    /// Remove space before, add space after (e.g., ".", ",", ";", ":", "?", "!")
-    CloseWithSpace,
-    /// Remove space before, no space after (e.g., ")" and ">>" when followed by punctuation)
-    CloseNoSpace,
-    /// Keep space before, remove space after (e.g., "(", "<<")
-    OpenNoSpace,
-    /// Remove space before, no space after but newline (e.g., "\n")
-    Newline,
+/// Dictation context that selects which commands `parse` recognizes
+#[derive(Debug, Clone, Copy)]
+pub struct ParseContext<'a> {
+    /// The current dictation mode (affects which contextual commands are recognized)
+    pub mode: DictationMode,
+    /// Available snippets for insertion commands
+    pub snippets: &'a [Snippet],
+    /// Whether system commands (volume, screenshot, etc.) are enabled
+    pub system_commands_enabled: bool,
+    /// Transcription or detected language, selects the command pack (fr, en, de, es, it)
+    pub language: &'a str,
+    /// Target language of Code mode symbols and casing
+    pub code_language: CodeLanguage,
 }
 
-/// Punctuation command with its replacement and spacing rule
-struct PunctuationMapping {
-    command: &'static str,
-    replacement: &'static str,
-    spacing: SpacingRule,
+impl<'a> ParseContext<'a> {
+    /// No snippets, system commands off, generic code language
+    pub fn new(mode: DictationMode, language: &'a str) -> Self {
+        Self {
+            mode,
+            snippets: &[],
+            system_commands_enabled: false,
+            language,
+            code_language: CodeLanguage::Generic,
+        }
+    }
 }
 
-/// Punctuation command mappings (French) - ordered from longest to shortest to avoid partial matches
-const PUNCTUATION_COMMANDS: &[PunctuationMapping] = &[
-    PunctuationMapping { command: "point d'interrogation", replacement: "?", spacing: SpacingRule::CloseWithSpace },
-    PunctuationMapping { command: "point d'exclamation", replacement: "!", spacing: SpacingRule::CloseWithSpace },
-    PunctuationMapping { command: "nouveau paragraphe", replacement: "\n\n", spacing: SpacingRule::Newline },
-    PunctuationMapping { command: "ouvrir parenthèse", replacement: "(", spacing: SpacingRule::OpenNoSpace },
-    PunctuationMapping { command: "fermer parenthèse", replacement: ")", spacing: SpacingRule::CloseWithSpace },
-    PunctuationMapping { command: "ouvrir guillemets", replacement: "\u{00AB}", spacing: SpacingRule::OpenNoSpace },
-    PunctuationMapping { command: "fermer guillemets", replacement: "\u{00BB}", spacing: SpacingRule::CloseWithSpace },
-    PunctuationMapping { command: "point virgule", replacement: ";", spacing: SpacingRule::CloseWithSpace },
-    PunctuationMapping { command: "deux points", replacement: ":", spacing: SpacingRule::CloseWithSpace },
-    PunctuationMapping { command: "à la ligne", replacement: "\n", spacing: SpacingRule::Newline },
-    PunctuationMapping { command: "virgule", replacement: ",", spacing: SpacingRule::CloseWithSpace },
-    PunctuationMapping { command: "point", replacement: ".", spacing: SpacingRule::CloseWithSpace },
-];
-
-/// Trigger words for opening apps (French, imperative forms only to avoid
-/// conflicts with punctuation commands like "ouvrir parenthèse")
-const APP_TRIGGERS: &[&str] = &[
-    "ouvre",
-    "lance",
-    "mets",
-    "démarre",
-    "demarre",
-];
-
-/// Edit command mappings (prefixed with "commande")
-const EDIT_COMMANDS: &[(&str, Action)] = &[
-    ("commande tout effacer", Action::ClearAll),
-    ("commande majuscules", Action::Uppercase),
-    ("commande annuler", Action::Undo),
-    ("commande efface", Action::Delete),
-    ("commande copier", Action::Copy),
-    ("commande stop", Action::Stop),
-];
-
-/// Email mode contextual commands
-const EMAIL_COMMANDS: &[(&str, Action)] = &[
-    ("commande formule politesse", Action::InsertGreeting),
-    ("commande signature", Action::InsertSignature),
-];
-
-/// Code mode contextual commands
-const CODE_COMMANDS: &[(&str, Action)] = &[
-    ("commande commentaire", Action::InsertComment),
-    ("commande fonction", Action::InsertFunction),
-];
-
-/// Notes mode contextual commands
-const NOTES_COMMANDS: &[(&str, Action)] = &[
-    ("commande titre", Action::InsertTitle),
-    ("commande puce", Action::InsertBullet),
-];
-
-/// System commands (require system_commands_enabled)
-const SYSTEM_COMMANDS: &[(&str, Action)] = &[
-    ("commande screenshot", Action::Screenshot),
-    ("commande capture ecran", Action::Screenshot),
-    ("commande capture écran", Action::Screenshot),
-    ("commande verrouille", Action::LockScreen),
-    ("commande ne pas déranger", Action::ToggleDND),
-    ("commande ne pas deranger", Action::ToggleDND),
-];
-
-/// Format commands
-const FORMAT_COMMANDS: &[(&str, Action)] = &[
-    ("mets en gras", Action::FormatBold),
-    ("en gras", Action::FormatBold),
-    ("mets en italique", Action::FormatItalic),
-    ("en italique", Action::FormatItalic),
-    ("commande souligne", Action::FormatUnderline),
-    ("commande souligné", Action::FormatUnderline),
-];
-
 /// Parse voice commands from transcribed text
 ///
 /// This function processes the input text to:
@@ -166,147 +126,277 @@ const FORMAT_COMMANDS: &[(&str, Action)] = &[
 /// # Arguments
 ///
 /// * `text` - The transcribed text to parse
-/// * `mode` - The current dictation mode (affects which contextual commands are recognized)
-/// * `snippets` - Available snippets for insertion commands
-/// * `system_commands_enabled` - Whether system commands (volume, screenshot, etc.) are enabled
+/// * `context` - Dictation mode, snippets, language and enabled command sets
+///
+/// User commands from `voice_commands.json` are applied before the built-in ones.
 ///
 /// # Returns
 ///
 /// A `ParseResult` containing the processed text and any extracted actions
-pub fn parse(text: &str, mode: DictationMode, snippets: &[Snippet], system_commands_enabled: bool) -> ParseResult {
-    let mut result_text = text.to_string();
+pub fn parse(text: &str, context: &ParseContext) -> ParseResult {
+    let user = user_commands::current();
+    parse_with(text, context, &user)
+}
+
+fn parse_with(text: &str, context: &ParseContext, user: &UserCommands) -> ParseResult {
+    let ParseContext { mode, snippets, system_commands_enabled, language, code_language } = *context;
+    let pack = packs::for_language(language);
     let mut actions = Vec::new();
 
+    // User commands first: they override built-ins sharing the same phrase
+    let mut result_text = user.apply(text, pack.language, mode, &mut actions);
+    let enabled = |command: &&(&str, Action)| !user.is_disabled(command.0);
+
     // Get contextual commands based on mode
     let contextual_commands: &[(&str, Action)] = match mode {
-        DictationMode::Email => EMAIL_COMMANDS,
-        DictationMode::Code => CODE_COMMANDS,
-        DictationMode::Notes => NOTES_COMMANDS,
-        DictationMode::General => &[],
+        DictationMode::Email => pack.email,
+        DictationMode::Code => pack.code,
+        DictationMode::Notes | DictationMode::General => &[],
     };
 
-    // Extract contextual commands first (they may contain "commande" prefix)
-    for (command, action) in contextual_commands {
+    // Notes structure is written as Markdown, before format commands claim "bold"
+    if mode == DictationMode::Notes {
+        for mapping in notes::markdown(pack.language).iter().filter(|m| !user.is_disabled(m.command)) {
+            result_text = replace_punctuation_command(&result_text, mapping);
+        }
+    }
+
+    // Extract contextual commands first (they may contain the "commande" prefix)
+    for (command, action) in contextual_commands.iter().filter(enabled) {
         result_text = extract_command(&result_text, command, action, &mut actions);
     }
 
     // Extract edit commands
-    for (command, action) in EDIT_COMMANDS {
+    for (command, action) in pack.edit.iter().filter(enabled) {
         result_text = extract_command(&result_text, command, action, &mut actions);
     }
 
     // Extract snippet insertion commands
     for snippet in snippets {
-        let triggers = [
-            format!("insere {}", snippet.trigger),
-            format!("insère {}", snippet.trigger),
-        ];
-        for trigger in &triggers {
-            let text_lower = result_text.to_lowercase();
-            let trigger_lower = trigger.to_lowercase();
-            if let Some(pos) = text_lower.find(&trigger_lower) {
-                actions.push(Action::InsertSnippet(snippet.trigger.clone()));
-                let mut new_text = String::new();
-                let before = &result_text[..pos];
-                new_text.push_str(before.trim_end());
-                let after = &result_text[pos + trigger.len()..];
-                if !new_text.is_empty() && !after.trim_start().is_empty() {
-                    new_text.push(' ');
-                }
-                new_text.push_str(after.trim_start());
-                result_text = new_text;
+        for prefix in pack.snippet_prefixes.iter().filter(|p| !user.is_disabled(p)) {
+            let trigger = format!("{} {}", prefix, snippet.trigger);
+            let action = Action::InsertSnippet(snippet.trigger.clone());
+            let before = actions.len();
+            result_text = extract_command(&result_text, &trigger, &action, &mut actions);
+            if actions.len() > before {
                 break;
             }
         }
     }
 
     // Extract format commands (before app commands to avoid "mets" trigger conflict)
-    for (command, action) in FORMAT_COMMANDS {
+    for (command, action) in pack.format.iter().filter(enabled) {
         result_text = extract_command(&result_text, command, action, &mut actions);
     }
 
     // Extract system commands if enabled
     if system_commands_enabled {
-        for (command, action) in SYSTEM_COMMANDS {
+        for (command, action) in pack.system.iter().filter(enabled) {
             result_text = extract_command(&result_text, command, action, &mut actions);
         }
+        if !user.is_disabled(pack.volume_command) {
+            result_text = extract_volume_command(&result_text, pack, &mut actions);
+        }
+    }
 
-        // Volume command: "commande volume [à] XX"
-        let text_lower = result_text.to_lowercase();
-        if let Some(vol_pos) = text_lower.find("commande volume") {
-            let after_volume = &result_text[vol_pos + "commande volume".len()..];
-            let trimmed = after_volume.trim_start();
-            // Skip "a " or "à " if present
-            let prefix_len = if trimmed.starts_with("à ") {
-                "à ".len()
-            } else if trimmed.starts_with("a ") {
-                "a ".len()
-            } else {
-                0
+    // Extract app open commands ("ouvre Safari", "launch Spotify", etc.)
+    let app_triggers: Vec<&str> = pack.app_triggers.iter().copied().filter(|t| !user.is_disabled(t)).collect();
+    result_text = extract_app_commands(&result_text, &app_triggers, &mut actions);
+
+    // Replace code symbols before punctuation ("new line indent" contains "new line")
+    if mode == DictationMode::Code {
+        for symbol in code::symbols(pack.language).filter(|m| !user.is_disabled(m.command)) {
+            let mapping = PunctuationMapping {
+                command: symbol.command,
+                replacement: symbol.symbol.text(code_language),
+                spacing: symbol.spacing,
             };
-            let num_part = &trimmed[prefix_len..];
-            // Parse the number
-            let num_str: String = num_part.chars().take_while(|c| c.is_ascii_digit()).collect();
-            if let Ok(level) = num_str.parse::<u8>() {
-                let level = level.min(100);
-                actions.push(Action::SetVolume(level));
-                // Remove the entire "commande volume [à] XX" from text
-                let end_pos = vol_pos + "commande volume".len() + (after_volume.len() - after_volume.trim_start().len()) + prefix_len + num_str.len();
-                let mut new_text = String::new();
-                new_text.push_str(result_text[..vol_pos].trim_end());
-                let remaining = &result_text[end_pos..];
-                if !new_text.is_empty() && !remaining.trim_start().is_empty() {
-                    new_text.push(' ');
-                }
-                new_text.push_str(remaining.trim_start());
-                result_text = new_text;
-            }
+            result_text = replace_punctuation_command(&result_text, &mapping);
         }
     }
 
-    // Extract app open commands ("ouvre Safari", "lance Spotify", etc.)
-    result_text = extract_app_commands(&result_text, &mut actions);
-
     // Replace punctuation commands (case-insensitive)
-    for mapping in PUNCTUATION_COMMANDS {
+    for mapping in pack.punctuation.iter().filter(|m| !user.is_disabled(m.command)) {
         result_text = replace_punctuation_command(&result_text, mapping);
     }
 
+    // Identifier casing last: it stops at the first punctuation or symbol
+    if mode == DictationMode::Code {
+        result_text = code::apply_casing(&result_text);
+    }
+
     // Clean up extra whitespace
     result_text = clean_whitespace(&result_text);
+    match mode {
+        DictationMode::Code => result_text = result_text.replace(code::INDENT, code::indent(code_language)),
+        DictationMode::Notes => result_text = notes::finish(&result_text),
+        DictationMode::General | DictationMode::Email => {}
+    }
+
+    assemble(&result_text, &actions)
+}
+
+/// Start of the private-use range marking where an action was spoken.
+/// One character per action: never alphanumeric, so phrase matching ignores it.
+const MARKER_BASE: u32 = 0xE000;
+const MARKER_LAST: u32 = 0xF8FF;
 
-    ParseResult {
-        text: result_text,
-        actions,
+fn marker_index(c: char) -> Option<usize> {
+    let code = c as u32;
+    (MARKER_BASE..=MARKER_LAST).contains(&code).then(|| (code - MARKER_BASE) as usize)
+}
+
+/// Replace `[start, end)` with a marker for `action`, so the action keeps
+/// its place relative to the surrounding text
+pub(super) fn mark_action(text: &str, start: usize, end: usize, action: Action, actions: &mut Vec<Action>) -> String {
+    let code = (MARKER_BASE + actions.len() as u32).min(MARKER_LAST);
+    let marker = char::from_u32(code).unwrap_or('\u{E000}');
+    actions.push(action);
+    format!("{} {} {}", text[..start].trim_end(), marker, text[end..].trim_start())
+}
+
+/// Split the marked text into tokens, in spoken order
+pub(super) fn assemble(marked: &str, actions: &[Action]) -> ParseResult {
+    let mut result = ParseResult {
+        text: String::new(),
+        actions: Vec::new(),
+        tokens: Vec::new(),
+    };
+    // Text typed since the last action that erased or inserted text
+    let mut typed = String::new();
+    let mut segment = String::new();
+
+    for c in marked.chars() {
+        match marker_index(c).and_then(|index| actions.get(index)) {
+            Some(action) => {
+                push_text(&mut result, &mut typed, &segment);
+                segment.clear();
+                if resets_context(action) {
+                    typed.clear();
+                }
+                result.actions.push(action.clone());
+                result.tokens.push(Token::Action(action.clone()));
+            }
+            None => segment.push(c),
+        }
+    }
+    push_text(&mut result, &mut typed, &segment);
+
+    result
+}
+
+fn push_text(result: &mut ParseResult, typed: &mut String, segment: &str) {
+    let text = segment.trim_matches(' ');
+    if text.is_empty() {
+        return;
+    }
+
+    if needs_space(&result.text, text) {
+        result.text.push(' ');
     }
+    result.text.push_str(text);
+
+    let token = if needs_space(typed, text) {
+        format!(" {}", text)
+    } else {
+        text.to_string()
+    };
+    typed.push_str(&token);
+    result.tokens.push(Token::Text(token));
+}
+
+/// Whether a space is needed between already inserted text and the next segment
+fn needs_space(before: &str, next: &str) -> bool {
+    !before.is_empty()
+        && !before.ends_with([' ', '\n', '(', '«', '¿', '¡', '\u{00A0}'])
+        && !next.starts_with(['.', ',', ';', ':', '!', '?', ')', '»', '…', '\n', '\u{00A0}', '\u{202F}'])
 }
 
-/// Extract app open commands from text (e.g. "ouvre Safari", "lance Spotify")
+/// Actions after which the next text starts without a leading space:
+/// they erase the previous word, or insert text of their own
+fn resets_context(action: &Action) -> bool {
+    matches!(
+        action,
+        Action::Delete
+            | Action::ClearAll
+            | Action::InsertSignature
+            | Action::InsertGreeting
+            | Action::InsertFunction
+            | Action::InsertComment
+            | Action::InsertClass
+            | Action::InsertLoop
+            | Action::InsertCondition
+            | Action::InsertBullet
+            | Action::InsertTitle
+            | Action::InsertSnippet(_)
+    )
+}
+
+/// True if the text contains the stop command ("commande stop", "command stop"...).
+/// Used on streaming chunks to end the recording while the user is still speaking.
+pub fn contains_stop_command(text: &str, language: &str) -> bool {
+    parse(text, &ParseContext::new(DictationMode::General, language))
+        .actions
+        .contains(&Action::Stop)
+}
+
+/// Volume command: "commande volume [à] XX", "command volume [to] XX"...
+fn extract_volume_command(text: &str, pack: &CommandPack, actions: &mut Vec<Action>) -> String {
+    let text_lower = text.to_lowercase();
+    let Some(vol_pos) = find_phrase(&text_lower, pack.volume_command) else {
+        return text.to_string();
+    };
+
+    let after_volume = &text[vol_pos + pack.volume_command.len()..];
+    let trimmed = after_volume.trim_start();
+    // Skip the connector ("à", "to", "auf"...) if present
+    let prefix_len = pack
+        .volume_connectors
+        .iter()
+        .find(|connector| {
+            trimmed
+                .get(..connector.len() + 1)
+                .is_some_and(|head| head.to_lowercase() == format!("{} ", connector))
+        })
+        .map(|connector| connector.len() + 1)
+        .unwrap_or(0);
+    let num_part = &trimmed[prefix_len..];
+    // Parse the number
+    let num_str: String = num_part.chars().take_while(|c| c.is_ascii_digit()).collect();
+    let Ok(level) = num_str.parse::<u8>() else {
+        return text.to_string();
+    };
+
+    // Replace the entire volume command with its marker
+    let end_pos = vol_pos
+        + pack.volume_command.len()
+        + (after_volume.len() - trimmed.len())
+        + prefix_len
+        + num_str.len();
+    mark_action(text, vol_pos, end_pos, Action::SetVolume(level.min(100)), actions)
+}
+
+/// Extract app open commands from text (e.g. "ouvre Safari", "launch Spotify")
 /// Takes only the first word after the trigger as the app name.
 /// Matches on word boundaries to avoid matching inside longer words (e.g. "ouvre" inside "ouvrir").
-fn extract_app_commands(text: &str, actions: &mut Vec<Action>) -> String {
+fn extract_app_commands(text: &str, triggers: &[&str], actions: &mut Vec<Action>) -> String {
     let words: Vec<&str> = text.split_whitespace().collect();
     let words_lower: Vec<String> = words.iter().map(|w| w.to_lowercase()).collect();
 
-    for trigger in APP_TRIGGERS {
+    for trigger in triggers {
         let trigger_lower = trigger.to_lowercase();
         for (i, word) in words_lower.iter().enumerate() {
             if *word == trigger_lower {
                 // Found the trigger as a whole word; next word is the app name
-                if i + 1 >= words.len() {
+                let Some(app_name) = words.get(i + 1).filter(|w| !w.chars().any(|c| marker_index(c).is_some())) else {
                     continue;
-                }
-                let app_name = words[i + 1];
-                actions.push(Action::OpenApp(app_name.to_string()));
-
-                // Rebuild text without trigger and app name
-                let mut parts: Vec<&str> = Vec::new();
-                for (j, w) in words.iter().enumerate() {
-                    if j != i && j != i + 1 {
-                        parts.push(w);
-                    }
-                }
-                return parts.join(" ");
+                };
+
+                // Rebuild text with the marker in place of trigger and app name
+                let before = words[..i].join(" ");
+                let rebuilt = format!("{} {}", before, words[i + 2..].join(" "));
+                let action = Action::OpenApp(app_name.to_string());
+                return mark_action(&rebuilt, before.len(), before.len(), action, actions);
             }
         }
     }
@@ -314,22 +404,46 @@ fn extract_app_commands(text: &str, actions: &mut Vec<Action>) -> String {
     text.to_string()
 }
 
-/// Extract a command from text and add its action to the actions vector
+/// Position of the first occurrence of `phrase` in `text_lower` that starts and
+/// ends on a word boundary ("comma" must not match inside "command").
+pub(super) fn find_phrase(text_lower: &str, phrase: &str) -> Option<usize> {
+    phrase_matches(text_lower, &phrase.to_lowercase()).next()
+}
+
+/// Like `find_phrase`, but on the original text: returns the byte range of the
+/// match in `text`, which differs from the lowercased offsets when lowercasing
+/// changes a character's length ("ẞ" -> "ß", "İ" -> "i̇")
+pub(super) fn find_phrase_range(text: &str, phrase: &str) -> Option<(usize, usize)> {
+    let mut lower = String::with_capacity(text.len());
+    // Original character range of each byte of `lower`
+    let mut origin = Vec::with_capacity(text.len());
+    for (start, c) in text.char_indices() {
+        for l in c.to_lowercase() {
+            lower.push(l);
+            origin.extend(std::iter::repeat_n((start, start + c.len_utf8()), l.len_utf8()));
+        }
+    }
+    let phrase = phrase.to_lowercase();
+    let pos = phrase_matches(&lower, &phrase).next()?;
+    Some((origin[pos].0, origin[pos + phrase.len() - 1].1))
+}
+
+/// Occurrences of an already lowercased phrase, on word boundaries only
+fn phrase_matches<'a>(text_lower: &'a str, phrase_lower: &'a str) -> impl Iterator<Item = usize> + 'a {
+    text_lower.match_indices(phrase_lower).filter_map(move |(start, matched)| {
+        let end = start + matched.len();
+        let before_ok = text_lower[..start].chars().next_back().is_none_or(|c| !c.is_alphanumeric());
+        let after_ok = text_lower[end..].chars().next().is_none_or(|c| !c.is_alphanumeric());
+        (before_ok && after_ok).then_some(start)
+    })
+}
+
+/// Extract a command from text, leaving a marker for its action in place
 fn extract_command(text: &str, command: &str, action: &Action, actions: &mut Vec<Action>) -> String {
     let text_lower = text.to_lowercase();
-    let command_lower = command.to_lowercase();
-
-    if let Some(pos) = text_lower.find(&command_lower) {
-        actions.push(action.clone());
-        let mut result = String::new();
-        let before = &text[..pos];
-        result.push_str(before.trim_end());
-        let after = &text[pos + command.len()..];
-        if !result.is_empty() && !after.trim_start().is_empty() {
-            result.push(' ');
-        }
-        result.push_str(after.trim_start());
-        result
+
+    if let Some(pos) = find_phrase(&text_lower, command) {
+        mark_action(text, pos, pos + command.len(), action.clone(), actions)
     } else {
         text.to_string()
     }
@@ -343,7 +457,7 @@ fn replace_punctuation_command(text: &str, mapping: &PunctuationMapping) -> Stri
     let mut result = String::new();
     let mut last_end = 0;
 
-    for (start, _) in text_lower.match_indices(&command_lower) {
+    for start in phrase_matches(&text_lower, &command_lower) {
         let before = &text[last_end..start];
         let end = start + mapping.command.len();
         let after_start = if text[end..].starts_with(' ') { end + 1 } else { end };
@@ -357,11 +471,6 @@ fn replace_punctuation_command(text: &str, mapping: &PunctuationMapping) -> Stri
                     result.push(' ');
                 }
             }
-            SpacingRule::CloseNoSpace => {
-                // Remove space before, no space after
-                result.push_str(before.trim_end());
-                result.push_str(mapping.replacement);
-            }
             SpacingRule::OpenNoSpace => {
                 // Keep space before, remove space after
                 result.push_str(before);
@@ -375,6 +484,35 @@ fn replace_punctuation_command(text: &str, mapping: &PunctuationMapping) -> Stri
                 result.push_str(before.trim_end());
                 result.push_str(mapping.replacement);
             }
+            SpacingRule::Spaced => {
+                // One space before (except at line start), one space after
+                result.push_str(before.trim_end());
+                if !result.is_empty() && !result.ends_with('\n') {
+                    result.push(' ');
+                }
+                result.push_str(mapping.replacement);
+                if after_start < text.len() {
+                    result.push(' ');
+                }
+            }
+            SpacingRule::Attached => {
+                // No space on either side
+                result.push_str(before.trim_end());
+                result.push_str(mapping.replacement);
+            }
+            SpacingRule::OpenCall => {
+                // Attached after an identifier or a closing bracket, spaced otherwise
+                let trimmed = before.trim_end();
+                let attach = trimmed
+                    .chars()
+                    .next_back()
+                    .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == ')' || c == ']');
+                result.push_str(if attach { trimmed } else { before });
+                if !attach && !result.is_empty() && !result.ends_with([' ', '\n']) {
+                    result.push(' ');
+                }
+                result.push_str(mapping.replacement);
+            }
         }
 
         last_end = after_start;
@@ -425,70 +563,70 @@ mod tests {
 
     #[test]
     fn test_punctuation_point() {
-        let result = parse("Bonjour point", DictationMode::General, &[], false);
+        let result = parse("Bonjour point", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "Bonjour.");
         assert!(result.actions.is_empty());
     }
 
     #[test]
     fn test_punctuation_virgule() {
-        let result = parse("un virgule deux virgule trois", DictationMode::General, &[], false);
+        let result = parse("un virgule deux virgule trois", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "un, deux, trois");
         assert!(result.actions.is_empty());
     }
 
     #[test]
     fn test_punctuation_question() {
-        let result = parse("Comment allez-vous point d'interrogation", DictationMode::General, &[], false);
-        assert_eq!(result.text, "Comment allez-vous?");
+        let result = parse("Comment allez-vous point d'interrogation", &ParseContext::new(DictationMode::General, "fr"));
+        assert_eq!(result.text, "Comment allez-vous\u{202F}?");
         assert!(result.actions.is_empty());
     }
 
     #[test]
     fn test_punctuation_exclamation() {
-        let result = parse("Super point d'exclamation", DictationMode::General, &[], false);
-        assert_eq!(result.text, "Super!");
+        let result = parse("Super point d'exclamation", &ParseContext::new(DictationMode::General, "fr"));
+        assert_eq!(result.text, "Super\u{202F}!");
         assert!(result.actions.is_empty());
     }
 
     #[test]
     fn test_punctuation_deux_points() {
-        let result = parse("Voici deux points la liste", DictationMode::General, &[], false);
-        assert_eq!(result.text, "Voici: la liste");
+        let result = parse("Voici deux points la liste", &ParseContext::new(DictationMode::General, "fr"));
+        assert_eq!(result.text, "Voici\u{00A0}: la liste");
         assert!(result.actions.is_empty());
     }
 
     #[test]
     fn test_punctuation_point_virgule() {
-        let result = parse("premier point virgule second", DictationMode::General, &[], false);
-        assert_eq!(result.text, "premier; second");
+        let result = parse("premier point virgule second", &ParseContext::new(DictationMode::General, "fr"));
+        assert_eq!(result.text, "premier\u{202F}; second");
         assert!(result.actions.is_empty());
     }
 
     #[test]
     fn test_punctuation_parentheses() {
-        let result = parse("texte ouvrir parenthèse note fermer parenthèse suite", DictationMode::General, &[], false);
+        let result = parse("texte ouvrir parenthèse note fermer parenthèse suite", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "texte (note) suite");
         assert!(result.actions.is_empty());
     }
 
     #[test]
     fn test_punctuation_guillemets() {
-        let result = parse("il a dit ouvrir guillemets bonjour fermer guillemets", DictationMode::General, &[], false);
-        assert_eq!(result.text, "il a dit \u{00AB}bonjour\u{00BB}");
+        let result = parse("il a dit ouvrir guillemets bonjour fermer guillemets", &ParseContext::new(DictationMode::General, "fr"));
+        assert_eq!(result.text, "il a dit \u{00AB}\u{00A0}bonjour\u{00A0}\u{00BB}");
         assert!(result.actions.is_empty());
     }
 
     #[test]
     fn test_punctuation_a_la_ligne() {
-        let result = parse("première ligne à la ligne deuxième ligne", DictationMode::General, &[], false);
+        let result = parse("première ligne à la ligne deuxième ligne", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "première ligne\ndeuxième ligne");
         assert!(result.actions.is_empty());
     }
 
     #[test]
     fn test_punctuation_nouveau_paragraphe() {
-        let result = parse("premier paragraphe nouveau paragraphe second paragraphe", DictationMode::General, &[], false);
+        let result = parse("premier paragraphe nouveau paragraphe second paragraphe", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "premier paragraphe\n\nsecond paragraphe");
         assert!(result.actions.is_empty());
     }
@@ -496,106 +634,130 @@ mod tests {
     #[test]
     fn test_case_insensitive() {
         // Commands are case-insensitive, but the surrounding text keeps its original case
-        let result = parse("Bonjour POINT comment allez-vous Point D'Interrogation", DictationMode::General, &[], false);
-        assert_eq!(result.text, "Bonjour. comment allez-vous?");
+        let result = parse("Bonjour POINT comment allez-vous Point D'Interrogation", &ParseContext::new(DictationMode::General, "fr"));
+        assert_eq!(result.text, "Bonjour. comment allez-vous\u{202F}?");
         assert!(result.actions.is_empty());
     }
 
     #[test]
     fn test_edit_command_efface() {
-        let result = parse("texte commande efface", DictationMode::General, &[], false);
+        let result = parse("texte commande efface", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "texte");
         assert_eq!(result.actions, vec![Action::Delete]);
     }
 
     #[test]
     fn test_edit_command_annuler() {
-        let result = parse("erreur commande annuler", DictationMode::General, &[], false);
+        let result = parse("erreur commande annuler", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "erreur");
         assert_eq!(result.actions, vec![Action::Undo]);
     }
 
     #[test]
     fn test_edit_command_tout_effacer() {
-        let result = parse("commande tout effacer", DictationMode::General, &[], false);
+        let result = parse("commande tout effacer", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "");
         assert_eq!(result.actions, vec![Action::ClearAll]);
     }
 
     #[test]
     fn test_edit_command_majuscules() {
-        let result = parse("titre commande majuscules", DictationMode::General, &[], false);
+        let result = parse("titre commande majuscules", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "titre");
         assert_eq!(result.actions, vec![Action::Uppercase]);
     }
 
     #[test]
     fn test_edit_command_copier() {
-        let result = parse("texte important commande copier", DictationMode::General, &[], false);
+        let result = parse("texte important commande copier", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "texte important");
         assert_eq!(result.actions, vec![Action::Copy]);
     }
 
     #[test]
     fn test_edit_command_stop() {
-        let result = parse("fini commande stop", DictationMode::General, &[], false);
+        let result = parse("fini commande stop", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "fini");
         assert_eq!(result.actions, vec![Action::Stop]);
     }
 
     #[test]
     fn test_email_mode_signature() {
-        let result = parse("Cordialement commande signature", DictationMode::Email, &[], false);
+        let result = parse("Cordialement commande signature", &ParseContext::new(DictationMode::Email, "fr"));
         assert_eq!(result.text, "Cordialement");
         assert_eq!(result.actions, vec![Action::InsertSignature]);
     }
 
     #[test]
     fn test_email_mode_formule_politesse() {
-        let result = parse("commande formule politesse", DictationMode::Email, &[], false);
+        let result = parse("commande formule politesse", &ParseContext::new(DictationMode::Email, "fr"));
         assert_eq!(result.text, "");
         assert_eq!(result.actions, vec![Action::InsertGreeting]);
     }
 
     #[test]
     fn test_email_commands_not_in_general_mode() {
-        let result = parse("commande signature", DictationMode::General, &[], false);
+        let result = parse("commande signature", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "commande signature");
         assert!(result.actions.is_empty());
     }
 
     #[test]
     fn test_code_mode_fonction() {
-        let result = parse("commande fonction", DictationMode::Code, &[], false);
+        let result = parse("commande fonction", &ParseContext::new(DictationMode::Code, "fr"));
         assert_eq!(result.text, "");
         assert_eq!(result.actions, vec![Action::InsertFunction]);
     }
 
     #[test]
     fn test_code_mode_commentaire() {
-        let result = parse("commande commentaire", DictationMode::Code, &[], false);
+        let result = parse("commande commentaire", &ParseContext::new(DictationMode::Code, "fr"));
         assert_eq!(result.text, "");
         assert_eq!(result.actions, vec![Action::InsertComment]);
     }
 
     #[test]
     fn test_notes_mode_puce() {
-        let result = parse("commande puce premier élément", DictationMode::Notes, &[], false);
-        assert_eq!(result.text, "premier élément");
-        assert_eq!(result.actions, vec![Action::InsertBullet]);
+        let result = parse("commande puce premier élément", &ParseContext::new(DictationMode::Notes, "fr"));
+        assert_eq!(result.text, "- premier élément");
+        assert!(result.actions.is_empty());
     }
 
     #[test]
     fn test_notes_mode_titre() {
-        let result = parse("commande titre Introduction", DictationMode::Notes, &[], false);
-        assert_eq!(result.text, "Introduction");
-        assert_eq!(result.actions, vec![Action::InsertTitle]);
+        let result = parse("commande titre Introduction", &ParseContext::new(DictationMode::Notes, "fr"));
+        assert_eq!(result.text, "# Introduction");
+        assert!(result.actions.is_empty());
+    }
+
+    #[test]
+    fn test_notes_mode_markdown_structure() {
+        let result = parse(
+            "commande titre deux Courses commande puce pain commande sous-puce complet commande puce lait \
+             commande numéro appeler commande numéro commande gras réserver commande fin gras la salle point \
+             commande case à cocher payer",
+            &ParseContext::new(DictationMode::Notes, "fr"),
+        );
+        assert_eq!(
+            result.text,
+            "## Courses\n- pain\n    - complet\n- lait\n1. appeler\n2. **réserver** la salle.\n- [ ] payer"
+        );
+
+        let result = parse(
+            "see command link the docs command url docs.rs command end link command code block cargo test command end code block done",
+            &ParseContext::new(DictationMode::Notes, "en"),
+        );
+        assert_eq!(result.text, "see [the docs](docs.rs)\n```\ncargo test\n```\ndone");
+
+        // Hors du mode Notes, "command bold" met la sélection en gras
+        let result = parse("command bold", &ParseContext::new(DictationMode::General, "en"));
+        assert_eq!(result.actions, vec![Action::FormatBold]);
     }
 
     #[test]
     fn test_multiple_punctuation_and_command() {
-        let result = parse("Bonjour point Comment ça va point d'interrogation commande copier", DictationMode::General, &[], false);
-        assert_eq!(result.text, "Bonjour. Comment ça va?");
+        let result = parse("Bonjour point Comment ça va point d'interrogation commande copier", &ParseContext::new(DictationMode::General, "fr"));
+        assert_eq!(result.text, "Bonjour. Comment ça va\u{202F}?");
         assert_eq!(result.actions, vec![Action::Copy]);
     }
 
@@ -603,9 +765,7 @@ mod tests {
     fn test_complex_sentence() {
         let result = parse(
             "Cher Monsieur virgule à la ligne Je vous écris pour vous informer que ouvrir parenthèse voir détails ci-dessous fermer parenthèse point nouveau paragraphe Cordialement",
-            DictationMode::General,
-            &[],
-            false,
+            &ParseContext::new(DictationMode::General, "fr"),
         );
         assert_eq!(
             result.text,
@@ -616,20 +776,20 @@ mod tests {
 
     #[test]
     fn test_whitespace_cleanup() {
-        let result = parse("texte   avec   espaces", DictationMode::General, &[], false);
+        let result = parse("texte   avec   espaces", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "texte avec espaces");
     }
 
     #[test]
     fn test_empty_input() {
-        let result = parse("", DictationMode::General, &[], false);
+        let result = parse("", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "");
         assert!(result.actions.is_empty());
     }
 
     #[test]
     fn test_no_commands() {
-        let result = parse("Texte normal sans commandes", DictationMode::General, &[], false);
+        let result = parse("Texte normal sans commandes", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "Texte normal sans commandes");
         assert!(result.actions.is_empty());
     }
@@ -638,48 +798,48 @@ mod tests {
 
     #[test]
     fn test_open_app_ouvre() {
-        let result = parse("ouvre Safari", DictationMode::General, &[], false);
+        let result = parse("ouvre Safari", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "");
         assert_eq!(result.actions, vec![Action::OpenApp("Safari".to_string())]);
     }
 
     #[test]
     fn test_open_app_lance() {
-        let result = parse("lance Spotify", DictationMode::General, &[], false);
+        let result = parse("lance Spotify", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "");
         assert_eq!(result.actions, vec![Action::OpenApp("Spotify".to_string())]);
     }
 
     #[test]
     fn test_open_app_mets() {
-        let result = parse("mets Spotify", DictationMode::General, &[], false);
+        let result = parse("mets Spotify", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "");
         assert_eq!(result.actions, vec![Action::OpenApp("Spotify".to_string())]);
     }
 
     #[test]
     fn test_open_app_with_surrounding_text() {
-        let result = parse("je veux ouvre Safari merci", DictationMode::General, &[], false);
+        let result = parse("je veux ouvre Safari merci", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "je veux merci");
         assert_eq!(result.actions, vec![Action::OpenApp("Safari".to_string())]);
     }
 
     #[test]
     fn test_open_app_case_insensitive() {
-        let result = parse("Ouvre safari", DictationMode::General, &[], false);
+        let result = parse("Ouvre safari", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.actions, vec![Action::OpenApp("safari".to_string())]);
     }
 
     #[test]
     fn test_open_app_demarre() {
-        let result = parse("démarre Firefox", DictationMode::General, &[], false);
+        let result = parse("démarre Firefox", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "");
         assert_eq!(result.actions, vec![Action::OpenApp("Firefox".to_string())]);
     }
 
     #[test]
     fn test_open_app_trigger_alone_no_crash() {
-        let result = parse("ouvre", DictationMode::General, &[], false);
+        let result = parse("ouvre", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "ouvre");
         assert!(result.actions.is_empty());
     }
@@ -688,21 +848,21 @@ mod tests {
 
     #[test]
     fn test_format_bold() {
-        let result = parse("texte mets en gras", DictationMode::General, &[], false);
+        let result = parse("texte mets en gras", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "texte");
         assert_eq!(result.actions, vec![Action::FormatBold]);
     }
 
     #[test]
     fn test_format_italic() {
-        let result = parse("texte en italique", DictationMode::General, &[], false);
+        let result = parse("texte en italique", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "texte");
         assert_eq!(result.actions, vec![Action::FormatItalic]);
     }
 
     #[test]
     fn test_format_underline() {
-        let result = parse("texte commande souligné", DictationMode::General, &[], false);
+        let result = parse("texte commande souligné", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "texte");
         assert_eq!(result.actions, vec![Action::FormatUnderline]);
     }
@@ -711,56 +871,56 @@ mod tests {
 
     #[test]
     fn test_system_screenshot_enabled() {
-        let result = parse("commande screenshot", DictationMode::General, &[], true);
+        let result = parse("commande screenshot", &ParseContext { system_commands_enabled: true, ..ParseContext::new(DictationMode::General, "fr") });
         assert_eq!(result.text, "");
         assert_eq!(result.actions, vec![Action::Screenshot]);
     }
 
     #[test]
     fn test_system_screenshot_disabled() {
-        let result = parse("commande screenshot", DictationMode::General, &[], false);
+        let result = parse("commande screenshot", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "commande screenshot");
         assert!(result.actions.is_empty());
     }
 
     #[test]
     fn test_system_lock_screen() {
-        let result = parse("commande verrouille", DictationMode::General, &[], true);
+        let result = parse("commande verrouille", &ParseContext { system_commands_enabled: true, ..ParseContext::new(DictationMode::General, "fr") });
         assert_eq!(result.text, "");
         assert_eq!(result.actions, vec![Action::LockScreen]);
     }
 
     #[test]
     fn test_system_dnd() {
-        let result = parse("commande ne pas déranger", DictationMode::General, &[], true);
+        let result = parse("commande ne pas déranger", &ParseContext { system_commands_enabled: true, ..ParseContext::new(DictationMode::General, "fr") });
         assert_eq!(result.text, "");
         assert_eq!(result.actions, vec![Action::ToggleDND]);
     }
 
     #[test]
     fn test_system_volume() {
-        let result = parse("commande volume 50", DictationMode::General, &[], true);
+        let result = parse("commande volume 50", &ParseContext { system_commands_enabled: true, ..ParseContext::new(DictationMode::General, "fr") });
         assert_eq!(result.text, "");
         assert_eq!(result.actions, vec![Action::SetVolume(50)]);
     }
 
     #[test]
     fn test_system_volume_with_a() {
-        let result = parse("commande volume à 75", DictationMode::General, &[], true);
+        let result = parse("commande volume à 75", &ParseContext { system_commands_enabled: true, ..ParseContext::new(DictationMode::General, "fr") });
         assert_eq!(result.text, "");
         assert_eq!(result.actions, vec![Action::SetVolume(75)]);
     }
 
     #[test]
     fn test_system_volume_capped_at_100() {
-        let result = parse("commande volume 200", DictationMode::General, &[], true);
+        let result = parse("commande volume 200", &ParseContext { system_commands_enabled: true, ..ParseContext::new(DictationMode::General, "fr") });
         assert_eq!(result.text, "");
         assert_eq!(result.actions, vec![Action::SetVolume(100)]);
     }
 
     #[test]
     fn test_system_volume_disabled() {
-        let result = parse("commande volume 50", DictationMode::General, &[], false);
+        let result = parse("commande volume 50", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "commande volume 50");
         assert!(result.actions.is_empty());
     }
@@ -768,8 +928,199 @@ mod tests {
     #[test]
     fn test_format_bold_does_not_trigger_mets_app() {
         // "mets en gras" should trigger FormatBold, not OpenApp("en")
-        let result = parse("mets en gras", DictationMode::General, &[], false);
+        let result = parse("mets en gras", &ParseContext::new(DictationMode::General, "fr"));
         assert_eq!(result.text, "");
         assert_eq!(result.actions, vec![Action::FormatBold]);
     }
+
+    // Language pack tests
+
+    #[test]
+    fn test_english_punctuation_has_no_space_before() {
+        let result = parse("How are you question mark great exclamation mark", &ParseContext::new(DictationMode::General, "en"));
+        assert_eq!(result.text, "How are you? great!");
+        assert!(result.actions.is_empty());
+    }
+
+    #[test]
+    fn test_english_commands() {
+        let result = parse("first line new line second comma third command copy", &ParseContext::new(DictationMode::General, "en"));
+        assert_eq!(result.text, "first line\nsecond, third");
+        assert_eq!(result.actions, vec![Action::Copy]);
+    }
+
+    #[test]
+    fn test_english_does_not_match_inside_words() {
+        let result = parse("the command line is periodic", &ParseContext::new(DictationMode::General, "en"));
+        assert_eq!(result.text, "the command line is periodic");
+        assert!(result.actions.is_empty());
+    }
+
+    #[test]
+    fn test_english_ignores_french_phrases() {
+        let result = parse("Bonjour point", &ParseContext::new(DictationMode::General, "en"));
+        assert_eq!(result.text, "Bonjour point");
+    }
+
+    #[test]
+    fn test_english_volume() {
+        let result = parse("command volume to 30", &ParseContext { system_commands_enabled: true, ..ParseContext::new(DictationMode::General, "en-US") });
+        assert_eq!(result.text, "");
+        assert_eq!(result.actions, vec![Action::SetVolume(30)]);
+    }
+
+    #[test]
+    fn test_german_pack() {
+        let result = parse("Hallo Komma wie geht es Fragezeichen Befehl löschen", &ParseContext::new(DictationMode::General, "de"));
+        assert_eq!(result.text, "Hallo, wie geht es?");
+        assert_eq!(result.actions, vec![Action::Delete]);
+    }
+
+    #[test]
+    fn test_german_punkt_not_in_doppelpunkt() {
+        let result = parse("Liste Doppelpunkt eins Punkt", &ParseContext::new(DictationMode::General, "de"));
+        assert_eq!(result.text, "Liste: eins.");
+    }
+
+    #[test]
+    fn test_spanish_inverted_question_mark() {
+        let result = parse("abrir interrogación qué tal cerrar interrogación", &ParseContext::new(DictationMode::General, "es"));
+        assert_eq!(result.text, "\u{00BF}qué tal?");
+    }
+
+    #[test]
+    fn test_italian_pack() {
+        let result = parse("ciao virgola come stai punto interrogativo", &ParseContext::new(DictationMode::Notes, "it"));
+        assert_eq!(result.text, "ciao, come stai?");
+    }
+
+    #[test]
+    fn test_unknown_language_falls_back_to_french() {
+        let result = parse("Bonjour point", &ParseContext::new(DictationMode::General, "auto"));
+        assert_eq!(result.text, "Bonjour.");
+    }
+
+    // User command tests
+
+    fn user_commands(json: &str) -> UserCommands {
+        UserCommands::from_json(json).unwrap()
+    }
+
+    #[test]
+    fn test_user_command_overrides_builtin() {
+        let user = user_commands(
+            r#"{ "commands": [ { "phrases": ["commande copier"], "action": { "type": "shortcut", "keys": "ctrl+shift+c" } } ] }"#,
+        );
+        let result = parse_with("texte commande copier", &ParseContext::new(DictationMode::General, "fr"), &user);
+        assert_eq!(result.text, "texte");
+        assert_eq!(result.actions, vec![Action::Shortcut(KeyCombo::parse("ctrl+shift+c").unwrap())]);
+    }
+
+    #[test]
+    fn test_disabled_builtin_is_kept_as_text() {
+        let user = user_commands(r#"{ "disabled_builtins": ["virgule", "commande stop"] }"#);
+        let result = parse_with("un virgule deux point commande stop", &ParseContext::new(DictationMode::General, "fr"), &user);
+        assert_eq!(result.text, "un virgule deux. commande stop");
+        assert!(result.actions.is_empty());
+    }
+
+    #[test]
+    fn test_contains_stop_command() {
+        assert!(contains_stop_command("bon commande stop", "fr"));
+        assert!(contains_stop_command("that's all command stop", "en"));
+        assert!(!contains_stop_command("stop", "fr"));
+    }
+
+    #[test]
+    fn test_tokens_keep_spoken_order() {
+        let result = parse("hello world commande efface new sentence", &ParseContext::new(DictationMode::General, "fr"));
+        assert_eq!(
+            result.tokens,
+            vec![
+                Token::Text("hello world".to_string()),
+                Token::Action(Action::Delete),
+                Token::Text("new sentence".to_string()),
+            ]
+        );
+        assert_eq!(result.text, "hello world new sentence");
+    }
+
+    #[test]
+    fn test_tokens_mixed_sequence() {
+        let result = parse(
+            "Bonjour virgule commande copier ça va point commande volume à 30 fin",
+            &ParseContext { system_commands_enabled: true, ..ParseContext::new(DictationMode::General, "fr") },
+        );
+        assert_eq!(
+            result.tokens,
+            vec![
+                Token::Text("Bonjour,".to_string()),
+                Token::Action(Action::Copy),
+                Token::Text(" ça va.".to_string()),
+                Token::Action(Action::SetVolume(30)),
+                Token::Text(" fin".to_string()),
+            ]
+        );
+        assert_eq!(result.actions, vec![Action::Copy, Action::SetVolume(30)]);
+        assert_eq!(result.text, "Bonjour, ça va. fin");
+    }
+
+    #[test]
+    fn test_tokens_actions_in_spoken_order() {
+        // Extraites par des tables différentes, les actions restent dans l'ordre dicté
+        let result = parse(
+            "command clear all launch Safari then command copy",
+            &ParseContext::new(DictationMode::General, "en"),
+        );
+        assert_eq!(
+            result.actions,
+            vec![Action::ClearAll, Action::OpenApp("Safari".to_string()), Action::Copy]
+        );
+        assert_eq!(
+            result.tokens,
+            vec![
+                Token::Action(Action::ClearAll),
+                Token::Action(Action::OpenApp("Safari".to_string())),
+                Token::Text("then".to_string()),
+                Token::Action(Action::Copy),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_code_symbols_and_casing() {
+        let result = parse(
+            "let camel case user id equals ten semicolon",
+            &ParseContext::new(DictationMode::Code, "en"),
+        );
+        assert_eq!(result.text, "let userId = ten;");
+
+        let result = parse(
+            "fn main open paren close paren arrow result open brace new line indent ok",
+            &ParseContext { code_language: CodeLanguage::Rust, ..ParseContext::new(DictationMode::Code, "en") },
+        );
+        assert_eq!(result.text, "fn main() -> result {\n    ok");
+
+        let result = parse(
+            "const add equals open paren a close paren arrow a",
+            &ParseContext { code_language: CodeLanguage::TypeScript, ..ParseContext::new(DictationMode::Code, "en") },
+        );
+        assert_eq!(result.text, "const add = (a) => a");
+
+        // Symboles français et anglais ensemble, casse en fin de phrase
+        let result = parse(
+            "si self dot snake case max size égal égal zéro accolade ouvrante",
+            &ParseContext { code_language: CodeLanguage::Python, ..ParseContext::new(DictationMode::Code, "fr") },
+        );
+        assert_eq!(result.text, "si self.max_size == zéro {");
+    }
+
+    #[test]
+    fn test_code_symbols_only_in_code_mode() {
+        let result = parse("x equals y dot z", &ParseContext { code_language: CodeLanguage::Rust, ..ParseContext::new(DictationMode::General, "en") });
+        assert_eq!(result.text, "x equals y dot z");
+
+        let result = parse("command class", &ParseContext { code_language: CodeLanguage::Rust, ..ParseContext::new(DictationMode::Code, "en") });
+        assert_eq!(result.actions, vec![Action::InsertClass]);
+    }
 }
@@ -0,0 +1,628 @@
+//! Commandes vocales définies par l'utilisateur
+//!
+//! Fichier `voice_commands.json` dans le dossier de données : phrases, alias
+//! et motifs regex associés à une action intégrée, un raccourci clavier, un
+//! snippet, une commande externe ou un texte de remplacement. Les commandes
+//! utilisateur passent avant les commandes intégrées (elles peuvent donc les
+//! surcharger), et `disabled_builtins` désactive des phrases intégrées.
+//! Le fichier est rechargé à chaud dès que sa date de modification change.
+
+use super::code;
+use super::notes;
+use super::packs;
+use super::parser::mark_action;
+use super::Action;
+use crate::platform::KeyCombo;
+use crate::types::DictationMode;
+use regex::{Captures, Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Emitter};
+
+const FILE_NAME: &str = "voice_commands.json";
+const WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Syntaxe qui n'a de sens que pour un shell : refusée dans une commande
+const SHELL_OPERATORS: &[&str] = &[";", "|", "&", "<", ">", "`", "$("];
+
+/// Caractères refusés dans une capture injectée dans une commande
+const SHELL_METACHARACTERS: &[char] = &[
+    ';', '|', '&', '$', '`', '<', '>', '(', ')', '{', '}', '\\', '"', '\'', '*', '?', '~', '\n', '\r',
+];
+
+/// Contenu du fichier utilisateur
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UserCommandFile {
+    /// Phrases intégrées à désactiver (ex : "virgule", "commande stop")
+    pub disabled_builtins: Vec<String>,
+    pub commands: Vec<UserCommandEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserCommandEntry {
+    /// Phrase et alias, reconnus sur des mots entiers sans tenir compte de la casse
+    #[serde(default)]
+    pub phrases: Vec<String>,
+    /// Motif regex (insensible à la casse). Les captures sont utilisables dans
+    /// l'action via `$1`, `${name}`...
+    #[serde(default)]
+    pub pattern: Option<String>,
+    pub action: UserAction,
+    /// Langues concernées (vide : toutes)
+    #[serde(default)]
+    pub languages: Vec<String>,
+    /// Modes de dictée concernés (vide : tous)
+    #[serde(default)]
+    pub modes: Vec<DictationMode>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum UserAction {
+    /// Action intégrée ("undo", "open_app" + argument "Firefox"...)
+    Builtin {
+        action: String,
+        #[serde(default)]
+        argument: Option<String>,
+    },
+    /// Combinaison de touches ("ctrl+shift+k", "primary+s")
+    Shortcut { keys: String },
+    /// Insère le snippet de ce déclencheur
+    Snippet { trigger: String },
+    /// Programme lancé en arrière-plan, sans shell : la commande est découpée
+    /// en arguments (guillemets acceptés) et chaque capture reste un argument
+    Shell { command: String },
+    /// Remplace la phrase par ce texte dans la transcription
+    Text { text: String },
+}
+
+/// État du fichier, affiché dans les réglages
+#[derive(Debug, Clone, Serialize)]
+pub struct UserCommandsStatus {
+    pub path: String,
+    pub exists: bool,
+    pub command_count: usize,
+    pub disabled_builtin_count: usize,
+    pub errors: Vec<String>,
+}
+
+enum Matcher {
+    Phrases(Vec<String>),
+    Pattern(Regex),
+}
+
+struct CompiledCommand {
+    matcher: Matcher,
+    action: UserAction,
+    languages: Vec<String>,
+    modes: Vec<DictationMode>,
+}
+
+/// Commandes utilisateur validées, prêtes pour le parser
+#[derive(Default)]
+pub struct UserCommands {
+    commands: Vec<CompiledCommand>,
+    /// Phrases intégrées désactivées, en minuscules
+    disabled: HashSet<String>,
+}
+
+struct Loaded {
+    commands: Arc<UserCommands>,
+    status: UserCommandsStatus,
+    modified: Option<SystemTime>,
+}
+
+static LOADED: Mutex<Option<Loaded>> = Mutex::new(None);
+
+pub fn file_path() -> PathBuf {
+    crate::storage::get_app_data_dir().join(FILE_NAME)
+}
+
+/// Commandes actuellement chargées (vides tant que `init` n'a pas été appelé)
+pub(super) fn current() -> Arc<UserCommands> {
+    LOADED
+        .lock()
+        .ok()
+        .and_then(|loaded| loaded.as_ref().map(|l| l.commands.clone()))
+        .unwrap_or_default()
+}
+
+pub fn status() -> UserCommandsStatus {
+    if let Some(loaded) = LOADED.lock().ok().and_then(|l| l.as_ref().map(|l| l.status.clone())) {
+        return loaded;
+    }
+    reload()
+}
+
+/// Recharge le fichier depuis le disque
+pub fn reload() -> UserCommandsStatus {
+    let path = file_path();
+    let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+    let exists = path.exists();
+
+    let (commands, errors) = if exists {
+        match std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| serde_json::from_str::<UserCommandFile>(&content).map_err(|e| e.to_string()))
+        {
+            Ok(file) => UserCommands::compile(&file),
+            Err(e) => (UserCommands::default(), vec![format!("{}: {}", FILE_NAME, e)]),
+        }
+    } else {
+        (UserCommands::default(), Vec::new())
+    };
+
+    for error in &errors {
+        log::warn!("[VOICE_CMD] {}", error);
+    }
+    log::info!(
+        "[VOICE_CMD] Loaded {} user command(s), {} disabled built-in(s)",
+        commands.commands.len(),
+        commands.disabled.len()
+    );
+
+    let status = UserCommandsStatus {
+        path: path.to_string_lossy().to_string(),
+        exists,
+        command_count: commands.commands.len(),
+        disabled_builtin_count: commands.disabled.len(),
+        errors,
+    };
+
+    if let Ok(mut loaded) = LOADED.lock() {
+        *loaded = Some(Loaded {
+            commands: Arc::new(commands),
+            status: status.clone(),
+            modified,
+        });
+    }
+    status
+}
+
+/// Crée un fichier vide s'il n'existe pas encore et retourne son chemin
+pub fn ensure_file() -> Result<String, String> {
+    let path = file_path();
+    if !path.exists() {
+        crate::storage::ensure_app_data_dir().map_err(|e| e.to_string())?;
+        crate::storage::write_json(&path, &UserCommandFile::default())?;
+        reload();
+    }
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Charge le fichier puis surveille ses modifications (rechargement à chaud).
+/// Émet `voice-commands-reloaded` avec le nouvel état à chaque changement.
+pub fn init(app: AppHandle) {
+    reload();
+
+    std::thread::spawn(move || loop {
+        std::thread::sleep(WATCH_INTERVAL);
+
+        let path = file_path();
+        let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+        let known = LOADED.lock().ok().and_then(|l| l.as_ref().map(|l| l.modified));
+        if known == Some(modified) {
+            continue;
+        }
+
+        log::info!("[VOICE_CMD] {} changed, reloading", FILE_NAME);
+        let status = reload();
+        let _ = app.emit("voice-commands-reloaded", &status);
+    });
+}
+
+impl UserCommands {
+    /// Valide le fichier. Les entrées invalides sont ignorées et décrites
+    /// dans la liste d'erreurs ; les autres sont appliquées.
+    fn compile(file: &UserCommandFile) -> (Self, Vec<String>) {
+        let mut errors = Vec::new();
+        let mut commands = Vec::new();
+
+        for (index, entry) in file.commands.iter().enumerate() {
+            match compile_entry(entry) {
+                Ok(command) => commands.push(command),
+                Err(e) => errors.push(format!("commands[{}]: {}", index, e)),
+            }
+        }
+
+        let mut disabled = HashSet::new();
+        for phrase in &file.disabled_builtins {
+            let phrase = phrase.trim().to_lowercase();
+            if packs::is_builtin_phrase(&phrase) || code::is_code_phrase(&phrase) || notes::is_notes_phrase(&phrase) {
+                disabled.insert(phrase);
+            } else {
+                errors.push(format!("disabled_builtins: '{}' is not a built-in command", phrase));
+            }
+        }
+
+        (Self { commands, disabled }, errors)
+    }
+
+    #[cfg(test)]
+    pub(super) fn from_json(json: &str) -> Result<Self, String> {
+        let file: UserCommandFile = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        Ok(Self::compile(&file).0)
+    }
+
+    pub(super) fn is_disabled(&self, builtin_phrase: &str) -> bool {
+        !self.disabled.is_empty() && self.disabled.contains(&builtin_phrase.to_lowercase())
+    }
+
+    /// Applique les commandes utilisateur : les remplacements de texte sont
+    /// faits sur place, les autres commandes sont remplacées par un marqueur
+    /// d'action (voir `parser::mark_action`).
+    pub(super) fn apply(
+        &self,
+        text: &str,
+        language: &str,
+        mode: DictationMode,
+        actions: &mut Vec<Action>,
+    ) -> String {
+        let mut text = text.to_string();
+
+        for command in &self.commands {
+            if !command.languages.is_empty() && !command.languages.iter().any(|l| l == language) {
+                continue;
+            }
+            if !command.modes.is_empty() && !command.modes.contains(&mode) {
+                continue;
+            }
+
+            match &command.matcher {
+                Matcher::Phrases(phrases) => {
+                    for phrase in phrases {
+                        if let UserAction::Text { text: replacement } = &command.action {
+                            text = replace_phrase(&text, phrase, replacement);
+                        } else if let Some((pos, end)) = super::parser::find_phrase_range(&text, phrase) {
+                            text = match to_action(&command.action, None) {
+                                Some(action) => mark_action(&text, pos, end, action, actions),
+                                None => remove_range(&text, pos, end),
+                            };
+                            break;
+                        }
+                    }
+                }
+                Matcher::Pattern(regex) => {
+                    if let UserAction::Text { text: replacement } = &command.action {
+                        text = regex.replace_all(&text, replacement.as_str()).into_owned();
+                    } else if let Some(caps) = regex.captures(&text) {
+                        let range = caps.get(0).map(|m| m.range()).unwrap_or_default();
+                        text = match to_action(&command.action, Some(&caps)) {
+                            Some(action) => mark_action(&text, range.start, range.end, action, actions),
+                            None => remove_range(&text, range.start, range.end),
+                        };
+                    }
+                }
+            }
+        }
+
+        text
+    }
+}
+
+fn compile_entry(entry: &UserCommandEntry) -> Result<CompiledCommand, String> {
+    let phrases: Vec<String> = entry
+        .phrases
+        .iter()
+        .map(|p| p.trim().to_lowercase())
+        .filter(|p| !p.is_empty())
+        .collect();
+
+    let matcher = match (&entry.pattern, phrases.is_empty()) {
+        (Some(_), false) => return Err("use either 'phrases' or 'pattern', not both".to_string()),
+        (Some(pattern), true) => Matcher::Pattern(
+            RegexBuilder::new(pattern)
+                .case_insensitive(true)
+                .build()
+                .map_err(|e| format!("invalid pattern '{}': {}", pattern, e))?,
+        ),
+        (None, false) => Matcher::Phrases(phrases),
+        (None, true) => return Err("a command needs at least one phrase or a pattern".to_string()),
+    };
+
+    // Les captures ne sont connues qu'à l'exécution : validation complète
+    // seulement pour les actions sans `$`
+    match &entry.action {
+        UserAction::Builtin { action, argument } => {
+            let templated = argument.as_deref().is_some_and(|a| a.contains('$'));
+            let probe = if templated { Some("50") } else { argument.as_deref() };
+            builtin_action(action, probe)?;
+        }
+        UserAction::Shortcut { keys } => {
+            KeyCombo::parse(keys)?;
+        }
+        UserAction::Snippet { trigger } if trigger.trim().is_empty() => {
+            return Err("snippet trigger is empty".to_string());
+        }
+        UserAction::Shell { command } => {
+            split_command(command)?;
+        }
+        _ => {}
+    }
+
+    Ok(CompiledCommand {
+        matcher,
+        action: entry.action.clone(),
+        languages: entry.languages.iter().map(|l| l.to_lowercase()).collect(),
+        modes: entry.modes.clone(),
+    })
+}
+
+/// Action intégrée à partir de son nom (snake_case) et de son argument
+fn builtin_action(name: &str, argument: Option<&str>) -> Result<Action, String> {
+    let argument = argument.map(str::trim).filter(|a| !a.is_empty());
+    let action = match name {
+        "delete" => Action::Delete,
+        "undo" => Action::Undo,
+        "clear_all" => Action::ClearAll,
+        "uppercase" => Action::Uppercase,
+        "copy" => Action::Copy,
+        "stop" => Action::Stop,
+        "insert_signature" => Action::InsertSignature,
+        "insert_greeting" => Action::InsertGreeting,
+        "insert_function" => Action::InsertFunction,
+        "insert_comment" => Action::InsertComment,
+        "insert_class" => Action::InsertClass,
+        "insert_loop" => Action::InsertLoop,
+        "insert_condition" => Action::InsertCondition,
+        "insert_bullet" => Action::InsertBullet,
+        "insert_title" => Action::InsertTitle,
+        "toggle_dnd" => Action::ToggleDND,
+        "screenshot" => Action::Screenshot,
+        "lock_screen" => Action::LockScreen,
+        "format_bold" => Action::FormatBold,
+        "format_italic" => Action::FormatItalic,
+        "format_underline" => Action::FormatUnderline,
+        "open_app" => Action::OpenApp(
+            argument
+                .ok_or("open_app needs an 'argument' (application name)")?
+                .to_string(),
+        ),
+        "set_volume" => {
+            let level = argument
+                .ok_or("set_volume needs an 'argument' (0-100)")?
+                .parse::<u8>()
+                .map_err(|_| "set_volume argument must be a number between 0 and 100".to_string())?;
+            Action::SetVolume(level.min(100))
+        }
+        other => return Err(format!("unknown built-in action '{}'", other)),
+    };
+    Ok(action)
+}
+
+/// Action à exécuter, avec les captures du motif substituées
+fn to_action(action: &UserAction, caps: Option<&Captures>) -> Option<Action> {
+    let expand = |template: &str| -> String {
+        match caps {
+            Some(caps) => {
+                let mut out = String::new();
+                caps.expand(template, &mut out);
+                out
+            }
+            None => template.to_string(),
+        }
+    };
+
+    match action {
+        UserAction::Builtin { action, argument } => {
+            let argument = argument.as_deref().map(expand);
+            match builtin_action(action, argument.as_deref()) {
+                Ok(action) => Some(action),
+                Err(e) => {
+                    log::warn!("[VOICE_CMD] Ignoring command: {}", e);
+                    None
+                }
+            }
+        }
+        UserAction::Shortcut { keys } => KeyCombo::parse(keys).ok().map(Action::Shortcut),
+        UserAction::Snippet { trigger } => Some(Action::InsertSnippet(expand(trigger))),
+        UserAction::Shell { command } => {
+            // Une capture n'apporte que du texte, jamais de syntaxe de shell
+            let hostile = caps.and_then(|caps| {
+                caps.iter().skip(1).flatten().find(|m| m.as_str().contains(SHELL_METACHARACTERS))
+            });
+            if let Some(hostile) = hostile {
+                log::warn!(
+                    "[VOICE_CMD] Ignoring command: captured text '{}' contains shell metacharacters",
+                    crate::privacy::redact(hostile.as_str())
+                );
+                return None;
+            }
+            let mut argv = Vec::new();
+            for arg in split_command(command).ok()? {
+                let value = expand(&arg);
+                // Une capture ne devient jamais une option du programme ("ouvre --help")
+                if value.starts_with('-') && !arg.starts_with('-') {
+                    log::warn!(
+                        "[VOICE_CMD] Ignoring command: captured argument '{}' starts with '-'",
+                        crate::privacy::redact(&value)
+                    );
+                    return None;
+                }
+                argv.push(value);
+            }
+            Some(Action::Shell(argv))
+        }
+        UserAction::Text { .. } => None,
+    }
+}
+
+/// Découpe une commande en programme + arguments (règles de guillemets POSIX).
+/// Elle est lancée sans shell : les opérateurs et substitutions sont refusés
+/// plutôt que passés silencieusement en arguments.
+fn split_command(command: &str) -> Result<Vec<String>, String> {
+    let argv = shlex::split(command).ok_or_else(|| format!("command '{}' has unbalanced quotes", command))?;
+    let program = argv.first().ok_or("shell command is empty")?;
+    if program.contains('$') {
+        return Err("the program of a command cannot come from a capture".to_string());
+    }
+    for arg in &argv {
+        if let Some(operator) = SHELL_OPERATORS.iter().find(|op| arg.contains(*op)) {
+            return Err(format!("'{}' needs a shell; commands run without one", operator));
+        }
+    }
+    Ok(argv)
+}
+
+/// Remplace toutes les occurrences (mots entiers, sans casse) de `phrase`
+fn replace_phrase(text: &str, phrase: &str, replacement: &str) -> String {
+    let mut text = text.to_string();
+    let mut from = 0;
+    while let Some((start, end)) = super::parser::find_phrase_range(&text[from..], phrase) {
+        text.replace_range(from + start..from + end, replacement);
+        from += start + replacement.len();
+    }
+    text
+}
+
+/// Retire `[start, end)` du texte en recollant les deux côtés avec un espace
+fn remove_range(text: &str, start: usize, end: usize) -> String {
+    let mut result = text[..start].trim_end().to_string();
+    let after = text[end..].trim_start();
+    if !result.is_empty() && !after.is_empty() {
+        result.push(' ');
+    }
+    result.push_str(after);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::voice_commands::parser::assemble;
+
+    fn compile(json: &str) -> (UserCommands, Vec<String>) {
+        UserCommands::compile(&serde_json::from_str(json).unwrap())
+    }
+
+    #[test]
+    fn test_validation_reports_invalid_entries() {
+        let (commands, errors) = compile(
+            r#"{
+                "disabled_builtins": ["virgule", "not a command"],
+                "commands": [
+                    { "phrases": ["bonne nuit"], "action": { "type": "builtin", "action": "lock_screen" } },
+                    { "phrases": ["x"], "action": { "type": "builtin", "action": "explode" } },
+                    { "pattern": "([", "action": { "type": "text", "text": "" } },
+                    { "phrases": ["sauve"], "action": { "type": "shortcut", "keys": "hyper+s" } },
+                    { "action": { "type": "shell", "command": "ls" } }
+                ]
+            }"#,
+        );
+
+        assert_eq!(commands.commands.len(), 1);
+        assert!(commands.is_disabled("Virgule"));
+        assert_eq!(errors.len(), 5);
+        assert!(errors[0].starts_with("commands[1]"));
+    }
+
+    #[test]
+    fn test_phrase_and_pattern_actions() {
+        let (commands, errors) = compile(
+            r#"{ "commands": [
+                { "phrases": ["sauvegarde", "enregistre"], "action": { "type": "shortcut", "keys": "ctrl+s" } },
+                { "pattern": "volume (\\d+) pour cent", "action": { "type": "builtin", "action": "set_volume", "argument": "$1" } },
+                { "phrases": ["mon adresse"], "action": { "type": "text", "text": "12 rue de la Paix" } }
+            ] }"#,
+        );
+        assert!(errors.is_empty(), "{:?}", errors);
+
+        let mut actions = Vec::new();
+        let text = commands.apply(
+            "envoie à mon adresse Volume 40 pour cent puis enregistre",
+            "fr",
+            DictationMode::General,
+            &mut actions,
+        );
+        let result = assemble(&text, &actions);
+        assert_eq!(result.text, "envoie à 12 rue de la Paix puis");
+        assert_eq!(
+            result.actions,
+            vec![
+                Action::SetVolume(40),
+                Action::Shortcut(KeyCombo::parse("ctrl+s").unwrap()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_language_and_mode_filters() {
+        let (commands, _) = compile(
+            r#"{ "commands": [
+                { "phrases": ["ship it"], "languages": ["en"], "modes": ["code"],
+                  "action": { "type": "shell", "command": "git push" } }
+            ] }"#,
+        );
+
+        let mut actions = Vec::new();
+        commands.apply("ship it", "fr", DictationMode::Code, &mut actions);
+        commands.apply("ship it", "en", DictationMode::General, &mut actions);
+        assert!(actions.is_empty());
+
+        commands.apply("ship it", "en", DictationMode::Code, &mut actions);
+        assert_eq!(actions, vec![Action::Shell(vec!["git".to_string(), "push".to_string()])]);
+    }
+
+    #[test]
+    fn test_shell_command_is_split_and_validated() {
+        let (commands, errors) = compile(
+            r#"{ "commands": [
+                { "phrases": ["a"], "action": { "type": "shell", "command": "notify-send 'Deux mots'" } },
+                { "phrases": ["b"], "action": { "type": "shell", "command": "ls; rm -rf ~" } },
+                { "phrases": ["c"], "action": { "type": "shell", "command": "echo $(whoami)" } },
+                { "phrases": ["d"], "action": { "type": "shell", "command": "'unbalanced" } },
+                { "pattern": "lance (\\w+)", "action": { "type": "shell", "command": "$1 --help" } }
+            ] }"#,
+        );
+        assert_eq!(commands.commands.len(), 1);
+        assert_eq!(errors.len(), 4, "{:?}", errors);
+
+        let mut actions = Vec::new();
+        commands.apply("a", "fr", DictationMode::General, &mut actions);
+        assert_eq!(actions, vec![Action::Shell(vec!["notify-send".to_string(), "Deux mots".to_string()])]);
+    }
+
+    #[test]
+    fn test_hostile_capture_is_not_executed() {
+        let (commands, errors) = compile(
+            r#"{ "commands": [
+                { "pattern": "ouvre (.+)", "action": { "type": "shell", "command": "xdg-open $1" } }
+            ] }"#,
+        );
+        assert!(errors.is_empty(), "{:?}", errors);
+
+        let mut actions = Vec::new();
+        commands.apply("ouvre foo; rm -rf ~", "fr", DictationMode::General, &mut actions);
+        assert!(actions.is_empty(), "hostile capture must be rejected: {:?}", actions);
+        commands.apply("ouvre --help", "fr", DictationMode::General, &mut actions);
+        assert!(actions.is_empty(), "a capture must not become an option: {:?}", actions);
+
+        // Une capture anodine reste un seul argument, même avec des espaces
+        commands.apply("ouvre rapport final", "fr", DictationMode::General, &mut actions);
+        assert_eq!(
+            actions,
+            vec![Action::Shell(vec!["xdg-open".to_string(), "rapport final".to_string()])]
+        );
+    }
+
+    #[test]
+    fn test_phrase_after_length_changing_lowercase() {
+        // "ẞ" (3 octets) devient "ß" (2 octets) et "İ" (2 octets) devient "i̇" (3 octets)
+        assert_eq!(replace_phrase("Die STRAẞE ist lang", "ist", "war"), "Die STRAẞE war lang");
+        assert_eq!(replace_phrase("İstanbul point final", "point final", "."), "İstanbul .");
+
+        let (commands, errors) = compile(
+            r#"{ "commands": [ { "phrases": ["menu suivant"], "action": { "type": "shortcut", "keys": "ctrl+tab" } } ] }"#,
+        );
+        assert!(errors.is_empty(), "{:?}", errors);
+        let mut actions = Vec::new();
+        let text = commands.apply("ĞİŞ menu suivant", "fr", DictationMode::General, &mut actions);
+        let result = assemble(&text, &actions);
+        assert_eq!(result.text, "ĞİŞ");
+        assert_eq!(result.actions, vec![Action::Shortcut(KeyCombo::parse("ctrl+tab").unwrap())]);
+    }
+}
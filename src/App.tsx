@@ -7,8 +7,7 @@ import { SettingsPanel } from './components/SettingsPanel';
 import { FileTranscription } from './components/FileTranscription';
 import { useSettingsStore } from './stores/settingsStore';
 import { useTranscriptionStore } from './stores/transcriptionStore';
-import { useHotkeys } from './hooks/useHotkeys';
-import { GroqQuota } from './types';
+import { AudioDeviceChangedEvent, GroqQuota, RecordingLimitEvent } from './types';
 import logoSvg from './assets/logo.svg';
 import { playStartSound, playStopSound } from './utils/sounds';
 import { OnboardingWizard } from './components/onboarding';
@@ -39,8 +38,6 @@ function App() {
   const { settings, loadSettings } = useSettingsStore();
   const { initialize } = useTranscriptionStore();
 
-  useHotkeys();
-
   // Écouter les événements de statut du backend
   useEffect(() => {
     const unlisteners: Array<() => void> = [];
@@ -74,6 +71,24 @@ function App() {
       }
     }).then(unlisten => unlisteners.push(unlisten));
 
+    // Enregistrement proche de sa durée maximale
+    listen<RecordingLimitEvent>('recording-limit-warning', (event) => {
+      const { recorded_secs, limit_secs, limit_reached } = event.payload;
+      const minutes = Math.floor(limit_secs / 60);
+      if (limit_reached) {
+        console.warn(`Recording limit reached (${minutes} min): further audio is not recorded`);
+      } else {
+        console.warn(`Recording at ${Math.floor(recorded_secs / 60)} min, limit is ${minutes} min`);
+      }
+    }).then(unlisten => unlisteners.push(unlisten));
+
+    // Micro débranché pendant l'enregistrement : le backend a basculé sur un autre
+    listen<AudioDeviceChangedEvent>('audio-device-changed', (event) => {
+      const { lost_device, new_device } = event.payload;
+      console.warn(`Microphone "${lost_device}" disconnected, now using: ${new_device ?? 'none'}`);
+      useSettingsStore.getState().loadDevices();
+    }).then(unlisten => unlisteners.push(unlisten));
+
     return () => {
       unlisteners.forEach(unlisten => unlisten());
     };
@@ -76,6 +76,19 @@ export function DictationPanel() {
     }
   };
 
+  // "commande stop" entendue pendant le streaming : terminer comme un clic
+  useEffect(() => {
+    if (status !== 'recording') return;
+
+    const unlistenStop = listen('voice-stop-requested', () => {
+      handleToggle();
+    });
+
+    return () => {
+      unlistenStop.then((fn) => fn());
+    };
+  }, [status, handleToggle]);
+
   const getStatusText = () => {
     switch (status) {
       case 'recording':
@@ -4,6 +4,7 @@ import { listen } from '@tauri-apps/api/event';
 import { open } from '@tauri-apps/plugin-dialog';
 import { FileTranscriptionResult, FileTranscriptionProgress, LlmProvider } from '../types';
 import { useSettingsStore } from '../stores/settingsStore';
+import { WHISPER_DECODE_PRESET_NAMES, WhisperDecodePreset, applyPreset } from '../utils/whisperDecode';
 
 interface FileTranscriptionProps {
   isOpen: boolean;
@@ -27,6 +28,9 @@ export function FileTranscription({ isOpen, initialFiles }: FileTranscriptionPro
   const [supportedFormats, setSupportedFormats] = useState<string[]>([]);
   const [summaries, setSummaries] = useState<SummaryState>({});
   const [localLlmAvailable, setLocalLlmAvailable] = useState(false);
+  const [hasGroqKey, setHasGroqKey] = useState(false);
+  // Preset de decodage Whisper pour ce lot ; vide : reglages
+  const [decodePreset, setDecodePreset] = useState<WhisperDecodePreset | ''>('');
   const settings = useSettingsStore(state => state.settings);
 
   useEffect(() => {
@@ -42,6 +46,13 @@ export function FileTranscription({ isOpen, initialFiles }: FileTranscriptionPro
     }
   }, [settings?.local_llm_model]);
 
+  // La clé Groq est dans le trousseau, pas dans les settings
+  useEffect(() => {
+    invoke<boolean>('has_groq_api_key')
+      .then(setHasGroqKey)
+      .catch(() => setHasGroqKey(false));
+  }, [settings]);
+
   useEffect(() => {
     const unlistenProgress = listen<FileTranscriptionProgress>('file-transcription-progress', (event) => {
       setProgress(event.payload);
@@ -93,6 +104,7 @@ export function FileTranscription({ isOpen, initialFiles }: FileTranscriptionPro
     try {
       const transcriptionResults = await invoke<FileTranscriptionResult[]>('transcribe_files', {
         paths: files,
+        decodeOptions: decodePreset && settings ? applyPreset(settings.whisper_decode, decodePreset) : null,
       });
       setResults(transcriptionResults);
     } catch (e) {
@@ -101,7 +113,7 @@ export function FileTranscription({ isOpen, initialFiles }: FileTranscriptionPro
       setIsProcessing(false);
       setProgress(null);
     }
-  }, [files]);
+  }, [files, decodePreset, settings]);
 
   const handleCopyResult = useCallback((text: string) => {
     navigator.clipboard.writeText(text);
@@ -261,6 +273,20 @@ export function FileTranscription({ isOpen, initialFiles }: FileTranscriptionPro
           </div>
         )}
 
+        {/* Decoding preset */}
+        {files.length > 0 && !isProcessing && settings?.engine_type === 'whisper' && (
+          <select
+            value={decodePreset}
+            onChange={(e) => setDecodePreset(e.target.value as WhisperDecodePreset | '')}
+            className="select-glass w-full"
+          >
+            <option value="">Decodage : reglages</option>
+            {(Object.keys(WHISPER_DECODE_PRESET_NAMES) as WhisperDecodePreset[]).map((preset) => (
+              <option key={preset} value={preset}>Decodage : {WHISPER_DECODE_PRESET_NAMES[preset]}</option>
+            ))}
+          </select>
+        )}
+
         {/* Transcribe button */}
         {files.length > 0 && !isProcessing && (
           <button
@@ -312,7 +338,7 @@ export function FileTranscription({ isOpen, initialFiles }: FileTranscriptionPro
                             <div className="w-3 h-3 border-2 border-current border-t-transparent rounded-full animate-spin" />
                             Resume...
                           </button>
-                        ) : localLlmAvailable && settings?.groq_api_key ? (
+                        ) : localLlmAvailable && hasGroqKey ? (
                           // Les deux providers sont disponibles - afficher un dropdown
                           <div className="relative group">
                             <button
@@ -350,9 +376,9 @@ export function FileTranscription({ isOpen, initialFiles }: FileTranscriptionPro
                           // Un seul provider disponible
                           <button
                             onClick={() => handleSummarize(index, result.transcription!.text)}
-                            disabled={!localLlmAvailable && !settings?.groq_api_key}
+                            disabled={!localLlmAvailable && !hasGroqKey}
                             className="btn-glass text-[0.75rem] py-1.5 px-3 disabled:opacity-50"
-                            title={localLlmAvailable ? 'Resume (local)' : settings?.groq_api_key ? 'Resume (cloud)' : 'Configurez un LLM dans les parametres'}
+                            title={localLlmAvailable ? 'Resume (local)' : hasGroqKey ? 'Resume (cloud)' : 'Configurez un LLM dans les parametres'}
                           >
                             <svg width="12" height="12" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
                               <path d="M14 2H6a2 2 0 0 0-2 2v16a2 2 0 0 0 2 2h12a2 2 0 0 0 2-2V8z" />
@@ -360,7 +386,7 @@ export function FileTranscription({ isOpen, initialFiles }: FileTranscriptionPro
                               <line x1="16" y1="13" x2="8" y2="13" />
                               <line x1="16" y1="17" x2="8" y2="17" />
                             </svg>
-                            Resumer {localLlmAvailable ? '(Local)' : settings?.groq_api_key ? '(Cloud)' : ''}
+                            Resumer {localLlmAvailable ? '(Local)' : hasGroqKey ? '(Cloud)' : ''}
                           </button>
                         )}
                         <button
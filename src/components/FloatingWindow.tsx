@@ -1,17 +1,26 @@
 import { useEffect, useState, useRef, useCallback } from "react";
 import { listen } from "@tauri-apps/api/event";
 import { getCurrentWindow } from "@tauri-apps/api/window";
-import { StreamingChunk } from "../types";
+import { HandsFreeProgress, StreamingChunk } from "../types";
 
 type RecordingStatus = "idle" | "recording" | "processing";
 
 const BAR_COUNT = 48;
 const CANVAS_WIDTH = 600;
 const CANVAS_HEIGHT = 80;
+// Secondes restantes a partir desquelles le decompte passe en rouge
+const HANDS_FREE_WARNING_SECS = 30;
+
+function formatDuration(secs: number): string {
+  const minutes = Math.floor(secs / 60);
+  const seconds = secs % 60;
+  return `${minutes}:${seconds.toString().padStart(2, "0")}`;
+}
 
 export default function FloatingWindow() {
   const [status, setStatus] = useState<RecordingStatus>("idle");
   const [streamingText, setStreamingText] = useState<string>("");
+  const [handsFree, setHandsFree] = useState<HandsFreeProgress | null>(null);
   const canvasRef = useRef<HTMLCanvasElement>(null);
   const animFrameRef = useRef<number>(0);
   const barsRef = useRef<number[]>(new Array(BAR_COUNT).fill(0));
@@ -98,9 +107,15 @@ export default function FloatingWindow() {
       setStatus(newStatus);
       if (newStatus === "recording") {
         setStreamingText("");
+      } else {
+        setHandsFree(null);
       }
     }).then((unlisten) => unlisteners.push(unlisten));
 
+    listen<HandsFreeProgress>("hands-free-progress", (event) => {
+      setHandsFree(event.payload);
+    }).then((unlisten) => unlisteners.push(unlisten));
+
     listen<StreamingChunk>("transcription-chunk", (event) => {
       setStreamingText(event.payload.text);
     }).then((unlisten) => unlisteners.push(unlisten));
@@ -177,6 +192,22 @@ export default function FloatingWindow() {
         >
           {isRecording ? "Ecoute..." : status === "processing" ? "Traitement..." : "Pret"}
         </span>
+        {isRecording && handsFree && (
+          <span
+            title="Duree maximale de la dictee mains libres"
+            style={{
+              fontSize: "0.6rem",
+              fontWeight: 600,
+              fontVariantNumeric: "tabular-nums",
+              color:
+                handsFree.max_secs - handsFree.elapsed_secs <= HANDS_FREE_WARNING_SECS
+                  ? "#ff3b3b"
+                  : "rgba(255, 255, 255, 0.35)",
+            }}
+          >
+            {formatDuration(handsFree.elapsed_secs)} / {formatDuration(handsFree.max_secs)}
+          </span>
+        )}
       </div>
 
       {/* Transcription text */}
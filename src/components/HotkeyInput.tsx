@@ -1,5 +1,19 @@
 import { useState, useCallback, useEffect } from 'react';
 
+const MODIFIER_KEYS = ['Control', 'Meta', 'Alt', 'Shift'];
+
+// Touches acceptées seules par le backend (fonction, pavé numérique, média)
+const STANDALONE_KEY = /^(F\d{1,2}|Numpad\w+|Media\w+|AudioVolume\w+)$/;
+
+// Nom de touche compris par le parseur Rust : le pavé numérique se distingue
+// par e.code, les touches imprimables gardent e.key (disposition du clavier)
+function keyName(e: KeyboardEvent): string {
+  if (e.code.startsWith('Numpad')) return e.code;
+  if (e.key === ' ') return 'Space';
+  if (e.key.length === 1) return e.key.toUpperCase();
+  return e.key;
+}
+
 interface HotkeyInputProps {
   value: string;
   onChange: (hotkey: string) => void;
@@ -19,7 +33,7 @@ export function HotkeyInput({ value, onChange, disabled }: HotkeyInputProps) {
       else if (key === 'Meta') modifiers.push('Cmd');
       else if (key === 'Alt') modifiers.push('Alt');
       else if (key === 'Shift') modifiers.push('Shift');
-      else regularKeys.push(key.toUpperCase());
+      else regularKeys.push(key);
     });
 
     // Ordre standard: Ctrl, Alt, Shift, Cmd, puis la touche
@@ -33,7 +47,7 @@ export function HotkeyInput({ value, onChange, disabled }: HotkeyInputProps) {
     e.preventDefault();
     e.stopPropagation();
 
-    const key = e.key === ' ' ? 'Space' : e.key;
+    const key = keyName(e);
 
     setCurrentKeys((prev) => {
       const newKeys = new Set(prev);
@@ -43,7 +57,7 @@ export function HotkeyInput({ value, onChange, disabled }: HotkeyInputProps) {
       if (e.shiftKey) newKeys.add('Shift');
 
       // Ajouter la touche si ce n'est pas un modifier seul
-      if (!['Control', 'Meta', 'Alt', 'Shift'].includes(key)) {
+      if (!MODIFIER_KEYS.includes(key)) {
         newKeys.add(key);
       }
 
@@ -57,14 +71,12 @@ export function HotkeyInput({ value, onChange, disabled }: HotkeyInputProps) {
     e.preventDefault();
     e.stopPropagation();
 
-    // Si on a au moins un modifier et une touche, sauvegarder
-    const hasModifier = currentKeys.has('Control') || currentKeys.has('Meta') ||
-                        currentKeys.has('Alt') || currentKeys.has('Shift');
-    const hasKey = Array.from(currentKeys).some(k =>
-      !['Control', 'Meta', 'Alt', 'Shift'].includes(k)
-    );
+    // Sauvegarder dès qu'on a une touche, avec un modifier sauf pour
+    // les touches de fonction, du pavé numérique et multimédia
+    const hasModifier = MODIFIER_KEYS.some(m => currentKeys.has(m));
+    const keys = Array.from(currentKeys).filter(k => !MODIFIER_KEYS.includes(k));
 
-    if (hasModifier && hasKey) {
+    if (keys.length > 0 && (hasModifier || keys.every(k => STANDALONE_KEY.test(k)))) {
       const formatted = formatHotkey(currentKeys);
       onChange(formatted);
       setIsRecording(false);
@@ -136,7 +148,7 @@ export function HotkeyInput({ value, onChange, disabled }: HotkeyInputProps) {
       </button>
       {isRecording && (
         <p className="mt-1 text-[0.6rem] text-[var(--text-muted)] uppercase tracking-wider">
-          Modifier + Touche requis (ex: Ctrl+Shift+R)
+          Modifier + Touche (ex: Ctrl+Shift+R), ou F1-F24, pave numerique, touche media
         </p>
       )}
     </div>
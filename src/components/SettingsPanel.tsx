@@ -5,9 +5,14 @@ import { useSettingsStore } from '../stores/settingsStore';
 import {
   AudioSection,
   EngineSection,
+  EngineFallbackSection,
+  WhisperDecodeSection,
+  EvaluationSection,
   LlmSection,
   TranslationSection,
   DictationSection,
+  ProfilesSection,
+  VoiceCommandsSection,
   TranscriptionSection,
   OptionsSection,
   SystemSection,
@@ -15,6 +20,8 @@ import {
   DictionarySection,
   SnippetsSection,
   IntegrationsSection,
+  ConfigTransferSection,
+  ManagedSection,
 } from './settings';
 import { StatisticsPanel } from './StatisticsPanel';
 import logoSvg from '../assets/logo.svg';
@@ -25,16 +32,17 @@ interface SettingsPanelProps {
 }
 
 export function SettingsPanel({ isOpen, onClose }: SettingsPanelProps) {
-  const { settings, devices, dictionary, loadSettings, loadDevices, loadDictionary, updateSettings, addWord, removeWord } = useSettingsStore();
+  const { settings, devices, dictionary, managed, loadSettings, loadManaged, loadDevices, loadDictionary, updateSettings, addWord, removeWord } = useSettingsStore();
   const [apiKeyStatus, setApiKeyStatus] = useState<'valid' | 'invalid' | null>(null);
 
   useEffect(() => {
     if (isOpen) {
       loadSettings();
+      loadManaged();
       loadDevices();
       loadDictionary();
     }
-  }, [isOpen, loadSettings, loadDevices, loadDictionary]);
+  }, [isOpen, loadSettings, loadManaged, loadDevices, loadDictionary]);
 
   const handleOpenLogs = useCallback(async () => {
     try {
@@ -81,19 +89,26 @@ export function SettingsPanel({ isOpen, onClose }: SettingsPanelProps) {
 
         {/* Content */}
         <div className="flex-1 overflow-y-auto p-6 space-y-8 scrollbar-thin">
+          {managed?.active && <ManagedSection managed={managed} />}
           <AudioSection settings={settings} devices={devices} updateSettings={updateSettings} />
           <EngineSection settings={settings} updateSettings={updateSettings} />
+          <EngineFallbackSection settings={settings} updateSettings={updateSettings} />
+          {settings.engine_type === 'whisper' && <WhisperDecodeSection settings={settings} updateSettings={updateSettings} />}
+          <EvaluationSection />
           <LlmSection settings={settings} updateSettings={updateSettings} onApiKeyStatusChange={setApiKeyStatus} />
           <TranslationSection settings={settings} updateSettings={updateSettings} apiKeyStatus={apiKeyStatus} />
           <DictationSection settings={settings} updateSettings={updateSettings} />
+          <ProfilesSection settings={settings} updateSettings={updateSettings} />
+          {settings.voice_commands_enabled && <VoiceCommandsSection />}
           <TranscriptionSection settings={settings} updateSettings={updateSettings} />
           <OptionsSection settings={settings} updateSettings={updateSettings} />
           <SystemSection settings={settings} updateSettings={updateSettings} />
           <StatisticsPanel />
           <IntegrationsSection settings={settings} updateSettings={updateSettings} />
           <ShortcutsSection settings={settings} updateSettings={updateSettings} />
-          <DictionarySection dictionary={dictionary} addWord={addWord} removeWord={removeWord} />
+          <DictionarySection dictionary={dictionary} teamWords={managed?.dictionary ?? []} addWord={addWord} removeWord={removeWord} />
           <SnippetsSection />
+          <ConfigTransferSection onImported={() => { loadSettings(); loadDictionary(); }} />
         </div>
 
         {/* Footer */}
@@ -4,13 +4,16 @@ import { UsageStats } from '../types';
 
 export function StatisticsPanel() {
   const [stats, setStats] = useState<UsageStats | null>(null);
+  const [error, setError] = useState<string | null>(null);
 
   const loadStats = useCallback(async () => {
     try {
       const data = await invoke<UsageStats>('get_usage_stats');
       setStats(data);
+      setError(null);
     } catch (e) {
       console.error('Failed to load stats:', e);
+      setError(String(e));
     }
   }, []);
 
@@ -25,7 +28,9 @@ export function StatisticsPanel() {
     }
   };
 
-  if (!stats) return null;
+  if (!stats) {
+    return error ? <p className="text-[0.75rem] text-[var(--accent-danger)]">{error}</p> : null;
+  }
 
   const formatDuration = (secs: number) => {
     const hours = Math.floor(secs / 3600);
@@ -44,7 +44,7 @@ export function TranscriptionHistory() {
     invoke<boolean>('has_groq_api_key')
       .then(setHasGroqKey)
       .catch(() => setHasGroqKey(false));
-  }, [settings?.groq_api_key]);
+  }, [settings]);
 
   const handleSummarize = useCallback(async (index: number, text: string, provider?: LlmProvider) => {
     setSummaries(prev => ({
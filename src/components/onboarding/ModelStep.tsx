@@ -23,6 +23,18 @@ const ENGINE_INFO: Record<EngineType, { description: string }> = {
   parakeet: { description: 'NVIDIA — Detection auto, 25 langues (macOS)' },
 };
 
+const qualityLabels: Partial<Record<ModelSize, string>> = {
+  tiny: 'Basique',
+  small: 'Bonne',
+  medium: 'Tres bonne',
+};
+
+const qualityColors: Partial<Record<ModelSize, string>> = {
+  tiny: 'var(--text-muted)',
+  small: 'var(--accent-primary)',
+  medium: 'var(--accent-success)',
+};
+
 export function ModelStep({ onValidChange }: StepProps) {
   const { settings, updateSettings } = useSettingsStore();
   const [selectedEngine, setSelectedEngine] = useState<EngineType>(settings?.engine_type || 'whisper');
@@ -46,7 +58,8 @@ export function ModelStep({ onValidChange }: StepProps) {
   const loadModels = async () => {
     try {
       const result = await invoke<ModelInfo[]>('get_available_models');
-      setModels(result);
+      // Premier lancement : les trois modeles de base, les variantes sont dans les parametres
+      setModels(result.filter((model) => model.size in qualityLabels));
     } catch (e) {
       console.error('Failed to load models:', e);
     }
@@ -171,18 +184,6 @@ export function ModelStep({ onValidChange }: StepProps) {
     }
   };
 
-  const qualityLabels: Record<ModelSize, string> = {
-    tiny: 'Basique',
-    small: 'Bonne',
-    medium: 'Tres bonne',
-  };
-
-  const qualityColors: Record<ModelSize, string> = {
-    tiny: 'var(--text-muted)',
-    small: 'var(--accent-primary)',
-    medium: 'var(--accent-success)',
-  };
-
   const downloadIcon = (
     <svg width="14" height="14" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
       <path d="M21 15v4a2 2 0 0 1-2 2H5a2 2 0 0 1-2-2v-4" />
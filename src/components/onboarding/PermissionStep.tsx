@@ -48,7 +48,8 @@ export function PermissionStep({ onValidChange }: StepProps) {
 
   const checkMicrophoneAccess = async () => {
     try {
-      const deviceList = await invoke<AudioDevice[]>('list_audio_devices');
+      const deviceList = (await invoke<AudioDevice[]>('list_audio_devices'))
+        .filter(d => d.kind === 'microphone');
       if (deviceList.length > 0) {
         setDevices(deviceList);
         setStatus('granted');
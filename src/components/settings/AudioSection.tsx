@@ -7,26 +7,120 @@ interface AudioSectionProps {
 }
 
 export function AudioSection({ settings, devices, updateSettings }: AudioSectionProps) {
+  // Ancien réglage à micro unique : sert de liste initiale
+  const preferred = settings.preferred_microphones.length > 0
+    ? settings.preferred_microphones
+    : settings.microphone_id ? [settings.microphone_id] : [];
+
+  const available = devices.filter((d) => !preferred.includes(d.id));
+  const systemDevices = devices.filter((d) => d.kind === 'system_audio');
+  const primary = devices.find((d) => d.id === preferred[0]) ?? devices.find((d) => d.is_default);
+  const maxChannels = primary?.channels ?? 1;
+
+  const setPreferred = (list: string[]) =>
+    updateSettings({ preferred_microphones: list, microphone_id: list[0] ?? null });
+
+  const move = (index: number, delta: number) => {
+    const target = index + delta;
+    if (target < 0 || target >= preferred.length) return;
+    const list = [...preferred];
+    [list[index], list[target]] = [list[target], list[index]];
+    setPreferred(list);
+  };
+
   return (
     <section className="space-y-4">
       <h3 className="section-title primary">Audio</h3>
 
       <div className="space-y-3">
-        <label className="block">
-          <span className="text-[0.8rem] text-[rgba(255,255,255,0.75)] mb-2 block">Microphone</span>
+        <span className="text-[0.8rem] text-[rgba(255,255,255,0.75)] mb-2 block">
+          Microphones (par ordre de preference)
+        </span>
+
+        {preferred.length === 0 && (
+          <p className="text-[0.75rem] text-[var(--text-muted)]">Micro par defaut du systeme</p>
+        )}
+
+        {preferred.map((id, index) => {
+          const device = devices.find((d) => d.id === id);
+          return (
+            <div key={id} className="glass-card p-2 flex items-center justify-between gap-2">
+              <span className={`text-[0.8rem] truncate ${device ? 'text-[var(--text-primary)]' : 'text-[var(--text-muted)]'}`}>
+                {index + 1}. {device?.name ?? id} {device ? '' : '(deconnecte)'}
+              </span>
+              <div className="flex gap-1 flex-shrink-0">
+                <button onClick={() => move(index, -1)} disabled={index === 0} className="btn-glass px-2 disabled:opacity-30">↑</button>
+                <button onClick={() => move(index, 1)} disabled={index === preferred.length - 1} className="btn-glass px-2 disabled:opacity-30">↓</button>
+                <button
+                  onClick={() => setPreferred(preferred.filter((p) => p !== id))}
+                  className="btn-glass px-2 text-[var(--text-muted)] hover:text-[var(--accent-danger)]"
+                >
+                  ✕
+                </button>
+              </div>
+            </div>
+          );
+        })}
+
+        {available.length > 0 && (
           <select
-            value={settings.microphone_id || ''}
-            onChange={(e) => updateSettings({ microphone_id: e.target.value || null })}
+            value=""
+            onChange={(e) => e.target.value && setPreferred([...preferred, e.target.value])}
             className="select-glass"
           >
-            <option value="">Par defaut</option>
-            {devices.map((device) => (
+            <option value="">Ajouter un microphone...</option>
+            {available.map((device) => (
               <option key={device.id} value={device.id}>
                 {device.name} {device.is_default ? '(defaut)' : ''}
               </option>
             ))}
           </select>
-        </label>
+        )}
+
+        {systemDevices.length > 0 && (
+          <>
+            <label className="checkbox-frost">
+              <input
+                type="checkbox"
+                checked={settings.mix_system_audio}
+                onChange={(e) => updateSettings({ mix_system_audio: e.target.checked })}
+              />
+              <span className="check-box" />
+              <span className="check-label">Enregistrer aussi l'audio systeme (appels, videos)</span>
+            </label>
+
+            {settings.mix_system_audio && (
+              <select
+                value={settings.system_audio_device ?? ''}
+                onChange={(e) => updateSettings({ system_audio_device: e.target.value || null })}
+                className="select-glass"
+              >
+                <option value="">Sortie par defaut</option>
+                {systemDevices.map((device) => (
+                  <option key={device.id} value={device.id}>{device.name}</option>
+                ))}
+              </select>
+            )}
+          </>
+        )}
+
+        {maxChannels > 1 && (
+          <label className="block">
+            <span className="text-[0.8rem] text-[rgba(255,255,255,0.75)] mb-2 block">Canal d'entree</span>
+            <select
+              value={settings.microphone_channel ?? ''}
+              onChange={(e) => updateSettings({
+                microphone_channel: e.target.value === '' ? null : Number(e.target.value),
+              })}
+              className="select-glass"
+            >
+              <option value="">Tous les canaux (mixage)</option>
+              {Array.from({ length: maxChannels }, (_, ch) => (
+                <option key={ch} value={ch}>Canal {ch + 1}</option>
+              ))}
+            </select>
+          </label>
+        )}
       </div>
     </section>
   );
@@ -0,0 +1,84 @@
+import { useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { open, save } from '@tauri-apps/plugin-dialog';
+import { SettingsImportSummary } from '../../types';
+
+interface ConfigTransferSectionProps {
+  onImported: () => void;
+}
+
+export function ConfigTransferSection({ onImported }: ConfigTransferSectionProps) {
+  const [message, setMessage] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [summary, setSummary] = useState<SettingsImportSummary | null>(null);
+
+  const handleExport = async () => {
+    setError(null);
+    setSummary(null);
+    try {
+      const path = await save({
+        defaultPath: 'phonon-config.json',
+        filters: [{ name: 'Configuration Phonon', extensions: ['json'] }],
+      });
+      if (!path) return;
+      await invoke('export_settings', { path });
+      setMessage(`Configuration exportee vers ${path}`);
+    } catch (e) {
+      setMessage(null);
+      setError(String(e));
+    }
+  };
+
+  const handleImport = async () => {
+    setError(null);
+    setMessage(null);
+    try {
+      const path = await open({
+        multiple: false,
+        filters: [{ name: 'Configuration Phonon', extensions: ['json'] }],
+      });
+      if (!path || Array.isArray(path)) return;
+      setSummary(await invoke<SettingsImportSummary>('import_settings', { path }));
+      onImported();
+    } catch (e) {
+      setSummary(null);
+      setError(String(e));
+    }
+  };
+
+  return (
+    <section className="space-y-4">
+      <h3 className="section-title primary">Partager la configuration</h3>
+      <p className="text-[0.75rem] text-[var(--text-muted)]">
+        Reglages, dictionnaire, snippets et modeles de texte dans un seul fichier.
+        Les cles API et les reglages propres a cette machine (micros, chemins) ne sont pas exportes.
+      </p>
+
+      <div className="flex gap-2">
+        <button onClick={handleExport} className="btn-glass px-4 text-[var(--accent-primary)]">
+          Exporter
+        </button>
+        <button onClick={handleImport} className="btn-glass px-4">
+          Importer
+        </button>
+      </div>
+
+      {message && <p className="text-[0.75rem] text-[var(--text-muted)]">{message}</p>}
+      {error && <p className="text-[0.75rem] text-[var(--accent-danger)]">{error}</p>}
+
+      {summary && (
+        <div className="glass-card p-3 space-y-1">
+          <p className="text-[0.75rem] text-[var(--text-primary)]">
+            Configuration importee : {summary.words_added} mot(s) ajoute(s) au dictionnaire,
+            {' '}{summary.snippets_added} snippet(s) ajoute(s), {summary.snippets_updated} mis a jour.
+          </p>
+          {summary.issues.map((issue) => (
+            <p key={issue.field} className="text-[0.75rem] text-[var(--accent-danger)]">
+              {issue.field} : {issue.message}
+            </p>
+          ))}
+        </div>
+      )}
+    </section>
+  );
+}
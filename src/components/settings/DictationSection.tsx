@@ -1,4 +1,4 @@
-import { AppSettings } from '../../types';
+import { AppSettings, CodeLanguage } from '../../types';
 
 interface DictationSectionProps {
   settings: AppSettings;
@@ -30,6 +30,34 @@ export function DictationSection({ settings, updateSettings }: DictationSectionP
           ))}
         </div>
 
+        {settings.dictation_mode === 'code' && (
+          <label className="block">
+            <span className="text-[0.8rem] text-[var(--text-muted)] mb-1 block">Langage (fleches, indentation, modeles)</span>
+            <select
+              value={settings.code_language}
+              onChange={(e) => updateSettings({ code_language: e.target.value as CodeLanguage })}
+              className="select-glass"
+            >
+              <option value="generic">Generique</option>
+              <option value="rust">Rust</option>
+              <option value="python">Python</option>
+              <option value="typescript">TypeScript</option>
+            </select>
+          </label>
+        )}
+
+        <label className="checkbox-frost">
+          <input
+            type="checkbox"
+            checked={settings.text_normalization[settings.dictation_mode]}
+            onChange={(e) => updateSettings({
+              text_normalization: { ...settings.text_normalization, [settings.dictation_mode]: e.target.checked },
+            })}
+          />
+          <span className="check-box" />
+          <span className="check-label">Nombres, dates et montants en chiffres (dans ce mode)</span>
+        </label>
+
         <label className="checkbox-frost">
           <input
             type="checkbox"
@@ -40,6 +68,27 @@ export function DictationSection({ settings, updateSettings }: DictationSectionP
           <span className="check-label">Commandes vocales activees</span>
         </label>
 
+        {settings.voice_commands_enabled && (settings.dictation_mode === 'email' || settings.dictation_mode === 'code') && (
+          <div className="pl-8 space-y-2">
+            {(settings.dictation_mode === 'email'
+              ? [['signature', 'Signature'], ['greeting', 'Formule de politesse']] as const
+              : [['function', 'Modele de fonction'], ['comment', 'Commentaire']] as const
+            ).map(([key, label]) => (
+              <label key={key} className="block">
+                <span className="text-[0.8rem] text-[var(--text-muted)] mb-1 block">{label}</span>
+                <textarea
+                  value={settings.command_templates[key]}
+                  onChange={(e) => updateSettings({
+                    command_templates: { ...settings.command_templates, [key]: e.target.value },
+                  })}
+                  rows={2}
+                  className="input-glass w-full resize-none font-mono text-[0.75rem]"
+                />
+              </label>
+            ))}
+          </div>
+        )}
+
         <label className="checkbox-frost">
           <input
             type="checkbox"
@@ -2,11 +2,13 @@ import { useState } from 'react';
 
 interface DictionarySectionProps {
   dictionary: string[];
+  /** Mots du dictionnaire d'equipe, non supprimables */
+  teamWords: string[];
   addWord: (word: string) => Promise<void>;
   removeWord: (word: string) => Promise<void>;
 }
 
-export function DictionarySection({ dictionary, addWord, removeWord }: DictionarySectionProps) {
+export function DictionarySection({ dictionary, teamWords, addWord, removeWord }: DictionarySectionProps) {
   const [newWord, setNewWord] = useState('');
 
   const handleAddWord = async () => {
@@ -48,15 +50,19 @@ export function DictionarySection({ dictionary, addWord, removeWord }: Dictionar
               className="tag-frost group"
             >
               {word}
-              <button
-                onClick={() => removeWord(word)}
-                className="opacity-50 hover:opacity-100 hover:text-[var(--accent-danger)] transition-opacity ml-1"
-              >
-                <svg width="12" height="12" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
-                  <line x1="18" y1="6" x2="6" y2="18" />
-                  <line x1="6" y1="6" x2="18" y2="18" />
-                </svg>
-              </button>
+              {teamWords.includes(word) ? (
+                <span className="ml-1 text-[0.65rem] text-[var(--text-muted)]" title="Dictionnaire d'equipe">equipe</span>
+              ) : (
+                <button
+                  onClick={() => removeWord(word)}
+                  className="opacity-50 hover:opacity-100 hover:text-[var(--accent-danger)] transition-opacity ml-1"
+                >
+                  <svg width="12" height="12" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
+                    <line x1="18" y1="6" x2="6" y2="18" />
+                    <line x1="6" y1="6" x2="18" y2="18" />
+                  </svg>
+                </button>
+              )}
             </span>
           ))}
         </div>
@@ -0,0 +1,130 @@
+import { useCallback, useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { AppSettings, EngineHealth, EngineType } from '../../types';
+
+const ENGINE_NAMES: Record<EngineType, string> = {
+  whisper: 'Whisper',
+  parakeet: 'Parakeet',
+  vosk: 'Vosk',
+};
+
+interface EngineFallbackSectionProps {
+  settings: AppSettings;
+  updateSettings: (settings: Partial<AppSettings>) => Promise<void>;
+}
+
+export function EngineFallbackSection({ settings, updateSettings }: EngineFallbackSectionProps) {
+  const [health, setHealth] = useState<EngineHealth[]>([]);
+  const chain = settings.engine_fallback_chain.filter((e) => e !== settings.engine_type);
+  // Moteurs de la chaine dans l'ordre, puis les moteurs non utilises
+  const engines = [
+    ...chain,
+    ...(Object.keys(ENGINE_NAMES) as EngineType[]).filter((e) => e !== settings.engine_type && !chain.includes(e)),
+  ];
+
+  const loadHealth = useCallback(async () => {
+    try {
+      setHealth(await invoke<EngineHealth[]>('get_engine_health'));
+    } catch (e) {
+      console.error('Failed to load engine health:', e);
+    }
+  }, []);
+
+  useEffect(() => {
+    loadHealth();
+  }, [loadHealth, settings.engine_type, settings.engine_fallback_chain]);
+
+  useEffect(() => {
+    const unlisten = listen<EngineHealth[]>('engine-health-updated', (event) => setHealth(event.payload));
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
+
+  const setChain = async (next: EngineType[]) => {
+    try {
+      await updateSettings({ engine_fallback_chain: next });
+    } catch (e) {
+      console.error('Failed to update fallback chain:', e);
+    }
+  };
+
+  const toggle = (engine: EngineType, enabled: boolean) =>
+    setChain(enabled ? [...chain, engine] : chain.filter((e) => e !== engine));
+
+  const moveUp = (engine: EngineType) => {
+    const index = chain.indexOf(engine);
+    if (index > 0) {
+      const next = [...chain];
+      [next[index - 1], next[index]] = [next[index], next[index - 1]];
+      setChain(next);
+    }
+  };
+
+  const statusOf = (engine: EngineType) => health.find((h) => h.engine_type === engine);
+  const primary = health.find((h) => h.primary);
+
+  const renderStatus = (status: EngineHealth | undefined) => {
+    if (!status) return null;
+    if (!status.healthy) {
+      return <span className="text-[0.7rem] text-[var(--accent-danger)] truncate" title={status.error ?? ''}>{status.error}</span>;
+    }
+    return (
+      <span className="text-[0.7rem] text-[var(--text-muted)]">{status.loaded ? 'Pret' : 'Disponible'}</span>
+    );
+  };
+
+  return (
+    <section className="space-y-4">
+      <h3 className="section-title success">Moteurs de secours</h3>
+      <p className="text-[0.75rem] text-[var(--text-muted)]">
+        Si le moteur actif echoue, la dictee est retranscrite avec le moteur suivant de la liste.
+      </p>
+
+      {primary && (
+        <div className="glass-card p-3 flex items-center justify-between gap-3">
+          <span className="text-[0.8rem] text-[var(--text-primary)]">
+            {ENGINE_NAMES[primary.engine_type]} <span className="text-[var(--text-muted)]">(actif)</span>
+          </span>
+          {renderStatus(primary)}
+        </div>
+      )}
+
+      <div className="space-y-2">
+        {engines.map((engine) => {
+          const index = chain.indexOf(engine);
+          return (
+            <div key={engine} className="glass-card p-3 flex items-center justify-between gap-3">
+              <label className="checkbox-frost">
+                <input
+                  type="checkbox"
+                  checked={index >= 0}
+                  onChange={(e) => toggle(engine, e.target.checked)}
+                />
+                <span className="check-box" />
+                <span className="check-label">
+                  {index >= 0 ? `${index + 1}. ` : ''}{ENGINE_NAMES[engine]}
+                </span>
+              </label>
+              <div className="flex items-center gap-2 min-w-0">
+                {index >= 0 && renderStatus(statusOf(engine))}
+                {index > 0 && (
+                  <button
+                    onClick={() => moveUp(engine)}
+                    className="p-1 text-[var(--text-muted)] hover:text-[var(--accent-primary)] transition-colors"
+                    title="Monter"
+                  >
+                    <svg width="14" height="14" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
+                      <polyline points="18 15 12 9 6 15" />
+                    </svg>
+                  </button>
+                )}
+              </div>
+            </div>
+          );
+        })}
+      </div>
+    </section>
+  );
+}
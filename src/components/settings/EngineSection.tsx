@@ -11,6 +11,7 @@ import {
   VoskModelInfo,
   ParakeetModelSize,
   ParakeetModelInfo,
+  SystemMemory,
 } from '../../types';
 import { useSettingsStore } from '../../stores/settingsStore';
 
@@ -20,8 +21,10 @@ interface EngineSectionProps {
 }
 
 export function EngineSection({ settings, updateSettings }: EngineSectionProps) {
-  const { loadSettings } = useSettingsStore();
+  const { loadSettings, managed } = useSettingsStore();
   const [models, setModels] = useState<ModelInfo[]>([]);
+  const [memory, setMemory] = useState<SystemMemory | null>(null);
+  const [showVariants, setShowVariants] = useState(false);
   const [downloading, setDownloading] = useState<ModelSize | null>(null);
   const [downloadProgress, setDownloadProgress] = useState<DownloadProgress | null>(null);
   const [voskModels, setVoskModels] = useState<VoskModelInfo[]>([]);
@@ -35,6 +38,7 @@ export function EngineSection({ settings, updateSettings }: EngineSectionProps)
     try {
       const result = await invoke<ModelInfo[]>('get_available_models');
       setModels(result);
+      setMemory(await invoke<SystemMemory>('get_system_memory'));
     } catch (e) {
       console.error('Failed to load models:', e);
     }
@@ -105,6 +109,21 @@ export function EngineSection({ settings, updateSettings }: EngineSectionProps)
     };
   }, []);
 
+  // Configuration d'equipe : moteur impose ou liste restreinte
+  const isEngineAllowed = (engine: EngineType) =>
+    !managed?.locked_fields.includes('engine_type')
+    && (!managed?.allowed_engines.length || managed.allowed_engines.includes(engine));
+
+  // Variantes anglais seul et quantifiees masquees sauf si installees ou demandees
+  const visibleModels = models.filter((model) =>
+    showVariants
+    || (!model.english_only && !model.quantization)
+    || model.available
+    || settings.whisper_model === model.size
+  );
+
+  const gigabytes = (bytes: number) => (bytes / 1_000_000_000).toFixed(1);
+
   const handleSwitchEngine = async (engineType: EngineType) => {
     try {
       await updateSettings({ engine_type: engineType });
@@ -208,7 +227,8 @@ export function EngineSection({ settings, updateSettings }: EngineSectionProps)
           <button
             key={engine}
             onClick={() => handleSwitchEngine(engine)}
-            className={`flex-1 px-4 py-2.5 text-[0.8rem] font-medium rounded-xl border transition-all ${
+            disabled={settings.engine_type !== engine && !isEngineAllowed(engine)}
+            className={`flex-1 px-4 py-2.5 text-[0.8rem] font-medium rounded-xl border transition-all disabled:opacity-40 disabled:cursor-not-allowed ${
               settings.engine_type === engine
                 ? 'bg-[var(--accent-success-soft)] border-[var(--accent-success)] text-[var(--accent-success)]'
                 : 'bg-[rgba(255,255,255,0.08)] border-[var(--glass-border)] text-[var(--text-muted)] hover:border-[var(--accent-success)]'
@@ -226,8 +246,11 @@ export function EngineSection({ settings, updateSettings }: EngineSectionProps)
         <div className="space-y-3">
           <p className="text-[0.75rem] text-[var(--text-muted)]">
             Whisper (OpenAI) - Haute precision, 99 langues
+            {memory && memory.total_bytes > 0 && (
+              <> · RAM : {gigabytes(memory.total_bytes)} Go ({gigabytes(memory.available_bytes)} Go libres)</>
+            )}
           </p>
-          {models.map((model) => (
+          {visibleModels.map((model) => (
             <div
               key={model.size}
               className={`glass-card p-4 ${
@@ -248,6 +271,14 @@ export function EngineSection({ settings, updateSettings }: EngineSectionProps)
                     {model.size === 'small' && (
                       <div className="text-[0.7rem] text-[var(--accent-primary)]">Recommande</div>
                     )}
+                    <div className="flex items-center gap-2 text-[0.7rem] text-[var(--text-muted)]">
+                      <span>~{gigabytes(model.ram_bytes)} Go de RAM · x{model.relative_speed} par rapport a large-v3</span>
+                      {model.english_only && <span className="tag-frost text-[0.6rem]">Anglais</span>}
+                      {model.quantization && <span className="tag-frost text-[0.6rem]">{model.quantization}</span>}
+                    </div>
+                    {!model.fits_in_memory && (
+                      <div className="text-[0.7rem] text-[var(--accent-danger)]">Memoire insuffisante sur cette machine</div>
+                    )}
                   </div>
                 </div>
 
@@ -300,6 +331,12 @@ export function EngineSection({ settings, updateSettings }: EngineSectionProps)
               </div>
             </div>
           ))}
+          <button
+            onClick={() => setShowVariants(!showVariants)}
+            className="text-[0.75rem] text-[var(--accent-primary)] hover:underline"
+          >
+            {showVariants ? 'Masquer les variantes' : 'Afficher les variantes (anglais seul, quantifiees)'}
+          </button>
         </div>
       )}
 
@@ -442,6 +479,20 @@ export function EngineSection({ settings, updateSettings }: EngineSectionProps)
               </div>
             </div>
           ))}
+
+          <div>
+            <label className="text-[0.75rem] text-[var(--text-muted)] mb-1 block">
+              Faisceau du decodage (1 : glouton, plus large : plus precis mais plus lent)
+            </label>
+            <input
+              type="number"
+              min={1}
+              max={8}
+              value={settings.parakeet_beam_size}
+              onChange={(e) => updateSettings({ parakeet_beam_size: Math.min(8, Math.max(1, Number(e.target.value) || 1)) })}
+              className="input-glass w-24"
+            />
+          </div>
         </div>
       )}
     </section>
@@ -0,0 +1,86 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { open } from '@tauri-apps/plugin-dialog';
+import { EvaluationProgress, EvaluationReport } from '../../types';
+
+const percent = (value: number) => `${(value * 100).toFixed(1)} %`;
+
+export function EvaluationSection() {
+  const [running, setRunning] = useState(false);
+  const [progress, setProgress] = useState<EvaluationProgress | null>(null);
+  const [report, setReport] = useState<EvaluationReport | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    const unlisten = listen<EvaluationProgress>('evaluation-progress', (event) => setProgress(event.payload));
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
+
+  const handleRun = async () => {
+    setError(null);
+    try {
+      const folder = await open({ directory: true, multiple: false });
+      if (!folder || Array.isArray(folder)) return;
+      setRunning(true);
+      setReport(null);
+      setReport(await invoke<EvaluationReport>('evaluate_engines', { folder }));
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setRunning(false);
+      setProgress(null);
+    }
+  };
+
+  return (
+    <section className="space-y-4">
+      <h3 className="section-title success">Comparer les moteurs</h3>
+      <p className="text-[0.75rem] text-[var(--text-muted)]">
+        Choisissez un dossier de fichiers audio accompagnes de leur transcription de reference
+        (meme nom, extension .txt). Chaque moteur installe est evalue : taux d'erreur mots (WER)
+        et caracteres (CER), vitesse et memoire. Le rapport est ecrit en JSON et CSV dans le dossier.
+      </p>
+
+      <button onClick={handleRun} disabled={running} className="btn-glass px-4 text-[var(--accent-primary)] disabled:opacity-50">
+        {running ? 'Evaluation en cours...' : 'Choisir un corpus et evaluer'}
+      </button>
+
+      {progress && (
+        <p className="text-[0.75rem] text-[var(--text-muted)]">
+          {progress.model} ({progress.engine_index}/{progress.engine_count}) : {progress.file_name} ({progress.current}/{progress.total})
+        </p>
+      )}
+      {error && <p className="text-[0.75rem] text-[var(--accent-danger)]">{error}</p>}
+
+      {report && (
+        <div className="space-y-2">
+          {report.engines.map((engine) => (
+            <div key={engine.model} className="glass-card p-3 space-y-1">
+              <p className="text-[0.8rem] text-[var(--text-primary)]">{engine.model}</p>
+              {engine.error ? (
+                <p className="text-[0.75rem] text-[var(--accent-danger)]">{engine.error}</p>
+              ) : (
+                <p className="text-[0.75rem] text-[var(--text-muted)]">
+                  WER {percent(engine.word_error_rate)} · CER {percent(engine.char_error_rate)} ·
+                  {' '}RTF {engine.real_time_factor.toFixed(2)} · {engine.peak_memory_mb.toFixed(0)} Mo
+                  {engine.files_failed > 0 && ` · ${engine.files_failed} echec(s)`}
+                </p>
+              )}
+            </div>
+          ))}
+          {report.skipped_files.length > 0 && (
+            <p className="text-[0.75rem] text-[var(--text-muted)]">
+              Sans reference, ignores : {report.skipped_files.join(', ')}
+            </p>
+          )}
+          {report.csv_path && (
+            <p className="text-[0.75rem] text-[var(--text-muted)] break-all">{report.csv_path}</p>
+          )}
+        </div>
+      )}
+    </section>
+  );
+}
@@ -2,12 +2,14 @@ import { useEffect, useState } from 'react';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
 import { openUrl } from '@tauri-apps/plugin-opener';
+import { useSettingsStore } from '../../stores/settingsStore';
 import {
   AppSettings,
   LocalLlmModel,
   DownloadProgress,
   LlmDownloadProgress,
   GroqQuota,
+  CredentialStoreStatus,
 } from '../../types';
 
 interface LlmSectionProps {
@@ -17,10 +19,14 @@ interface LlmSectionProps {
 }
 
 export function LlmSection({ settings, updateSettings, onApiKeyStatusChange }: LlmSectionProps) {
+  const { managed } = useSettingsStore();
+  const groqDisabled = managed?.groq_disabled ?? false;
+  const providerLocked = managed?.locked_fields.includes('llm_provider') ?? false;
   const [apiKey, setApiKey] = useState('');
   const [showApiKey, setShowApiKey] = useState(false);
   const [apiKeyStatus, setApiKeyStatus] = useState<'valid' | 'invalid' | null>(null);
   const [groqQuota, setGroqQuota] = useState<GroqQuota | null>(null);
+  const [credentialStore, setCredentialStore] = useState<CredentialStoreStatus | null>(null);
   const [llmModelsAvailable, setLlmModelsAvailable] = useState<LocalLlmModel[]>([]);
   const [downloadingLlm, setDownloadingLlm] = useState<LocalLlmModel | null>(null);
   const [llmDownloadProgress, setLlmDownloadProgress] = useState<DownloadProgress | null>(null);
@@ -49,6 +55,14 @@ export function LlmSection({ settings, updateSettings, onApiKeyStatusChange }: L
     }
   };
 
+  const loadCredentialStore = async () => {
+    try {
+      setCredentialStore(await invoke<CredentialStoreStatus>('get_credential_store_status'));
+    } catch (e) {
+      console.error('Failed to load credential store status:', e);
+    }
+  };
+
   const checkApiKey = async () => {
     try {
       const hasKey = await invoke<boolean>('has_groq_api_key');
@@ -71,6 +85,7 @@ export function LlmSection({ settings, updateSettings, onApiKeyStatusChange }: L
     loadLlmModels();
     checkApiKey();
     loadGroqQuota();
+    loadCredentialStore();
   }, []);
 
   useEffect(() => {
@@ -105,6 +120,7 @@ export function LlmSection({ settings, updateSettings, onApiKeyStatusChange }: L
       }
 
       setShowApiKey(false);
+      loadCredentialStore();
     } catch (e) {
       console.error('Failed to save API key:', e);
       setApiKeyStatus('invalid');
@@ -159,14 +175,17 @@ export function LlmSection({ settings, updateSettings, onApiKeyStatusChange }: L
               <div className="flex gap-2">
                 <button
                   onClick={() => updateSettings({ llm_provider: 'groq' })}
-                  className={`btn-glass flex-1 ${settings.llm_provider === 'groq' ? 'border-[var(--accent-primary)] bg-[var(--accent-primary-soft)]' : ''}`}
+                  disabled={groqDisabled || providerLocked}
+                  title={groqDisabled ? 'Desactive par votre organisation' : undefined}
+                  className={`btn-glass flex-1 disabled:opacity-40 disabled:cursor-not-allowed ${settings.llm_provider === 'groq' ? 'border-[var(--accent-primary)] bg-[var(--accent-primary-soft)]' : ''}`}
                 >
                   <span className="w-2 h-2 rounded-full bg-blue-500" />
                   Cloud (Groq)
                 </button>
                 <button
                   onClick={() => updateSettings({ llm_provider: 'local' })}
-                  className={`btn-glass flex-1 ${settings.llm_provider === 'local' ? 'border-[var(--accent-primary)] bg-[var(--accent-primary-soft)]' : ''}`}
+                  disabled={providerLocked}
+                  className={`btn-glass flex-1 disabled:opacity-40 disabled:cursor-not-allowed ${settings.llm_provider === 'local' ? 'border-[var(--accent-primary)] bg-[var(--accent-primary-soft)]' : ''}`}
                 >
                   <span className="w-2 h-2 rounded-full bg-green-500" />
                   Local
@@ -198,6 +217,13 @@ export function LlmSection({ settings, updateSettings, onApiKeyStatusChange }: L
                     {apiKeyStatus === 'valid' ? '✓ Cle valide' : '✗ Cle invalide'}
                   </p>
                 )}
+                {credentialStore && (
+                  <p className="text-[0.75rem] text-[var(--text-muted)] mt-1">
+                    {credentialStore.backend === 'keyring'
+                      ? 'Stockee dans le trousseau du systeme'
+                      : 'Trousseau indisponible : stockee dans un fichier chiffre'}
+                  </p>
+                )}
                 <a
                   href="#"
                   onClick={(e) => { e.preventDefault(); openUrl('https://console.groq.com/keys'); }}
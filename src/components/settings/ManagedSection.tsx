@@ -0,0 +1,36 @@
+import { ManagedStatus } from '../../types';
+
+interface ManagedSectionProps {
+  managed: ManagedStatus;
+}
+
+export function ManagedSection({ managed }: ManagedSectionProps) {
+  return (
+    <section className="glass-card p-4 space-y-2">
+      <p className="text-[0.8rem] text-[var(--text-primary)]">
+        Certains reglages sont geres par votre organisation
+      </p>
+      {managed.locked_fields.length > 0 && (
+        <p className="text-[0.75rem] text-[var(--text-muted)]">
+          Imposes : {managed.locked_fields.join(', ')}
+        </p>
+      )}
+      {managed.allowed_engines.length > 0 && (
+        <p className="text-[0.75rem] text-[var(--text-muted)]">
+          Moteurs autorises : {managed.allowed_engines.join(', ')}
+        </p>
+      )}
+      {managed.groq_disabled && (
+        <p className="text-[0.75rem] text-[var(--text-muted)]">Le fournisseur cloud Groq est desactive.</p>
+      )}
+      {(managed.dictionary.length > 0 || managed.snippet_ids.length > 0) && (
+        <p className="text-[0.75rem] text-[var(--text-muted)]">
+          Partages par l'equipe : {managed.dictionary.length} mot(s), {managed.snippet_ids.length} snippet(s)
+        </p>
+      )}
+      {managed.path && (
+        <p className="text-[0.75rem] text-[var(--text-muted)] break-all">{managed.path}</p>
+      )}
+    </section>
+  );
+}
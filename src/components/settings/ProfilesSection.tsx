@@ -0,0 +1,195 @@
+import { AppProfile, AppSettings, DictationMode, EngineType, LlmMode, PasteMethod } from '../../types';
+import { WHISPER_DECODE_PRESET_NAMES, WhisperDecodePreset, applyPreset, matchPreset } from '../../utils/whisperDecode';
+
+interface ProfilesSectionProps {
+  settings: AppSettings;
+  updateSettings: (settings: Partial<AppSettings>) => Promise<void>;
+}
+
+const emptyProfile: AppProfile = {
+  name: 'Nouveau profil',
+  apps: [],
+  dictation_mode: null,
+  engine_type: null,
+  transcription_language: null,
+  llm_mode: null,
+  llm_prompt: null,
+  voice_commands_enabled: null,
+  paste_method: null,
+  whisper_decode: null,
+};
+
+// Valeur vide dans les listes : réglage global
+function orNull<T extends string>(value: string): T | null {
+  return value === '' ? null : (value as T);
+}
+
+export function ProfilesSection({ settings, updateSettings }: ProfilesSectionProps) {
+  const profiles = settings.app_profiles;
+
+  const updateProfile = (index: number, changes: Partial<AppProfile>) => {
+    updateSettings({
+      app_profiles: profiles.map((profile, i) => (i === index ? { ...profile, ...changes } : profile)),
+    });
+  };
+
+  const removeProfile = (index: number) => {
+    updateSettings({ app_profiles: profiles.filter((_, i) => i !== index) });
+  };
+
+  return (
+    <section className="space-y-4">
+      <h3 className="section-title secondary">Profils par application</h3>
+
+      <label className="checkbox-frost">
+        <input
+          type="checkbox"
+          checked={settings.app_profiles_enabled}
+          onChange={(e) => updateSettings({ app_profiles_enabled: e.target.checked })}
+        />
+        <span className="check-box" />
+        <div>
+          <span className="check-label block">Adapter la dictee a l'application active</span>
+          <span className="text-[0.75rem] text-[var(--text-muted)]">Le premier profil dont un nom correspond au processus ou a la fenetre l'emporte</span>
+        </div>
+      </label>
+
+      {settings.app_profiles_enabled && (
+        <div className="space-y-3">
+          {profiles.map((profile, index) => (
+            <div key={index} className="glass-card p-3 space-y-2">
+              <div className="flex gap-2">
+                <input
+                  type="text"
+                  value={profile.name}
+                  onChange={(e) => updateProfile(index, { name: e.target.value })}
+                  className="input-glass flex-1 text-[0.8rem]"
+                />
+                <button onClick={() => removeProfile(index)} className="btn-glass px-3 text-[var(--accent-danger)]">
+                  Supprimer
+                </button>
+              </div>
+
+              <input
+                type="text"
+                value={profile.apps.join(', ')}
+                onChange={(e) => updateProfile(index, { apps: e.target.value.split(',').map((app) => app.trim()) })}
+                placeholder="code, thunderbird, obsidian"
+                className="input-glass w-full font-mono text-[0.75rem]"
+              />
+
+              <div className="grid grid-cols-2 gap-2">
+                <select
+                  value={profile.dictation_mode ?? ''}
+                  onChange={(e) => updateProfile(index, { dictation_mode: orNull<DictationMode>(e.target.value) })}
+                  className="select-glass"
+                >
+                  <option value="">Mode : global</option>
+                  <option value="general">General</option>
+                  <option value="email">Email</option>
+                  <option value="code">Code</option>
+                  <option value="notes">Notes</option>
+                </select>
+
+                <select
+                  value={profile.engine_type ?? ''}
+                  onChange={(e) => updateProfile(index, { engine_type: orNull<EngineType>(e.target.value) })}
+                  className="select-glass"
+                >
+                  <option value="">Moteur : global</option>
+                  <option value="whisper">Whisper</option>
+                  <option value="parakeet">Parakeet</option>
+                  <option value="vosk">Vosk</option>
+                </select>
+
+                <select
+                  value={profile.transcription_language ?? ''}
+                  onChange={(e) => updateProfile(index, { transcription_language: orNull<string>(e.target.value) })}
+                  className="select-glass"
+                >
+                  <option value="">Langue : globale</option>
+                  <option value="auto">Automatique</option>
+                  <option value="fr">Francais</option>
+                  <option value="en">English</option>
+                  <option value="de">Deutsch</option>
+                  <option value="es">Espanol</option>
+                  <option value="it">Italiano</option>
+                </select>
+
+                <select
+                  value={profile.paste_method ?? ''}
+                  onChange={(e) => updateProfile(index, { paste_method: orNull<PasteMethod>(e.target.value) })}
+                  className="select-glass"
+                >
+                  <option value="">Collage : global</option>
+                  <option value="paste">Ctrl+V</option>
+                  <option value="terminal_paste">Ctrl+Maj+V (terminal)</option>
+                  <option value="clipboard_only">Presse-papiers seul</option>
+                </select>
+
+                <select
+                  value={profile.llm_mode ?? ''}
+                  onChange={(e) => updateProfile(index, { llm_mode: orNull<LlmMode>(e.target.value) })}
+                  className="select-glass"
+                >
+                  <option value="">LLM : global</option>
+                  <option value="off">Desactive</option>
+                  <option value="basic">Basique</option>
+                  <option value="smart">Intelligent</option>
+                  <option value="contextual">Contextuel</option>
+                </select>
+
+                <select
+                  value={profile.voice_commands_enabled === null ? '' : String(profile.voice_commands_enabled)}
+                  onChange={(e) => updateProfile(index, {
+                    voice_commands_enabled: e.target.value === '' ? null : e.target.value === 'true',
+                  })}
+                  className="select-glass"
+                >
+                  <option value="">Commandes : global</option>
+                  <option value="true">Commandes activees</option>
+                  <option value="false">Commandes desactivees</option>
+                </select>
+
+                <select
+                  value={profile.whisper_decode ? matchPreset(profile.whisper_decode) ?? 'custom' : ''}
+                  onChange={(e) => updateProfile(index, {
+                    whisper_decode: e.target.value === ''
+                      ? null
+                      : applyPreset(settings.whisper_decode, e.target.value as WhisperDecodePreset),
+                  })}
+                  className="select-glass"
+                >
+                  <option value="">Decodage Whisper : global</option>
+                  {(Object.keys(WHISPER_DECODE_PRESET_NAMES) as WhisperDecodePreset[]).map((preset) => (
+                    <option key={preset} value={preset}>Decodage : {WHISPER_DECODE_PRESET_NAMES[preset]}</option>
+                  ))}
+                  {profile.whisper_decode && !matchPreset(profile.whisper_decode) && (
+                    <option value="custom" disabled>Decodage : personnalise</option>
+                  )}
+                </select>
+              </div>
+
+              {profile.llm_mode !== null && profile.llm_mode !== 'off' && (
+                <textarea
+                  value={profile.llm_prompt ?? ''}
+                  onChange={(e) => updateProfile(index, { llm_prompt: e.target.value || null })}
+                  placeholder="Prompt LLM (vide : prompt du mode)"
+                  rows={2}
+                  className="input-glass w-full resize-none text-[0.75rem]"
+                />
+              )}
+            </div>
+          ))}
+
+          <button
+            onClick={() => updateSettings({ app_profiles: [...profiles, { ...emptyProfile }] })}
+            className="btn-glass px-4 text-[var(--accent-primary)]"
+          >
+            Ajouter un profil
+          </button>
+        </div>
+      )}
+    </section>
+  );
+}
@@ -1,4 +1,7 @@
-import { AppSettings } from '../../types';
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { AppSettings, HotkeyAction, HotkeyStatus } from '../../types';
 import { HotkeyInput } from '../HotkeyInput';
 
 interface ShortcutsSectionProps {
@@ -7,6 +10,41 @@ interface ShortcutsSectionProps {
 }
 
 export function ShortcutsSection({ settings, updateSettings }: ShortcutsSectionProps) {
+  const [statuses, setStatuses] = useState<HotkeyStatus[]>([]);
+  const [saveErrors, setSaveErrors] = useState<Partial<Record<HotkeyAction, string>>>({});
+
+  useEffect(() => {
+    invoke<HotkeyStatus[]>('get_hotkey_status')
+      .then(setStatuses)
+      .catch((e) => console.error('Failed to load hotkey status:', e));
+    // Les raccourcis sont réenregistrés à chaque modification des réglages
+    const unlisten = listen<HotkeyStatus[]>('hotkeys-updated', (event) => {
+      setStatuses(event.payload);
+    });
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, []);
+
+  // Un raccourci invalide est refusé par le backend : l'ancien reste actif
+  const setHotkey = async (action: HotkeyAction, patch: Partial<AppSettings>) => {
+    try {
+      await updateSettings(patch);
+      setSaveErrors(prev => ({ ...prev, [action]: undefined }));
+    } catch (e) {
+      setSaveErrors(prev => ({ ...prev, [action]: String(e) }));
+    }
+  };
+
+  const hotkeyError = (action: HotkeyAction) => {
+    const status = statuses.find(s => s.action === action);
+    const error = saveErrors[action] ?? status?.error;
+    if (error) {
+      return <p className="text-[0.65rem] text-[var(--accent-danger)] mt-1">{error}</p>;
+    }
+    return status?.warning ? <p className="text-[0.65rem] text-[var(--accent-warning)] mt-1">{status.warning}</p> : null;
+  };
+
   return (
     <section className="space-y-4">
       <h3 className="section-title primary">Raccourcis</h3>
@@ -16,37 +54,78 @@ export function ShortcutsSection({ settings, updateSettings }: ShortcutsSectionP
           <label className="text-[0.8rem] text-[var(--text-muted)] mb-2 block">Push-to-talk (maintenir)</label>
           <HotkeyInput
             value={settings.hotkey_push_to_talk}
-            onChange={(hotkey) => updateSettings({ hotkey_push_to_talk: hotkey })}
+            onChange={(hotkey) => setHotkey('push_to_talk', { hotkey_push_to_talk: hotkey })}
           />
+          {hotkeyError('push_to_talk')}
           <p className="text-[0.65rem] text-[var(--text-muted)] mt-1">Dicte et colle le texte transcrit</p>
         </div>
         <div>
           <label className="text-[0.8rem] text-[var(--text-muted)] mb-2 block">Voice Action (maintenir)</label>
           <HotkeyInput
             value={settings.hotkey_voice_action}
-            onChange={(hotkey) => updateSettings({ hotkey_voice_action: hotkey })}
+            onChange={(hotkey) => setHotkey('voice_action', { hotkey_voice_action: hotkey })}
           />
+          {hotkeyError('voice_action')}
           <p className="text-[0.65rem] text-[var(--text-muted)] mt-1">Selectionne du texte, parle une instruction (ex: "resume", "traduis")</p>
         </div>
         <div>
           <label className="text-[0.8rem] text-[var(--text-muted)] mb-2 block">Traduction rapide</label>
           <HotkeyInput
             value={settings.hotkey_translate}
-            onChange={(hotkey) => updateSettings({ hotkey_translate: hotkey })}
+            onChange={(hotkey) => setHotkey('translate', { hotkey_translate: hotkey })}
           />
+          {hotkeyError('translate')}
           <p className="text-[0.65rem] text-[var(--text-muted)] mt-1">Traduit le texte selectionne vers la langue cible</p>
         </div>
         <div>
-          <label className="text-[0.8rem] text-[var(--text-muted)] mb-2 block">Toggle enregistrement</label>
+          <label className="text-[0.8rem] text-[var(--text-muted)] mb-2 block">Dictee mains libres (appuyer)</label>
           <HotkeyInput
             value={settings.hotkey_toggle_record}
-            onChange={(hotkey) => updateSettings({ hotkey_toggle_record: hotkey })}
+            onChange={(hotkey) => setHotkey('toggle_record', { hotkey_toggle_record: hotkey })}
           />
+          {hotkeyError('toggle_record')}
+          <p className="text-[0.65rem] text-[var(--text-muted)] mt-1">Un appui demarre la dictee, un second l'arrete et colle le texte</p>
+        </div>
+
+        <label className="checkbox-frost">
+          <input
+            type="checkbox"
+            checked={settings.hands_free_auto_stop}
+            onChange={(e) => updateSettings({ hands_free_auto_stop: e.target.checked })}
+          />
+          <span className="check-box" />
+          <div>
+            <span className="check-label block">Arret automatique au silence</span>
+            <span className="text-[0.75rem] text-[var(--text-muted)]">Termine la dictee mains libres quand vous cessez de parler</span>
+          </div>
+        </label>
+
+        <div className="grid grid-cols-2 gap-2">
+          <div>
+            <label className="text-[0.75rem] text-[var(--text-muted)] mb-1 block">Silence (secondes)</label>
+            <input
+              type="number"
+              min={1}
+              max={30}
+              value={settings.hands_free_silence_secs}
+              disabled={!settings.hands_free_auto_stop}
+              onChange={(e) => updateSettings({ hands_free_silence_secs: Math.max(1, Number(e.target.value) || 1) })}
+              className="input-glass w-full"
+            />
+          </div>
+          <div>
+            <label className="text-[0.75rem] text-[var(--text-muted)] mb-1 block">Duree maximale (minutes)</label>
+            <input
+              type="number"
+              min={1}
+              max={60}
+              value={Math.round(settings.hands_free_max_secs / 60)}
+              onChange={(e) => updateSettings({ hands_free_max_secs: Math.max(1, Number(e.target.value) || 1) * 60 })}
+              className="input-glass w-full"
+            />
+          </div>
         </div>
       </div>
-      <p className="text-[0.75rem] text-[var(--text-muted)]">
-        Redemarrez l'application pour appliquer les changements de raccourcis.
-      </p>
     </section>
   );
 }
@@ -1,7 +1,11 @@
 import { useState, useEffect, useCallback } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { Snippet } from '../../types';
 
+/** Snippets de la bibliotheque d'equipe (lecture seule) */
+const isTeamSnippet = (s: Snippet) => s.id.startsWith('team:');
+
 export function SnippetsSection() {
   const [snippets, setSnippets] = useState<Snippet[]>([]);
   const [name, setName] = useState('');
@@ -18,7 +22,14 @@ export function SnippetsSection() {
     }
   }, []);
 
-  useEffect(() => { loadSnippets(); }, [loadSnippets]);
+  useEffect(() => {
+    loadSnippets();
+    // Import d'une configuration partagée
+    const unlisten = listen('snippets-changed', () => loadSnippets());
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, [loadSnippets]);
 
   const handleAdd = async () => {
     if (!name.trim() || !trigger.trim() || !content.trim()) return;
@@ -120,29 +131,34 @@ export function SnippetsSection() {
                 <div className="flex items-center gap-2 mb-1">
                   <span className="text-[0.875rem] font-medium text-[var(--text-primary)]">{s.name}</span>
                   <span className="tag-frost text-[0.65rem]">{s.trigger}</span>
+                  {isTeamSnippet(s) && (
+                    <span className="text-[0.65rem] text-[var(--text-muted)]">equipe</span>
+                  )}
                 </div>
                 <p className="text-[0.75rem] text-[var(--text-muted)] truncate">{s.content}</p>
               </div>
-              <div className="flex gap-1 flex-shrink-0">
-                <button
-                  onClick={() => handleEdit(s)}
-                  className="p-1.5 rounded-lg hover:bg-[rgba(255,255,255,0.08)] text-[var(--text-muted)] hover:text-[var(--accent-primary)] transition-colors"
-                >
-                  <svg width="14" height="14" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
-                    <path d="M11 4H4a2 2 0 0 0-2 2v14a2 2 0 0 0 2 2h14a2 2 0 0 0 2-2v-7" />
-                    <path d="M18.5 2.5a2.121 2.121 0 0 1 3 3L12 15l-4 1 1-4 9.5-9.5z" />
-                  </svg>
-                </button>
-                <button
-                  onClick={() => handleRemove(s.id)}
-                  className="p-1.5 rounded-lg hover:bg-[rgba(255,255,255,0.08)] text-[var(--text-muted)] hover:text-[var(--accent-danger)] transition-colors"
-                >
-                  <svg width="14" height="14" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
-                    <line x1="18" y1="6" x2="6" y2="18" />
-                    <line x1="6" y1="6" x2="18" y2="18" />
-                  </svg>
-                </button>
-              </div>
+              {!isTeamSnippet(s) && (
+                <div className="flex gap-1 flex-shrink-0">
+                  <button
+                    onClick={() => handleEdit(s)}
+                    className="p-1.5 rounded-lg hover:bg-[rgba(255,255,255,0.08)] text-[var(--text-muted)] hover:text-[var(--accent-primary)] transition-colors"
+                  >
+                    <svg width="14" height="14" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
+                      <path d="M11 4H4a2 2 0 0 0-2 2v14a2 2 0 0 0 2 2h14a2 2 0 0 0 2-2v-7" />
+                      <path d="M18.5 2.5a2.121 2.121 0 0 1 3 3L12 15l-4 1 1-4 9.5-9.5z" />
+                    </svg>
+                  </button>
+                  <button
+                    onClick={() => handleRemove(s.id)}
+                    className="p-1.5 rounded-lg hover:bg-[rgba(255,255,255,0.08)] text-[var(--text-muted)] hover:text-[var(--accent-danger)] transition-colors"
+                  >
+                    <svg width="14" height="14" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
+                      <line x1="18" y1="6" x2="6" y2="18" />
+                      <line x1="6" y1="6" x2="18" y2="18" />
+                    </svg>
+                  </button>
+                </div>
+              )}
             </div>
           ))}
         </div>
@@ -1,5 +1,6 @@
+import { useState } from 'react';
 import { invoke } from '@tauri-apps/api/core';
-import { AppSettings } from '../../types';
+import { AppSettings, TextInsertionReport } from '../../types';
 
 interface SystemSectionProps {
   settings: AppSettings;
@@ -7,6 +8,16 @@ interface SystemSectionProps {
 }
 
 export function SystemSection({ settings, updateSettings }: SystemSectionProps) {
+  const [insertionReport, setInsertionReport] = useState<TextInsertionReport | null>(null);
+
+  const handleDiagnose = async () => {
+    try {
+      setInsertionReport(await invoke<TextInsertionReport>('diagnose_text_insertion'));
+    } catch (err) {
+      console.error('Failed to diagnose text insertion:', err);
+    }
+  };
+
   return (
     <section className="space-y-4">
       <h3 className="section-title warning">Integration Systeme</h3>
@@ -63,6 +74,28 @@ export function SystemSection({ settings, updateSettings }: SystemSectionProps)
           </div>
         </label>
       </div>
+
+      <div className="space-y-2">
+        <button onClick={handleDiagnose} className="btn-glass px-4 text-[var(--accent-primary)]">
+          Tester l'insertion de texte
+        </button>
+
+        {insertionReport && (
+          <div className="glass-card p-3 space-y-1">
+            <p className="text-[0.75rem] text-[var(--text-muted)]">
+              Session {insertionReport.session} : frappe directe {insertionReport.typing ?? 'indisponible'}, collage {insertionReport.paste ?? 'impossible'}
+            </p>
+            {insertionReport.checks.map((check) => (
+              <p
+                key={check.name}
+                className={`text-[0.75rem] break-words ${check.ok ? 'text-[var(--text-muted)]' : 'text-[var(--accent-danger)]'}`}
+              >
+                {check.ok ? 'OK' : 'Echec'} {check.name} : {check.detail}
+              </p>
+            ))}
+          </div>
+        )}
+      </div>
     </section>
   );
 }
@@ -0,0 +1,79 @@
+import { useState, useEffect, useCallback } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { openPath } from '@tauri-apps/plugin-opener';
+import { UserVoiceCommandsStatus } from '../../types';
+
+export function VoiceCommandsSection() {
+  const [status, setStatus] = useState<UserVoiceCommandsStatus | null>(null);
+
+  const loadStatus = useCallback(async () => {
+    try {
+      setStatus(await invoke<UserVoiceCommandsStatus>('get_user_voice_commands_status'));
+    } catch (e) {
+      console.error('Failed to load voice commands status:', e);
+    }
+  }, []);
+
+  useEffect(() => {
+    loadStatus();
+    // Rechargement à chaud : le backend surveille le fichier
+    const unlisten = listen<UserVoiceCommandsStatus>('voice-commands-reloaded', (event) => {
+      setStatus(event.payload);
+    });
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, [loadStatus]);
+
+  const handleOpen = async () => {
+    try {
+      const path = await invoke<string>('create_user_voice_commands_file');
+      await openPath(path);
+    } catch (e) {
+      console.error('Failed to open voice commands file:', e);
+    }
+  };
+
+  const handleReload = async () => {
+    try {
+      setStatus(await invoke<UserVoiceCommandsStatus>('reload_user_voice_commands'));
+    } catch (e) {
+      console.error('Failed to reload voice commands:', e);
+    }
+  };
+
+  return (
+    <section className="space-y-4">
+      <h3 className="section-title secondary">Commandes personnalisees</h3>
+      <p className="text-[0.75rem] text-[var(--text-muted)]">
+        Phrases, alias et motifs definis dans voice_commands.json. Elles passent avant les commandes integrees.
+      </p>
+
+      {status && (
+        <p className="text-[0.75rem] text-[var(--text-muted)] break-all">
+          {status.exists
+            ? `${status.command_count} commande(s), ${status.disabled_builtin_count} commande(s) integree(s) desactivee(s)`
+            : 'Aucun fichier de commandes'}
+        </p>
+      )}
+
+      {status && status.errors.length > 0 && (
+        <div className="glass-card p-3 space-y-1">
+          {status.errors.map((error, i) => (
+            <p key={i} className="text-[0.75rem] text-[var(--accent-danger)] break-words">{error}</p>
+          ))}
+        </div>
+      )}
+
+      <div className="flex gap-2">
+        <button onClick={handleOpen} className="btn-glass px-4 text-[var(--accent-primary)]">
+          {status?.exists ? 'Modifier le fichier' : 'Creer le fichier'}
+        </button>
+        <button onClick={handleReload} className="btn-glass px-4 text-[var(--text-muted)]">
+          Recharger
+        </button>
+      </div>
+    </section>
+  );
+}
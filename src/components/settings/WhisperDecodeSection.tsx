@@ -0,0 +1,117 @@
+import { AppSettings, WhisperDecodeOptions } from '../../types';
+import { WHISPER_DECODE_PRESET_NAMES, WhisperDecodePreset, applyPreset, matchPreset } from '../../utils/whisperDecode';
+
+type ToggleField = 'carry_context' | 'translate' | 'suppress_blank' | 'suppress_non_speech';
+
+interface WhisperDecodeSectionProps {
+  settings: AppSettings;
+  updateSettings: (settings: Partial<AppSettings>) => Promise<void>;
+}
+
+export function WhisperDecodeSection({ settings, updateSettings }: WhisperDecodeSectionProps) {
+  const options = settings.whisper_decode;
+  const current = matchPreset(options);
+
+  const update = (changes: Partial<WhisperDecodeOptions>) =>
+    updateSettings({ whisper_decode: { ...options, ...changes } });
+
+  const toggle = (field: ToggleField, label: string, hint?: string) => (
+    <label className="checkbox-frost">
+      <input
+        type="checkbox"
+        checked={options[field]}
+        onChange={(e) => update({ [field]: e.target.checked } as Partial<WhisperDecodeOptions>)}
+      />
+      <span className="check-box" />
+      <div>
+        <span className="check-label block">{label}</span>
+        {hint && <span className="text-[0.75rem] text-[var(--text-muted)]">{hint}</span>}
+      </div>
+    </label>
+  );
+
+  return (
+    <section className="space-y-4">
+      <h3 className="section-title secondary">Decodage Whisper</h3>
+      <p className="text-[0.75rem] text-[var(--text-muted)]">
+        Plus precis = plus lent. Un profil d'application peut imposer son propre preset.
+      </p>
+
+      <div className="flex gap-2">
+        {(Object.keys(WHISPER_DECODE_PRESET_NAMES) as WhisperDecodePreset[]).map((preset) => (
+          <button
+            key={preset}
+            onClick={() => updateSettings({ whisper_decode: applyPreset(options, preset) })}
+            className={`btn-glass flex-1 ${current === preset ? 'border-[var(--accent-primary)] bg-[var(--accent-primary-soft)]' : ''}`}
+          >
+            {WHISPER_DECODE_PRESET_NAMES[preset]}
+          </button>
+        ))}
+      </div>
+
+      <div className="grid grid-cols-3 gap-3">
+        <div>
+          <label className="text-[0.75rem] text-[var(--text-muted)] mb-1 block">Faisceau (1 : glouton)</label>
+          <input
+            type="number"
+            min={1}
+            max={8}
+            value={options.beam_size}
+            onChange={(e) => update({ beam_size: Math.min(8, Math.max(1, Number(e.target.value) || 1)) })}
+            className="input-glass w-full"
+          />
+        </div>
+        <div>
+          <label className="text-[0.75rem] text-[var(--text-muted)] mb-1 block">Candidats (best of)</label>
+          <input
+            type="number"
+            min={1}
+            max={8}
+            value={options.best_of}
+            onChange={(e) => update({ best_of: Math.min(8, Math.max(1, Number(e.target.value) || 1)) })}
+            className="input-glass w-full"
+          />
+        </div>
+        <div>
+          <label className="text-[0.75rem] text-[var(--text-muted)] mb-1 block">Threads (0 : auto)</label>
+          <input
+            type="number"
+            min={0}
+            max={64}
+            value={options.n_threads}
+            onChange={(e) => update({ n_threads: Math.min(64, Math.max(0, Number(e.target.value) || 0)) })}
+            className="input-glass w-full"
+          />
+        </div>
+      </div>
+
+      <label className="checkbox-frost">
+        <input
+          type="checkbox"
+          checked={options.temperature_increment > 0}
+          onChange={(e) => update({ temperature_increment: e.target.checked ? 0.2 : 0 })}
+        />
+        <span className="check-box" />
+        <div>
+          <span className="check-label block">Redecoder les passages incertains</span>
+          <span className="text-[0.75rem] text-[var(--text-muted)]">Augmente la temperature quand la probabilite du texte est trop basse</span>
+        </div>
+      </label>
+      {toggle('carry_context', 'Garder le contexte entre les segments', 'Le texte precedent guide la suite des longs enregistrements')}
+      {toggle('translate', 'Traduire vers l\'anglais', 'Whisper transcrit directement en anglais')}
+      {toggle('suppress_blank', 'Ignorer les segments vides')}
+      {toggle('suppress_non_speech', 'Ignorer les sons non vocaux', 'Evite les hallucinations sur la musique ou le bruit')}
+
+      <div>
+        <label className="text-[0.75rem] text-[var(--text-muted)] mb-1 block">Prompt initial</label>
+        <textarea
+          value={options.initial_prompt}
+          onChange={(e) => update({ initial_prompt: e.target.value })}
+          placeholder="Vocabulaire, noms propres ou style attendus (ex : Phonon, Tauri, Kubernetes.)"
+          rows={2}
+          className="input-glass w-full resize-none text-[0.75rem]"
+        />
+      </div>
+    </section>
+  );
+}
@@ -1,8 +1,13 @@
 export { AudioSection } from './AudioSection';
 export { EngineSection } from './EngineSection';
+export { EngineFallbackSection } from './EngineFallbackSection';
+export { WhisperDecodeSection } from './WhisperDecodeSection';
+export { EvaluationSection } from './EvaluationSection';
 export { LlmSection } from './LlmSection';
 export { TranslationSection } from './TranslationSection';
 export { DictationSection } from './DictationSection';
+export { ProfilesSection } from './ProfilesSection';
+export { VoiceCommandsSection } from './VoiceCommandsSection';
 export { TranscriptionSection } from './TranscriptionSection';
 export { OptionsSection } from './OptionsSection';
 export { SystemSection } from './SystemSection';
@@ -10,3 +15,5 @@ export { ShortcutsSection } from './ShortcutsSection';
 export { DictionarySection } from './DictionarySection';
 export { SnippetsSection } from './SnippetsSection';
 export { IntegrationsSection } from './IntegrationsSection';
+export { ConfigTransferSection } from './ConfigTransferSection';
+export { ManagedSection } from './ManagedSection';
@@ -1,14 +1,17 @@
 import { create } from 'zustand';
 import { invoke } from '@tauri-apps/api/core';
-import { AppSettings, AudioDevice } from '../types';
+import { AppProfile, AppSettings, AudioDevice, DictationMode, ManagedStatus, PasteMethod } from '../types';
+import { WHISPER_DECODE_PRESETS } from '../utils/whisperDecode';
 
 interface SettingsStore {
   settings: AppSettings | null;
   devices: AudioDevice[];
   dictionary: string[];
+  managed: ManagedStatus | null;
   isLoading: boolean;
 
   loadSettings: () => Promise<void>;
+  loadManaged: () => Promise<void>;
   updateSettings: (settings: Partial<AppSettings>) => Promise<void>;
   loadDevices: () => Promise<void>;
   loadDictionary: () => Promise<void>;
@@ -16,7 +19,23 @@ interface SettingsStore {
   removeWord: (word: string) => Promise<void>;
 }
 
+function profilePreset(name: string, apps: string[], dictation_mode: DictationMode, paste_method: PasteMethod | null = null): AppProfile {
+  return {
+    name,
+    apps,
+    dictation_mode,
+    engine_type: null,
+    transcription_language: null,
+    llm_mode: null,
+    llm_prompt: null,
+    voice_commands_enabled: null,
+    paste_method,
+    whisper_decode: null,
+  };
+}
+
 const defaultSettings: AppSettings = {
+  schema_version: 1,
   microphone_id: null,
   hotkey_push_to_talk: 'Control+Space',
   hotkey_toggle_record: 'Control+Shift+R',
@@ -30,7 +49,9 @@ const defaultSettings: AppSettings = {
   engine_type: 'whisper',
   vosk_language: null,
   parakeet_model: 'tdt06bv3',
-  groq_api_key: null,
+  parakeet_beam_size: 1,
+  engine_fallback_chain: ['whisper', 'parakeet'],
+  whisper_decode: WHISPER_DECODE_PRESETS.balanced,
   llm_provider: 'groq',
   local_llm_model: 'phi3_mini',
   llm_enabled: false,
@@ -55,12 +76,42 @@ const defaultSettings: AppSettings = {
     obsidian_enabled: false,
     obsidian_vault_path: null,
   },
+  storage_encryption_enabled: false,
+  privacy_mode: false,
+  preferred_microphones: [],
+  microphone_channel: null,
+  mix_system_audio: false,
+  system_audio_device: null,
+  command_templates: {
+    signature: 'Cordialement,\n',
+    greeting: 'Bonjour,\n\n',
+    function: 'function name() {\n    \n}',
+    comment: '// ',
+  },
+  code_language: 'generic',
+  text_normalization: {
+    general: false,
+    email: false,
+    code: false,
+    notes: false,
+  },
+  app_profiles_enabled: false,
+  app_profiles: [
+    profilePreset('Terminal', ['gnome-terminal', 'konsole', 'alacritty', 'kitty', 'wezterm', 'foot', 'xterm', 'terminator', 'tilix'], 'code', 'terminal_paste'),
+    profilePreset('Code', ['code', 'codium', 'jetbrains', 'zed', 'neovide', 'sublime'], 'code'),
+    profilePreset('Email', ['thunderbird', 'evolution', 'geary', 'outlook', 'com.apple.mail'], 'email'),
+    profilePreset('Notes', ['obsidian', 'joplin', 'logseq', 'com.apple.notes'], 'notes'),
+  ],
+  hands_free_auto_stop: false,
+  hands_free_silence_secs: 3,
+  hands_free_max_secs: 300,
 };
 
 export const useSettingsStore = create<SettingsStore>((set, get) => ({
   settings: null,
   devices: [],
   dictionary: [],
+  managed: null,
   isLoading: false,
 
   loadSettings: async () => {
@@ -74,6 +125,15 @@ export const useSettingsStore = create<SettingsStore>((set, get) => ({
     }
   },
 
+  loadManaged: async () => {
+    try {
+      const managed = await invoke<ManagedStatus>('get_managed_settings');
+      set({ managed });
+    } catch (error) {
+      console.error('Failed to load managed settings:', error);
+    }
+  },
+
   updateSettings: async (newSettings: Partial<AppSettings>) => {
     const current = get().settings || defaultSettings;
     const updated = { ...current, ...newSettings };
@@ -69,7 +69,9 @@ export const useTranscriptionStore = create<TranscriptionStore>((set) => ({
       const history = await invoke<TranscriptionResult[]>('get_history');
       set({ history });
     } catch (error) {
+      // Historique chiffré illisible (trousseau verrouillé, clé absente)
       console.error('Failed to load history:', error);
+      set({ error: String(error) });
     }
   },
 
@@ -6,12 +6,85 @@ export interface TranscriptionResult {
   detected_language: string | null;
   timestamp: number;
   model_used: string | null;
+  tokens?: TokenTimestamp[];
 }
 
-export type ModelSize = 'tiny' | 'small' | 'medium';
+export interface TokenTimestamp {
+  token: string;
+  start: number;
+  end: number;
+  confidence: number;
+}
+
+export type ModelSize =
+  | 'tiny' | 'tiny_en' | 'tiny_q5_1'
+  | 'base' | 'base_en' | 'base_q5_1'
+  | 'small' | 'small_en' | 'small_q5_1' | 'small_q8_0'
+  | 'medium' | 'medium_en' | 'medium_q5_0' | 'medium_q8_0'
+  | 'large_v3' | 'large_v3_q5_0'
+  | 'large_v3_turbo' | 'large_v3_turbo_q5_0' | 'large_v3_turbo_q8_0';
 
 export type EngineType = 'whisper' | 'parakeet' | 'vosk';
 
+export interface EngineHealth {
+  engine_type: EngineType;
+  primary: boolean;
+  loaded: boolean;
+  healthy: boolean;
+  error: string | null;
+  checked_at: number;
+}
+
+export interface ErrorCounts {
+  errors: number;
+  reference_len: number;
+}
+
+export interface FileEvaluation {
+  file_name: string;
+  reference: string;
+  hypothesis: string | null;
+  audio_seconds: number;
+  processing_ms: number;
+  word_errors: ErrorCounts;
+  char_errors: ErrorCounts;
+  error: string | null;
+}
+
+export interface EngineEvaluation {
+  engine_type: EngineType;
+  model: string;
+  word_error_rate: number;
+  char_error_rate: number;
+  real_time_factor: number;
+  load_time_ms: number;
+  peak_memory_mb: number;
+  memory_delta_mb: number;
+  files_evaluated: number;
+  files_failed: number;
+  error: string | null;
+  files: FileEvaluation[];
+}
+
+export interface EvaluationReport {
+  corpus_path: string;
+  created_at: string;
+  file_count: number;
+  skipped_files: string[];
+  engines: EngineEvaluation[];
+  json_path: string | null;
+  csv_path: string | null;
+}
+
+export interface EvaluationProgress {
+  model: string;
+  engine_index: number;
+  engine_count: number;
+  current: number;
+  total: number;
+  file_name: string;
+}
+
 export type VoskLanguage = 'en' | 'fr' | 'de' | 'es' | 'it' | 'ru' | 'zh' | 'ja' | 'ko' | 'pt' | 'nl' | 'pl' | 'uk' | 'tr' | 'vi' | 'ar' | 'hi' | 'fa' | 'ca' | 'cs';
 
 export type ParakeetModelSize = 'tdt06bv3';
@@ -41,11 +114,23 @@ export type QwenModelSize = LocalLlmModel;
 
 export type DictationMode = 'general' | 'email' | 'code' | 'notes';
 
+export type CodeLanguage = 'generic' | 'rust' | 'python' | 'typescript';
+
 export interface ModelInfo {
   size: ModelSize;
   display_name: string;
   available: boolean;
   size_bytes: number;
+  ram_bytes: number;
+  relative_speed: number;
+  english_only: boolean;
+  quantization: string | null;
+  fits_in_memory: boolean;
+}
+
+export interface SystemMemory {
+  total_bytes: number;
+  available_bytes: number;
 }
 
 export interface DownloadProgress {
@@ -55,6 +140,7 @@ export interface DownloadProgress {
 }
 
 export interface AppSettings {
+  schema_version: number;
   microphone_id: string | null;
   hotkey_push_to_talk: string;
   hotkey_toggle_record: string;
@@ -68,7 +154,9 @@ export interface AppSettings {
   engine_type: EngineType;
   vosk_language: VoskLanguage | null;
   parakeet_model: ParakeetModelSize;
-  groq_api_key: string | null;
+  parakeet_beam_size: number;
+  engine_fallback_chain: EngineType[];
+  whisper_decode: WhisperDecodeOptions;
   llm_provider: LlmProvider;
   local_llm_model: LocalLlmModel;
   llm_enabled: boolean;
@@ -89,8 +177,67 @@ export interface AppSettings {
   subtitles_font_size: number;
   stats_tracking_enabled: boolean;
   integrations: IntegrationConfig;
+  storage_encryption_enabled: boolean;
+  privacy_mode: boolean;
+  preferred_microphones: string[];
+  microphone_channel: number | null;
+  mix_system_audio: boolean;
+  system_audio_device: string | null;
+  command_templates: CommandTemplates;
+  code_language: CodeLanguage;
+  text_normalization: TextNormalization;
+  app_profiles_enabled: boolean;
+  app_profiles: AppProfile[];
+  hands_free_auto_stop: boolean;
+  hands_free_silence_secs: number;
+  hands_free_max_secs: number;
+}
+
+export interface HandsFreeProgress {
+  elapsed_secs: number;
+  max_secs: number;
+}
+
+export type PasteMethod = 'paste' | 'terminal_paste' | 'clipboard_only';
+
+/** Réglages appliqués selon l'application au premier plan ; `null` garde le réglage global */
+export interface AppProfile {
+  name: string;
+  apps: string[];
+  dictation_mode: DictationMode | null;
+  engine_type: EngineType | null;
+  transcription_language: string | null;
+  llm_mode: LlmMode | null;
+  llm_prompt: string | null;
+  voice_commands_enabled: boolean | null;
+  paste_method: PasteMethod | null;
+  whisper_decode: WhisperDecodeOptions | null;
 }
 
+/** Options de décodage Whisper : précision contre vitesse */
+export interface WhisperDecodeOptions {
+  beam_size: number;
+  best_of: number;
+  temperature: number;
+  temperature_increment: number;
+  logprob_threshold: number;
+  initial_prompt: string;
+  carry_context: boolean;
+  translate: boolean;
+  n_threads: number;
+  suppress_blank: boolean;
+  suppress_non_speech: boolean;
+}
+
+export interface CommandTemplates {
+  signature: string;
+  greeting: string;
+  function: string;
+  comment: string;
+}
+
+export type TextNormalization = Record<DictationMode, boolean>;
+
 export interface VoskModelInfo {
   language: VoskLanguage;
   display_name: string;
@@ -142,6 +289,22 @@ export interface AudioDevice {
   id: string;
   name: string;
   is_default: boolean;
+  channels: number;
+  kind: AudioDeviceKind;
+}
+
+export type AudioDeviceKind = 'microphone' | 'system_audio';
+
+export interface RecordingLimitEvent {
+  recorded_secs: number;
+  limit_secs: number;
+  spooled: boolean;
+  limit_reached: boolean;
+}
+
+export interface AudioDeviceChangedEvent {
+  lost_device: string;
+  new_device: string | null;
 }
 
 export type TranscriptionStatus = 'idle' | 'recording' | 'processing' | 'completed' | 'error';
@@ -185,3 +348,72 @@ export interface LogEntry {
   category: string;
   message: string;
 }
+
+export interface StorageEncryptionStatus {
+  enabled: boolean;
+  key_available: boolean;
+  key_id: string | null;
+  encrypted_files: string[];
+  plaintext_files: string[];
+  unreadable_files: string[];
+}
+
+export interface InsertionCheck {
+  name: string;
+  ok: boolean;
+  detail: string;
+}
+
+export type HotkeyAction = 'push_to_talk' | 'toggle_record' | 'translate' | 'voice_action';
+
+export interface HotkeyStatus {
+  action: HotkeyAction;
+  hotkey: string;
+  registered: boolean;
+  error: string | null;
+  warning: string | null;
+}
+
+export interface SettingsIssue {
+  field: string;
+  message: string;
+}
+
+export interface SettingsImportSummary {
+  issues: SettingsIssue[];
+  words_added: number;
+  snippets_added: number;
+  snippets_updated: number;
+}
+
+export interface ManagedStatus {
+  active: boolean;
+  path: string | null;
+  defaulted_fields: string[];
+  locked_fields: string[];
+  allowed_engines: EngineType[];
+  groq_disabled: boolean;
+  dictionary: string[];
+  snippet_ids: string[];
+}
+
+export interface CredentialStoreStatus {
+  backend: 'keyring' | 'encrypted_file';
+  keyring_error: string | null;
+  groq_api_key_stored: boolean;
+}
+
+export interface TextInsertionReport {
+  session: string;
+  typing: string | null;
+  paste: string | null;
+  checks: InsertionCheck[];
+}
+
+export interface UserVoiceCommandsStatus {
+  path: string;
+  exists: boolean;
+  command_count: number;
+  disabled_builtin_count: number;
+  errors: string[];
+}
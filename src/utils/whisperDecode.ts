@@ -0,0 +1,51 @@
+import { WhisperDecodeOptions } from '../types';
+
+export type WhisperDecodePreset = 'fast' | 'balanced' | 'accurate';
+
+const base: WhisperDecodeOptions = {
+  beam_size: 1,
+  best_of: 1,
+  temperature: 0,
+  temperature_increment: 0.2,
+  logprob_threshold: -1,
+  initial_prompt: '',
+  carry_context: false,
+  translate: false,
+  n_threads: 0,
+  suppress_blank: true,
+  suppress_non_speech: true,
+};
+
+// Rapide : glouton sans repli ; Equilibre : defauts whisper.cpp ; Precis : faisceau de 5
+export const WHISPER_DECODE_PRESETS: Record<WhisperDecodePreset, WhisperDecodeOptions> = {
+  fast: { ...base, temperature_increment: 0 },
+  balanced: base,
+  accurate: { ...base, beam_size: 5, best_of: 5, carry_context: true },
+};
+
+export const WHISPER_DECODE_PRESET_NAMES: Record<WhisperDecodePreset, string> = {
+  fast: 'Rapide',
+  balanced: 'Equilibre',
+  accurate: 'Precis',
+};
+
+/** Preset correspondant aux options (prompt, traduction et threads mis a part), sinon `null` */
+export function matchPreset(options: WhisperDecodeOptions): WhisperDecodePreset | null {
+  const core = (o: WhisperDecodeOptions) => [
+    o.beam_size, o.best_of, o.temperature, o.temperature_increment, o.logprob_threshold,
+    o.carry_context, o.suppress_blank, o.suppress_non_speech,
+  ].join('|');
+  const found = (Object.keys(WHISPER_DECODE_PRESETS) as WhisperDecodePreset[])
+    .find((preset) => core(WHISPER_DECODE_PRESETS[preset]) === core(options));
+  return found ?? null;
+}
+
+/** Applique un preset en gardant le prompt, la traduction et le nombre de threads */
+export function applyPreset(options: WhisperDecodeOptions, preset: WhisperDecodePreset): WhisperDecodeOptions {
+  return {
+    ...WHISPER_DECODE_PRESETS[preset],
+    initial_prompt: options.initial_prompt,
+    translate: options.translate,
+    n_threads: options.n_threads,
+  };
+}